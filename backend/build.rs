@@ -0,0 +1,15 @@
+//! Compiles the gRPC interface (`proto/systematics.proto`) with the `grpc` feature.
+
+fn main() {
+    println!("cargo:rerun-if-changed=proto");
+    #[cfg(feature = "grpc")]
+    {
+        // protox parses the proto in Rust, so building needs no protoc
+        let descriptors = protox::compile(["systematics.proto"], ["proto"])
+            .expect("proto/systematics.proto compiles");
+        tonic_build::configure()
+            .build_client(false)
+            .compile_fds(descriptors)
+            .expect("gRPC code generation");
+    }
+}
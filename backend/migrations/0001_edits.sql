@@ -0,0 +1,8 @@
+-- Edits made through the API, replayed in id order onto the baseline graph.
+-- The body is the edit's JSON form, kept as TEXT since JSONB would reorder its keys.
+CREATE TABLE IF NOT EXISTS edits (
+    id         BIGSERIAL PRIMARY KEY,
+    kind       TEXT NOT NULL,
+    body       TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
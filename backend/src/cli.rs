@@ -0,0 +1,200 @@
+//! Command-line interface.
+//!
+//! Without a subcommand the binary serves the API, as it always has. The other
+//! subcommands work on the same data the server would load (canonical data,
+//! the configured data directory and persisted edits) without starting it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use systematics_backend::config::Config;
+use systematics_backend::core::Graph;
+use systematics_backend::data::{self, Edit, ExportFormat, VocabularyError, VocabularyFormat};
+
+use crate::load_store;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Systematics GraphQL server and data tools")]
+pub struct Cli {
+    /// Configuration file [default: $SYSTEMATICS_CONFIG or systematics.toml]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the GraphQL server (the default)
+    Serve,
+    /// Check the data against the structural invariants of each system
+    Validate,
+    /// Write the graph, or one system, as json, graphml, dot, ttl (Turtle) or svg
+    Export {
+        #[arg(long, short, value_parser = parse_format, default_value = "json")]
+        format: ExportFormat,
+        /// Export only the system of this order (required for svg)
+        #[arg(long)]
+        order: Option<u8>,
+        /// File to write [default: standard output]
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Import a CSV or YAML vocabulary pack into the database or the data directory
+    Import {
+        file: PathBuf,
+        /// Vocabulary name [default: the file name without its extension]
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+fn parse_format(name: &str) -> Result<ExportFormat, String> {
+    ExportFormat::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = ExportFormat::ALL.iter().map(|f| f.extension()).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+/// Report every validation and audit problem; fails when there are any, or when
+/// the data directory cannot be loaded
+pub fn validate(config: &Config) -> ExitCode {
+    let graph = match &config.data_dir {
+        Some(dir) => match data::load_graph(dir) {
+            Ok(graph) => graph,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => data::build_graph(),
+    };
+    let mut problems = graph.validate();
+    problems.extend(data::audit(&graph));
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!(
+            "No problems found in {} entries and {} links",
+            graph.entries.len(),
+            graph.links.len()
+        );
+        ExitCode::SUCCESS
+    } else {
+        println!("{} problem(s) found", problems.len());
+        ExitCode::FAILURE
+    }
+}
+
+/// Export the served graph, including persisted edits
+pub fn export(
+    config: &Config,
+    format: ExportFormat,
+    order: Option<u8>,
+    output: Option<&Path>,
+) -> ExitCode {
+    let graph = load_store(config).snapshot();
+    let text = match data::export(&graph, format, order) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(path) = output else {
+        print!("{}", text);
+        return ExitCode::SUCCESS;
+    };
+    match fs::write(path, text) {
+        Ok(()) => {
+            eprintln!("Wrote {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Import a vocabulary pack. With a database configured, the import is recorded
+/// as an edit, as the API's `importVocabulary` mutation does; otherwise it is
+/// written to the data directory as a fragment (`vocabulary_<name>.json`) that
+/// the server loads on start.
+pub fn import(config: &Config, file: &Path, name: Option<String>) -> ExitCode {
+    match import_pack(config, file, name) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn import_pack(config: &Config, file: &Path, name: Option<String>) -> Result<(), String> {
+    let file_name = file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let format = VocabularyFormat::from_file_name(file_name)
+        .ok_or_else(|| VocabularyError::UnsupportedFormat(file_name.to_string()).to_string())?;
+    let name = name.unwrap_or_else(|| {
+        let stem = file_name
+            .rsplit_once('.')
+            .map_or(file_name, |(stem, _)| stem);
+        stem.to_string()
+    });
+    let text = fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let pack = data::parse_vocabulary(&text, format).map_err(|e| e.to_string())?;
+
+    let store = load_store(config);
+    let (report, destination) = if store.is_persistent() {
+        let edit = Edit::Vocabulary {
+            name: name.clone(),
+            pack: pack.clone(),
+        };
+        let report = store
+            .apply(edit, |graph| data::import_vocabulary(graph, &name, &pack))
+            .map_err(|e| e.to_string())?;
+        (report, "the database".to_string())
+    } else if let Some(dir) = &config.data_dir {
+        let mut graph = data::load_graph(dir).map_err(|e| e.to_string())?;
+        let before = graph.clone();
+        let report =
+            data::import_vocabulary(&mut graph, &name, &pack).map_err(|e| e.to_string())?;
+        let path = dir.join(format!("vocabulary_{}.json", report.language.slug()));
+        write_fragment(&path, graph.changes_since(&before))?;
+        (report, path.display().to_string())
+    } else {
+        return Err(
+            "Nowhere to import to: configure a database (sqlite feature) or a data_dir".to_string(),
+        );
+    };
+
+    println!(
+        "Imported vocabulary '{}' into {}: {} terms, {} connectives",
+        report.language, destination, report.terms, report.connectives
+    );
+    for skipped in &report.skipped {
+        println!("Skipped {}", skipped);
+    }
+    Ok(())
+}
+
+/// Write a fragment, merged over the file's previous content so re-importing a
+/// vocabulary keeps the values it did not change
+fn write_fragment(path: &Path, changes: Graph) -> Result<(), String> {
+    let mut fragment = match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+        Err(_) => Graph::new(),
+    };
+    fragment.merge(changes);
+    let text = serde_json::to_string_pretty(&fragment).expect("graphs serialize to JSON");
+    fs::write(path, text + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,492 @@
+//! Server configuration.
+//!
+//! Settings are layered: built-in defaults, then the TOML file named by
+//! `SYSTEMATICS_CONFIG` (`systematics.toml` by default, skipped when missing),
+//! then `SYSTEMATICS_*` environment variables. Each field's variable is its
+//! upper-cased name, e.g. `SYSTEMATICS_PORT` or `SYSTEMATICS_DATA_DIR`; the
+//! rate-limit fields use `SYSTEMATICS_RATE_LIMIT_BURST`,
+//! `SYSTEMATICS_RATE_LIMIT_PER_SECOND` and so on, and `SYSTEMATICS_RATE_LIMIT=0`
+//! disables limiting.
+//!
+//! ```toml
+//! port = 8080
+//! cors_origins = ["https://systematics.example"]
+//! cors_credentials = true
+//! webhooks = ["https://ci.example/hooks/systematics"]
+//! data_dir = "data"
+//! introspection = false
+//!
+//! [rate_limit]
+//! burst = 60
+//! per_second = 10.0
+//! trusted_proxies = 1
+//! ```
+//!
+//! Token authentication is configured separately, from the environment (see
+//! `http::auth`), so secrets stay out of configuration files.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use axum::http::HeaderValue;
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::Figment;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::graphql::SchemaOptions;
+use crate::http::{ClientKeys, RateLimit};
+
+/// Environment variable naming the configuration file
+pub const CONFIG_ENV: &str = "SYSTEMATICS_CONFIG";
+
+/// Configuration file read when `SYSTEMATICS_CONFIG` is unset
+pub const DEFAULT_CONFIG_FILE: &str = "systematics.toml";
+
+/// `cors_origins` entry allowing any origin
+pub const ANY_ORIGIN: &str = "*";
+
+/// Prefix of the environment variables overriding configuration fields
+const ENV_PREFIX: &str = "SYSTEMATICS_";
+
+/// Server settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Port the local server listens on (Shuttle assigns its own)
+    pub port: u16,
+    /// Origins allowed to call the API from a browser: `["*"]` allows any, and
+    /// empty allows none (same-origin requests only)
+    pub cors_origins: Vec<String>,
+    /// Let browsers send cookies and `Authorization` headers cross-origin
+    /// (requires listed origins)
+    #[serde(deserialize_with = "flag")]
+    pub cors_credentials: bool,
+    /// Directory of external data files overlaid on the canonical data
+    pub data_dir: Option<PathBuf>,
+    /// SQLite file that edits are persisted to (`sqlite` feature)
+    pub database: Option<PathBuf>,
+    /// PEM certificate chain served over HTTPS by the local server (`tls` feature)
+    pub tls_cert: Option<PathBuf>,
+    /// PEM private key of `tls_cert`
+    pub tls_key: Option<PathBuf>,
+    /// Directory of the built frontend, served outside the API routes
+    pub static_dir: PathBuf,
+    /// Serve the GraphiQL IDE at `ide_path` (formerly `playground`)
+    #[serde(deserialize_with = "flag")]
+    pub ide: bool,
+    /// Path of the GraphiQL IDE
+    pub ide_path: String,
+    /// Answer introspection queries (and serve the SDL)
+    #[serde(deserialize_with = "flag")]
+    pub introspection: bool,
+    /// Reject requests without a persisted query hash
+    #[serde(deserialize_with = "flag")]
+    pub persisted_only: bool,
+    /// Execute only the frontend's operations
+    #[serde(deserialize_with = "flag")]
+    pub allowed_operations_only: bool,
+    /// Seconds in-flight requests may take to finish once shutdown is requested
+    pub shutdown_timeout: u64,
+    /// Most workspaces held at once; 0 disables workspaces
+    pub max_workspaces: usize,
+    /// Seconds after which an unused workspace is dropped
+    pub workspace_idle_timeout: u64,
+    /// Seconds after which an unused draft session is discarded
+    pub session_idle_timeout: u64,
+    /// Log line format
+    pub log_format: LogFormat,
+    /// URLs sent a JSON event (`POST`) after each successful edit
+    pub webhooks: Vec<String>,
+    pub rate_limit: RateLimitConfig,
+}
+
+/// Format of log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line, with the fields of the enclosing spans
+    Json,
+}
+
+/// Per-client rate limiting settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    #[serde(deserialize_with = "flag")]
+    pub enabled: bool,
+    /// Requests a client may send at once
+    pub burst: u32,
+    /// Steady rate, in requests per second
+    pub per_second: f64,
+    /// `X-Api-Key` values that identify a client; requests with any other key
+    /// are limited by address
+    pub api_keys: Vec<String>,
+    /// Proxies in front of the server whose `X-Forwarded-For` entries are
+    /// trusted; with none, clients are told apart by peer address
+    pub trusted_proxies: usize,
+}
+
+/// Read a flag given as a boolean, number or string: `0`, `false`, `off` and `no`
+/// disable it, any other value enables it
+fn flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Flag {
+        Bool(bool),
+        Number(i64),
+        Text(String),
+    }
+    Ok(match Flag::deserialize(deserializer)? {
+        Flag::Bool(value) => value,
+        Flag::Number(value) => value != 0,
+        Flag::Text(value) => !matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "0" | "false" | "off" | "no"
+        ),
+    })
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let limit = RateLimit::default();
+        Self {
+            enabled: true,
+            burst: limit.burst,
+            per_second: limit.per_second,
+            api_keys: Vec::new(),
+            // Shuttle serves through one proxy
+            trusted_proxies: if cfg!(feature = "shuttle") { 1 } else { 0 },
+        }
+    }
+}
+
+impl Default for Config {
+    /// The IDE, introspection and calls from any origin are on in
+    /// development and off in production (`shuttle`) builds, which log JSON lines
+    fn default() -> Self {
+        let development = !cfg!(feature = "shuttle");
+        Self {
+            port: 8000,
+            cors_origins: if development {
+                vec![ANY_ORIGIN.to_string()]
+            } else {
+                Vec::new()
+            },
+            cors_credentials: false,
+            data_dir: None,
+            database: None,
+            tls_cert: None,
+            tls_key: None,
+            static_dir: PathBuf::from("frontend/dist"),
+            ide: development,
+            ide_path: "/graphiql".to_string(),
+            introspection: development,
+            persisted_only: false,
+            allowed_operations_only: false,
+            shutdown_timeout: 10,
+            max_workspaces: 16,
+            workspace_idle_timeout: 24 * 60 * 60,
+            session_idle_timeout: 60 * 60,
+            log_format: if development {
+                LogFormat::Text
+            } else {
+                LogFormat::Json
+            },
+            webhooks: Vec::new(),
+            rate_limit: RateLimitConfig::default(),
+        }
+    }
+}
+
+/// Error raised when the configuration cannot be read or is invalid
+#[derive(Debug)]
+pub enum ConfigError {
+    Load(Box<figment::Error>),
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Load(e) => write!(f, "{}", e),
+            ConfigError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Load the configuration from the defaults, the configuration file and the
+    /// environment
+    pub fn load() -> Result<Self, ConfigError> {
+        let file = std::env::var_os(CONFIG_ENV)
+            .map_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE), PathBuf::from);
+        Self::load_file(&file)
+    }
+
+    /// Load the configuration from the defaults, the given file (skipped when
+    /// missing) and the environment
+    pub fn load_file(file: &Path) -> Result<Self, ConfigError> {
+        Self::from_figment(Self::figment(file))
+    }
+
+    /// The configuration sources, lowest precedence first. `playground`, the
+    /// former name of `ide`, is read as it.
+    fn figment(file: &Path) -> Figment {
+        let env = Env::prefixed(ENV_PREFIX).map(|key| {
+            let key = key.as_str().to_ascii_lowercase();
+            match key.as_str() {
+                "rate_limit" => "rate_limit.enabled".into(),
+                "playground" => "ide".into(),
+                key => key.replacen("rate_limit_", "rate_limit.", 1).into(),
+            }
+        });
+        let mut figment = Figment::from(Serialized::defaults(Config::default()));
+        if let Ok(playground) = Figment::from(Toml::file(file)).find_value("playground") {
+            figment = figment.merge(("ide", playground));
+        }
+        figment.merge(Toml::file(file)).merge(env)
+    }
+
+    fn from_figment(figment: Figment) -> Result<Self, ConfigError> {
+        let config: Config = figment
+            .extract()
+            .map_err(|e| ConfigError::Load(Box::new(e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.cors_any_origin() {
+            if self.cors_origins.len() > 1 {
+                return Err(ConfigError::Invalid(
+                    "cors_origins: \"*\" cannot be combined with other origins".to_string(),
+                ));
+            }
+            if self.cors_credentials {
+                return Err(ConfigError::Invalid(
+                    "cors_credentials requires listed cors_origins, not \"*\"".to_string(),
+                ));
+            }
+        } else {
+            for origin in &self.cors_origins {
+                let valid = (origin.starts_with("http://") || origin.starts_with("https://"))
+                    && HeaderValue::from_str(origin).is_ok();
+                if !valid {
+                    return Err(ConfigError::Invalid(format!(
+                        "cors_origins: '{}' is not an origin, e.g. https://systematics.example",
+                        origin
+                    )));
+                }
+            }
+        }
+        for url in &self.webhooks {
+            let valid =
+                reqwest::Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                return Err(ConfigError::Invalid(format!(
+                    "webhooks: '{}' is not an http(s) URL",
+                    url
+                )));
+            }
+        }
+        let api_path =
+            ["/graphql", "/graphql/ws", "/schema.graphql"].contains(&self.ide_path.as_str());
+        if !self.ide_path.starts_with('/') || api_path {
+            return Err(ConfigError::Invalid(format!(
+                "ide_path: '{}' must be a path outside the API, e.g. /graphiql",
+                self.ide_path
+            )));
+        }
+        if self.tls_cert.is_some() != self.tls_key.is_some() {
+            return Err(ConfigError::Invalid(
+                "tls_cert and tls_key must be given together".to_string(),
+            ));
+        }
+        if self.rate_limit.burst == 0 {
+            return Err(ConfigError::Invalid(
+                "rate_limit.burst must be at least 1".to_string(),
+            ));
+        }
+        if self.rate_limit.per_second.is_nan() || self.rate_limit.per_second <= 0.0 {
+            return Err(ConfigError::Invalid(
+                "rate_limit.per_second must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether any origin may call the API from a browser
+    pub fn cors_any_origin(&self) -> bool {
+        self.cors_origins.iter().any(|origin| origin == ANY_ORIGIN)
+    }
+
+    /// Schema settings
+    pub fn schema_options(&self) -> SchemaOptions {
+        SchemaOptions {
+            introspection: self.introspection,
+            persisted_only: self.persisted_only,
+            allowed_operations_only: self.allowed_operations_only,
+        }
+    }
+
+    /// Certificate and key paths when HTTPS is configured
+    pub fn tls(&self) -> Option<(&Path, &Path)> {
+        Some((self.tls_cert.as_deref()?, self.tls_key.as_deref()?))
+    }
+
+    /// How long to wait for in-flight requests when shutting down
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout)
+    }
+
+    /// How long an unused workspace is kept
+    pub fn workspace_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.workspace_idle_timeout)
+    }
+
+    /// How long an unused draft session is kept
+    pub fn session_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.session_idle_timeout)
+    }
+
+    /// Per-client limits, or `None` when limiting is disabled
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.enabled.then_some(RateLimit {
+            burst: self.rate_limit.burst,
+            per_second: self.rate_limit.per_second,
+        })
+    }
+
+    /// How rate-limited clients are told apart
+    pub fn client_keys(&self) -> ClientKeys {
+        ClientKeys {
+            api_keys: self.rate_limit.api_keys.iter().cloned().collect(),
+            trusted_proxies: self.rate_limit.trusted_proxies,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // `Jail` closures return `figment::Error`
+mod tests {
+    use super::*;
+    use figment::Jail;
+
+    #[test]
+    fn test_file_and_environment_layers() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "systematics.toml",
+                r#"
+                    port = 8080
+                    cors_origins = ["https://systematics.example"]
+                    data_dir = "data"
+                    playground = false
+
+                    [rate_limit]
+                    burst = 60
+                    api_keys = ["partner"]
+                "#,
+            )?;
+            jail.set_env("SYSTEMATICS_PORT", "9000");
+            jail.set_env("SYSTEMATICS_INTROSPECTION", "0");
+            jail.set_env("SYSTEMATICS_SHUTDOWN_TIMEOUT", "30");
+            jail.set_env("SYSTEMATICS_LOG_FORMAT", "json");
+            jail.set_env("SYSTEMATICS_CORS_CREDENTIALS", "true");
+            jail.set_env("SYSTEMATICS_RATE_LIMIT_PER_SECOND", "2.5");
+            jail.set_env("SYSTEMATICS_RATE_LIMIT_TRUSTED_PROXIES", "2");
+            jail.set_env("SYSTEMATICS_JWT_ISSUER", "https://id.example");
+
+            let config = Config::from_figment(Config::figment(Path::new("systematics.toml")))
+                .map_err(|e| e.to_string())?;
+            assert_eq!(config.port, 9000);
+            assert_eq!(config.cors_origins, vec!["https://systematics.example"]);
+            assert!(config.cors_credentials && !config.cors_any_origin());
+            assert_eq!(config.data_dir, Some(PathBuf::from("data")));
+            assert!(!config.introspection);
+            assert!(!config.ide);
+            assert_eq!(config.shutdown_timeout(), Duration::from_secs(30));
+            assert_eq!(config.log_format, LogFormat::Json);
+            assert_eq!(
+                config.rate_limit(),
+                Some(RateLimit {
+                    burst: 60,
+                    per_second: 2.5
+                })
+            );
+            assert_eq!(
+                config.client_keys(),
+                ClientKeys {
+                    api_keys: ["partner".to_string()].into(),
+                    trusted_proxies: 2,
+                }
+            );
+
+            jail.set_env("SYSTEMATICS_RATE_LIMIT", "off");
+            jail.set_env("SYSTEMATICS_CORS_CREDENTIALS", "0");
+            jail.set_env("SYSTEMATICS_PLAYGROUND", "0");
+            let config = Config::from_figment(Config::figment(Path::new("missing.toml")))
+                .map_err(|e| e.to_string())?;
+            assert_eq!(config.port, 9000);
+            assert!(!config.ide);
+            assert_eq!(config.rate_limit(), None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        Jail::expect_with(|jail| {
+            jail.create_file("systematics.toml", "port = \"eighty\"")?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Load(_))
+            ));
+
+            jail.create_file(
+                "systematics.toml",
+                "cors_origins = [\"*\"]\ncors_credentials = true",
+            )?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Invalid(_))
+            ));
+
+            jail.create_file("systematics.toml", "ide_path = \"/graphql\"")?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Invalid(_))
+            ));
+
+            jail.create_file("systematics.toml", "webhooks = [\"ftp://ci.example\"]")?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Invalid(_))
+            ));
+
+            jail.create_file("systematics.toml", "tls_cert = \"cert.pem\"")?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Invalid(_))
+            ));
+
+            jail.create_file("systematics.toml", "[rate_limit]\nburst = 0")?;
+            let figment = Config::figment(Path::new("systematics.toml"));
+            assert!(matches!(
+                Config::from_figment(figment),
+                Err(ConfigError::Invalid(_))
+            ));
+            Ok(())
+        });
+    }
+}
@@ -38,13 +38,16 @@ impl Point3d {
 // Anchor Types - The fundamental objects we map TO
 // =============================================================================
 
-/// Order: the system level (1-12)
+/// Order: the system level (1-12 canonical; custom systems may use higher values)
 /// Order-level entries (CoherenceAttribute, TermDesignation, etc.) reference this.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Order {
     pub id: String,
-    /// System order value (1-12)
+    /// System order value (1-12 canonical)
     pub value: u8,
+    /// User ID of the creator of a custom system (none for canonical systems)
+    #[serde(default)]
+    pub created_by: Option<String>,
 }
 
 impl Order {
@@ -52,9 +55,15 @@ impl Order {
         Self {
             id: format!("order_{}", value),
             value,
+            created_by: None,
         }
     }
 
+    /// Whether this is one of the twelve canonical systems
+    pub fn is_canonical(&self) -> bool {
+        (1..=12).contains(&self.value)
+    }
+
     /// Get the standard name for this order
     pub fn standard_name(&self) -> Option<&'static str> {
         match self.value {
@@ -134,15 +143,65 @@ impl Location {
 // Semantic Content - Reusable vocabulary elements
 // =============================================================================
 
+/// ResearchStatus records how well-established a piece of vocabulary is.
+/// Variants are ordered from most to least established.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ResearchStatus {
+    /// Published in Bennett's works or the curated sources
+    #[default]
+    Canonical,
+    /// In use but not yet confirmed against the sources
+    Provisional,
+    /// Placeholder awaiting research
+    NeedsResearch,
+}
+
+/// Citation records where a piece of content was published.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Citation {
+    pub author: String,
+    pub book: String,
+    /// Page number, where known
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+impl Citation {
+    pub fn new(author: impl Into<String>, book: impl Into<String>) -> Self {
+        Self {
+            author: author.into(),
+            book: book.into(),
+            page: None,
+        }
+    }
+
+    /// Set the page number
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+}
+
 /// Character is the semantic content, independent of structural position.
 /// Same Character can appear as a Term (at a location) or referenced by a Connective (as a link).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Character {
     pub id: String,
-    /// The vocabulary language (Canonical, Energy, Values, Society)
+    /// The vocabulary language (Canonical, Energy, Values, Society, or Custom)
     pub language: Language,
     /// The semantic value (e.g., "Will", "act1")
     pub value: String,
+    /// Research status of this vocabulary element
+    #[serde(default)]
+    pub status: ResearchStatus,
+    /// Locale of a translation (e.g., "de"); `None` for source vocabulary
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Where this vocabulary element was published
+    #[serde(default)]
+    pub source: Option<Citation>,
 }
 
 impl Character {
@@ -151,23 +210,78 @@ impl Character {
             id: id.into(),
             language,
             value: value.into(),
+            status: ResearchStatus::default(),
+            locale: None,
+            source: None,
         }
     }
 
+    /// Create a translation of another entry's display value.
+    /// The ID is the source entry's ID suffixed with `@locale` (e.g., "char_canonical_will@de").
+    pub fn translation(
+        source_id: &str,
+        locale: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        let locale = locale.into();
+        Self {
+            id: format!("{}@{}", source_id, locale),
+            language: Language::Canonical,
+            value: value.into(),
+            status: ResearchStatus::default(),
+            locale: Some(locale),
+            source: None,
+        }
+    }
+
+    /// ID of the entry this character translates, if it is a translation
+    pub fn translated_id(&self) -> Option<&str> {
+        let locale = self.locale.as_deref()?;
+        self.id.strip_suffix(locale)?.strip_suffix('@')
+    }
+
+    /// Set the research status
+    pub fn with_status(mut self, status: ResearchStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the source citation
+    pub fn with_source(mut self, source: Citation) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     /// Create a character with an auto-generated ID
     pub fn with_auto_id(language: Language, value: impl Into<String>) -> Self {
         let value = value.into();
         let id = format!(
             "char_{}_{}",
-            language.to_string().to_lowercase(),
+            language.slug(),
             value.to_lowercase().replace(' ', "_")
         );
         Self {
             id,
             language,
             value,
+            status: ResearchStatus::default(),
+            locale: None,
+            source: None,
+        }
+    }
+
+    /// Create the character naming a connective link in a non-canonical vocabulary
+    pub fn for_connective(language: Language, link_id: &str, value: impl Into<String>) -> Self {
+        Self {
+            id: Character::connective_id(&language, link_id),
+            ..Character::with_auto_id(language, value)
         }
     }
+
+    /// ID of the character naming a connective link in a non-canonical vocabulary
+    pub fn connective_id(language: &Language, link_id: &str) -> String {
+        format!("char_{}_{}", language.slug(), link_id)
+    }
 }
 
 // =============================================================================
@@ -232,6 +346,7 @@ impl SystemName {
 
 /// CoherenceAttribute is a per-order attribute describing the coherence quality.
 /// For example, Order 3 has coherence "Dynamism".
+/// An order has one primary attribute and may have any number of alternates.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CoherenceAttribute {
     pub id: String,
@@ -239,6 +354,12 @@ pub struct CoherenceAttribute {
     pub order: String,
     /// The coherence value (e.g., "Dynamism")
     pub value: String,
+    /// Where this attribute was published
+    #[serde(default)]
+    pub source: Option<Citation>,
+    /// False for the order's primary attribute
+    #[serde(default)]
+    pub alternate: bool,
 }
 
 impl CoherenceAttribute {
@@ -247,6 +368,8 @@ impl CoherenceAttribute {
             id: id.into(),
             order,
             value: value.into(),
+            source: None,
+            alternate: false,
         }
     }
 
@@ -256,9 +379,26 @@ impl CoherenceAttribute {
             id: format!("coherence_{}", order_value),
             order: format!("order_{}", order_value),
             value: value.into(),
+            source: None,
+            alternate: false,
         }
     }
 
+    /// Create the `index`-th (from 1) alternate attribute for a given order value
+    pub fn alternate(order_value: u8, index: u8, value: impl Into<String>) -> Self {
+        Self {
+            id: format!("coherence_{}_alt_{}", order_value, index),
+            alternate: true,
+            ..Self::with_auto_id(order_value, value)
+        }
+    }
+
+    /// Set the source citation
+    pub fn with_source(mut self, source: Citation) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     /// Extract order value from order reference ID
     pub fn order_value(&self) -> Option<u8> {
         self.order
@@ -276,6 +416,9 @@ pub struct TermDesignation {
     pub order: String,
     /// The designation value (e.g., "Impulses", "Sources", "Limits")
     pub value: String,
+    /// Where this designation was published
+    #[serde(default)]
+    pub source: Option<Citation>,
 }
 
 impl TermDesignation {
@@ -284,6 +427,7 @@ impl TermDesignation {
             id: id.into(),
             order,
             value: value.into(),
+            source: None,
         }
     }
 
@@ -293,9 +437,16 @@ impl TermDesignation {
             id: format!("term_des_{}", order_value),
             order: format!("order_{}", order_value),
             value: value.into(),
+            source: None,
         }
     }
 
+    /// Set the source citation
+    pub fn with_source(mut self, source: Citation) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     /// Extract order value from order reference ID
     pub fn order_value(&self) -> Option<u8> {
         self.order
@@ -341,6 +492,54 @@ impl ConnectiveDesignation {
     }
 }
 
+/// Sequence is a canonical process order through the positions of a system
+/// (e.g., the Ennead's inner lines 1-4-2-8-5-7), as distinct from positional order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sequence {
+    pub id: String,
+    /// References Order entry ID
+    pub order: String,
+    /// The sequence name (e.g., "Inner Lines")
+    pub name: String,
+    /// References Location entry IDs, in process order
+    pub locations: Vec<String>,
+}
+
+impl Sequence {
+    /// Create a sequence with an ID generated from its name, visiting `positions` in turn
+    pub fn with_auto_id(order_value: u8, name: impl Into<String>, positions: &[u8]) -> Self {
+        let name = name.into();
+        Self {
+            id: format!(
+                "seq_{}_{}",
+                order_value,
+                name.to_lowercase().replace(' ', "_")
+            ),
+            order: format!("order_{}", order_value),
+            name,
+            locations: positions
+                .iter()
+                .map(|p| format!("loc_{}_{}", order_value, p))
+                .collect(),
+        }
+    }
+
+    /// Extract order value from order reference ID
+    pub fn order_value(&self) -> Option<u8> {
+        self.order
+            .strip_prefix("order_")
+            .and_then(|s| s.parse().ok())
+    }
+
+    /// Extract the position values visited, in process order
+    pub fn position_values(&self) -> Vec<u8> {
+        self.locations
+            .iter()
+            .filter_map(|l| l.rsplit('_').next()?.parse().ok())
+            .collect()
+    }
+}
+
 // =============================================================================
 // Location-Level Entries - Reference Location anchor
 // =============================================================================
@@ -354,6 +553,9 @@ pub struct Term {
     pub location: String,
     /// ID of the Character entry this term references
     pub character: String,
+    /// Research status of this term's placement
+    #[serde(default)]
+    pub status: ResearchStatus,
 }
 
 impl Term {
@@ -366,6 +568,7 @@ impl Term {
             id: id.into(),
             location: location.into(),
             character: character.into(),
+            status: ResearchStatus::default(),
         }
     }
 
@@ -376,9 +579,30 @@ impl Term {
             id: format!("term_{}_{}", order, position),
             location: format!("loc_{}_{}", order, position),
             character,
+            status: ResearchStatus::default(),
         }
     }
 
+    /// Create a term in a non-canonical vocabulary, whose ID is suffixed with the language
+    /// so it can sit alongside the canonical term at the same location
+    pub fn with_language_id(
+        order: u8,
+        position: u8,
+        language: &Language,
+        character: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: format!("term_{}_{}_{}", order, position, language.slug()),
+            ..Self::with_auto_id(order, position, character)
+        }
+    }
+
+    /// Set the research status
+    pub fn with_status(mut self, status: ResearchStatus) -> Self {
+        self.status = status;
+        self
+    }
+
     /// Extract order value from location reference ID
     pub fn order_value(&self) -> Option<u8> {
         self.location
@@ -505,6 +729,141 @@ impl Colour {
     }
 }
 
+// =============================================================================
+// Instance Overlays - A system applied to a concrete domain
+// =============================================================================
+
+/// Instance is a named application of a system to a domain
+/// (e.g., a project-planning Tetrad). Its labels are InstanceLabel entries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instance {
+    pub id: String,
+    /// References Order entry ID
+    pub order: String,
+    /// The instance name (e.g., "Project Planning")
+    pub name: String,
+}
+
+impl Instance {
+    pub fn new(id: impl Into<String>, order: String, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            order,
+            name: name.into(),
+        }
+    }
+
+    /// Create an instance with an ID generated from its name
+    pub fn with_auto_id(order_value: u8, name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            id: format!("instance_{}", name.to_lowercase().replace(' ', "_")),
+            order: format!("order_{}", order_value),
+            name,
+        }
+    }
+
+    /// Extract order value from order reference ID
+    pub fn order_value(&self) -> Option<u8> {
+        self.order
+            .strip_prefix("order_")
+            .and_then(|s| s.parse().ok())
+    }
+}
+
+/// InstanceLabel binds a user-supplied label to a location on behalf of an Instance.
+/// It sits alongside the canonical Term at that location without replacing it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceLabel {
+    pub id: String,
+    /// References Instance entry ID
+    pub instance: String,
+    /// References Location entry ID
+    pub location: String,
+    /// The label (e.g., "Vision")
+    pub value: String,
+}
+
+impl InstanceLabel {
+    /// Create a label for an instance at a given order and position
+    pub fn with_auto_id(
+        instance: &Instance,
+        order: u8,
+        position: u8,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: format!("{}_{}_{}", instance.id, order, position),
+            instance: instance.id.clone(),
+            location: format!("loc_{}_{}", order, position),
+            value: value.into(),
+        }
+    }
+
+    /// Extract order value from location reference ID
+    pub fn order_value(&self) -> Option<u8> {
+        self.location
+            .strip_prefix("loc_")
+            .and_then(|s| s.split('_').next())
+            .and_then(|s| s.parse().ok())
+    }
+
+    /// Extract position value from location reference ID
+    pub fn position_value(&self) -> Option<u8> {
+        self.location
+            .strip_prefix("loc_")
+            .and_then(|s| s.split('_').nth(1))
+            .and_then(|s| s.parse().ok())
+    }
+}
+
+// =============================================================================
+// Annotations - User commentary on the graph
+// =============================================================================
+
+/// Annotation is a user note attached to an entry or link (e.g., a study group's
+/// commentary on a term or connective)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: String,
+    /// References the annotated Entry or Link ID
+    pub target: String,
+    /// The note itself
+    pub text: String,
+    /// Who wrote the note
+    pub author: String,
+    /// User ID of the author, when the note was added by a signed-in user
+    #[serde(default)]
+    pub author_id: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(
+        id: impl Into<String>,
+        target: impl Into<String>,
+        text: impl Into<String>,
+        author: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            target: target.into(),
+            text: text.into(),
+            author: author.into(),
+            author_id: None,
+        }
+    }
+
+    /// Create the `number`th annotation of the graph, with a sequential ID
+    pub fn with_auto_id(
+        number: usize,
+        target: impl Into<String>,
+        text: impl Into<String>,
+        author: impl Into<String>,
+    ) -> Self {
+        Self::new(format!("annotation_{}", number), target, text, author)
+    }
+}
+
 // =============================================================================
 // Entry Sum Type
 // =============================================================================
@@ -524,6 +883,7 @@ pub enum Entry {
     CoherenceAttribute(CoherenceAttribute),
     TermDesignation(TermDesignation),
     ConnectiveDesignation(ConnectiveDesignation),
+    Sequence(Sequence),
 
     // Location-level entries (reference Location)
     Term(Term),
@@ -532,6 +892,13 @@ pub enum Entry {
 
     // Semantic content (reusable)
     Character(Character),
+
+    // Instance overlays (reference Order / Location)
+    Instance(Instance),
+    InstanceLabel(InstanceLabel),
+
+    // User commentary (references any Entry or Link)
+    Annotation(Annotation),
 }
 
 impl Entry {
@@ -545,10 +912,14 @@ impl Entry {
             Entry::CoherenceAttribute(e) => &e.id,
             Entry::TermDesignation(e) => &e.id,
             Entry::ConnectiveDesignation(e) => &e.id,
+            Entry::Sequence(e) => &e.id,
             Entry::Term(e) => &e.id,
             Entry::Colour(e) => &e.id,
             Entry::Coordinate(e) => &e.id,
             Entry::Character(e) => &e.id,
+            Entry::Instance(e) => &e.id,
+            Entry::InstanceLabel(e) => &e.id,
+            Entry::Annotation(e) => &e.id,
         }
     }
 
@@ -565,10 +936,14 @@ impl Entry {
             Entry::CoherenceAttribute(e) => e.order_value(),
             Entry::TermDesignation(e) => e.order_value(),
             Entry::ConnectiveDesignation(e) => e.order_value(),
+            Entry::Sequence(e) => e.order_value(),
             Entry::Term(e) => e.order_value(),
             Entry::Colour(e) => e.order_value(),
             Entry::Coordinate(e) => e.order_value(),
             Entry::Character(_) => None,
+            Entry::Instance(e) => e.order_value(),
+            Entry::InstanceLabel(e) => e.order_value(),
+            Entry::Annotation(_) => None,
         }
     }
 
@@ -581,6 +956,7 @@ impl Entry {
             Entry::Term(e) => e.position_value(),
             Entry::Colour(e) => e.position_value(),
             Entry::Coordinate(e) => e.position_value(),
+            Entry::InstanceLabel(e) => e.position_value(),
             _ => None,
         }
     }
@@ -601,6 +977,7 @@ impl Entry {
                 | Entry::CoherenceAttribute(_)
                 | Entry::TermDesignation(_)
                 | Entry::ConnectiveDesignation(_)
+                | Entry::Sequence(_)
         )
     }
 
@@ -616,6 +993,11 @@ impl Entry {
     pub fn is_semantic(&self) -> bool {
         matches!(self, Entry::Character(_))
     }
+
+    /// Check if this entry belongs to an instance overlay
+    pub fn is_instance(&self) -> bool {
+        matches!(self, Entry::Instance(_) | Entry::InstanceLabel(_))
+    }
 }
 
 #[cfg(test)]
@@ -628,6 +1010,8 @@ mod tests {
         assert_eq!(order.id, "order_3");
         assert_eq!(order.value, 3);
         assert_eq!(order.standard_name(), Some("Triad"));
+        assert!(order.is_canonical());
+        assert!(!Order::new(13).is_canonical());
     }
 
     #[test]
@@ -655,6 +1039,79 @@ mod tests {
         assert_eq!(char.language, Language::Canonical);
     }
 
+    #[test]
+    fn test_translation_creation() {
+        let char = Character::translation("char_canonical_will", "de", "Wille");
+        assert_eq!(char.id, "char_canonical_will@de");
+        assert_eq!(char.locale.as_deref(), Some("de"));
+        assert_eq!(char.translated_id(), Some("char_canonical_will"));
+
+        let source = Character::with_auto_id(Language::Canonical, "Will");
+        assert_eq!(source.translated_id(), None);
+    }
+
+    #[test]
+    fn test_alternate_coherence() {
+        let alt = CoherenceAttribute::alternate(3, 1, "Relatedness");
+        assert_eq!(alt.id, "coherence_3_alt_1");
+        assert_eq!(alt.order_value(), Some(3));
+        assert!(alt.alternate);
+    }
+
+    #[test]
+    fn test_instance_creation() {
+        let instance = Instance::with_auto_id(4, "Project Planning");
+        assert_eq!(instance.id, "instance_project_planning");
+        assert_eq!(instance.order_value(), Some(4));
+
+        let label = InstanceLabel::with_auto_id(&instance, 4, 2, "Team");
+        assert_eq!(label.id, "instance_project_planning_4_2");
+        assert_eq!(label.location, "loc_4_2");
+        assert_eq!(label.position_value(), Some(2));
+
+        let entry = Entry::InstanceLabel(label);
+        assert_eq!(entry.order(), Some(4));
+        assert!(entry.is_instance());
+    }
+
+    #[test]
+    fn test_sequence_creation() {
+        let sequence = Sequence::with_auto_id(9, "Inner Lines", &[1, 4, 2, 8, 5, 7]);
+        assert_eq!(sequence.id, "seq_9_inner_lines");
+        assert_eq!(sequence.locations[1], "loc_9_4");
+        assert_eq!(sequence.position_values(), vec![1, 4, 2, 8, 5, 7]);
+
+        let entry = Entry::Sequence(sequence);
+        assert_eq!(entry.order(), Some(9));
+        assert!(entry.is_order_level());
+    }
+
+    #[test]
+    fn test_citation() {
+        let source = Citation::new("J. G. Bennett", "Elementary Systematics").with_page(12);
+        let char = Character::with_auto_id(Language::Canonical, "Will").with_source(source.clone());
+        assert_eq!(char.source.as_ref().unwrap().page, Some(12));
+
+        let coherence = CoherenceAttribute::with_auto_id(3, "Dynamism");
+        assert_eq!(coherence.source, None);
+        assert!(!coherence.alternate);
+        assert_eq!(coherence.with_source(source.clone()).source, Some(source));
+    }
+
+    #[test]
+    fn test_research_status_defaults_to_canonical() {
+        let char = Character::with_auto_id(Language::Canonical, "Will");
+        assert_eq!(char.status, ResearchStatus::Canonical);
+
+        let char = Character::with_auto_id(Language::Canonical, "Step 1 Needs Research")
+            .with_status(ResearchStatus::NeedsResearch);
+        assert_eq!(char.status, ResearchStatus::NeedsResearch);
+
+        let term = Term::with_auto_id(9, 1, "char_canonical_term_1")
+            .with_status(ResearchStatus::NeedsResearch);
+        assert_eq!(term.status, ResearchStatus::NeedsResearch);
+    }
+
     #[test]
     fn test_term_creation() {
         let term = Term::with_auto_id(3, 1, "char_will");
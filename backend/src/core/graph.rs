@@ -7,20 +7,115 @@
 //! - **Anchor Queries**: Query the fundamental graph structure (Order, Position, Location)
 //! - **Systematic Queries**: Query semantic/categorical content mapped to anchors
 
+use std::collections::{HashMap, HashSet, VecDeque};
+
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use super::entries::{
-    Character, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate, Entry, Location,
-    Order, Position, SystemName, Term, TermDesignation,
+    Annotation, Character, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate, Entry,
+    Instance, InstanceLabel, Location, Order, Position, ResearchStatus, Sequence, SystemName, Term,
+    TermDesignation,
 };
-use super::language::Language;
+use super::language::{Language, VocabularyPack};
 use super::links::{Link, LinkType};
 
+/// Tally of vocabulary by research status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResearchCoverage {
+    pub canonical: usize,
+    pub provisional: usize,
+    pub needs_research: usize,
+}
+
+impl ResearchCoverage {
+    fn count(&mut self, status: ResearchStatus) {
+        match status {
+            ResearchStatus::Canonical => self.canonical += 1,
+            ResearchStatus::Provisional => self.provisional += 1,
+            ResearchStatus::NeedsResearch => self.needs_research += 1,
+        }
+    }
+
+    /// Total number of items counted
+    pub fn total(&self) -> usize {
+        self.canonical + self.provisional + self.needs_research
+    }
+
+    /// Fraction of items that are not placeholders (0.0-1.0)
+    pub fn researched_ratio(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        (self.canonical + self.provisional) as f64 / self.total() as f64
+    }
+}
+
+/// What a search hit matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchHitKind {
+    /// A term, by its character's value
+    Term,
+    /// A connective, by its character's value
+    Connective,
+    /// A character not placed at any location or connective
+    Character,
+    SystemName,
+    CoherenceAttribute,
+    TermDesignation,
+    ConnectiveDesignation,
+}
+
+/// A search match, with the order/position that owns it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    /// ID of the matched entry or link
+    pub id: String,
+    /// The matched text
+    pub value: String,
+    pub order: Option<u8>,
+    /// Position of a term (None for order-level hits and connectives)
+    pub position: Option<u8>,
+}
+
+/// Two systems compared position by position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemComparison<'a> {
+    pub order_a: u8,
+    pub order_b: u8,
+    /// Positions both systems have, with the location in each
+    pub aligned: Vec<(u8, &'a Location, &'a Location)>,
+    /// Character values used by terms or connectives of both systems (sorted)
+    pub shared_values: Vec<String>,
+    /// Connectives of system A paired with a connective of system B joining the
+    /// same two positions
+    pub projections: Vec<(&'a Link, &'a Link)>,
+}
+
+/// Index of the lines and connectives joining each pair of positions of an order,
+/// keyed by (order, lower position, higher position)
+#[derive(Debug, Clone, Default)]
+struct CorrespondenceIndex {
+    by_pair: HashMap<(u8, u8, u8), Vec<String>>,
+    pair_of: HashMap<String, (u8, u8, u8)>,
+}
+
 /// Graph is the primary container for the property graph (AD4M: Perspective).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Graph {
     pub entries: Vec<Entry>,
     pub links: Vec<Link>,
+    /// Kept current by `add_link` and `merge`; rebuilt by `reindex`
+    #[serde(skip)]
+    correspondences: CorrespondenceIndex,
+}
+
+/// Graphs are equal when their entries and links are, in order
+impl PartialEq for Graph {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries && self.links == other.links
+    }
 }
 
 impl Graph {
@@ -35,9 +130,44 @@ impl Graph {
 
     /// Add a link to the graph
     pub fn add_link(&mut self, link: Link) {
+        self.index_link(&link);
         self.links.push(link);
     }
 
+    /// SHA-256 of the serialized entries and links, as hex; equal graphs share a hash
+    pub fn content_hash(&self) -> String {
+        let json = serde_json::to_vec(self).expect("graph serializes to JSON");
+        format!("{:x}", Sha256::digest(json))
+    }
+
+    /// Rebuild the correspondence index, after editing `links` or `entries` directly
+    pub fn reindex(&mut self) {
+        self.correspondences = CorrespondenceIndex::default();
+        for link in std::mem::take(&mut self.links) {
+            self.add_link(link);
+        }
+    }
+
+    /// The order and (ascending) positions a line or connective joins
+    fn link_pair(&self, link: &Link) -> Option<(u8, u8, u8)> {
+        if link.is_grouping() {
+            return None;
+        }
+        let base = self.get_entry(link.base_single()?)?;
+        let target = self.get_entry(link.target_single()?)?;
+        let (a, b) = (base.position()?, target.position()?);
+        Some((base.order()?, a.min(b), a.max(b)))
+    }
+
+    fn index_link(&mut self, link: &Link) {
+        let Some(pair) = self.link_pair(link) else {
+            return;
+        };
+        let index = &mut self.correspondences;
+        index.by_pair.entry(pair).or_default().push(link.id.clone());
+        index.pair_of.insert(link.id.clone(), pair);
+    }
+
     /// Find an entry by ID
     pub fn get_entry(&self, id: &str) -> Option<&Entry> {
         self.entries.iter().find(|e| e.id() == id)
@@ -48,6 +178,229 @@ impl Graph {
         self.links.iter().find(|l| l.id == id)
     }
 
+    /// Merge another graph into this one.
+    /// Entries and links whose ID already exists replace the original in place;
+    /// new ones are appended, and the correspondence index is rebuilt if links changed.
+    pub fn merge(&mut self, other: Graph) {
+        for entry in other.entries {
+            match self.entries.iter_mut().find(|e| e.id() == entry.id()) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+        if other.links.is_empty() {
+            return;
+        }
+        for link in other.links {
+            match self.links.iter_mut().find(|l| l.id == link.id) {
+                Some(existing) => *existing = link,
+                None => self.links.push(link),
+            }
+        }
+        self.reindex();
+    }
+
+    /// The entries and links that are new or different from those in `base`: the
+    /// fragment that, merged into `base`, gives this graph (removals are not represented)
+    pub fn changes_since(&self, base: &Graph) -> Graph {
+        let entries: HashMap<&str, &Entry> = base.entries.iter().map(|e| (e.id(), e)).collect();
+        let links: HashMap<&str, &Link> = base.links.iter().map(|l| (l.id.as_str(), l)).collect();
+        Graph {
+            entries: self
+                .entries
+                .iter()
+                .filter(|e| entries.get(e.id()) != Some(e))
+                .cloned()
+                .collect(),
+            links: self
+                .links
+                .iter()
+                .filter(|l| links.get(l.id.as_str()) != Some(l))
+                .cloned()
+                .collect(),
+            correspondences: CorrespondenceIndex::default(),
+        }
+    }
+
+    /// Add (or replace) the translation of an entry's display value into a locale
+    pub fn add_translation(&mut self, source_id: &str, locale: &str, value: &str) {
+        let mut fragment = Graph::new();
+        fragment.add_entry(Entry::Character(Character::translation(
+            source_id, locale, value,
+        )));
+        self.merge(fragment);
+    }
+
+    /// Copy of the graph with display values (characters, system names, coherences and
+    /// designations) translated into `locale` wherever a translation exists
+    pub fn localized(&self, locale: &str) -> Graph {
+        let mut graph = self.clone();
+        for entry in &mut graph.entries {
+            let Some(translated) = self.translation(entry.id(), locale) else {
+                continue;
+            };
+            let value = translated.value.clone();
+            match entry {
+                Entry::Character(c) if c.locale.is_none() => c.value = value,
+                Entry::SystemName(e) => e.value = value,
+                Entry::CoherenceAttribute(e) => e.value = value,
+                Entry::TermDesignation(e) => e.value = value,
+                Entry::ConnectiveDesignation(e) => e.value = value,
+                Entry::Sequence(e) => e.name = value,
+                _ => {}
+            }
+        }
+        graph
+    }
+
+    /// Register a vocabulary by name, adding a Character and Term for each value in the pack.
+    /// Built-in names (e.g., "Energy") resolve to their variant; any other name becomes
+    /// `Language::Custom`. Registering again replaces terms at the same locations.
+    /// Blank values and values for locations that do not exist in the graph are skipped, as
+    /// are connective names for pairs without a connective (and all of them for the canonical
+    /// vocabulary).
+    pub fn register_vocabulary(&mut self, language_name: &str, pack: &VocabularyPack) -> Language {
+        let language = self
+            .vocabularies()
+            .into_iter()
+            .find(|l| l.to_string().eq_ignore_ascii_case(language_name))
+            .unwrap_or_else(|| Language::from_name(language_name));
+        let mut fragment = Graph::new();
+        for (&order, values) in &pack.terms {
+            for (idx, value) in values.iter().enumerate() {
+                let position = (idx + 1) as u8;
+                if value.trim().is_empty() || self.location(order, position).is_none() {
+                    continue;
+                }
+                let character = Character::with_auto_id(language.clone(), value)
+                    .with_status(ResearchStatus::Provisional);
+                let term = if language == Language::Canonical {
+                    Term::with_auto_id(order, position, character.id.clone())
+                } else {
+                    Term::with_language_id(order, position, &language, character.id.clone())
+                };
+                fragment.add_entry(Entry::Character(character));
+                fragment.add_entry(Entry::Term(term.with_status(ResearchStatus::Provisional)));
+            }
+        }
+        if language != Language::Canonical {
+            for (&order, values) in &pack.connectives {
+                let mut values = values.iter();
+                for (i, j) in (1..=order).flat_map(|i| ((i + 1)..=order).map(move |j| (i, j))) {
+                    let Some(value) = values.next() else { break };
+                    if value.trim().is_empty() {
+                        continue;
+                    }
+                    let Some(link) = self
+                        .links_between(order, i, j)
+                        .into_iter()
+                        .find(|l| l.is_connective())
+                    else {
+                        continue;
+                    };
+                    let character = Character::for_connective(language.clone(), &link.id, value)
+                        .with_status(ResearchStatus::Provisional);
+                    fragment.add_entry(Entry::Character(character));
+                }
+            }
+        }
+        self.merge(fragment);
+        language
+    }
+
+    /// Check referential integrity, returning a description of each problem found.
+    /// An empty result means every reference resolves.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let mut entry_ids = HashSet::new();
+        for entry in &self.entries {
+            if !entry_ids.insert(entry.id()) {
+                problems.push(format!("duplicate entry ID '{}'", entry.id()));
+            }
+        }
+        let mut link_ids = HashSet::new();
+        for link in &self.links {
+            if !link_ids.insert(link.id.as_str()) {
+                problems.push(format!("duplicate link ID '{}'", link.id));
+            }
+        }
+
+        let missing = |owner: &str, field: &str, id: &str| {
+            (!entry_ids.contains(id))
+                .then(|| format!("{} references missing {} '{}'", owner, field, id))
+        };
+
+        for entry in &self.entries {
+            match entry {
+                Entry::Location(l) => {
+                    problems.extend(missing(&l.id, "order", &l.order));
+                    problems.extend(missing(&l.id, "position", &l.position));
+                }
+                Entry::SystemName(e) => problems.extend(missing(&e.id, "order", &e.order)),
+                Entry::CoherenceAttribute(e) => problems.extend(missing(&e.id, "order", &e.order)),
+                Entry::TermDesignation(e) => problems.extend(missing(&e.id, "order", &e.order)),
+                Entry::ConnectiveDesignation(e) => {
+                    problems.extend(missing(&e.id, "order", &e.order))
+                }
+                Entry::Sequence(e) => {
+                    problems.extend(missing(&e.id, "order", &e.order));
+                    for location in &e.locations {
+                        problems.extend(missing(&e.id, "location", location));
+                    }
+                }
+                Entry::Term(t) => {
+                    problems.extend(missing(&t.id, "location", &t.location));
+                    problems.extend(missing(&t.id, "character", &t.character));
+                }
+                Entry::Coordinate(c) => problems.extend(missing(&c.id, "location", &c.location)),
+                Entry::Colour(c) => problems.extend(missing(&c.id, "location", &c.location)),
+                Entry::Character(c) => {
+                    if let Some(source) = c.translated_id() {
+                        problems.extend(missing(&c.id, "translated entry", source));
+                    }
+                }
+                Entry::Instance(e) => problems.extend(missing(&e.id, "order", &e.order)),
+                Entry::InstanceLabel(e) => {
+                    problems.extend(missing(&e.id, "instance", &e.instance));
+                    problems.extend(missing(&e.id, "location", &e.location));
+                }
+                Entry::Annotation(e) => {
+                    if !link_ids.contains(e.target.as_str()) {
+                        problems.extend(missing(&e.id, "target", &e.target));
+                    }
+                }
+                Entry::Order(_) | Entry::Position(_) => {}
+            }
+        }
+
+        for link in &self.links {
+            for id in link.bases() {
+                problems.extend(missing(&link.id, "base", id));
+            }
+            // A grouping's target is its enclosing grouping, which is a link
+            if link.is_grouping() {
+                if let Some(parent) = link.parent_grouping() {
+                    if !link_ids.contains(parent) {
+                        problems.push(format!(
+                            "{} references missing parent grouping '{}'",
+                            link.id, parent
+                        ));
+                    }
+                }
+            } else {
+                for id in link.targets() {
+                    problems.extend(missing(&link.id, "target", id));
+                }
+            }
+            if let Some(char_id) = link.character_id() {
+                problems.extend(missing(&link.id, "character", char_id));
+            }
+        }
+
+        problems
+    }
+
     // ==========================================================================
     // Anchor Queries - Query the fundamental graph structure
     // ==========================================================================
@@ -158,15 +511,35 @@ impl Graph {
         })
     }
 
-    /// Get the coherence attribute for an order
-    pub fn coherence(&self, order: u8) -> Option<&CoherenceAttribute> {
-        let order_id = format!("order_{}", order);
+    /// Find the order whose system name matches `name` (case-insensitive)
+    pub fn order_by_name(&self, name: &str) -> Option<u8> {
         self.entries.iter().find_map(|e| match e {
-            Entry::CoherenceAttribute(c) if c.order == order_id => Some(c),
+            Entry::SystemName(s) if s.value.eq_ignore_ascii_case(name) => s.order_value(),
             _ => None,
         })
     }
 
+    /// Get the primary coherence attribute for an order
+    pub fn coherence(&self, order: u8) -> Option<&CoherenceAttribute> {
+        self.coherences(order).into_iter().next()
+    }
+
+    /// Get all coherence attributes for an order, primary first
+    pub fn coherences(&self, order: u8) -> Vec<&CoherenceAttribute> {
+        let order_id = format!("order_{}", order);
+        let mut coherences: Vec<&CoherenceAttribute> = self
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::CoherenceAttribute(c) if c.order == order_id => Some(c),
+                _ => None,
+            })
+            .collect();
+        // Stable, so alternates keep their insertion order
+        coherences.sort_by_key(|c| c.alternate);
+        coherences
+    }
+
     /// Get the term designation for an order
     pub fn term_designation(&self, order: u8) -> Option<&TermDesignation> {
         let order_id = format!("order_{}", order);
@@ -185,6 +558,26 @@ impl Graph {
         })
     }
 
+    /// Get the canonical sequences (process orders) of an order
+    pub fn sequences(&self, order: u8) -> Vec<&Sequence> {
+        let order_id = format!("order_{}", order);
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Sequence(s) if s.order == order_id => Some(s),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get a sequence of an order by name (case-insensitive), or its first sequence
+    /// when no name is given
+    pub fn sequence(&self, order: u8, name: Option<&str>) -> Option<&Sequence> {
+        self.sequences(order)
+            .into_iter()
+            .find(|s| name.is_none_or(|n| s.name.eq_ignore_ascii_case(n)))
+    }
+
     // -------------------- Location-Level Systematic Queries --------------------
 
     /// Get all terms for an order, optionally filtered by language of their character
@@ -212,6 +605,53 @@ impl Graph {
         }
     }
 
+    /// Get one term per position of an order in `language`, using the canonical
+    /// term where the language has none, by position
+    pub fn terms_with_fallback(&self, order: u8, language: &Language) -> Vec<&Term> {
+        let in_language = self.terms(order, Some(language.clone()));
+        let canonical = self.terms(order, Some(Language::Canonical));
+        let mut positions: Vec<u8> = self
+            .locations_for_order(order)
+            .iter()
+            .filter_map(|l| l.position_value())
+            .collect();
+        positions.sort_unstable();
+        positions
+            .into_iter()
+            .filter_map(|p| {
+                in_language
+                    .iter()
+                    .chain(&canonical)
+                    .find(|t| t.position_value() == Some(p))
+                    .copied()
+            })
+            .collect()
+    }
+
+    /// Get the terms of an order in `language` (falling back to canonical) in the
+    /// process order of `sequence`, or by position when no sequence is given
+    pub fn traversal(
+        &self,
+        order: u8,
+        sequence: Option<&Sequence>,
+        language: &Language,
+    ) -> Vec<&Term> {
+        let terms = self.terms_with_fallback(order, language);
+        let Some(sequence) = sequence else {
+            return terms;
+        };
+        sequence
+            .position_values()
+            .into_iter()
+            .filter_map(|p| {
+                terms
+                    .iter()
+                    .find(|t| t.position_value() == Some(p))
+                    .copied()
+            })
+            .collect()
+    }
+
     /// Get a specific term by order and position
     pub fn term(&self, order: u8, position: u8) -> Option<&Term> {
         let location_id = format!("loc_{}_{}", order, position);
@@ -246,6 +686,24 @@ impl Graph {
         self.get_character(&term.character)
     }
 
+    /// Get every term, in any order or language, whose character has this value
+    /// (case-insensitive)
+    pub fn terms_by_value(&self, value: &str) -> Vec<&Term> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Term(t)
+                    if self
+                        .get_character(&t.character)
+                        .is_some_and(|c| c.value.eq_ignore_ascii_case(value)) =>
+                {
+                    Some(t)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Get all coordinates for an order
     pub fn coordinates(&self, order: u8) -> Vec<&Coordinate> {
         self.entries
@@ -288,17 +746,79 @@ impl Graph {
 
     // -------------------- Character Queries --------------------
 
-    /// Get all characters for a language
+    /// Get all characters for a language (translations are excluded)
     pub fn characters(&self, language: Language) -> Vec<&Character> {
         self.entries
             .iter()
             .filter_map(|e| match e {
-                Entry::Character(c) if c.language == language => Some(c),
+                Entry::Character(c) if c.language == language && c.locale.is_none() => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// IDs of the characters an order uses for its terms and connectives
+    pub fn character_ids_in_order(&self, order: u8) -> HashSet<&str> {
+        let mut ids: HashSet<&str> = self
+            .terms(order, None)
+            .into_iter()
+            .map(|t| t.character.as_str())
+            .collect();
+        ids.extend(
+            self.connectives(order, None, None)
+                .into_iter()
+                .filter_map(|l| l.character_id()),
+        );
+        ids
+    }
+
+    /// Vocabulary languages in use: the built-in vocabularies, then any custom
+    /// vocabularies in the order they were registered
+    pub fn vocabularies(&self) -> Vec<Language> {
+        let mut languages = Language::vocabularies().to_vec();
+        for entry in &self.entries {
+            if let Entry::Character(c) = entry {
+                if c.language.is_custom() && !languages.contains(&c.language) {
+                    languages.push(c.language.clone());
+                }
+            }
+        }
+        languages
+    }
+
+    /// Get the translation of an entry's display value into a locale
+    pub fn translation(&self, id: &str, locale: &str) -> Option<&Character> {
+        self.get_character(&format!("{}@{}", id, locale))
+    }
+
+    /// Translations of an entry's display value, by locale
+    pub fn translations(&self, id: &str) -> HashMap<String, String> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Character(c) if c.translated_id() == Some(id) => {
+                    Some((c.locale.clone()?, c.value.clone()))
+                }
                 _ => None,
             })
             .collect()
     }
 
+    /// Locales with at least one translation, sorted
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Character(c) => c.locale.clone(),
+                _ => None,
+            })
+            .collect();
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+
     /// Get a character by ID
     pub fn get_character(&self, id: &str) -> Option<&Character> {
         self.entries.iter().find_map(|e| match e {
@@ -307,6 +827,197 @@ impl Graph {
         })
     }
 
+    /// Research coverage of an order's vocabulary: its terms plus the characters
+    /// labelling its connectives. Without an order, every Character is counted
+    /// except translations.
+    pub fn research_coverage(&self, order: Option<u8>) -> ResearchCoverage {
+        let mut coverage = ResearchCoverage::default();
+        match order {
+            Some(order) => {
+                // A term is only as established as the weaker of its placement and character
+                for term in self.terms(order, None) {
+                    let status = self
+                        .get_character(&term.character)
+                        .map(|c| c.status.max(term.status))
+                        .unwrap_or(term.status);
+                    coverage.count(status);
+                }
+                for link in self.connectives(order, None, None) {
+                    if let Some(c) = link.character_id().and_then(|id| self.get_character(id)) {
+                        coverage.count(c.status);
+                    }
+                }
+            }
+            None => {
+                for entry in &self.entries {
+                    match entry {
+                        Entry::Character(c) if c.locale.is_none() => coverage.count(c.status),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        coverage
+    }
+
+    /// Case-insensitive substring search over characters, system names,
+    /// coherence attributes and designations.
+    ///
+    /// `languages` restricts character matches to those vocabularies; order-level
+    /// metadata is canonical, so it only matches when Canonical is included.
+    pub fn search(&self, text: &str, languages: Option<&[Language]>) -> Vec<SearchHit> {
+        let needle = text.trim().to_lowercase();
+        if needle.is_empty() {
+            return vec![];
+        }
+        let matches = |value: &str| value.to_lowercase().contains(&needle);
+        let language_allowed =
+            |language: &Language| languages.is_none_or(|langs| langs.contains(language));
+        let matching_character = |id: &str| {
+            self.get_character(id)
+                .filter(|c| language_allowed(&c.language) && matches(&c.value))
+        };
+        let hit = |kind, id: &str, value: &str, order, position| SearchHit {
+            kind,
+            id: id.to_string(),
+            value: value.to_string(),
+            order,
+            position,
+        };
+
+        let mut hits = Vec::new();
+        let mut placed = HashSet::new();
+        for entry in &self.entries {
+            let (kind, id, value) = match entry {
+                Entry::Term(t) => {
+                    placed.insert(t.character.as_str());
+                    if let Some(c) = matching_character(&t.character) {
+                        hits.push(hit(
+                            SearchHitKind::Term,
+                            &t.id,
+                            &c.value,
+                            t.order_value(),
+                            t.position_value(),
+                        ));
+                    }
+                    continue;
+                }
+                Entry::SystemName(n) => (SearchHitKind::SystemName, &n.id, &n.value),
+                Entry::CoherenceAttribute(c) => {
+                    (SearchHitKind::CoherenceAttribute, &c.id, &c.value)
+                }
+                Entry::TermDesignation(d) => (SearchHitKind::TermDesignation, &d.id, &d.value),
+                Entry::ConnectiveDesignation(d) => {
+                    (SearchHitKind::ConnectiveDesignation, &d.id, &d.value)
+                }
+                _ => continue,
+            };
+            if language_allowed(&Language::Canonical) && matches(value) {
+                hits.push(hit(kind, id, value, entry.order(), None));
+            }
+        }
+
+        for link in self.links.iter().filter(|l| l.is_connective()) {
+            let Some(char_id) = link.character_id() else {
+                continue;
+            };
+            placed.insert(char_id);
+            if let Some(c) = matching_character(char_id) {
+                let order = link
+                    .base_single()
+                    .and_then(|id| self.get_entry(id))
+                    .and_then(|e| e.order());
+                hits.push(hit(
+                    SearchHitKind::Connective,
+                    &link.id,
+                    &c.value,
+                    order,
+                    None,
+                ));
+            }
+        }
+
+        for entry in &self.entries {
+            if let Entry::Character(c) = entry {
+                if c.locale.is_none()
+                    && !placed.contains(c.id.as_str())
+                    && language_allowed(&c.language)
+                    && matches(&c.value)
+                {
+                    hits.push(hit(SearchHitKind::Character, &c.id, &c.value, None, None));
+                }
+            }
+        }
+        hits
+    }
+
+    // -------------------- Annotation Queries --------------------
+
+    /// Get all annotations, in the order they were added
+    pub fn all_annotations(&self) -> Vec<&Annotation> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Annotation(a) => Some(a),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the annotations of an entry or link, in the order they were added
+    pub fn annotations(&self, target_id: &str) -> Vec<&Annotation> {
+        self.all_annotations()
+            .into_iter()
+            .filter(|a| a.target == target_id)
+            .collect()
+    }
+
+    // -------------------- Instance Queries --------------------
+
+    /// Get all instances, optionally only those applying a given order
+    pub fn instances(&self, order: Option<u8>) -> Vec<&Instance> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Instance(i) if order.is_none() || i.order_value() == order => Some(i),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get an instance by ID
+    pub fn instance(&self, id: &str) -> Option<&Instance> {
+        self.entries.iter().find_map(|e| match e {
+            Entry::Instance(i) if i.id == id => Some(i),
+            _ => None,
+        })
+    }
+
+    /// Get an instance's labels, by position
+    pub fn instance_labels(&self, instance_id: &str) -> Vec<&InstanceLabel> {
+        let mut labels: Vec<&InstanceLabel> = self
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::InstanceLabel(l) if l.instance == instance_id => Some(l),
+                _ => None,
+            })
+            .collect();
+        labels.sort_by_key(|l| l.position_value());
+        labels
+    }
+
+    /// Get the labels every instance binds to a location
+    pub fn instance_labels_at_location(&self, location_id: &str) -> Vec<&InstanceLabel> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::InstanceLabel(l) if l.location == location_id => Some(l),
+                _ => None,
+            })
+            .collect()
+    }
+
     // -------------------- Cross-Cutting Systematic Queries --------------------
 
     /// Get all entries at a specific order+position (the "slice" / fiber)
@@ -388,6 +1099,42 @@ impl Graph {
             .collect()
     }
 
+    /// Get the character naming a connective in `language`, falling back to its
+    /// canonical character
+    pub fn connective_character(&self, link: &Link, language: &Language) -> Option<&Character> {
+        let translated = (*language != Language::Canonical)
+            .then(|| self.get_character(&Character::connective_id(language, &link.id)))
+            .flatten();
+        translated.or_else(|| link.character_id().and_then(|id| self.get_character(id)))
+    }
+
+    /// Get the n×n connective matrix of an order: cell [i][j] holds the connective
+    /// between positions i+1 and j+1 in either direction (the diagonal holds
+    /// self-relations such as the Monad's)
+    pub fn connective_matrix(&self, order: u8) -> Vec<Vec<Option<&Link>>> {
+        let n = order as usize;
+        let mut matrix = vec![vec![None; n]; n];
+        for link in self.connectives(order, None, None) {
+            let positions = link
+                .base_single()
+                .and_then(|id| self.get_entry(id))
+                .and_then(|e| e.position())
+                .zip(
+                    link.target_single()
+                        .and_then(|id| self.get_entry(id))
+                        .and_then(|e| e.position()),
+                );
+            if let Some((base, target)) = positions {
+                let (i, j) = (base as usize - 1, target as usize - 1);
+                if i < n && j < n {
+                    matrix[i][j] = Some(link);
+                    matrix[j][i] = Some(link);
+                }
+            }
+        }
+        matrix
+    }
+
     /// Get all connectives involving a specific location
     pub fn connectives_for_location(&self, location_id: &str) -> Vec<&Link> {
         self.links
@@ -400,6 +1147,99 @@ impl Graph {
             .collect()
     }
 
+    /// Get the locations linked to a location by a connective, each with the linking
+    /// connective, by position (a self-relation such as the Monad's yields the location itself)
+    pub fn neighbors(&self, location_id: &str) -> Vec<(&Location, &Link)> {
+        let mut neighbors: Vec<(&Location, &Link)> = self
+            .connectives_for_location(location_id)
+            .into_iter()
+            .filter_map(|link| {
+                let other = if link.base_single() == Some(location_id) {
+                    link.target_single()?
+                } else {
+                    link.base_single()?
+                };
+                match self.get_entry(other)? {
+                    Entry::Location(l) => Some((l, link)),
+                    _ => None,
+                }
+            })
+            .collect();
+        neighbors.sort_by_key(|(l, _)| l.position_value());
+        neighbors
+    }
+
+    /// Compare two systems: aligned positions, shared character values, and the
+    /// connectives of one projected onto the other
+    pub fn compare_systems(&self, order_a: u8, order_b: u8) -> SystemComparison<'_> {
+        let mut aligned: Vec<(u8, &Location, &Location)> = self
+            .locations_for_order(order_a)
+            .into_iter()
+            .filter_map(|a| {
+                let position = a.position_value()?;
+                Some((position, a, self.location(order_b, position)?))
+            })
+            .collect();
+        aligned.sort_by_key(|(p, _, _)| *p);
+
+        let values = |order: u8| -> HashSet<String> {
+            let terms = self
+                .terms(order, Some(Language::Canonical))
+                .into_iter()
+                .filter_map(|t| self.get_character(&t.character));
+            let connectives = self
+                .connectives(order, None, None)
+                .into_iter()
+                .filter_map(|l| self.get_character(l.character_id()?));
+            terms.chain(connectives).map(|c| c.value.clone()).collect()
+        };
+        let values_b = values(order_b);
+        let mut shared_values: Vec<String> = values(order_a)
+            .into_iter()
+            .filter(|v| values_b.contains(v))
+            .collect();
+        shared_values.sort();
+
+        let projections = self
+            .connectives(order_a, None, None)
+            .into_iter()
+            .filter_map(|a| {
+                let base = self.get_entry(a.base_single()?)?.position()?;
+                let target = self.get_entry(a.target_single()?)?.position()?;
+                let b = self
+                    .links_between(order_b, base, target)
+                    .into_iter()
+                    .find(|l| l.is_connective())?;
+                Some((a, b))
+            })
+            .collect();
+
+        SystemComparison {
+            order_a,
+            order_b,
+            aligned,
+            shared_values,
+            projections,
+        }
+    }
+
+    /// Get connectives across all orders labelled by a character, given by ID
+    /// or by value (case-insensitive)
+    pub fn connectives_by_character(&self, character: &str) -> Vec<&Link> {
+        self.links
+            .iter()
+            .filter(|l| {
+                l.is_connective()
+                    && l.character_id().is_some_and(|id| {
+                        id == character
+                            || self
+                                .get_character(id)
+                                .is_some_and(|c| c.value.eq_ignore_ascii_case(character))
+                    })
+            })
+            .collect()
+    }
+
     /// Get all connectives involving a specific term (by resolving term to location)
     pub fn connectives_for_term(&self, term_id: &str) -> Vec<&Link> {
         // Find the term's location
@@ -413,6 +1253,136 @@ impl Graph {
         }
     }
 
+    /// Get all grouping links for an order (groupings whose members lie in that order)
+    pub fn groupings(&self, order: u8) -> Vec<&Link> {
+        let order_id = format!("order_{}", order);
+        self.links
+            .iter()
+            .filter(|l| {
+                l.is_grouping()
+                    && !l.bases().is_empty()
+                    && l.bases().iter().all(|id| {
+                        self.entries.iter().any(|e| match e {
+                            Entry::Location(loc) if &loc.id == id => loc.order == order_id,
+                            _ => false,
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// Get all groupings that contain a specific location
+    pub fn groupings_for_location(&self, location_id: &str) -> Vec<&Link> {
+        self.links
+            .iter()
+            .filter(|l| l.is_grouping() && l.bases().iter().any(|id| id == location_id))
+            .collect()
+    }
+
+    /// Get the lines and connectives joining two positions of an order, in either direction
+    pub fn links_between(&self, order: u8, position_a: u8, position_b: u8) -> Vec<&Link> {
+        let ends = |prefix: &str| {
+            (
+                format!("{}_{}_{}", prefix, order, position_a),
+                format!("{}_{}_{}", prefix, order, position_b),
+            )
+        };
+        let (coord_a, coord_b) = ends("coord");
+        let (loc_a, loc_b) = ends("loc");
+        self.links
+            .iter()
+            .filter(|l| {
+                let (a, b) = match l.link_type {
+                    LinkType::Line => (&coord_a, &coord_b),
+                    LinkType::Connective => (&loc_a, &loc_b),
+                    LinkType::Grouping => return false,
+                };
+                let (Some(base), Some(target)) = (l.base_single(), l.target_single()) else {
+                    return false;
+                };
+                (base == a && target == b) || (base == b && target == a)
+            })
+            .collect()
+    }
+
+    /// Get the shortest route between two positions of an order, as the links to
+    /// follow in sequence (in either direction). `link_type` restricts the route to
+    /// lines or connectives; by default both are used. Returns `None` when no route
+    /// exists, and an empty route from a position to itself.
+    pub fn shortest_path(
+        &self,
+        order: u8,
+        from: u8,
+        to: u8,
+        link_type: Option<&LinkType>,
+    ) -> Option<Vec<&Link>> {
+        let position = |id: Option<&str>| id.and_then(|id| self.get_entry(id)?.position());
+        let mut adjacent: HashMap<u8, Vec<(u8, &Link)>> = HashMap::new();
+        let links = self
+            .lines(order)
+            .into_iter()
+            .chain(self.connectives(order, None, None))
+            .filter(|l| link_type.is_none_or(|t| &l.link_type == t));
+        for link in links {
+            if let (Some(a), Some(b)) =
+                (position(link.base_single()), position(link.target_single()))
+            {
+                adjacent.entry(a).or_default().push((b, link));
+                adjacent.entry(b).or_default().push((a, link));
+            }
+        }
+
+        // Breadth-first search, remembering the link each position was reached by
+        let mut reached_by: HashMap<u8, (u8, &Link)> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        let mut visited = HashSet::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = Vec::new();
+                let mut at = to;
+                while let Some(&(previous, link)) = reached_by.get(&at) {
+                    path.push(link);
+                    at = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &(next, link) in adjacent.get(&current).into_iter().flatten() {
+                if visited.insert(next) {
+                    reached_by.insert(next, (current, link));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the other lines and connectives joining the same two positions as a link
+    /// (the line drawn for a connective, and vice versa), from the correspondence index
+    pub fn corresponding_links(&self, link: &Link) -> Vec<&Link> {
+        let Some(pair) = self.correspondences.pair_of.get(&link.id) else {
+            return vec![];
+        };
+        self.correspondences.by_pair[pair]
+            .iter()
+            .filter(|id| **id != link.id)
+            .filter_map(|id| self.get_link(id))
+            .collect()
+    }
+
+    /// Get all lines touching the coordinate at an order and position
+    pub fn lines_at(&self, order: u8, position: u8) -> Vec<&Link> {
+        let coordinate_id = format!("coord_{}_{}", order, position);
+        self.links
+            .iter()
+            .filter(|l| {
+                matches!(l.link_type, LinkType::Line)
+                    && (l.base_single() == Some(coordinate_id.as_str())
+                        || l.target_single() == Some(coordinate_id.as_str()))
+            })
+            .collect()
+    }
+
     /// Get all line links for an order
     pub fn lines(&self, order: u8) -> Vec<&Link> {
         self.links
@@ -570,9 +1540,37 @@ mod tests {
 
         assert!(graph.coherence(3).is_some());
         assert_eq!(graph.coherence(3).unwrap().value, "Dynamism");
+        assert_eq!(graph.coherences(3).len(), 1);
 
         assert!(graph.term_designation(3).is_some());
         assert_eq!(graph.term_designation(3).unwrap().value, "Impulses");
+
+        assert_eq!(graph.order_by_name("triad"), Some(3));
+        assert_eq!(graph.order_by_name("Tetrad"), None);
+    }
+
+    #[test]
+    fn test_alternate_coherences() {
+        let mut graph = Graph::new();
+        graph.add_entry(Entry::Order(Order::new(3)));
+        // Alternates added before the primary still sort after it
+        graph.add_entry(Entry::CoherenceAttribute(CoherenceAttribute::alternate(
+            3,
+            1,
+            "Relatedness",
+        )));
+        graph.add_entry(Entry::CoherenceAttribute(CoherenceAttribute::with_auto_id(
+            3, "Dynamism",
+        )));
+
+        assert_eq!(graph.coherence(3).unwrap().value, "Dynamism");
+        let values: Vec<&str> = graph
+            .coherences(3)
+            .iter()
+            .map(|c| c.value.as_str())
+            .collect();
+        assert_eq!(values, vec!["Dynamism", "Relatedness"]);
+        assert!(graph.validate().is_empty());
     }
 
     #[test]
@@ -609,6 +1607,215 @@ mod tests {
         assert_eq!(char.unwrap().value, "Will");
     }
 
+    #[test]
+    fn test_merge_replaces_and_appends() {
+        let mut graph = create_test_graph();
+        let before = graph.entries.len();
+
+        let mut overlay = Graph::new();
+        overlay.add_entry(Entry::Character(Character::new(
+            "char_canonical_will",
+            Language::Canonical,
+            "Volition",
+        )));
+        overlay.add_entry(Entry::Character(Character::with_auto_id(
+            Language::Energy,
+            "Affirming",
+        )));
+        graph.merge(overlay);
+
+        assert_eq!(graph.entries.len(), before + 1);
+        assert_eq!(
+            graph.get_character("char_canonical_will").unwrap().value,
+            "Volition"
+        );
+        assert!(graph.get_character("char_energy_affirming").is_some());
+    }
+
+    #[test]
+    fn test_changes_since() {
+        let base = create_test_graph();
+        let mut graph = base.clone();
+        let mut overlay = Graph::new();
+        overlay.add_entry(Entry::Character(Character::new(
+            "char_canonical_will",
+            Language::Canonical,
+            "Volition",
+        )));
+        overlay.add_entry(Entry::Character(Character::with_auto_id(
+            Language::Energy,
+            "Affirming",
+        )));
+        graph.merge(overlay);
+
+        let changes = graph.changes_since(&base);
+        let ids: Vec<&str> = changes.entries.iter().map(|e| e.id()).collect();
+        assert_eq!(ids, ["char_canonical_will", "char_energy_affirming"]);
+        assert!(changes.links.is_empty());
+
+        let mut rebuilt = base.clone();
+        rebuilt.merge(changes);
+        assert!(graph.changes_since(&rebuilt).entries.is_empty());
+    }
+
+    #[test]
+    fn test_register_vocabulary() {
+        let mut graph = create_test_graph();
+        let pack = VocabularyPack::new()
+            .with_terms(3, ["Cups", "Wands", "Swords"])
+            .with_terms(7, ["Unplaced"]);
+
+        let language = graph.register_vocabulary("Tarot", &pack);
+        assert_eq!(language, Language::Custom("Tarot".to_string()));
+
+        let terms = graph.terms(3, Some(language.clone()));
+        assert_eq!(terms.len(), 3);
+        assert_eq!(terms[0].id, "term_3_1_tarot");
+        assert_eq!(terms[0].character, "char_tarot_cups");
+        assert_eq!(graph.terms_at_location("loc_3_1").len(), 2);
+        assert_eq!(graph.term(3, 1).unwrap().character, "char_canonical_will");
+        assert!(graph.terms(7, None).is_empty());
+
+        assert_eq!(graph.vocabularies().last(), Some(&language));
+        assert!(graph.validate().is_empty());
+
+        // Re-registering replaces rather than duplicates
+        let before = graph.entries.len();
+        let again =
+            graph.register_vocabulary("tarot", &VocabularyPack::new().with_terms(3, ["Cups"]));
+        assert_eq!(again, language);
+        assert_eq!(graph.entries.len(), before);
+    }
+
+    #[test]
+    fn test_vocabulary_fallback_to_canonical() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::connective("loc_3_1", "loc_3_2").with_tag("char_canonical_being"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_3").with_tag("char_canonical_will"));
+        let pack = VocabularyPack::new()
+            .with_terms(3, ["Cups", "Wands"])
+            .with_connectives(3, ["Trade", "Unlinked", "Duel"]);
+        let tarot = graph.register_vocabulary("Tarot", &pack);
+
+        let values: Vec<&str> = graph
+            .terms_with_fallback(3, &tarot)
+            .iter()
+            .map(|t| t.character.as_str())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                "char_tarot_cups",
+                "char_tarot_wands",
+                "char_canonical_being"
+            ]
+        );
+
+        // (1,3) has no connective, so "Unlinked" is skipped
+        let first = graph.connectives(3, Some(1), Some(2))[0];
+        let second = graph.connectives(3, Some(2), Some(3))[0];
+        let value = |link, language| {
+            graph
+                .connective_character(link, language)
+                .map(|c| c.value.as_str())
+        };
+        assert_eq!(value(first, &tarot), Some("Trade"));
+        assert_eq!(value(second, &tarot), Some("Duel"));
+        assert_eq!(value(first, &Language::Canonical), Some("Being"));
+        assert_eq!(value(first, &Language::Energy), Some("Being"));
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn test_localized() {
+        let mut graph = create_test_graph();
+        graph.add_translation("char_canonical_will", "de", "Wille");
+        graph.add_translation("coherence_3", "de", "Dynamik");
+        graph.add_translation("char_canonical_will", "fr", "Volonté");
+
+        assert_eq!(graph.locales(), vec!["de".to_string(), "fr".to_string()]);
+        let translations = graph.translations("char_canonical_will");
+        assert_eq!(translations.len(), 2);
+        assert_eq!(translations["fr"], "Volonté");
+        assert!(graph.translations("coherence_3").contains_key("de"));
+        assert_eq!(graph.characters(Language::Canonical).len(), 3);
+
+        let de = graph.localized("de");
+        assert_eq!(
+            de.get_character("char_canonical_will").unwrap().value,
+            "Wille"
+        );
+        assert_eq!(de.coherence(3).unwrap().value, "Dynamik");
+        // Untranslated values fall back to the source
+        assert_eq!(de.system_name(3).unwrap().value, "Triad");
+        assert_eq!(
+            graph
+                .localized("fr")
+                .get_character("char_canonical_will")
+                .unwrap()
+                .value,
+            "Volonté"
+        );
+        // The source graph is unchanged
+        assert_eq!(
+            graph.get_character("char_canonical_will").unwrap().value,
+            "Will"
+        );
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_dangling_references() {
+        let mut graph = create_test_graph();
+        assert!(graph.validate().is_empty());
+
+        graph.add_entry(Entry::Term(Term::with_auto_id(3, 1, "char_missing")));
+        graph.add_link(Link::connective("loc_3_1", "loc_9_9"));
+
+        let problems = graph.validate();
+        assert_eq!(problems.len(), 3);
+        assert!(problems
+            .iter()
+            .any(|p| p.contains("duplicate entry ID 'term_3_1'")));
+        assert!(problems.iter().any(|p| p.contains("'char_missing'")));
+        assert!(problems.iter().any(|p| p.contains("'loc_9_9'")));
+    }
+
+    #[test]
+    fn test_search() {
+        let mut graph = create_test_graph();
+        graph.add_entry(Entry::Character(Character::with_auto_id(
+            Language::Energy,
+            "Willing",
+        )));
+        graph.add_link(Link::connective("loc_3_1", "loc_3_2").with_tag("char_canonical_being"));
+
+        let hits = graph.search("WILL", None);
+        let kinds: Vec<_> = hits.iter().map(|h| (h.kind, h.id.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (SearchHitKind::Term, "term_3_1"),
+                (SearchHitKind::Character, "char_energy_willing"),
+            ]
+        );
+        assert_eq!((hits[0].order, hits[0].position), (Some(3), Some(1)));
+
+        let hits = graph.search("dynam", None);
+        assert_eq!(hits[0].kind, SearchHitKind::CoherenceAttribute);
+        assert_eq!(hits[0].order, Some(3));
+
+        let hits = graph.search("being", None);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[1].kind, SearchHitKind::Connective);
+        assert_eq!(hits[1].order, Some(3));
+
+        // Language filter applies to characters and excludes canonical metadata
+        let hits = graph.search("i", Some(&[Language::Energy]));
+        assert!(hits.iter().all(|h| h.id == "char_energy_willing"));
+        assert!(graph.search("  ", None).is_empty());
+    }
+
     #[test]
     fn test_isomorphic_terms() {
         let graph = create_test_graph();
@@ -617,4 +1824,209 @@ mod tests {
         assert_eq!(iso.len(), 1);
         assert_eq!(iso[0].1.value, "Will");
     }
+
+    #[test]
+    fn test_connectives_by_character() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::connective("loc_3_1", "loc_3_2").with_tag("char_canonical_being"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_3").with_tag("char_canonical_will"));
+
+        assert_eq!(graph.connectives_by_character("being").len(), 1);
+        assert_eq!(
+            graph.connectives_by_character("char_canonical_will")[0].base_single(),
+            Some("loc_3_2")
+        );
+        assert!(graph.connectives_by_character("Function").is_empty());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::connective("loc_3_3", "loc_3_2").with_tag("char_canonical_will"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_1").with_tag("char_canonical_being"));
+
+        let neighbors = graph.neighbors("loc_3_2");
+        let ids: Vec<(&str, Option<&str>)> = neighbors
+            .iter()
+            .map(|(l, link)| (l.id.as_str(), link.character_id()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("loc_3_1", Some("char_canonical_being")),
+                ("loc_3_3", Some("char_canonical_will")),
+            ]
+        );
+        assert_eq!(graph.neighbors("loc_3_1").len(), 1);
+        assert!(graph.neighbors("loc_9_9").is_empty());
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut graph = create_test_graph();
+        graph.add_entry(Entry::Sequence(Sequence::with_auto_id(
+            3,
+            "Cycle",
+            &[2, 3, 1],
+        )));
+        graph.add_entry(Entry::Sequence(Sequence::with_auto_id(
+            3,
+            "Return",
+            &[3, 1],
+        )));
+
+        let values = |terms: Vec<&Term>| -> Vec<String> {
+            terms.iter().map(|t| t.character.clone()).collect()
+        };
+        let sequence = graph.sequence(3, None);
+        assert_eq!(sequence.map(|s| s.id.as_str()), Some("seq_3_cycle"));
+        assert_eq!(
+            values(graph.traversal(3, sequence, &Language::Canonical)),
+            vec![
+                "char_canonical_function",
+                "char_canonical_being",
+                "char_canonical_will"
+            ]
+        );
+        let sequence = graph.sequence(3, Some("return"));
+        assert_eq!(graph.traversal(3, sequence, &Language::Canonical).len(), 2);
+        assert!(graph.sequence(3, Some("Missing")).is_none());
+
+        // Without a sequence, terms come in positional order
+        assert_eq!(
+            values(graph.traversal(3, None, &Language::Canonical))[0],
+            "char_canonical_will"
+        );
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn test_terms_by_value() {
+        let mut graph = create_test_graph();
+        graph.add_entry(Entry::Character(Character::with_auto_id(
+            Language::Energy,
+            "Will",
+        )));
+        graph.add_entry(Entry::Term(Term::with_language_id(
+            3,
+            1,
+            &Language::Energy,
+            "char_energy_will",
+        )));
+
+        let terms = graph.terms_by_value("will");
+        assert_eq!(terms.len(), 2);
+        assert!(terms.iter().all(|t| t.location == "loc_3_1"));
+        assert!(graph.terms_by_value("Wil").is_empty());
+    }
+
+    #[test]
+    fn test_links_between() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::line("coord_3_1", "coord_3_2"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_1"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_3"));
+
+        assert_eq!(graph.links_between(3, 1, 2).len(), 2);
+        assert_eq!(graph.links_between(3, 2, 1).len(), 2);
+        assert_eq!(graph.links_between(3, 1, 3).len(), 0);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::line("coord_3_1", "coord_3_2"));
+        graph.add_link(Link::line("coord_3_2", "coord_3_3"));
+        graph.add_link(Link::connective("loc_3_3", "loc_3_1"));
+
+        let ids = |path: Option<Vec<&Link>>| {
+            path.map(|p| p.iter().map(|l| l.id.clone()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            ids(graph.shortest_path(3, 1, 3, None)),
+            Some(vec!["conn_loc_3_3_loc_3_1".to_string()])
+        );
+        assert_eq!(
+            ids(graph.shortest_path(3, 1, 3, Some(&LinkType::Line))),
+            Some(vec![
+                "line_coord_3_1_coord_3_2".to_string(),
+                "line_coord_3_2_coord_3_3".to_string(),
+            ])
+        );
+        assert_eq!(graph.shortest_path(3, 2, 2, None), Some(vec![]));
+        assert_eq!(
+            graph.shortest_path(3, 1, 2, Some(&LinkType::Connective)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_corresponding_links() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::line("coord_3_1", "coord_3_2"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_1"));
+        graph.add_link(Link::connective("loc_3_2", "loc_3_3"));
+
+        let line = graph.get_link("line_coord_3_1_coord_3_2").unwrap().clone();
+        let ids =
+            |links: Vec<&Link>| -> Vec<String> { links.iter().map(|l| l.id.clone()).collect() };
+        assert_eq!(
+            ids(graph.corresponding_links(&line)),
+            vec!["conn_loc_3_2_loc_3_1"]
+        );
+
+        // Merged links are indexed too
+        let mut fragment = Graph::new();
+        fragment.add_link(Link::line("coord_3_2", "coord_3_3"));
+        graph.merge(fragment);
+        let connective = graph.get_link("conn_loc_3_2_loc_3_3").unwrap().clone();
+        assert_eq!(
+            ids(graph.corresponding_links(&connective)),
+            vec!["line_coord_3_2_coord_3_3"]
+        );
+
+        // Links edited in place are picked up on reindex
+        graph.links.retain(|l| l.id != "conn_loc_3_2_loc_3_1");
+        graph.reindex();
+        assert!(graph.corresponding_links(&line).is_empty());
+    }
+
+    #[test]
+    fn test_character_ids_in_order() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::connective("loc_3_1", "loc_3_2").with_tag("char_canonical_unity"));
+
+        let ids = graph.character_ids_in_order(3);
+        assert_eq!(ids.len(), 4);
+        assert!(ids.contains("char_canonical_will"));
+        assert!(ids.contains("char_canonical_unity"));
+        assert!(graph.character_ids_in_order(4).is_empty());
+    }
+
+    #[test]
+    fn test_connective_matrix() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::connective("loc_3_2", "loc_3_1").with_tag("char_canonical_being"));
+
+        let matrix = graph.connective_matrix(3);
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(
+            matrix[0][1].and_then(|l| l.character_id()),
+            Some("char_canonical_being")
+        );
+        assert_eq!(matrix[1][0], matrix[0][1]);
+        assert!(matrix[0][2].is_none());
+        assert!(matrix[0][0].is_none());
+    }
+
+    #[test]
+    fn test_lines_at() {
+        let mut graph = create_test_graph();
+        graph.add_link(Link::line("coord_3_1", "coord_3_2"));
+        graph.add_link(Link::line("coord_3_2", "coord_3_3"));
+
+        assert_eq!(graph.lines_at(3, 1).len(), 1);
+        assert_eq!(graph.lines_at(3, 2).len(), 2);
+        assert!(graph.lines_at(4, 1).is_empty());
+    }
 }
@@ -1,16 +1,19 @@
 //! Language enum for semantic vocabularies and representation types.
 //!
 //! This unified enum covers both:
-//! - Semantic vocabularies for Character entries (Canonical, Energy, Values, Society)
+//! - Semantic vocabularies for Character entries (Canonical, Energy, Values, Society,
+//!   plus any vocabulary registered at runtime as `Custom`)
 //! - Representation types for Colour entries (Hex, Name)
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Language represents either a semantic vocabulary or a representation type.
 ///
-/// For Character entries: Canonical, Energy, Values, Society
+/// For Character entries: Canonical, Energy, Values, Society, Custom
 /// For Colour entries: Hex, Name
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
     // Semantic vocabularies (for Character entries)
     /// The canonical/standard vocabulary from Elementary Systematics
@@ -21,6 +24,8 @@ pub enum Language {
     Values,
     /// Society/social vocabulary
     Society,
+    /// A vocabulary registered at runtime, identified by name
+    Custom(String),
 
     // Representation types (for Colour entries)
     /// Hexadecimal color representation (e.g., "#FF0000")
@@ -34,10 +39,34 @@ impl Language {
     pub fn is_vocabulary(&self) -> bool {
         matches!(
             self,
-            Language::Canonical | Language::Energy | Language::Values | Language::Society
+            Language::Canonical
+                | Language::Energy
+                | Language::Values
+                | Language::Society
+                | Language::Custom(_)
         )
     }
 
+    /// Returns true if this is a runtime-registered vocabulary
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Language::Custom(_))
+    }
+
+    /// Resolve a language by name (case-insensitive), falling back to a custom vocabulary
+    pub fn from_name(name: &str) -> Language {
+        Language::vocabularies()
+            .iter()
+            .chain(Language::representations())
+            .find(|l| l.to_string().eq_ignore_ascii_case(name))
+            .cloned()
+            .unwrap_or_else(|| Language::Custom(name.to_string()))
+    }
+
+    /// Lowercase, underscore-separated form used in generated IDs
+    pub fn slug(&self) -> String {
+        self.to_string().to_lowercase().replace(' ', "_")
+    }
+
     /// Returns true if this language is a representation type (for Colours)
     pub fn is_representation(&self) -> bool {
         matches!(self, Language::Hex | Language::Name)
@@ -59,6 +88,46 @@ impl Language {
     }
 }
 
+/// A vocabulary's term values, registered with `Graph::register_vocabulary`.
+///
+/// Keyed by order; each list gives the term values by position (1..=order).
+/// Orders may be omitted, and a shorter list leaves the remaining positions empty.
+/// Connective names follow the pair order (1,2), (1,3), ... (2,3), ...
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VocabularyPack {
+    pub terms: BTreeMap<u8, Vec<String>>,
+    #[serde(default)]
+    pub connectives: BTreeMap<u8, Vec<String>>,
+}
+
+impl VocabularyPack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the term values for an order, by position
+    pub fn with_terms<S: Into<String>>(
+        mut self,
+        order: u8,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.terms
+            .insert(order, values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add the connective names for an order, by position pair
+    pub fn with_connectives<S: Into<String>>(
+        mut self,
+        order: u8,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.connectives
+            .insert(order, values.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -66,6 +135,7 @@ impl std::fmt::Display for Language {
             Language::Energy => write!(f, "Energy"),
             Language::Values => write!(f, "Values"),
             Language::Society => write!(f, "Society"),
+            Language::Custom(name) => write!(f, "{}", name),
             Language::Hex => write!(f, "Hex"),
             Language::Name => write!(f, "Name"),
         }
@@ -89,4 +159,16 @@ mod tests {
         assert!(Language::Name.is_representation());
         assert!(!Language::Canonical.is_representation());
     }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Language::from_name("energy"), Language::Energy);
+        assert_eq!(Language::from_name("HEX"), Language::Hex);
+
+        let tarot = Language::from_name("Tarot Suits");
+        assert_eq!(tarot, Language::Custom("Tarot Suits".to_string()));
+        assert!(tarot.is_vocabulary());
+        assert!(tarot.is_custom());
+        assert_eq!(tarot.slug(), "tarot_suits");
+    }
 }
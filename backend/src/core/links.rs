@@ -13,6 +13,52 @@ pub enum LinkType {
     /// Connective connects Location → Location (simplex-anchored)
     /// Character ID stored in Link's `tag` field
     Connective,
+    /// Grouping gathers member Locations (base) into a named structural unit.
+    /// The enclosing Grouping's ID (if nested) is stored as the target;
+    /// the label Character ID is stored in Link's `tag` field
+    Grouping,
+}
+
+/// LineRole classifies a Line by its place in the figure.
+/// Stored in the Line's `tag` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LineRole {
+    /// Tetrad edge bounding the activity field
+    Side,
+    /// Tetrad edge crossing the activity field
+    Diagonal,
+    /// Edge between neighbouring points on the outer boundary (orders 5+)
+    Circumference,
+    /// Edge crossing the interior (orders 5+)
+    Chord,
+}
+
+impl LineRole {
+    /// Tag value stored on Line links
+    pub fn as_tag(&self) -> &'static str {
+        match self {
+            LineRole::Side => "side",
+            LineRole::Diagonal => "diagonal",
+            LineRole::Circumference => "circumference",
+            LineRole::Chord => "chord",
+        }
+    }
+
+    /// Parse a tag value back into a role
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "side" => Some(LineRole::Side),
+            "diagonal" => Some(LineRole::Diagonal),
+            "circumference" => Some(LineRole::Circumference),
+            "chord" => Some(LineRole::Chord),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this line lies on the outer boundary of the figure
+    pub fn is_boundary(&self) -> bool {
+        matches!(self, LineRole::Side | LineRole::Circumference)
+    }
 }
 
 /// Link is an explicit relationship between entries.
@@ -74,6 +120,17 @@ impl Link {
         )
     }
 
+    /// Create a Grouping link over member locations, optionally nested in a parent grouping
+    /// Use `.with_tag(character_id)` to set the label character
+    pub fn grouping(id: impl Into<String>, members: Vec<String>, parent: Option<String>) -> Self {
+        Self::new(
+            id,
+            Some(members),
+            parent.map(|p| vec![p]),
+            LinkType::Grouping,
+        )
+    }
+
     // =========================================================================
     // Helper methods for accessing base/target
     // =========================================================================
@@ -107,14 +164,36 @@ impl Link {
         matches!(self.link_type, LinkType::Connective)
     }
 
-    /// Get the character ID (from tag field) if this is a connective link
+    /// Check if this is a grouping link
+    pub fn is_grouping(&self) -> bool {
+        matches!(self.link_type, LinkType::Grouping)
+    }
+
+    /// Get the character ID (from tag field) if this is a connective or grouping link
     pub fn character_id(&self) -> Option<&str> {
-        if self.is_connective() {
+        if self.is_connective() || self.is_grouping() {
             self.tag.as_deref()
         } else {
             None
         }
     }
+
+    /// Get the enclosing grouping's ID if this is a nested grouping link
+    pub fn parent_grouping(&self) -> Option<&str> {
+        if self.is_grouping() {
+            self.target_single()
+        } else {
+            None
+        }
+    }
+
+    /// Get the line role (from tag field) if this is a line link
+    pub fn line_role(&self) -> Option<LineRole> {
+        match self.link_type {
+            LinkType::Line => self.tag.as_deref().and_then(LineRole::from_tag),
+            LinkType::Connective | LinkType::Grouping => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -138,6 +217,42 @@ mod tests {
         assert_eq!(link.target_single(), Some("loc_3_2"));
     }
 
+    #[test]
+    fn test_line_role_tag() {
+        let link = Link::line("coord_4_1", "coord_4_2").with_tag(LineRole::Diagonal.as_tag());
+        assert_eq!(link.line_role(), Some(LineRole::Diagonal));
+        assert!(!LineRole::Diagonal.is_boundary());
+
+        // Connective tags are character IDs, never roles
+        let conn = Link::connective("loc_4_1", "loc_4_2").with_tag("side");
+        assert_eq!(conn.line_role(), None);
+    }
+
+    #[test]
+    fn test_grouping_link() {
+        let outer = Link::grouping(
+            "group_5_significance",
+            vec!["loc_5_5".to_string(), "loc_5_2".to_string()],
+            None,
+        );
+        let inner = Link::grouping(
+            "group_5_potential",
+            vec!["loc_5_3".to_string(), "loc_5_4".to_string()],
+            Some(outer.id.clone()),
+        )
+        .with_tag("char_canonical_range_of_potential");
+
+        assert!(inner.is_grouping());
+        assert!(!inner.is_connective());
+        assert_eq!(inner.bases().len(), 2);
+        assert_eq!(inner.parent_grouping(), Some("group_5_significance"));
+        assert_eq!(
+            inner.character_id(),
+            Some("char_canonical_range_of_potential")
+        );
+        assert_eq!(outer.parent_grouping(), None);
+    }
+
     #[test]
     fn test_link_with_tag() {
         let link = Link::line("a", "b").with_tag("my_tag");
@@ -1,9 +1,9 @@
 //! Core types for the Systematics property graph.
 //!
 //! This module provides the fundamental building blocks:
-//! - `Language` - Semantic vocabularies and representation types
+//! - `Language` - Semantic vocabularies and representation types, and vocabulary packs
 //! - `entries` - Entry types (Character, Term, Coordinate, Colour, etc.) and the Entry enum
-//! - `links` - Link types (Line, Connective)
+//! - `links` - Link types (Line, Connective) and line roles
 //! - `graph` - Graph structure with query methods
 
 pub mod entries;
@@ -12,16 +12,17 @@ pub mod language;
 pub mod links;
 
 // Re-export language types
-pub use language::Language;
+pub use language::{Language, VocabularyPack};
 
 // Re-export entry types (including Entry enum and anchor types)
 pub use entries::{
-    Character, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate, Entry, Location,
-    Order, Point3d, Position, SystemName, Term, TermDesignation,
+    Annotation, Character, Citation, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate,
+    Entry, Instance, InstanceLabel, Location, Order, Point3d, Position, ResearchStatus, Sequence,
+    SystemName, Term, TermDesignation,
 };
 
 // Re-export link types
-pub use links::{Link, LinkType};
+pub use links::{LineRole, Link, LinkType};
 
 // Re-export graph types
-pub use graph::Graph;
+pub use graph::{Graph, ResearchCoverage, SearchHit, SearchHitKind, SystemComparison};
@@ -0,0 +1,116 @@
+//! Annotations: user notes attached to entries and links.
+//!
+//! Study groups attach commentary to terms, connectives or any other entry.
+//! Notes are `Annotation` entries referencing their target by ID, so the
+//! annotated content is untouched.
+
+use std::fmt;
+
+use crate::core::{Annotation, Entry, Graph};
+
+/// Error raised when an annotation cannot be added
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationError {
+    EmptyText,
+    EmptyAuthor,
+    UnknownTarget(String),
+}
+
+impl fmt::Display for AnnotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnnotationError::EmptyText => write!(f, "Annotation text is empty"),
+            AnnotationError::EmptyAuthor => write!(f, "Annotation author is empty"),
+            AnnotationError::UnknownTarget(id) => {
+                write!(f, "No entry or link with ID '{}'", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnnotationError {}
+
+/// Attach a note by `author` (with user ID `author_id`, if signed in) to the entry or
+/// link `target_id`
+pub fn add_annotation(
+    graph: &mut Graph,
+    target_id: &str,
+    text: &str,
+    author: &str,
+    author_id: Option<&str>,
+) -> Result<Annotation, AnnotationError> {
+    if text.trim().is_empty() {
+        return Err(AnnotationError::EmptyText);
+    }
+    if author.trim().is_empty() {
+        return Err(AnnotationError::EmptyAuthor);
+    }
+    if graph.get_entry(target_id).is_none() && graph.get_link(target_id).is_none() {
+        return Err(AnnotationError::UnknownTarget(target_id.to_string()));
+    }
+
+    let number = graph.all_annotations().len() + 1;
+    let annotation = Annotation {
+        author_id: author_id.map(str::to_string),
+        ..Annotation::with_auto_id(number, target_id, text.trim(), author.trim())
+    };
+    graph.add_entry(Entry::Annotation(annotation.clone()));
+    Ok(annotation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_add_annotation() {
+        let mut graph = build_graph();
+        let on_term = add_annotation(
+            &mut graph,
+            "term_3_1",
+            "Compare with Affirmation",
+            "Ana",
+            None,
+        )
+        .unwrap();
+        let on_connective = add_annotation(
+            &mut graph,
+            "conn_loc_3_1_loc_3_2",
+            " The first act ",
+            "Ben",
+            Some("user-2"),
+        )
+        .unwrap();
+
+        assert_eq!(on_term.id, "annotation_1");
+        assert_eq!(on_connective.id, "annotation_2");
+        assert_eq!(on_connective.text, "The first act");
+        assert_eq!(on_connective.author_id.as_deref(), Some("user-2"));
+        assert_eq!(graph.annotations("term_3_1"), vec![&on_term]);
+        assert!(graph.annotations("term_3_2").is_empty());
+
+        // The annotated term is untouched
+        assert_eq!(graph.term(3, 1).unwrap().character, "char_canonical_will");
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn test_add_annotation_rejects_invalid_input() {
+        let mut graph = build_graph();
+
+        assert_eq!(
+            add_annotation(&mut graph, "term_3_1", " ", "Ana", None),
+            Err(AnnotationError::EmptyText)
+        );
+        assert_eq!(
+            add_annotation(&mut graph, "term_3_1", "Note", "", None),
+            Err(AnnotationError::EmptyAuthor)
+        );
+        assert_eq!(
+            add_annotation(&mut graph, "term_99_1", "Note", "Ana", None),
+            Err(AnnotationError::UnknownTarget("term_99_1".to_string()))
+        );
+        assert!(graph.all_annotations().is_empty());
+    }
+}
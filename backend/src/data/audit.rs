@@ -0,0 +1,106 @@
+//! Structural invariants of the curated data.
+//!
+//! Where `Graph::validate` checks that references resolve, `audit` checks that
+//! each system is complete: a system of order n has n canonical terms, n
+//! coordinates, n hex colours and a line for each of its n(n-1)/2 pairs.
+//! Connective counts follow Bennett's tables: the Monad's single self-relation,
+//! then one connective per pair from the Dyad onward.
+
+use crate::core::{Graph, Language};
+
+/// Number of connectives a system of this order has in Bennett's tables
+pub fn expected_connectives(order: u8) -> usize {
+    match order {
+        0 => 0,
+        1 => 1,
+        n => pairs(n),
+    }
+}
+
+/// Number of position pairs in a system of this order
+fn pairs(order: u8) -> usize {
+    let n = order as usize;
+    n * n.saturating_sub(1) / 2
+}
+
+/// Check every system in the graph against its structural invariants,
+/// returning a description of each violation (naming the order).
+/// Custom systems may omit connectives; if present they must be complete.
+pub fn audit(graph: &Graph) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut orders: Vec<_> = graph.orders().into_iter().cloned().collect();
+    orders.sort_by_key(|o| o.value);
+
+    for order in orders {
+        let n = order.value;
+        let mut check = |what: &str, expected: usize, found: usize| {
+            if expected != found {
+                problems.push(format!(
+                    "order {}: expected {} {}, found {}",
+                    n, expected, what, found
+                ));
+            }
+        };
+
+        check(
+            "terms",
+            n as usize,
+            graph.terms(n, Some(Language::Canonical)).len(),
+        );
+        check("coordinates", n as usize, graph.coordinates(n).len());
+        check(
+            "colours",
+            n as usize,
+            graph
+                .colours(n)
+                .iter()
+                .filter(|c| c.language == Language::Hex)
+                .count(),
+        );
+        check("lines", pairs(n), graph.lines(n).len());
+
+        let connectives = graph.connectives(n, None, None).len();
+        if order.is_canonical() || connectives > 0 {
+            check("connectives", expected_connectives(n), connectives);
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Link;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_expected_connectives() {
+        assert_eq!(expected_connectives(1), 1);
+        assert_eq!(expected_connectives(2), 1);
+        assert_eq!(expected_connectives(3), 3);
+        assert_eq!(expected_connectives(4), 6);
+        assert_eq!(expected_connectives(12), 66);
+    }
+
+    #[test]
+    fn test_build_graph_passes_audit() {
+        let problems = audit(&build_graph());
+        assert!(problems.is_empty(), "{:#?}", problems);
+    }
+
+    #[test]
+    fn test_audit_names_offending_order() {
+        let mut graph = build_graph();
+        graph.links.retain(|l| l.id != "line_coord_4_1_coord_4_2");
+        graph.add_link(Link::connective("loc_3_1", "loc_3_1"));
+
+        let problems = audit(&graph);
+        assert_eq!(
+            problems,
+            vec![
+                "order 3: expected 3 connectives, found 4".to_string(),
+                "order 4: expected 6 lines, found 5".to_string(),
+            ]
+        );
+    }
+}
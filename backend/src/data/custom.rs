@@ -0,0 +1,334 @@
+//! User-defined custom systems.
+//!
+//! A custom system claims an order value not used by the canonical systems
+//! (e.g., 13 and above) and supplies its own vocabulary. Everything structural
+//! is generated: Order/Position/Location anchors, a regular-polygon geometry,
+//! colours and the complete set of lines. Connectives are added when names
+//! are supplied.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    Character, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate, Entry, Graph,
+    Language, Link, Location, Order, Point3d, Position, ResearchStatus, SystemName, Term,
+    TermDesignation,
+};
+
+use super::{add_lines, get_colours};
+
+/// Largest order a custom system may use
+pub const MAX_CUSTOM_ORDER: u8 = 64;
+
+/// Definition of a user-defined system, as read from a `*.system.json` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomSystem {
+    /// Order value; must not already exist in the graph
+    pub order: u8,
+    /// System name (e.g., "Tridecad")
+    pub name: String,
+    #[serde(default)]
+    pub coherence: Option<String>,
+    /// Further coherence attributes after the primary one
+    #[serde(default)]
+    pub alternate_coherences: Vec<String>,
+    #[serde(default)]
+    pub term_designation: Option<String>,
+    #[serde(default)]
+    pub connective_designation: Option<String>,
+    /// Term names by position; exactly `order` of them
+    pub terms: Vec<String>,
+    /// Connective names for each pair (1,2), (1,3), ... (2,3), ...;
+    /// either empty or exactly `order * (order - 1) / 2` of them
+    #[serde(default)]
+    pub connectives: Vec<String>,
+    /// User ID of the creator
+    #[serde(default)]
+    pub created_by: Option<String>,
+}
+
+/// Error raised when a custom system definition cannot be added
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomSystemError {
+    OrderExists(u8),
+    OrderOutOfRange(u8),
+    TermCount { expected: usize, found: usize },
+    ConnectiveCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for CustomSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomSystemError::OrderExists(order) => {
+                write!(f, "Order {} already exists", order)
+            }
+            CustomSystemError::OrderOutOfRange(order) => {
+                write!(f, "Order {} is outside 1-{}", order, MAX_CUSTOM_ORDER)
+            }
+            CustomSystemError::TermCount { expected, found } => {
+                write!(f, "Expected {} terms, found {}", expected, found)
+            }
+            CustomSystemError::ConnectiveCount { expected, found } => {
+                write!(f, "Expected {} connectives, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CustomSystemError {}
+
+/// Add a custom system to the graph, generating its structure
+pub fn add_custom_system(graph: &mut Graph, def: &CustomSystem) -> Result<(), CustomSystemError> {
+    let order = def.order;
+    if order == 0 || order > MAX_CUSTOM_ORDER {
+        return Err(CustomSystemError::OrderOutOfRange(order));
+    }
+    if graph.order(order).is_some() {
+        return Err(CustomSystemError::OrderExists(order));
+    }
+    let n = order as usize;
+    if def.terms.len() != n {
+        return Err(CustomSystemError::TermCount {
+            expected: n,
+            found: def.terms.len(),
+        });
+    }
+    let pairs = n * (n - 1) / 2;
+    if !def.connectives.is_empty() && def.connectives.len() != pairs {
+        return Err(CustomSystemError::ConnectiveCount {
+            expected: pairs,
+            found: def.connectives.len(),
+        });
+    }
+
+    // Anchors (positions are shared across orders; only add missing ones)
+    graph.add_entry(Entry::Order(Order {
+        created_by: def.created_by.clone(),
+        ..Order::new(order)
+    }));
+    for position in 1..=order {
+        if graph.position(position).is_none() {
+            graph.add_entry(Entry::Position(Position::new(position)));
+        }
+        graph.add_entry(Entry::Location(Location::new(order, position)));
+    }
+
+    // Geometry
+    let coords = polygon_coordinates(order);
+    let palette = get_colours(12);
+    for (idx, coord) in coords.iter().enumerate() {
+        let position = (idx + 1) as u8;
+        graph.add_entry(Entry::Coordinate(Coordinate::with_auto_id(
+            order, position, *coord,
+        )));
+        graph.add_entry(Entry::Colour(Colour::with_auto_id(
+            order,
+            position,
+            Language::Hex,
+            palette[idx % palette.len()],
+        )));
+    }
+
+    // Order-level metadata
+    graph.add_entry(Entry::SystemName(SystemName::with_auto_id(
+        order, &def.name,
+    )));
+    if let Some(coherence) = &def.coherence {
+        graph.add_entry(Entry::CoherenceAttribute(CoherenceAttribute::with_auto_id(
+            order, coherence,
+        )));
+    }
+    for (idx, coherence) in def.alternate_coherences.iter().enumerate() {
+        graph.add_entry(Entry::CoherenceAttribute(CoherenceAttribute::alternate(
+            order,
+            (idx + 1) as u8,
+            coherence,
+        )));
+    }
+    if let Some(designation) = &def.term_designation {
+        graph.add_entry(Entry::TermDesignation(TermDesignation::with_auto_id(
+            order,
+            designation,
+        )));
+    }
+    if let Some(designation) = &def.connective_designation {
+        graph.add_entry(Entry::ConnectiveDesignation(
+            ConnectiveDesignation::with_auto_id(order, designation),
+        ));
+    }
+
+    // Vocabulary: user-supplied, so never canonical
+    for (idx, value) in def.terms.iter().enumerate() {
+        let char_id = ensure_character(graph, value);
+        graph.add_entry(Entry::Term(
+            Term::with_auto_id(order, (idx + 1) as u8, char_id)
+                .with_status(ResearchStatus::Provisional),
+        ));
+    }
+
+    // Links
+    if !def.connectives.is_empty() {
+        let mut names = def.connectives.iter();
+        for i in 1..=order {
+            for j in (i + 1)..=order {
+                let Some(value) = names.next() else { break };
+                let char_id = ensure_character(graph, value);
+                graph.add_link(
+                    Link::connective(
+                        format!("loc_{}_{}", order, i),
+                        format!("loc_{}_{}", order, j),
+                    )
+                    .with_tag(char_id),
+                );
+            }
+        }
+    }
+    add_lines(graph, order, &coords);
+
+    Ok(())
+}
+
+/// Get the ID of the canonical-language character with this value, adding it if missing
+fn ensure_character(graph: &mut Graph, value: &str) -> String {
+    let character = Character::with_auto_id(Language::Canonical, value)
+        .with_status(ResearchStatus::Provisional);
+    let id = character.id.clone();
+    if graph.get_character(&id).is_none() {
+        graph.add_entry(Entry::Character(character));
+    }
+    id
+}
+
+/// Regular polygon on the unit circle, position 1 at the top, proceeding clockwise
+fn polygon_coordinates(order: u8) -> Vec<Point3d> {
+    if order == 1 {
+        return vec![Point3d::new(0.0, 0.0, 0.0)];
+    }
+    (0..order)
+        .map(|k| {
+            let angle = FRAC_PI_2 - TAU * k as f64 / order as f64;
+            Point3d::new(angle.cos(), angle.sin(), 0.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LineRole;
+    use crate::data::{audit, build_graph};
+
+    fn tridecad() -> CustomSystem {
+        CustomSystem {
+            order: 13,
+            name: "Tridecad".to_string(),
+            coherence: Some("Extension".to_string()),
+            alternate_coherences: vec!["Continuation".to_string()],
+            term_designation: Some("Members".to_string()),
+            connective_designation: None,
+            terms: (1..=13).map(|i| format!("Member {}", i)).collect(),
+            connectives: vec![],
+            created_by: Some("user-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_add_custom_system_generates_structure() {
+        let mut graph = build_graph();
+        add_custom_system(&mut graph, &tridecad()).unwrap();
+
+        assert!(graph.order(13).is_some());
+        assert!(graph.position(13).is_some());
+        assert_eq!(graph.locations_for_order(13).len(), 13);
+        assert_eq!(graph.terms(13, None).len(), 13);
+        assert_eq!(graph.coordinates(13).len(), 13);
+        assert_eq!(graph.colours(13).len(), 13);
+        assert_eq!(graph.lines(13).len(), 13 * 12 / 2);
+        assert!(graph.connectives(13, None, None).is_empty());
+        assert_eq!(graph.system_name(13).unwrap().value, "Tridecad");
+        assert_eq!(
+            graph.order(13).unwrap().created_by.as_deref(),
+            Some("user-1")
+        );
+        assert_eq!(graph.coherence(13).unwrap().value, "Extension");
+        assert_eq!(graph.coherences(13).len(), 2);
+        assert_eq!(
+            graph.term(13, 1).unwrap().status,
+            ResearchStatus::Provisional
+        );
+
+        let circumference = graph
+            .lines(13)
+            .iter()
+            .filter(|l| l.line_role() == Some(LineRole::Circumference))
+            .count();
+        assert_eq!(circumference, 13);
+
+        assert!(graph.validate().is_empty());
+        assert!(audit(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_add_custom_system_with_connectives() {
+        let mut graph = build_graph();
+        let def = CustomSystem {
+            order: 13,
+            connectives: (1..=78).map(|i| format!("Bond {}", i)).collect(),
+            ..tridecad()
+        };
+        add_custom_system(&mut graph, &def).unwrap();
+
+        let connectives = graph.connectives(13, Some(1), Some(2));
+        assert_eq!(connectives.len(), 1);
+        assert_eq!(connectives[0].character_id(), Some("char_canonical_bond_1"));
+        assert_eq!(graph.connectives(13, None, None).len(), 78);
+    }
+
+    #[test]
+    fn test_add_custom_system_rejects_invalid_definitions() {
+        let mut graph = build_graph();
+
+        let def = CustomSystem {
+            order: 4,
+            terms: (1..=4).map(|i| i.to_string()).collect(),
+            ..tridecad()
+        };
+        assert_eq!(
+            add_custom_system(&mut graph, &def),
+            Err(CustomSystemError::OrderExists(4))
+        );
+
+        let def = CustomSystem {
+            terms: vec!["Only one".to_string()],
+            ..tridecad()
+        };
+        assert_eq!(
+            add_custom_system(&mut graph, &def),
+            Err(CustomSystemError::TermCount {
+                expected: 13,
+                found: 1
+            })
+        );
+
+        let def = CustomSystem {
+            connectives: vec!["Too few".to_string()],
+            ..tridecad()
+        };
+        assert!(matches!(
+            add_custom_system(&mut graph, &def),
+            Err(CustomSystemError::ConnectiveCount { expected: 78, .. })
+        ));
+
+        // Nothing was added by the failed attempts
+        assert!(graph.order(13).is_none());
+    }
+
+    #[test]
+    fn test_polygon_coordinates() {
+        let coords = polygon_coordinates(4);
+        assert!((coords[0].y - 1.0).abs() < 1e-9);
+        assert!((coords[1].x - 1.0).abs() < 1e-9); // clockwise: second point on the right
+    }
+}
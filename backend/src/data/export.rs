@@ -0,0 +1,695 @@
+//! Exporting the graph to files.
+//!
+//! `Json` writes a `Graph` fragment in the format `loader` reads, so an export
+//! can be edited and loaded back from the data directory. `Graphml` and `Dot`
+//! write the property graph for graph tools: one node per entry, and one edge
+//! per line, connective, grouping membership and entry reference (a term's
+//! location and character, a location's order and position, and so on).
+//! `Turtle` writes the same graph as RDF for linked-data tools: a resource per
+//! entry and link (`urn:systematics:<id>`), typed and labelled, with a
+//! property per reference. `Svg` draws a single system, as the frontend does.
+//!
+//! Every format can be restricted to one order: its entries, the positions and
+//! characters they use, their annotations, and the links between them.
+
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+
+use systematics_middleware::{display, Viewport};
+
+use crate::core::{Entry, Graph, Language, Link, LinkType};
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Graphml,
+    Dot,
+    Turtle,
+    Svg,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::Json,
+        ExportFormat::Graphml,
+        ExportFormat::Dot,
+        ExportFormat::Turtle,
+        ExportFormat::Svg,
+    ];
+
+    /// Parse a format name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(name))
+    }
+
+    /// The format's name, which is also its file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Graphml => "graphml",
+            ExportFormat::Dot => "dot",
+            ExportFormat::Turtle => "ttl",
+            ExportFormat::Svg => "svg",
+        }
+    }
+
+    /// The format's media type
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Graphml => "application/graphml+xml",
+            ExportFormat::Dot => "text/vnd.graphviz",
+            ExportFormat::Turtle => "text/turtle",
+            ExportFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// Error raised when an export cannot be produced
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+    UnknownOrder(u8),
+    /// SVG draws a single system
+    OrderRequired(ExportFormat),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::UnknownOrder(order) => write!(f, "No system of order {}", order),
+            ExportError::OrderRequired(format) => {
+                write!(f, "The {} format needs an order", format.extension())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// Export the graph, or the system of one order, in a format
+pub fn export(
+    graph: &Graph,
+    format: ExportFormat,
+    order: Option<u8>,
+) -> Result<String, ExportError> {
+    let system;
+    let graph = match order {
+        Some(order) => {
+            system = system_graph(graph, order)?;
+            &system
+        }
+        None if format == ExportFormat::Svg => return Err(ExportError::OrderRequired(format)),
+        None => graph,
+    };
+    Ok(match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(graph).expect("graphs serialize to JSON") + "\n"
+        }
+        ExportFormat::Graphml => graphml(graph),
+        ExportFormat::Dot => dot(graph),
+        ExportFormat::Turtle => turtle(graph),
+        ExportFormat::Svg => svg(graph, order.unwrap_or_default(), true),
+    })
+}
+
+/// Draw the system of one order, with or without its term labels
+pub(super) fn system_svg(graph: &Graph, order: u8, labels: bool) -> Result<String, ExportError> {
+    Ok(svg(&system_graph(graph, order)?, order, labels))
+}
+
+/// The part of the graph describing one system
+fn system_graph(graph: &Graph, order: u8) -> Result<Graph, ExportError> {
+    if graph.order(order).is_none() {
+        return Err(ExportError::UnknownOrder(order));
+    }
+    let mut system = Graph::new();
+    for entry in graph.entries.iter().filter(|e| e.order() == Some(order)) {
+        system.add_entry(entry.clone());
+    }
+    for link in &graph.links {
+        let bases = link.bases();
+        if !bases.is_empty() && bases.iter().all(|id| system.get_entry(id).is_some()) {
+            system.add_link(link.clone());
+        }
+    }
+
+    // Positions and characters used by the system
+    let mut used: HashSet<&str> = HashSet::new();
+    for entry in &system.entries {
+        match entry {
+            Entry::Location(location) => {
+                used.insert(&location.position);
+            }
+            Entry::Term(term) => {
+                used.insert(&term.character);
+            }
+            _ => {}
+        }
+    }
+    used.extend(system.links.iter().filter_map(|l| l.tag.as_deref()));
+    let shared: Vec<Entry> = graph
+        .entries
+        .iter()
+        .filter(|e| used.contains(e.id()))
+        .cloned()
+        .collect();
+    for entry in shared {
+        system.add_entry(entry);
+    }
+
+    // Translations and annotations of everything included
+    let ids: HashSet<&str> = system
+        .entries
+        .iter()
+        .map(|e| e.id())
+        .chain(system.links.iter().map(|l| l.id.as_str()))
+        .collect();
+    let attached: Vec<Entry> = graph
+        .entries
+        .iter()
+        .filter(|e| match e {
+            Entry::Character(c) => c.locale.is_some() && ids.contains(translated_id(&c.id)),
+            Entry::Annotation(a) => ids.contains(a.target.as_str()),
+            _ => false,
+        })
+        .cloned()
+        .collect();
+    for entry in attached {
+        system.add_entry(entry);
+    }
+    Ok(system)
+}
+
+/// The ID of the entry a translation (`<id>@<locale>`) translates
+fn translated_id(id: &str) -> &str {
+    id.rsplit_once('@').map_or(id, |(source, _)| source)
+}
+
+/// The entry's kind, as named in its serialized form
+fn entry_kind(entry: &Entry) -> &'static str {
+    match entry {
+        Entry::Order(_) => "Order",
+        Entry::Position(_) => "Position",
+        Entry::Location(_) => "Location",
+        Entry::SystemName(_) => "SystemName",
+        Entry::CoherenceAttribute(_) => "CoherenceAttribute",
+        Entry::TermDesignation(_) => "TermDesignation",
+        Entry::ConnectiveDesignation(_) => "ConnectiveDesignation",
+        Entry::Sequence(_) => "Sequence",
+        Entry::Term(_) => "Term",
+        Entry::Colour(_) => "Colour",
+        Entry::Coordinate(_) => "Coordinate",
+        Entry::Character(_) => "Character",
+        Entry::Instance(_) => "Instance",
+        Entry::InstanceLabel(_) => "InstanceLabel",
+        Entry::Annotation(_) => "Annotation",
+    }
+}
+
+/// Display label of an entry
+fn entry_label(graph: &Graph, entry: &Entry) -> String {
+    match entry {
+        Entry::Order(e) => format!("Order {}", e.value),
+        Entry::Position(e) => format!("Position {}", e.value),
+        Entry::Location(e) => e.id.clone(),
+        Entry::SystemName(e) => e.value.clone(),
+        Entry::CoherenceAttribute(e) => e.value.clone(),
+        Entry::TermDesignation(e) => e.value.clone(),
+        Entry::ConnectiveDesignation(e) => e.value.clone(),
+        Entry::Sequence(e) => e.name.clone(),
+        Entry::Term(e) => graph
+            .get_character(&e.character)
+            .map_or_else(|| e.id.clone(), |c| c.value.clone()),
+        Entry::Colour(e) => e.value.clone(),
+        Entry::Coordinate(e) => format!("({}, {}, {})", e.value.x, e.value.y, e.value.z),
+        Entry::Character(e) => e.value.clone(),
+        Entry::Instance(e) => e.name.clone(),
+        Entry::InstanceLabel(e) => e.value.clone(),
+        Entry::Annotation(e) => e.text.clone(),
+    }
+}
+
+/// The entries an entry refers to, with the name of each reference
+fn references(entry: &Entry) -> Vec<(&'static str, &str)> {
+    match entry {
+        Entry::Order(_) | Entry::Position(_) | Entry::Character(_) => Vec::new(),
+        Entry::Location(e) => vec![("order", &e.order), ("position", &e.position)],
+        Entry::SystemName(e) => vec![("order", &e.order)],
+        Entry::CoherenceAttribute(e) => vec![("order", &e.order)],
+        Entry::TermDesignation(e) => vec![("order", &e.order)],
+        Entry::ConnectiveDesignation(e) => vec![("order", &e.order)],
+        Entry::Sequence(e) => std::iter::once(("order", e.order.as_str()))
+            .chain(e.locations.iter().map(|l| ("step", l.as_str())))
+            .collect(),
+        Entry::Term(e) => vec![("location", &e.location), ("character", &e.character)],
+        Entry::Colour(e) => vec![("location", &e.location)],
+        Entry::Coordinate(e) => vec![("location", &e.location)],
+        Entry::Instance(e) => vec![("order", &e.order)],
+        Entry::InstanceLabel(e) => vec![("instance", &e.instance), ("location", &e.location)],
+        Entry::Annotation(e) => vec![("target", &e.target)],
+    }
+}
+
+/// An edge of the exported graph
+struct Edge<'a> {
+    id: String,
+    source: &'a str,
+    target: &'a str,
+    kind: &'static str,
+    label: Option<&'a str>,
+}
+
+/// Edges for links and entry references. A grouping is exported as a node of its
+/// own, with a `member` edge from each of its locations and a `within` edge to the
+/// grouping enclosing it.
+fn edges(graph: &Graph) -> Vec<Edge<'_>> {
+    let mut edges = Vec::new();
+    for link in &graph.links {
+        match link.link_type {
+            LinkType::Line | LinkType::Connective => {
+                let kind = match link.link_type {
+                    LinkType::Line => "line",
+                    _ => "connective",
+                };
+                if let (Some(source), Some(target)) = (link.base_single(), link.target_single()) {
+                    edges.push(Edge {
+                        id: link.id.clone(),
+                        source,
+                        target,
+                        kind,
+                        label: link.tag.as_deref(),
+                    });
+                }
+            }
+            LinkType::Grouping => {
+                for base in link.bases() {
+                    edges.push(Edge {
+                        id: format!("{}:{}", link.id, base),
+                        source: base,
+                        target: &link.id,
+                        kind: "member",
+                        label: None,
+                    });
+                }
+                for target in link.targets() {
+                    edges.push(Edge {
+                        id: format!("{}:{}", link.id, target),
+                        source: &link.id,
+                        target,
+                        kind: "within",
+                        label: None,
+                    });
+                }
+            }
+        }
+    }
+    for entry in &graph.entries {
+        for (kind, target) in references(entry) {
+            edges.push(Edge {
+                id: format!("{}:{}", entry.id(), target),
+                source: entry.id(),
+                target,
+                kind,
+                label: None,
+            });
+        }
+    }
+    edges
+}
+
+/// Label of a grouping node: its label character's value
+fn grouping_label(graph: &Graph, link: &Link) -> String {
+    link.tag
+        .as_deref()
+        .and_then(|tag| graph.get_character(tag))
+        .map_or_else(|| link.id.clone(), |c| c.value.clone())
+}
+
+/// Escape text for XML content and attributes
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quote text as a DOT string
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn graphml(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"kind\" for=\"all\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"label\" for=\"all\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    out.push_str("  <graph id=\"systematics\" edgedefault=\"directed\">\n");
+    let mut node = |id: &str, kind: &str, label: &str| {
+        let _ = writeln!(
+            out,
+            "    <node id=\"{}\"><data key=\"kind\">{}</data><data key=\"label\">{}</data></node>",
+            xml_escape(id),
+            kind,
+            xml_escape(label)
+        );
+    };
+    for entry in &graph.entries {
+        node(entry.id(), entry_kind(entry), &entry_label(graph, entry));
+    }
+    for link in graph.links.iter().filter(|l| l.is_grouping()) {
+        node(&link.id, "Grouping", &grouping_label(graph, link));
+    }
+    for edge in edges(graph) {
+        let _ = write!(
+            out,
+            "    <edge id=\"{}\" source=\"{}\" target=\"{}\"><data key=\"kind\">{}</data>",
+            xml_escape(&edge.id),
+            xml_escape(edge.source),
+            xml_escape(edge.target),
+            edge.kind
+        );
+        if let Some(label) = edge.label {
+            let _ = write!(out, "<data key=\"label\">{}</data>", xml_escape(label));
+        }
+        out.push_str("</edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph systematics {\n");
+    let mut node = |id: &str, kind: &str, label: &str| {
+        let _ = writeln!(
+            out,
+            "  {} [kind={}, label={}];",
+            dot_quote(id),
+            dot_quote(kind),
+            dot_quote(label)
+        );
+    };
+    for entry in &graph.entries {
+        node(entry.id(), entry_kind(entry), &entry_label(graph, entry));
+    }
+    for link in graph.links.iter().filter(|l| l.is_grouping()) {
+        node(&link.id, "Grouping", &grouping_label(graph, link));
+    }
+    for edge in edges(graph) {
+        let _ = write!(
+            out,
+            "  {} -> {} [kind={}",
+            dot_quote(edge.source),
+            dot_quote(edge.target),
+            dot_quote(edge.kind)
+        );
+        if let Some(label) = edge.label {
+            let _ = write!(out, ", label={}", dot_quote(label));
+        }
+        out.push_str("];\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// IRI of an entry or link, percent-encoding characters IRIs do not allow
+fn iri(id: &str) -> String {
+    let mut iri = String::from("<urn:systematics:");
+    for byte in id.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'@' | b':' => {
+                iri.push(byte as char)
+            }
+            byte => {
+                let _ = write!(iri, "%{:02X}", byte);
+            }
+        }
+    }
+    iri.push('>');
+    iri
+}
+
+/// Quote text as a Turtle string literal
+fn turtle_quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn turtle(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("@prefix sys: <urn:systematics:vocab#> .\n");
+    out.push_str("@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n");
+    let mut resource = |id: &str, kind: &str, label: &str, properties: Vec<(&str, &str)>| {
+        let _ = write!(
+            out,
+            "\n{} a sys:{} ;\n    rdfs:label {}",
+            iri(id),
+            kind,
+            turtle_quote(label)
+        );
+        for (property, target) in properties {
+            let _ = write!(out, " ;\n    sys:{} {}", property, iri(target));
+        }
+        out.push_str(" .\n");
+    };
+    for entry in &graph.entries {
+        resource(
+            entry.id(),
+            entry_kind(entry),
+            &entry_label(graph, entry),
+            references(entry),
+        );
+    }
+    for link in &graph.links {
+        let (kind, label, base, target) = match link.link_type {
+            LinkType::Line => ("Line", link.id.clone(), "base", "target"),
+            LinkType::Connective => ("Connective", link.id.clone(), "base", "target"),
+            LinkType::Grouping => ("Grouping", grouping_label(graph, link), "member", "within"),
+        };
+        let mut properties: Vec<(&str, &str)> = link
+            .bases()
+            .iter()
+            .map(|id| (base, id.as_str()))
+            .chain(link.targets().iter().map(|id| (target, id.as_str())))
+            .collect();
+        if let Some(tag) = link.tag.as_deref() {
+            properties.push(("character", tag));
+        }
+        resource(&link.id, kind, &label, properties);
+    }
+    out
+}
+
+/// Width and height of an SVG drawing
+pub(super) const SVG_SIZE: f64 = 400.0;
+
+/// Drawing area: the points fit within its margins, leaving room for labels
+const SVG_VIEWPORT: Viewport = Viewport {
+    width: SVG_SIZE,
+    height: SVG_SIZE,
+    margin: 90.0,
+};
+
+/// Radius of a point of the drawing
+const SVG_POINT_RADIUS: f64 = 8.0;
+
+/// Draw one system: its lines, points in their palette colours, and (with `labels`)
+/// term labels, fitted to `SVG_VIEWPORT` as in the frontend's view. Coordinates are
+/// y-up; SVG is y-down.
+fn svg(graph: &Graph, order: u8, labels: bool) -> String {
+    let transform = SVG_VIEWPORT.fit(
+        graph
+            .coordinates(order)
+            .iter()
+            .map(|c| (c.value.x, c.value.y)),
+    );
+    let project = |x: f64, y: f64| transform.apply(x, y);
+    let point = |coordinate_id: &str| match graph.get_entry(coordinate_id) {
+        Some(Entry::Coordinate(c)) => Some(project(c.value.x, c.value.y)),
+        _ => None,
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">",
+        size = SVG_SIZE
+    );
+    if let Some(name) = graph.system_name(order) {
+        let _ = writeln!(out, "  <title>{}</title>", xml_escape(&name.value));
+    }
+    out.push_str("  <g stroke=\"#888888\" stroke-width=\"1.5\">\n");
+    for line in graph.lines(order) {
+        let ends = line
+            .base_single()
+            .and_then(point)
+            .zip(line.target_single().and_then(point));
+        if let Some(((x1, y1), (x2, y2))) = ends {
+            let _ = writeln!(
+                out,
+                "    <line class=\"{}\" x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>",
+                line.tag.as_deref().unwrap_or("line"),
+                x1,
+                y1,
+                x2,
+                y2
+            );
+        }
+    }
+    out.push_str("  </g>\n");
+    out.push_str("  <g font-family=\"sans-serif\" font-size=\"12\">\n");
+    let designation = graph.term_designation(order).map(|d| d.value.as_str());
+    for coordinate in graph.coordinates(order) {
+        let (x, y) = project(coordinate.value.x, coordinate.value.y);
+        let fill = coordinate
+            .position_value()
+            .and_then(|position| graph.colour(order, position, Language::Hex))
+            .map_or("#ffffff", |c| c.value.as_str());
+        // Titled as in the frontend: "2nd Impulse: Function"
+        let term = graph.term_character_at(&coordinate.location);
+        let mut title = display::position_label(
+            coordinate.position_value().map_or(0, i32::from),
+            designation,
+        );
+        if let Some(term) = term {
+            title = format!("{}: {}", title, term.value);
+        }
+        let _ = writeln!(
+            out,
+            "    <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" stroke=\"#333333\"><title>{}</title></circle>",
+            x,
+            y,
+            SVG_POINT_RADIUS,
+            xml_escape(fill),
+            xml_escape(&title)
+        );
+        // Labels sit outside the figure, on the side of the point away from the centre
+        let character = term.filter(|_| labels);
+        if let Some(character) = character {
+            let (dx, dy) = (coordinate.value.x, -coordinate.value.y);
+            let length = (dx * dx + dy * dy).sqrt();
+            let (ux, uy) = if length > f64::EPSILON {
+                (dx / length, dy / length)
+            } else {
+                (0.0, 1.0)
+            };
+            let offset = SVG_POINT_RADIUS + 6.0;
+            let anchor = if ux > 0.3 {
+                "start"
+            } else if ux < -0.3 {
+                "end"
+            } else {
+                "middle"
+            };
+            let _ = writeln!(
+                out,
+                "    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"{}\" dominant-baseline=\"middle\">{}</text>",
+                x + ux * offset,
+                y + uy * offset,
+                anchor,
+                xml_escape(&character.value)
+            );
+        }
+    }
+    out.push_str("  </g>\n</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_json_export_round_trips() {
+        let graph = build_graph();
+        let json = export(&graph, ExportFormat::Json, Some(3)).unwrap();
+        let system: Graph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(system.terms(3, Some(Language::Canonical)).len(), 3);
+        assert_eq!(system.lines(3).len(), graph.lines(3).len());
+        assert!(system.position(3).is_some());
+        assert!(system
+            .entries
+            .iter()
+            .all(|e| e.order().is_none_or(|o| o == 3)));
+        assert!(system.validate().is_empty());
+    }
+
+    #[test]
+    fn test_graph_formats() {
+        let graph = build_graph();
+        let graphml = export(&graph, ExportFormat::Graphml, Some(4)).unwrap();
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains("<node id=\"loc_4_1\">"));
+        assert!(graphml.contains("<data key=\"kind\">connective</data>"));
+        assert!(graphml.trim_end().ends_with("</graphml>"));
+
+        let dot = export(&graph, ExportFormat::Dot, Some(4)).unwrap();
+        assert!(dot.starts_with("digraph systematics {"));
+        assert!(dot.contains("\"term_4_1\" -> \"loc_4_1\" [kind=\"location\"];"));
+
+        let turtle = export(&graph, ExportFormat::Turtle, Some(4)).unwrap();
+        assert!(turtle.starts_with("@prefix sys: <urn:systematics:vocab#> ."));
+        assert!(turtle.contains("<urn:systematics:term_4_1> a sys:Term ;"));
+        assert!(turtle.contains("sys:location <urn:systematics:loc_4_1>"));
+        assert_eq!(
+            turtle.matches(" a sys:Connective ;").count(),
+            graph.connectives(4, None, None).len()
+        );
+    }
+
+    #[test]
+    fn test_svg_draws_one_system() {
+        let graph = build_graph();
+        let svg = export(&graph, ExportFormat::Svg, Some(3)).unwrap();
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("<line").count(), graph.lines(3).len());
+        assert_eq!(svg.matches("<text").count(), 3);
+        assert!(svg.contains("<title>2nd Impulse: Function</title>"));
+
+        assert_eq!(
+            export(&graph, ExportFormat::Svg, None),
+            Err(ExportError::OrderRequired(ExportFormat::Svg))
+        );
+        assert_eq!(
+            export(&graph, ExportFormat::Json, Some(99)),
+            Err(ExportError::UnknownOrder(99))
+        );
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(
+            ExportFormat::from_name("GraphML"),
+            Some(ExportFormat::Graphml)
+        );
+        assert_eq!(ExportFormat::from_name("TTL"), Some(ExportFormat::Turtle));
+        assert_eq!(ExportFormat::from_name("png"), None);
+        assert_eq!(iri("term_3_1@fr"), "<urn:systematics:term_3_1@fr>");
+        assert_eq!(iri("a b"), "<urn:systematics:a%20b>");
+        assert_eq!(turtle_quote("say \"hi\"\n"), r#""say \"hi\"\n""#);
+    }
+}
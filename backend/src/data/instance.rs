@@ -0,0 +1,131 @@
+//! Instance overlays: a system applied to a concrete domain.
+//!
+//! An instance binds one user-supplied label to each location of an existing
+//! system (e.g., a project-planning Tetrad labelled "Vision", "Team", "Plan",
+//! "Budget"). Labels are separate entries, so the canonical terms are untouched.
+
+use std::fmt;
+
+use crate::core::{Entry, Graph, Instance, InstanceLabel};
+
+/// Error raised when an instance cannot be added
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstanceError {
+    EmptyName,
+    UnknownOrder(u8),
+    InstanceExists(String),
+    LabelCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceError::EmptyName => write!(f, "Instance name is empty"),
+            InstanceError::UnknownOrder(order) => write!(f, "Order {} does not exist", order),
+            InstanceError::InstanceExists(id) => write!(f, "Instance '{}' already exists", id),
+            InstanceError::LabelCount { expected, found } => {
+                write!(f, "Expected {} labels, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstanceError {}
+
+/// Add an instance of the system at `order`, with one label per position
+pub fn add_instance(
+    graph: &mut Graph,
+    name: &str,
+    order: u8,
+    labels: &[String],
+) -> Result<Instance, InstanceError> {
+    if name.trim().is_empty() {
+        return Err(InstanceError::EmptyName);
+    }
+    if graph.order(order).is_none() {
+        return Err(InstanceError::UnknownOrder(order));
+    }
+    let instance = Instance::with_auto_id(order, name.trim());
+    if graph.instance(&instance.id).is_some() {
+        return Err(InstanceError::InstanceExists(instance.id));
+    }
+    let expected = graph.locations_for_order(order).len();
+    if labels.len() != expected {
+        return Err(InstanceError::LabelCount {
+            expected,
+            found: labels.len(),
+        });
+    }
+
+    for (idx, value) in labels.iter().enumerate() {
+        let position = (idx + 1) as u8;
+        graph.add_entry(Entry::InstanceLabel(InstanceLabel::with_auto_id(
+            &instance, order, position, value,
+        )));
+    }
+    graph.add_entry(Entry::Instance(instance.clone()));
+    Ok(instance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    fn labels(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_add_instance() {
+        let mut graph = build_graph();
+        let instance = add_instance(
+            &mut graph,
+            "Project Planning",
+            4,
+            &labels(&["Vision", "Team", "Plan", "Budget"]),
+        )
+        .unwrap();
+
+        assert_eq!(graph.instances(Some(4)), vec![&instance]);
+        assert!(graph.instances(Some(3)).is_empty());
+
+        let bound = graph.instance_labels(&instance.id);
+        assert_eq!(bound.len(), 4);
+        assert_eq!(bound[1].value, "Team");
+
+        // Canonical terms are untouched and sit alongside the labels
+        assert_eq!(graph.term(4, 2).unwrap().character, "char_canonical_ground");
+        assert_eq!(graph.instance_labels_at_location("loc_4_2").len(), 1);
+
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn test_add_instance_rejects_invalid_input() {
+        let mut graph = build_graph();
+        let four = labels(&["Vision", "Team", "Plan", "Budget"]);
+
+        assert_eq!(
+            add_instance(&mut graph, " ", 4, &four),
+            Err(InstanceError::EmptyName)
+        );
+        assert_eq!(
+            add_instance(&mut graph, "Plan", 40, &four),
+            Err(InstanceError::UnknownOrder(40))
+        );
+        assert_eq!(
+            add_instance(&mut graph, "Plan", 3, &four),
+            Err(InstanceError::LabelCount {
+                expected: 3,
+                found: 4
+            })
+        );
+
+        add_instance(&mut graph, "Plan", 4, &four).unwrap();
+        assert_eq!(
+            add_instance(&mut graph, "plan", 4, &four),
+            Err(InstanceError::InstanceExists("instance_plan".to_string()))
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Loading curated data from external files.
+//!
+//! External data lives in a directory of JSON files, each holding a serialized
+//! `Graph` fragment (entries and links). Fragments are applied in file-name order
+//! on top of the built-in canonical graph, so a file can both add new content and
+//! replace built-in entries by reusing their IDs.
+//!
+//! Files ending in `.system.json` instead hold a `CustomSystem` definition,
+//! whose structure is generated by `add_custom_system`. Files named
+//! `<locale>.locale.json` map entry IDs to translated display values
+//! (e.g., `de.locale.json`: `{ "char_canonical_will": "Wille" }`).
+//!
+//! `reload` re-reads the directory into a running store, for the admin reload
+//! endpoint and the `dev` hot-reload.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::Graph;
+
+use super::{add_custom_system, audit, build_graph, CustomSystem, GraphStore};
+
+/// Suffix marking a custom system definition file
+const SYSTEM_SUFFIX: &str = ".system.json";
+
+/// Suffix marking a translation file; the file stem gives the locale
+const LOCALE_SUFFIX: &str = ".locale.json";
+
+/// Error raised while loading external data files
+#[derive(Debug)]
+pub enum DataError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, serde_json::Error),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io(path, e) => write!(f, "Failed to read {}: {}", path.display(), e),
+            DataError::Parse(path, e) => write!(f, "Failed to parse {}: {}", path.display(), e),
+            DataError::Invalid(path, e) => write!(f, "Invalid data in {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+/// Build the canonical graph and apply every `*.json` file in `dir`
+pub fn load_graph(dir: &Path) -> Result<Graph, DataError> {
+    let mut graph = build_graph();
+    for path in fragment_paths(dir)? {
+        let text = fs::read_to_string(&path).map_err(|e| DataError::Io(path.clone(), e))?;
+        if file_name(&path).ends_with(SYSTEM_SUFFIX) {
+            let def: CustomSystem =
+                serde_json::from_str(&text).map_err(|e| DataError::Parse(path.clone(), e))?;
+            add_custom_system(&mut graph, &def)
+                .map_err(|e| DataError::Invalid(path.clone(), e.to_string()))?;
+        } else if let Some(locale) = file_name(&path).strip_suffix(LOCALE_SUFFIX) {
+            let translations: BTreeMap<String, String> =
+                serde_json::from_str(&text).map_err(|e| DataError::Parse(path.clone(), e))?;
+            for (id, value) in &translations {
+                graph.add_translation(id, locale, value);
+            }
+        } else {
+            let fragment: Graph =
+                serde_json::from_str(&text).map_err(|e| DataError::Parse(path.clone(), e))?;
+            graph.merge(fragment);
+        }
+    }
+    Ok(graph)
+}
+
+/// Outcome of reloading the data directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadReport {
+    /// Entries of the reloaded data (before persisted edits are replayed)
+    pub entries: usize,
+    pub links: usize,
+    /// Validation and audit problems of the reloaded data
+    pub problems: Vec<String>,
+}
+
+/// Reload the graph from `dir` into `store`, replaying its recorded edits. The
+/// new graph is swapped in whole, even with validation problems (they are
+/// reported); a directory that fails to load leaves the previous graph in place.
+pub fn reload(dir: &Path, store: &GraphStore) -> Result<ReloadReport, DataError> {
+    let graph = load_graph(dir)?;
+    let mut problems = graph.validate();
+    problems.extend(audit(&graph));
+    let report = ReloadReport {
+        entries: graph.entries.len(),
+        links: graph.links.len(),
+        problems,
+    };
+    store.replace(graph);
+    Ok(report)
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+/// JSON fragment files in `dir`, sorted by file name
+fn fragment_paths(dir: &Path) -> Result<Vec<PathBuf>, DataError> {
+    let read_dir = fs::read_dir(dir).map_err(|e| DataError::Io(dir.to_path_buf(), e))?;
+    let mut paths: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Character, Entry, Language};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("systematics_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_load_graph_applies_fragments() {
+        let dir = temp_dir("loader_ok");
+        let mut fragment = Graph::new();
+        fragment.add_entry(Entry::Character(Character::new(
+            "char_canonical_act1",
+            Language::Canonical,
+            "Affirmation",
+        )));
+        fs::write(
+            dir.join("10_acts.json"),
+            serde_json::to_string(&fragment).unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let graph = load_graph(&dir).unwrap();
+        assert_eq!(
+            graph.get_character("char_canonical_act1").unwrap().value,
+            "Affirmation"
+        );
+        assert_eq!(graph.entries.len(), build_graph().entries.len());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_graph_reports_parse_errors() {
+        let dir = temp_dir("loader_bad");
+        fs::write(dir.join("broken.json"), "{ not json").unwrap();
+
+        let err = load_graph(&dir).unwrap_err();
+        assert!(matches!(err, DataError::Parse(_, _)));
+        assert!(err.to_string().contains("broken.json"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_graph_adds_custom_systems() {
+        let dir = temp_dir("loader_system");
+        fs::write(
+            dir.join("tridecad.system.json"),
+            r#"{
+                "order": 13,
+                "name": "Tridecad",
+                "terms": ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13"]
+            }"#,
+        )
+        .unwrap();
+
+        let graph = load_graph(&dir).unwrap();
+        assert_eq!(graph.system_name(13).unwrap().value, "Tridecad");
+        assert_eq!(graph.terms(13, None).len(), 13);
+
+        fs::write(
+            dir.join("tridecad.system.json"),
+            r#"{ "order": 13, "name": "Tridecad", "terms": ["1"] }"#,
+        )
+        .unwrap();
+        let err = load_graph(&dir).unwrap_err();
+        assert!(matches!(err, DataError::Invalid(_, _)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_graph_adds_translations() {
+        let dir = temp_dir("loader_locale");
+        fs::write(
+            dir.join("nl.locale.json"),
+            r#"{ "char_canonical_will": "Wil", "system_3": "Triade" }"#,
+        )
+        .unwrap();
+
+        let graph = load_graph(&dir).unwrap();
+        assert!(graph.locales().contains(&"nl".to_string()));
+        let nl = graph.localized("nl");
+        assert_eq!(
+            nl.get_character("char_canonical_will").unwrap().value,
+            "Wil"
+        );
+        assert_eq!(nl.system_name(3).unwrap().value, "Triade");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reload_swaps_the_graph() {
+        let dir = temp_dir("loader_reload");
+        let store = GraphStore::new(build_graph());
+        let mut fragment = Graph::new();
+        fragment.add_entry(Entry::Character(Character::new(
+            "char_canonical_act1",
+            Language::Canonical,
+            "Affirmation",
+        )));
+        fs::write(
+            dir.join("10_acts.json"),
+            serde_json::to_string(&fragment).unwrap(),
+        )
+        .unwrap();
+
+        let report = reload(&dir, &store).unwrap();
+        assert_eq!(report.entries, store.snapshot().entries.len());
+        assert_eq!(
+            store
+                .snapshot()
+                .get_character("char_canonical_act1")
+                .unwrap()
+                .value,
+            "Affirmation"
+        );
+
+        // A broken file leaves the reloaded graph in place
+        let revision = store.revision();
+        fs::write(dir.join("20_broken.json"), "{ not json").unwrap();
+        assert!(reload(&dir, &store).is_err());
+        assert_eq!(store.revision(), revision);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_graph_missing_dir() {
+        let err = load_graph(Path::new("/nonexistent/systematics")).unwrap_err();
+        assert!(matches!(err, DataError::Io(_, _)));
+    }
+}
@@ -0,0 +1,126 @@
+//! The colour palette assigned to positions.
+//!
+//! Colours are cumulative: position n of every system takes the nth palette
+//! colour, so a position keeps its colour from one order to the next. Custom
+//! systems beyond the Dodecad cycle through the twelve colours.
+
+use crate::core::{Graph, Language};
+
+/// A palette colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteColour {
+    pub hex: &'static str,
+    pub name: &'static str,
+}
+
+impl PaletteColour {
+    const fn new(hex: &'static str, name: &'static str) -> Self {
+        Self { hex, name }
+    }
+}
+
+/// The twelve palette colours, by position
+pub const PALETTE: [PaletteColour; 12] = [
+    PaletteColour::new("#FF0000", "Red"),
+    PaletteColour::new("#0000FF", "Blue"),
+    PaletteColour::new("#FFFF00", "Yellow"),
+    PaletteColour::new("#099902", "Green"),
+    PaletteColour::new("#9900FF", "Purple"),
+    PaletteColour::new("#FFA500", "Orange"),
+    PaletteColour::new("#00FFFF", "Light Blue"),
+    PaletteColour::new("#8B4513", "Brown"),
+    PaletteColour::new("#FF00FF", "Magenta"),
+    PaletteColour::new("#FFFFFF", "White"),
+    PaletteColour::new("#C0C0C0", "Silver"),
+    PaletteColour::new("#FFD700", "Gold"),
+];
+
+/// How colours are assigned to positions, for clients reproducing the palette
+pub const PALETTE_RULES: [&str; 3] = [
+    "Position n takes the nth palette colour, in every order",
+    "A position keeps its colour from one order to the next",
+    "Orders beyond 12 cycle through the twelve palette colours",
+];
+
+/// One position of an order's palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub position: u8,
+    pub hex: String,
+    /// Colour name: a `Name` colour entry where one exists, else the palette name
+    pub name: Option<String>,
+}
+
+/// Get the hex colours of an order's positions (empty beyond the Dodecad)
+pub(super) fn get_colours(order: u8) -> Vec<&'static str> {
+    if order > 12 {
+        return vec![];
+    }
+    PALETTE.iter().take(order as usize).map(|c| c.hex).collect()
+}
+
+/// Get the palette name of a hex colour (case-insensitive)
+pub fn palette_name(hex: &str) -> Option<&'static str> {
+    PALETTE
+        .iter()
+        .find(|c| c.hex.eq_ignore_ascii_case(hex))
+        .map(|c| c.name)
+}
+
+/// Get the colour of each position of an order as served by the graph (including
+/// colours overridden by data files), by position
+pub fn colour_palette(graph: &Graph, order: u8) -> Vec<PaletteEntry> {
+    let mut palette: Vec<PaletteEntry> = graph
+        .colours(order)
+        .into_iter()
+        .filter(|c| c.language == Language::Hex)
+        .filter_map(|c| {
+            let position = c.position_value()?;
+            let name = graph
+                .colour(order, position, Language::Name)
+                .map(|n| n.value.clone())
+                .or_else(|| palette_name(&c.value).map(String::from));
+            Some(PaletteEntry {
+                position,
+                hex: c.value.clone(),
+                name,
+            })
+        })
+        .collect();
+    palette.sort_by_key(|p| p.position);
+    palette
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Colour, Entry};
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_colour_palette() {
+        let mut graph = build_graph();
+
+        let tetrad = colour_palette(&graph, 4);
+        let names: Vec<Option<&str>> = tetrad.iter().map(|p| p.name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![Some("Red"), Some("Blue"), Some("Yellow"), Some("Green")]
+        );
+        assert_eq!(tetrad[3].hex, "#099902");
+        assert_eq!(colour_palette(&graph, 12).len(), 12);
+        assert!(colour_palette(&graph, 13).is_empty());
+
+        // A Name colour entry takes precedence over the palette name
+        graph.add_entry(Entry::Colour(Colour::with_auto_id(
+            4,
+            4,
+            Language::Name,
+            "Emerald",
+        )));
+        assert_eq!(
+            colour_palette(&graph, 4)[3].name.as_deref(),
+            Some("Emerald")
+        );
+    }
+}
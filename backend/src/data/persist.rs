@@ -0,0 +1,303 @@
+//! Persistence of edits made through the API.
+//!
+//! The canonical data (and any external data files) stays the seeded baseline;
+//! what is persisted is the sequence of edits applied on top of it: instances,
+//! annotations, imported vocabularies, renamed terms and connectives, custom
+//! systems, and restored backups
+//! (which replace the whole graph). An `EditLog` stores
+//! them in order, and replaying the log onto a freshly built baseline
+//! reproduces the served graph, at startup and after data reloads.
+//!
+//! With the `sqlite` feature, `SqliteLog` keeps the log in a SQLite database;
+//! with `postgres`, `PgLog` keeps it in Postgres (the Shuttle deployment).
+//! `MemoryLog` keeps it for the life of the process, for drafts (see
+//! `session`) whose edits are committed elsewhere.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Graph, Language, VocabularyPack};
+
+use super::{
+    add_annotation, add_custom_system, add_instance, import_vocabulary, label_connective,
+    rename_term, CustomSystem,
+};
+
+/// An edit of the served graph, recorded so it can be replayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum Edit {
+    Instance {
+        name: String,
+        order: u8,
+        labels: Vec<String>,
+    },
+    Annotation {
+        target: String,
+        text: String,
+        author: String,
+        #[serde(default)]
+        author_id: Option<String>,
+    },
+    Vocabulary {
+        name: String,
+        pack: VocabularyPack,
+    },
+    /// New value of the term at a location, in a vocabulary
+    Term {
+        order: u8,
+        position: u8,
+        language: Language,
+        value: String,
+    },
+    /// New name of the connective between two positions, in a vocabulary
+    ConnectiveLabel {
+        order: u8,
+        base: u8,
+        target: u8,
+        language: Language,
+        value: String,
+    },
+    CustomSystem(CustomSystem),
+    /// Replace the whole graph with a backup
+    Restore(Box<Graph>),
+}
+
+impl Edit {
+    /// Apply the edit to a graph, as the mutation that recorded it did
+    pub fn apply(&self, graph: &mut Graph) -> Result<(), String> {
+        match self {
+            Edit::Instance {
+                name,
+                order,
+                labels,
+            } => add_instance(graph, name, *order, labels)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::Annotation {
+                target,
+                text,
+                author,
+                author_id,
+            } => add_annotation(graph, target, text, author, author_id.as_deref())
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::Vocabulary { name, pack } => import_vocabulary(graph, name, pack)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::Term {
+                order,
+                position,
+                language,
+                value,
+            } => rename_term(graph, *order, *position, language, value)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::ConnectiveLabel {
+                order,
+                base,
+                target,
+                language,
+                value,
+            } => label_connective(graph, *order, *base, *target, language, value)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::CustomSystem(def) => add_custom_system(graph, def)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Edit::Restore(backup) => {
+                *graph = (**backup).clone();
+                graph.reindex();
+                Ok(())
+            }
+        }
+    }
+
+    /// The `kind` tag of the stored form
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Edit::Instance { .. } => "instance",
+            Edit::Annotation { .. } => "annotation",
+            Edit::Vocabulary { .. } => "vocabulary",
+            Edit::Term { .. } => "term",
+            Edit::ConnectiveLabel { .. } => "connective_label",
+            Edit::CustomSystem(_) => "custom_system",
+            Edit::Restore(_) => "restore",
+        }
+    }
+
+    /// Short description for logs
+    pub fn describe(&self) -> String {
+        match self {
+            Edit::Instance { name, .. } => format!("instance '{}'", name),
+            Edit::Annotation { target, .. } => format!("annotation on {}", target),
+            Edit::Vocabulary { name, .. } => format!("vocabulary '{}'", name),
+            Edit::Term {
+                order,
+                position,
+                language,
+                ..
+            } => format!("{} term {}-{}", language, order, position),
+            Edit::ConnectiveLabel {
+                order,
+                base,
+                target,
+                language,
+                ..
+            } => format!("{} connective {}: {}-{}", language, order, base, target),
+            Edit::CustomSystem(def) => format!("custom system {}", def.order),
+            Edit::Restore(backup) => format!("restore of {} entries", backup.entries.len()),
+        }
+    }
+}
+
+/// Replay edits onto a baseline graph in order. Edits that no longer apply (e.g.,
+/// a custom system whose order the data files now define) are skipped and reported.
+pub fn replay(graph: &mut Graph, edits: &[Edit]) -> Vec<String> {
+    edits
+        .iter()
+        .filter_map(|edit| {
+            edit.apply(graph)
+                .err()
+                .map(|e| format!("Skipped {}: {}", edit.describe(), e))
+        })
+        .collect()
+}
+
+/// Error raised by an edit log's storage
+#[derive(Debug)]
+pub enum PersistError {
+    Storage(String),
+    /// A stored edit that cannot be read back
+    Corrupt {
+        id: i64,
+        message: String,
+    },
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Storage(e) => write!(f, "Storage error: {}", e),
+            PersistError::Corrupt { id, message } => {
+                write!(f, "Stored edit {} is unreadable: {}", id, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+/// Ordered, durable storage of edits
+pub trait EditLog: Send + Sync + fmt::Debug {
+    /// All recorded edits, oldest first
+    fn load(&self) -> Result<Vec<Edit>, PersistError>;
+
+    /// Record an edit after the ones already stored
+    fn append(&self, edit: &Edit) -> Result<(), PersistError>;
+
+    /// Wait until every appended edit is stored. Logs that write on append have
+    /// nothing to do; others may block, so call this outside async code.
+    fn flush(&self) -> Result<(), PersistError> {
+        Ok(())
+    }
+}
+
+/// Edit log kept in memory
+#[derive(Debug, Default)]
+pub struct MemoryLog(Mutex<Vec<Edit>>);
+
+impl EditLog for MemoryLog {
+    fn load(&self) -> Result<Vec<Edit>, PersistError> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone())
+    }
+
+    fn append(&self, edit: &Edit) -> Result<(), PersistError> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(edit.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Language;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_replay_edits() {
+        let edits = vec![
+            Edit::Annotation {
+                target: "term_3_1".to_string(),
+                text: "Compare with Affirmation".to_string(),
+                author: "Ana".to_string(),
+                author_id: None,
+            },
+            Edit::Vocabulary {
+                name: "Tarot".to_string(),
+                pack: VocabularyPack::new().with_terms(3, ["Fool", "Magician", "Priestess"]),
+            },
+            Edit::Annotation {
+                target: "no_such_entry".to_string(),
+                text: "Lost".to_string(),
+                author: "Ana".to_string(),
+                author_id: None,
+            },
+        ];
+
+        // Edits survive a round trip through their stored form
+        let stored = serde_json::to_string(&edits).unwrap();
+        assert!(stored.contains(r#""kind":"annotation""#));
+        assert!(edits.iter().all(|edit| stored.contains(edit.kind())));
+        let edits: Vec<Edit> = serde_json::from_str(&stored).unwrap();
+
+        let mut graph = build_graph();
+        let problems = replay(&mut graph, &edits);
+        assert_eq!(graph.all_annotations().len(), 1);
+        assert!(graph
+            .vocabularies()
+            .contains(&Language::Custom("Tarot".to_string())));
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("no_such_entry"));
+    }
+
+    #[test]
+    fn test_restore_replaces_graph() {
+        let annotate = |target: &str| Edit::Annotation {
+            target: target.to_string(),
+            text: "Note".to_string(),
+            author: "Ana".to_string(),
+            author_id: None,
+        };
+        let mut backup = build_graph();
+        annotate("term_3_1").apply(&mut backup).unwrap();
+
+        let edits = vec![
+            annotate("term_3_2"),
+            Edit::Restore(Box::new(backup.clone())),
+            annotate("term_3_3"),
+        ];
+        let stored = serde_json::to_string(&edits).unwrap();
+        let edits: Vec<Edit> = serde_json::from_str(&stored).unwrap();
+        assert_eq!(edits[1], Edit::Restore(Box::new(backup)));
+
+        // The restore drops the earlier annotation; later edits apply on top
+        let mut graph = build_graph();
+        assert!(replay(&mut graph, &edits).is_empty());
+        let targets: Vec<&str> = graph
+            .all_annotations()
+            .iter()
+            .map(|a| a.target.as_str())
+            .collect();
+        assert_eq!(targets, ["term_3_1", "term_3_3"]);
+    }
+}
@@ -0,0 +1,161 @@
+//! Postgres storage of the edit log (requires the `postgres` feature).
+//!
+//! Used by the Shuttle deployment, whose shared database outlives redeploys.
+//! The schema is created by the migrations in `backend/migrations`. Edits are
+//! read once when connecting; appends are queued and written in order by a
+//! background task, so mutations never wait on the database. Flushing waits
+//! for the queue to drain.
+//!
+//! The database holds the edit log (one `edits` table), not tables of entries,
+//! links and annotations. The served graph is the baseline, which each deploy
+//! may change, with the edits replayed onto it: rows copied from the graph would
+//! pin baseline entries at their old values, and one edit (a restore, a custom
+//! system) touches many of them. Replaying the log hydrates the graph at startup
+//! exactly as after a data reload, and keeps this log and `SqliteLog` two
+//! implementations of the same `EditLog`. Annotations are `annotation` edits.
+
+use std::sync::Mutex;
+
+use sqlx::migrate::Migrator;
+use sqlx::PgPool;
+use tokio::sync::{mpsc, oneshot};
+
+use super::persist::{Edit, EditLog, PersistError};
+
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+fn storage_error(e: impl std::fmt::Display) -> PersistError {
+    PersistError::Storage(e.to_string())
+}
+
+fn writer_stopped() -> PersistError {
+    PersistError::Storage("Edit writer has stopped".to_string())
+}
+
+/// Work queued for the writer task
+#[derive(Debug)]
+enum Write {
+    Edit(Edit),
+    /// Signalled once the writes queued before it are done
+    Flush(oneshot::Sender<()>),
+}
+
+/// Edit log kept in a Postgres database
+#[derive(Debug)]
+pub struct PgLog {
+    edits: Mutex<Vec<Edit>>,
+    writes: mpsc::UnboundedSender<Write>,
+}
+
+impl PgLog {
+    /// Migrate the database, read the recorded edits and start the writer task
+    pub async fn connect(pool: PgPool) -> Result<Self, PersistError> {
+        MIGRATOR.run(&pool).await.map_err(storage_error)?;
+        let rows: Vec<(i64, String)> = sqlx::query_as("SELECT id, body FROM edits ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .map_err(storage_error)?;
+        let edits = rows
+            .into_iter()
+            .map(|(id, body)| {
+                serde_json::from_str(&body).map_err(|e| PersistError::Corrupt {
+                    id,
+                    message: e.to_string(),
+                })
+            })
+            .collect::<Result<Vec<Edit>, _>>()?;
+
+        let (writes, queued) = mpsc::unbounded_channel();
+        tokio::spawn(write_edits(pool, queued));
+        Ok(Self {
+            edits: Mutex::new(edits),
+            writes,
+        })
+    }
+}
+
+/// Write queued edits in order until the log is dropped
+async fn write_edits(pool: PgPool, mut queued: mpsc::UnboundedReceiver<Write>) {
+    while let Some(write) = queued.recv().await {
+        let edit = match write {
+            Write::Edit(edit) => edit,
+            Write::Flush(done) => {
+                let _ = done.send(());
+                continue;
+            }
+        };
+        let result = match serde_json::to_string(&edit) {
+            Ok(body) => sqlx::query("INSERT INTO edits (kind, body) VALUES ($1, $2)")
+                .bind(edit.kind())
+                .bind(body)
+                .execute(&pool)
+                .await
+                .map(|_| ())
+                .map_err(storage_error),
+            Err(e) => Err(storage_error(e)),
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to persist {}: {}", edit.describe(), e);
+        }
+    }
+}
+
+impl EditLog for PgLog {
+    fn load(&self) -> Result<Vec<Edit>, PersistError> {
+        Ok(self
+            .edits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone())
+    }
+
+    fn append(&self, edit: &Edit) -> Result<(), PersistError> {
+        self.writes
+            .send(Write::Edit(edit.clone()))
+            .map_err(|_| writer_stopped())?;
+        self.edits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(edit.clone());
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), PersistError> {
+        let (done, flushed) = oneshot::channel();
+        self.writes
+            .send(Write::Flush(done))
+            .map_err(|_| writer_stopped())?;
+        flushed.blocking_recv().map_err(|_| writer_stopped())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    #[ignore = "needs a Postgres database named by SYSTEMATICS_TEST_DATABASE_URL"]
+    async fn test_edits_survive_reconnecting() {
+        let url = std::env::var("SYSTEMATICS_TEST_DATABASE_URL").unwrap();
+        let pool = PgPool::connect(&url).await.unwrap();
+        let log = PgLog::connect(pool.clone()).await.unwrap();
+        let before = log.load().unwrap().len();
+
+        let edit = Edit::Annotation {
+            target: "term_3_1".to_string(),
+            text: "Survives redeploys".to_string(),
+            author: "Ana".to_string(),
+            author_id: None,
+        };
+        log.append(&edit).unwrap();
+        drop(log);
+        // The writer task drains its queue once the log is dropped
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let log = PgLog::connect(pool).await.unwrap();
+        let edits = log.load().unwrap();
+        assert_eq!(edits.len(), before + 1);
+        assert_eq!(edits.last(), Some(&edit));
+    }
+}
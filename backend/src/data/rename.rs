@@ -0,0 +1,194 @@
+//! Renames: new values for single terms and connectives, as edited in the
+//! browser.
+//!
+//! A renamed term gets a provisional Character in its vocabulary, and the
+//! vocabulary's Term at the location is replaced to point to it. A connective
+//! label is the connective's Character in a vocabulary other than the canonical
+//! one, whose connectives are curated (as for vocabulary packs).
+
+use std::fmt;
+
+use crate::core::{Character, Entry, Graph, Language, ResearchStatus, Term};
+
+/// Error raised when a term or connective cannot be renamed
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenameError {
+    EmptyValue,
+    /// A representation (e.g., Hex) rather than a vocabulary
+    NotVocabulary(Language),
+    UnknownLocation {
+        order: u8,
+        position: u8,
+    },
+    NoConnective {
+        order: u8,
+        base: u8,
+        target: u8,
+    },
+    CanonicalConnective,
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::EmptyValue => write!(f, "Value is empty"),
+            RenameError::NotVocabulary(language) => {
+                write!(f, "{} is not a vocabulary", language)
+            }
+            RenameError::UnknownLocation { order, position } => {
+                write!(f, "Order {} has no position {}", order, position)
+            }
+            RenameError::NoConnective {
+                order,
+                base,
+                target,
+            } => write!(
+                f,
+                "Order {} has no connective between positions {} and {}",
+                order, base, target
+            ),
+            RenameError::CanonicalConnective => write!(f, "Canonical connectives are curated"),
+        }
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+/// Name the term at `order` and `position` in `language` with `value`,
+/// returning the Term now at the location
+pub fn rename_term(
+    graph: &mut Graph,
+    order: u8,
+    position: u8,
+    language: &Language,
+    value: &str,
+) -> Result<Term, RenameError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(RenameError::EmptyValue);
+    }
+    if !language.is_vocabulary() {
+        return Err(RenameError::NotVocabulary(language.clone()));
+    }
+    if graph.location(order, position).is_none() {
+        return Err(RenameError::UnknownLocation { order, position });
+    }
+
+    let character =
+        Character::with_auto_id(language.clone(), value).with_status(ResearchStatus::Provisional);
+    let term = if *language == Language::Canonical {
+        Term::with_auto_id(order, position, character.id.clone())
+    } else {
+        Term::with_language_id(order, position, language, character.id.clone())
+    }
+    .with_status(ResearchStatus::Provisional);
+    let mut fragment = Graph::new();
+    fragment.add_entry(Entry::Character(character));
+    fragment.add_entry(Entry::Term(term.clone()));
+    graph.merge(fragment);
+    Ok(term)
+}
+
+/// Name the connective between positions `base` and `target` (either way round)
+/// of `order` with `value` in `language`, returning the naming Character
+pub fn label_connective(
+    graph: &mut Graph,
+    order: u8,
+    base: u8,
+    target: u8,
+    language: &Language,
+    value: &str,
+) -> Result<Character, RenameError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(RenameError::EmptyValue);
+    }
+    if !language.is_vocabulary() {
+        return Err(RenameError::NotVocabulary(language.clone()));
+    }
+    if *language == Language::Canonical {
+        return Err(RenameError::CanonicalConnective);
+    }
+    let link_id = graph
+        .links_between(order, base, target)
+        .into_iter()
+        .find(|l| l.is_connective())
+        .map(|l| l.id.clone())
+        .ok_or(RenameError::NoConnective {
+            order,
+            base,
+            target,
+        })?;
+
+    let character = Character::for_connective(language.clone(), &link_id, value)
+        .with_status(ResearchStatus::Provisional);
+    let mut fragment = Graph::new();
+    fragment.add_entry(Entry::Character(character.clone()));
+    graph.merge(fragment);
+    Ok(character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_rename_term() {
+        let mut graph = build_graph();
+        let term = rename_term(&mut graph, 3, 1, &Language::Canonical, " Intent ").unwrap();
+        assert_eq!(term.id, "term_3_1");
+        assert_eq!(graph.term(3, 1).unwrap().character, "char_canonical_intent");
+        assert_eq!(graph.term_character_at("loc_3_1").unwrap().value, "Intent");
+
+        // Other vocabularies get their own term beside the canonical one
+        let energy = rename_term(&mut graph, 3, 2, &Language::Energy, "Pull").unwrap();
+        assert_eq!(energy.id, "term_3_2_energy");
+        assert!(graph
+            .terms(3, Some(Language::Energy))
+            .iter()
+            .any(|t| t.character == "char_energy_pull"));
+
+        assert_eq!(
+            rename_term(&mut graph, 3, 1, &Language::Canonical, "  "),
+            Err(RenameError::EmptyValue)
+        );
+        assert_eq!(
+            rename_term(&mut graph, 3, 4, &Language::Canonical, "Extra"),
+            Err(RenameError::UnknownLocation {
+                order: 3,
+                position: 4
+            })
+        );
+        assert_eq!(
+            rename_term(&mut graph, 3, 1, &Language::Hex, "#000000"),
+            Err(RenameError::NotVocabulary(Language::Hex))
+        );
+    }
+
+    #[test]
+    fn test_label_connective() {
+        let mut graph = build_graph();
+        let character = label_connective(&mut graph, 3, 2, 1, &Language::Values, "Trust").unwrap();
+        let link = graph
+            .links_between(3, 1, 2)
+            .into_iter()
+            .find(|l| l.is_connective())
+            .unwrap();
+        assert_eq!(
+            graph
+                .connective_character(link, &Language::Values)
+                .map(|c| c.id.as_str()),
+            Some(character.id.as_str())
+        );
+
+        assert_eq!(
+            label_connective(&mut graph, 3, 1, 2, &Language::Canonical, "Act"),
+            Err(RenameError::CanonicalConnective)
+        );
+        assert_eq!(
+            label_connective(&mut graph, 3, 1, 3, &Language::Values, ""),
+            Err(RenameError::EmptyValue)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Raster rendering of systems.
+//!
+//! A system's SVG drawing (see `export`) is rasterized to PNG with resvg, for
+//! places that cannot show SVG: chats, slides and Open Graph previews. Labels
+//! are set in a sans-serif system font; the fonts are loaded on the first
+//! render, and labels are left out where none is installed.
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use resvg::tiny_skia::{Color, Pixmap, Transform};
+use resvg::usvg::{fontdb, Options, Tree};
+
+use crate::core::Graph;
+
+use super::export::{system_svg, ExportError, SVG_SIZE};
+
+/// Largest width rendered, in pixels
+pub const MAX_RENDER_WIDTH: u32 = 4096;
+
+/// Size and content of a rendered image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    /// Width (and height: drawings are square) in pixels
+    pub width: u32,
+    /// Draw the term labels
+    pub labels: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: SVG_SIZE as u32,
+            labels: true,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Options for the drawing scaled by `scale` (1.0 is 400 pixels wide)
+    pub fn scaled(scale: f64) -> Self {
+        Self {
+            width: (SVG_SIZE * scale).round().clamp(0.0, u32::MAX as f64) as u32,
+            ..Self::default()
+        }
+    }
+}
+
+/// Error raised when a system cannot be rendered
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    UnknownOrder(u8),
+    /// Zero, or wider than `MAX_RENDER_WIDTH`
+    InvalidWidth(u32),
+    Rasterize(String),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::UnknownOrder(order) => write!(f, "No system of order {}", order),
+            RenderError::InvalidWidth(width) => write!(
+                f,
+                "Width must be between 1 and {} pixels, not {}",
+                MAX_RENDER_WIDTH, width
+            ),
+            RenderError::Rasterize(e) => write!(f, "Rendering failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<ExportError> for RenderError {
+    fn from(e: ExportError) -> Self {
+        match e {
+            ExportError::UnknownOrder(order) => RenderError::UnknownOrder(order),
+            e => RenderError::Rasterize(e.to_string()),
+        }
+    }
+}
+
+/// Families tried, in order, for the drawing's `sans-serif` labels
+const SANS_SERIF_FAMILIES: [&str; 6] = [
+    "Arial",
+    "Helvetica",
+    "DejaVu Sans",
+    "Liberation Sans",
+    "Noto Sans",
+    "FreeSans",
+];
+
+/// The system fonts, loaded once. `sans-serif` maps to the first installed
+/// family of `SANS_SERIF_FAMILIES`, or else to any installed font.
+fn fonts() -> Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = fontdb::Database::new();
+            fonts.load_system_fonts();
+            let installed = |family: &str| {
+                fonts
+                    .faces()
+                    .any(|face| face.families.iter().any(|(name, _)| name == family))
+            };
+            let family = SANS_SERIF_FAMILIES
+                .into_iter()
+                .find(|family| installed(family))
+                .map(str::to_string)
+                .or_else(|| Some(fonts.faces().next()?.families.first()?.0.clone()));
+            match family {
+                Some(family) => fonts.set_sans_serif_family(family),
+                None => tracing::warn!("No system fonts found; rendered images have no labels"),
+            }
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+/// Render the system of one order as a PNG image
+pub fn render_png(
+    graph: &Graph,
+    order: u8,
+    options: &RenderOptions,
+) -> Result<Vec<u8>, RenderError> {
+    if options.width == 0 || options.width > MAX_RENDER_WIDTH {
+        return Err(RenderError::InvalidWidth(options.width));
+    }
+    let svg = system_svg(graph, order, options.labels)?;
+    let usvg_options = Options {
+        fontdb: fonts(),
+        ..Options::default()
+    };
+    let tree =
+        Tree::from_str(&svg, &usvg_options).map_err(|e| RenderError::Rasterize(e.to_string()))?;
+
+    let mut pixmap = Pixmap::new(options.width, options.width)
+        .ok_or_else(|| RenderError::Rasterize("Cannot allocate the image".to_string()))?;
+    // Drawings have a transparent background; previews need an opaque one
+    pixmap.fill(Color::WHITE);
+    let scale = options.width as f32 / tree.size().width();
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|e| RenderError::Rasterize(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    /// PNG signature, then the IHDR chunk's width and height (big-endian)
+    fn png_size(png: &[u8]) -> (u32, u32) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let word = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+        (word(16), word(20))
+    }
+
+    #[test]
+    fn test_render_png() {
+        let graph = build_graph();
+        let png = render_png(&graph, 4, &RenderOptions::default()).unwrap();
+        assert_eq!(png_size(&png), (400, 400));
+
+        let options = RenderOptions {
+            labels: false,
+            ..RenderOptions::scaled(0.5)
+        };
+        let png = render_png(&graph, 4, &options).unwrap();
+        assert_eq!(png_size(&png), (200, 200));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let graph = build_graph();
+        assert_eq!(
+            render_png(&graph, 99, &RenderOptions::default()),
+            Err(RenderError::UnknownOrder(99))
+        );
+        let options = RenderOptions {
+            width: MAX_RENDER_WIDTH + 1,
+            labels: true,
+        };
+        assert_eq!(
+            render_png(&graph, 4, &options),
+            Err(RenderError::InvalidWidth(MAX_RENDER_WIDTH + 1))
+        );
+    }
+}
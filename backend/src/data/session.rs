@@ -0,0 +1,194 @@
+//! Draft sessions.
+//!
+//! A session is a draft over a store (the shared graph, or a workspace's): a
+//! copy of its graph taken when the session starts, in which mutations are
+//! tried out without affecting anyone else. The draft records each mutation's
+//! `Edit` in memory. Committing replays those edits onto the store the session
+//! was started from, which persists them as if they had been made there;
+//! discarding drops them.
+//!
+//! Sessions are identified by random IDs, and a session unused for longer than
+//! the idle timeout is dropped by `evict_idle`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::store::GraphStore;
+
+/// Most sessions held at once
+pub const MAX_SESSIONS: usize = 256;
+
+/// Error raised when a session cannot be started or used
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionError {
+    /// No session with this ID (it may have been committed, discarded or expired)
+    Unknown(String),
+    /// `MAX_SESSIONS` sessions are already open
+    LimitReached,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Unknown(id) => write!(f, "No session '{}'", id),
+            SessionError::LimitReached => {
+                write!(f, "The server already holds {} sessions", MAX_SESSIONS)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// Outcome of committing a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionCommit {
+    /// Number of edits applied
+    pub applied: usize,
+    /// Edits that no longer apply to the store (e.g., a custom system whose order
+    /// was taken meanwhile), described
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Session {
+    draft: GraphStore,
+    origin: GraphStore,
+    last_used: Instant,
+}
+
+/// Thread-safe registry of draft sessions. Cloning shares the same registry.
+#[derive(Debug, Clone, Default)]
+pub struct SessionRegistry {
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+}
+
+impl SessionRegistry {
+    fn sessions(&self) -> MutexGuard<'_, HashMap<String, Session>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Start a session drafting over `origin`'s current graph, returning its ID
+    pub fn start(&self, origin: &GraphStore) -> Result<String, SessionError> {
+        let mut sessions = self.sessions();
+        if sessions.len() >= MAX_SESSIONS {
+            return Err(SessionError::LimitReached);
+        }
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        sessions.insert(
+            id.clone(),
+            Session {
+                draft: GraphStore::draft(origin.snapshot()),
+                origin: origin.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        Ok(id)
+    }
+
+    /// The draft store of a session
+    pub fn draft(&self, id: &str) -> Result<GraphStore, SessionError> {
+        let mut sessions = self.sessions();
+        let session = sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::Unknown(id.to_string()))?;
+        session.last_used = Instant::now();
+        Ok(session.draft.clone())
+    }
+
+    /// End a session, applying its edits to the store it was started from
+    pub fn commit(&self, id: &str) -> Result<SessionCommit, SessionError> {
+        let session = self
+            .sessions()
+            .remove(id)
+            .ok_or_else(|| SessionError::Unknown(id.to_string()))?;
+        let mut commit = SessionCommit {
+            applied: 0,
+            skipped: Vec::new(),
+        };
+        for edit in session.draft.edits() {
+            match session
+                .origin
+                .apply(edit.clone(), |graph| edit.apply(graph))
+            {
+                Ok(()) => commit.applied += 1,
+                Err(e) => commit
+                    .skipped
+                    .push(format!("Skipped {}: {}", edit.describe(), e)),
+            }
+        }
+        Ok(commit)
+    }
+
+    /// End a session, dropping its edits; returns whether it existed
+    pub fn discard(&self, id: &str) -> bool {
+        self.sessions().remove(id).is_some()
+    }
+
+    /// Drop the sessions unused for longer than `max_idle` as of `now`, returning
+    /// how many were dropped
+    pub fn evict_idle(&self, max_idle: Duration, now: Instant) -> usize {
+        let mut sessions = self.sessions();
+        let before = sessions.len();
+        sessions.retain(|_, session| now.saturating_duration_since(session.last_used) <= max_idle);
+        before - sessions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{add_annotation, build_graph, Edit};
+
+    fn annotate(store: &GraphStore, target: &str, text: &str) {
+        let edit = Edit::Annotation {
+            target: target.to_string(),
+            text: text.to_string(),
+            author: "Ana".to_string(),
+            author_id: None,
+        };
+        let _ = store.apply(edit, |graph| {
+            add_annotation(graph, target, text, "Ana", None)
+        });
+    }
+
+    #[test]
+    fn test_commit_applies_draft_edits() {
+        let shared = GraphStore::new(build_graph());
+        let sessions = SessionRegistry::default();
+        let id = sessions.start(&shared).unwrap();
+
+        let draft = sessions.draft(&id).unwrap();
+        annotate(&draft, "term_3_1", "Drafted");
+        annotate(&draft, "term_3_2", "Also drafted");
+        assert_eq!(draft.snapshot().all_annotations().len(), 2);
+        assert!(shared.snapshot().all_annotations().is_empty());
+
+        let commit = sessions.commit(&id).unwrap();
+        assert_eq!(commit.applied, 2);
+        assert!(commit.skipped.is_empty());
+        assert_eq!(shared.snapshot().all_annotations().len(), 2);
+        assert_eq!(sessions.commit(&id), Err(SessionError::Unknown(id.clone())));
+    }
+
+    #[test]
+    fn test_discard_and_expiry() {
+        let shared = GraphStore::new(build_graph());
+        let sessions = SessionRegistry::default();
+        let id = sessions.start(&shared).unwrap();
+        annotate(&sessions.draft(&id).unwrap(), "term_3_1", "Dropped");
+        assert!(sessions.discard(&id));
+        assert!(!sessions.discard(&id));
+        assert!(shared.snapshot().all_annotations().is_empty());
+
+        let id = sessions.start(&shared).unwrap();
+        let later = Instant::now() + Duration::from_secs(60);
+        assert_eq!(sessions.evict_idle(Duration::from_secs(120), later), 0);
+        assert_eq!(sessions.evict_idle(Duration::from_secs(30), later), 1);
+        assert!(sessions.draft(&id).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! SQLite storage of the edit log (requires the `sqlite` feature).
+//!
+//! Edits are rows of a single `edits` table, holding each edit's JSON form in
+//! insertion order. The database is created on first use. Edits are read once
+//! when opening; appends are queued and written in order by a background
+//! thread, so mutations never wait on the disk while holding the graph lock.
+//! Flushing waits for the queue to drain.
+
+use std::path::Path;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection};
+
+use super::persist::{Edit, EditLog, PersistError};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS edits (
+        id         INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind       TEXT NOT NULL,
+        body       TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+";
+
+fn storage_error(e: impl std::fmt::Display) -> PersistError {
+    PersistError::Storage(e.to_string())
+}
+
+fn writer_stopped() -> PersistError {
+    PersistError::Storage("Edit writer has stopped".to_string())
+}
+
+/// Work queued for the writer thread
+#[derive(Debug)]
+enum Write {
+    /// An edit and when it was appended, in seconds since the Unix epoch
+    Edit(Edit, i64),
+    /// Signalled once the writes queued before it are done
+    Flush(mpsc::Sender<()>),
+}
+
+/// Edit log kept in a SQLite database file
+#[derive(Debug)]
+pub struct SqliteLog {
+    edits: Mutex<Vec<Edit>>,
+    writes: mpsc::Sender<Write>,
+}
+
+impl SqliteLog {
+    /// Open (or create) the database at `path`
+    pub fn open(path: &Path) -> Result<Self, PersistError> {
+        Self::init(Connection::open(path).map_err(storage_error)?)
+    }
+
+    /// An in-memory database, discarded when dropped
+    pub fn in_memory() -> Result<Self, PersistError> {
+        Self::init(Connection::open_in_memory().map_err(storage_error)?)
+    }
+
+    /// Create the schema, read the recorded edits and start the writer thread
+    fn init(conn: Connection) -> Result<Self, PersistError> {
+        // Write-ahead logging lets each append commit without rewriting the file
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(storage_error)?;
+        conn.execute_batch(SCHEMA).map_err(storage_error)?;
+        let edits = read_edits(&conn)?;
+
+        let (writes, queued) = mpsc::channel();
+        thread::Builder::new()
+            .name("sqlite-edits".to_string())
+            .spawn(move || write_edits(conn, queued))
+            .map_err(storage_error)?;
+        Ok(Self {
+            edits: Mutex::new(edits),
+            writes,
+        })
+    }
+
+    fn send(&self, write: Write) -> Result<(), PersistError> {
+        self.writes.send(write).map_err(|_| writer_stopped())
+    }
+}
+
+fn read_edits(conn: &Connection) -> Result<Vec<Edit>, PersistError> {
+    let mut statement = conn
+        .prepare("SELECT id, body FROM edits ORDER BY id")
+        .map_err(storage_error)?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(storage_error)?;
+    let mut edits = Vec::new();
+    for row in rows {
+        let (id, body) = row.map_err(storage_error)?;
+        let edit = serde_json::from_str(&body).map_err(|e| PersistError::Corrupt {
+            id,
+            message: e.to_string(),
+        })?;
+        edits.push(edit);
+    }
+    Ok(edits)
+}
+
+/// Write queued edits in order until the log is dropped
+fn write_edits(conn: Connection, queued: mpsc::Receiver<Write>) {
+    for write in queued {
+        let (edit, created_at) = match write {
+            Write::Edit(edit, created_at) => (edit, created_at),
+            Write::Flush(done) => {
+                let _ = done.send(());
+                continue;
+            }
+        };
+        let result = match serde_json::to_string(&edit) {
+            Ok(body) => conn
+                .execute(
+                    "INSERT INTO edits (kind, body, created_at) VALUES (?1, ?2, ?3)",
+                    params![edit.kind(), body, created_at],
+                )
+                .map(|_| ())
+                .map_err(storage_error),
+            Err(e) => Err(storage_error(e)),
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to persist {}: {}", edit.describe(), e);
+        }
+    }
+}
+
+impl EditLog for SqliteLog {
+    fn load(&self) -> Result<Vec<Edit>, PersistError> {
+        Ok(self
+            .edits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone())
+    }
+
+    fn append(&self, edit: &Edit) -> Result<(), PersistError> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.send(Write::Edit(edit.clone(), created_at))?;
+        self.edits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(edit.clone());
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), PersistError> {
+        let (done, flushed) = mpsc::channel();
+        self.send(Write::Flush(done))?;
+        flushed.recv().map_err(|_| writer_stopped())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::VocabularyPack;
+
+    fn annotation(text: &str) -> Edit {
+        Edit::Annotation {
+            target: "term_3_1".to_string(),
+            text: text.to_string(),
+            author: "Ana".to_string(),
+            author_id: Some("user-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_edits_survive_reopening() {
+        let path =
+            std::env::temp_dir().join(format!("systematics_edits_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let log = SqliteLog::open(&path).unwrap();
+        log.append(&annotation("First")).unwrap();
+        let vocabulary = Edit::Vocabulary {
+            name: "Tarot".to_string(),
+            pack: VocabularyPack::new().with_terms(3, ["Fool", "Magician", "Priestess"]),
+        };
+        log.append(&vocabulary).unwrap();
+        log.flush().unwrap();
+        drop(log);
+
+        let log = SqliteLog::open(&path).unwrap();
+        assert_eq!(log.load().unwrap(), vec![annotation("First"), vocabulary]);
+
+        drop(log);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[test]
+    fn test_corrupt_edit() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn.execute(
+            "INSERT INTO edits (kind, body, created_at) VALUES ('annotation', '{}', 0)",
+            [],
+        )
+        .unwrap();
+        assert!(matches!(
+            SqliteLog::init(conn),
+            Err(PersistError::Corrupt { id: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+//! Shared graph storage.
+//!
+//! The GraphStore holds the graph served by the API. Readers take a snapshot
+//! per request; reloads swap the whole graph atomically, and mutations edit it
+//! in place under the write lock. Each change updates the store's `Revision`
+//! (a content hash and modification time), which HTTP caching validates
+//! against and subscribers are notified of.
+//!
+//! A persistent store (see `persist`) also records each mutation's `Edit` in
+//! its edit log, and replays the recorded edits onto every graph it is given:
+//! the baseline at startup and each reloaded one. Every store broadcasts the
+//! edits it applies, for webhooks.
+
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use tokio::sync::{broadcast, watch};
+
+use crate::core::Graph;
+
+use super::persist::{replay, Edit, EditLog, MemoryLog, PersistError};
+
+/// Applied edits buffered for slow listeners before they miss some
+const EDIT_BUFFER: usize = 64;
+
+/// Content hash of the stored graph, and when it last changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    pub hash: String,
+    pub modified: SystemTime,
+}
+
+impl Revision {
+    fn of(graph: &Graph) -> Self {
+        Self {
+            hash: graph.content_hash(),
+            modified: SystemTime::now(),
+        }
+    }
+}
+
+/// The edit log of a persistent store, and the edits recorded so far
+#[derive(Debug)]
+struct Journal {
+    log: Box<dyn EditLog>,
+    edits: Mutex<Vec<Edit>>,
+}
+
+impl Journal {
+    fn edits(&self) -> std::sync::MutexGuard<'_, Vec<Edit>> {
+        self.edits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Replay the recorded edits onto a graph, logging those that no longer apply
+    fn replay(&self, graph: &mut Graph) {
+        for problem in replay(graph, &self.edits()) {
+            tracing::warn!("{}", problem);
+        }
+    }
+
+    /// Record an edit. A failed write is logged and the edit kept in memory, so it
+    /// still survives reloads (but not a restart).
+    fn record(&self, edit: Edit) {
+        if let Err(e) = self.log.append(&edit) {
+            tracing::error!("Failed to persist {}: {}", edit.describe(), e);
+        }
+        self.edits().push(edit);
+    }
+}
+
+/// Thread-safe handle to the shared graph. Cloning shares the same graph.
+#[derive(Debug, Clone)]
+pub struct GraphStore {
+    inner: Arc<RwLock<Graph>>,
+    revision: Arc<watch::Sender<Revision>>,
+    applied: broadcast::Sender<Edit>,
+    journal: Option<Arc<Journal>>,
+}
+
+impl Default for GraphStore {
+    fn default() -> Self {
+        Self::new(Graph::default())
+    }
+}
+
+impl GraphStore {
+    pub fn new(graph: Graph) -> Self {
+        let (revision, _) = watch::channel(Revision::of(&graph));
+        Self {
+            inner: Arc::new(RwLock::new(graph)),
+            revision: Arc::new(revision),
+            applied: broadcast::channel(EDIT_BUFFER).0,
+            journal: None,
+        }
+    }
+
+    /// A store persisting its edits to `log`, serving `baseline` with the edits
+    /// already recorded there replayed onto it
+    pub fn persistent(baseline: Graph, log: impl EditLog + 'static) -> Result<Self, PersistError> {
+        let journal = Journal {
+            edits: Mutex::new(log.load()?),
+            log: Box::new(log),
+        };
+        let mut graph = baseline;
+        journal.replay(&mut graph);
+        Ok(Self {
+            journal: Some(Arc::new(journal)),
+            ..Self::new(graph)
+        })
+    }
+
+    /// A store over `baseline` recording its edits in memory, so a draft's edits can
+    /// be read back (see `edits`) and applied elsewhere
+    pub fn draft(baseline: Graph) -> Self {
+        Self::persistent(baseline, MemoryLog::default()).expect("memory logs load")
+    }
+
+    /// Whether edits are persisted
+    pub fn is_persistent(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Wait until the edits recorded so far are stored, e.g. before exiting. This
+    /// may block, so call it outside async code.
+    pub fn flush(&self) -> Result<(), PersistError> {
+        match &self.journal {
+            Some(journal) => journal.log.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Number of edits recorded by a persistent store
+    pub fn edit_count(&self) -> usize {
+        self.journal.as_ref().map_or(0, |j| j.edits().len())
+    }
+
+    /// The edits recorded by a persistent store, oldest first
+    pub fn edits(&self) -> Vec<Edit> {
+        self.journal
+            .as_ref()
+            .map_or_else(Vec::new, |j| j.edits().clone())
+    }
+
+    /// Get a copy of the current graph
+    pub fn snapshot(&self) -> Graph {
+        self.inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Get the revision of the current graph
+    pub fn revision(&self) -> Revision {
+        self.revision.borrow().clone()
+    }
+
+    /// Watch the revision, which changes whenever the graph's content does
+    pub fn subscribe(&self) -> watch::Receiver<Revision> {
+        self.revision.subscribe()
+    }
+
+    /// Replace the current graph (with the recorded edits replayed onto it, if persistent)
+    pub fn replace(&self, mut graph: Graph) {
+        let mut current = self
+            .inner
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(journal) = &self.journal {
+            journal.replay(&mut graph);
+        }
+        *current = graph;
+        self.refresh(&current);
+    }
+
+    /// Modify the current graph in place, returning the closure's result
+    pub fn update<R>(&self, f: impl FnOnce(&mut Graph) -> R) -> R {
+        let mut graph = self
+            .inner
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = f(&mut graph);
+        self.refresh(&graph);
+        result
+    }
+
+    /// Modify the current graph in place like `update`; if `f` succeeds, a persistent
+    /// store records `edit`, which must reproduce the change when replayed, and the
+    /// edit is sent to `applied_edits` listeners
+    pub fn apply<R, E>(
+        &self,
+        edit: Edit,
+        f: impl FnOnce(&mut Graph) -> Result<R, E>,
+    ) -> Result<R, E> {
+        self.update(|graph| {
+            let result = f(graph)?;
+            if self.applied.receiver_count() > 0 {
+                // Fails only if the listeners have just gone
+                let _ = self.applied.send(edit.clone());
+            }
+            if let Some(journal) = &self.journal {
+                journal.record(edit);
+            }
+            Ok(result)
+        })
+    }
+
+    /// Listen for the edits applied from now on
+    pub fn applied_edits(&self) -> broadcast::Receiver<Edit> {
+        self.applied.subscribe()
+    }
+
+    /// Update the revision (under the write lock), keeping the modification time
+    /// and notifying no one if the content is unchanged
+    fn refresh(&self, graph: &Graph) {
+        let revision = Revision::of(graph);
+        self.revision.send_if_modified(|current| {
+            let changed = current.hash != revision.hash;
+            if changed {
+                *current = revision;
+            }
+            changed
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Entry, Order};
+    use crate::data::{add_annotation, build_graph};
+
+    #[test]
+    fn test_replace_is_visible_to_clones() {
+        let store = GraphStore::new(Graph::new());
+        let handle = store.clone();
+
+        let mut graph = Graph::new();
+        graph.add_entry(Entry::Order(Order::new(3)));
+        store.replace(graph);
+
+        assert!(handle.snapshot().order(3).is_some());
+    }
+
+    #[test]
+    fn test_update_is_visible_to_clones() {
+        let store = GraphStore::new(Graph::new());
+        let handle = store.clone();
+
+        let count = store.update(|graph| {
+            graph.add_entry(Entry::Order(Order::new(3)));
+            graph.entries.len()
+        });
+
+        assert_eq!(count, 1);
+        assert!(handle.snapshot().order(3).is_some());
+    }
+
+    #[test]
+    fn test_revision_follows_content() {
+        let store = GraphStore::new(Graph::new());
+        let empty = store.revision();
+
+        // A failed or no-op update keeps the revision
+        store.update(|_| ());
+        assert_eq!(store.revision(), empty);
+
+        let changes = store.subscribe();
+        store.update(|graph| graph.add_entry(Entry::Order(Order::new(3))));
+        assert!(changes.has_changed().unwrap());
+        let revision = store.revision();
+        assert_ne!(revision.hash, empty.hash);
+        assert!(revision.modified >= empty.modified);
+
+        store.replace(Graph::new());
+        assert_eq!(store.revision().hash, empty.hash);
+    }
+
+    #[test]
+    fn test_persistent_store_replays_edits() {
+        let note = |text: &str| Edit::Annotation {
+            target: "term_3_1".to_string(),
+            text: text.to_string(),
+            author: "Ana".to_string(),
+            author_id: None,
+        };
+        let log = MemoryLog::default();
+        log.append(&note("Recorded earlier")).unwrap();
+        let store = GraphStore::persistent(build_graph(), log).unwrap();
+        assert_eq!(store.snapshot().all_annotations().len(), 1);
+
+        let edit = note("Added now");
+        store
+            .apply(edit.clone(), |graph| {
+                add_annotation(graph, "term_3_1", "Added now", "Ana", None)
+            })
+            .unwrap();
+        // A failed mutation is not recorded
+        let failed = store.apply(note("Lost"), |graph| {
+            add_annotation(graph, "no_such_entry", "Lost", "Ana", None)
+        });
+        assert!(failed.is_err());
+        assert_eq!(store.edit_count(), 2);
+
+        // Reloading the baseline keeps the edits
+        store.replace(build_graph());
+        assert_eq!(store.snapshot().all_annotations().len(), 2);
+    }
+}
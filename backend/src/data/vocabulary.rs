@@ -0,0 +1,519 @@
+//! Importing vocabulary packs from uploaded files.
+//!
+//! A pack names terms (by position) and connectives (by position pair) for some
+//! orders, in one of two formats. CSV has an `order,position,value` header and
+//! one row per value, where a position of the form `1-2` names a connective:
+//!
+//! ```text
+//! order,position,value
+//! 3,1,Cups
+//! 3,1-2,Pour
+//! ```
+//!
+//! YAML maps `terms` and `connectives` to lists by order (a small subset of
+//! YAML: block or flow lists of plain or quoted strings):
+//!
+//! ```text
+//! terms:
+//!   3: [Cups, Wands, Swords]
+//! connectives:
+//!   3: [Pour, Kindle, Cut]
+//! ```
+//!
+//! Blank values leave a position unnamed. Values for orders without a system,
+//! or pairs without a connective, are skipped and listed in the import report.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::core::{Graph, Language, VocabularyPack};
+
+/// File format of a vocabulary pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VocabularyFormat {
+    Csv,
+    Yaml,
+}
+
+impl VocabularyFormat {
+    /// Format given by a file extension (`.csv`, `.yaml` or `.yml`)
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(VocabularyFormat::Csv),
+            "yaml" | "yml" => Some(VocabularyFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Format given by a MIME type (e.g., `text/csv`, `application/yaml`)
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next()?.trim() {
+            "text/csv" => Some(VocabularyFormat::Csv),
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+                Some(VocabularyFormat::Yaml)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Error raised when a vocabulary pack cannot be imported
+#[derive(Debug, Clone, PartialEq)]
+pub enum VocabularyError {
+    EmptyName,
+    /// The name belongs to a built-in language (e.g., "Canonical" or "Hex")
+    ReservedName(String),
+    UnsupportedFormat(String),
+    /// Malformed input, with the 1-based line number
+    Parse {
+        line: usize,
+        message: String,
+    },
+    Empty,
+    /// More values than the order has positions (or pairs)
+    TooManyValues {
+        order: u8,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::EmptyName => write!(f, "Vocabulary name is empty"),
+            VocabularyError::ReservedName(name) => {
+                write!(f, "'{}' is a built-in language", name)
+            }
+            VocabularyError::UnsupportedFormat(file) => {
+                write!(f, "Unsupported vocabulary file '{}'", file)
+            }
+            VocabularyError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            VocabularyError::Empty => write!(f, "Vocabulary pack has no values"),
+            VocabularyError::TooManyValues {
+                order,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Order {} takes at most {} values, found {}",
+                order, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+/// Outcome of importing a vocabulary pack
+#[derive(Debug, Clone, PartialEq)]
+pub struct VocabularyImport {
+    pub language: Language,
+    pub terms: usize,
+    pub connectives: usize,
+    /// Values that were not applied, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Parse a vocabulary pack in the given format
+pub fn parse_vocabulary(
+    text: &str,
+    format: VocabularyFormat,
+) -> Result<VocabularyPack, VocabularyError> {
+    match format {
+        VocabularyFormat::Csv => parse_csv(text),
+        VocabularyFormat::Yaml => parse_yaml(text),
+    }
+}
+
+/// Validate a pack against the graph and register it as vocabulary `name`
+pub fn import_vocabulary(
+    graph: &mut Graph,
+    name: &str,
+    pack: &VocabularyPack,
+) -> Result<VocabularyImport, VocabularyError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(VocabularyError::EmptyName);
+    }
+    if !Language::from_name(name).is_custom() {
+        return Err(VocabularyError::ReservedName(name.to_string()));
+    }
+    let named = |values: &Vec<String>| values.iter().any(|v| !v.trim().is_empty());
+    if !pack.terms.values().any(named) && !pack.connectives.values().any(named) {
+        return Err(VocabularyError::Empty);
+    }
+    for (&order, values) in &pack.terms {
+        check_count(order, order as usize, values)?;
+    }
+    for (&order, values) in &pack.connectives {
+        check_count(order, pair_count(order), values)?;
+    }
+
+    let mut terms = 0;
+    let mut connectives = 0;
+    let mut skipped = Vec::new();
+    for (&order, values) in &pack.terms {
+        let count = values.iter().filter(|v| !v.trim().is_empty()).count();
+        if graph.order(order).is_none() {
+            skipped.push(format!(
+                "{} terms for order {}: no such system",
+                count, order
+            ));
+        } else {
+            terms += count;
+        }
+    }
+    for (&order, values) in &pack.connectives {
+        if graph.order(order).is_none() {
+            skipped.push(format!("connectives for order {}: no such system", order));
+            continue;
+        }
+        for ((i, j), value) in pairs(order).zip(values) {
+            if value.trim().is_empty() {
+                continue;
+            }
+            if graph
+                .links_between(order, i, j)
+                .iter()
+                .any(|l| l.is_connective())
+            {
+                connectives += 1;
+            } else {
+                skipped.push(format!(
+                    "'{}' for order {} pair {}-{}: no connective",
+                    value, order, i, j
+                ));
+            }
+        }
+    }
+
+    let language = graph.register_vocabulary(name, pack);
+    Ok(VocabularyImport {
+        language,
+        terms,
+        connectives,
+        skipped,
+    })
+}
+
+fn check_count(order: u8, expected: usize, values: &[String]) -> Result<(), VocabularyError> {
+    if values.len() > expected {
+        return Err(VocabularyError::TooManyValues {
+            order,
+            expected,
+            found: values.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Position pairs of an order in pack order: (1,2), (1,3), ... (2,3), ...
+fn pairs(order: u8) -> impl Iterator<Item = (u8, u8)> {
+    (1..=order).flat_map(move |i| ((i + 1)..=order).map(move |j| (i, j)))
+}
+
+fn pair_count(order: u8) -> usize {
+    let n = order as usize;
+    n * n.saturating_sub(1) / 2
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> VocabularyError {
+    VocabularyError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Set `values[index]`, padding earlier positions with blanks
+fn place(values: &mut Vec<String>, index: usize, value: String) -> bool {
+    if values.len() <= index {
+        values.resize(index + 1, String::new());
+    } else if !values[index].is_empty() {
+        return false;
+    }
+    values[index] = value;
+    true
+}
+
+fn parse_csv(text: &str) -> Result<VocabularyPack, VocabularyError> {
+    let mut pack = VocabularyPack::new();
+    let mut header = false;
+    for (idx, raw) in text.lines().enumerate() {
+        let line = idx + 1;
+        if raw.trim().is_empty() || raw.trim_start().starts_with('#') {
+            continue;
+        }
+        let fields = split_fields(raw, ',', &['"']).map_err(|m| parse_error(line, m))?;
+        if !header {
+            let names: Vec<String> = fields.iter().map(|f| f.to_ascii_lowercase()).collect();
+            if names != ["order", "position", "value"] {
+                return Err(parse_error(line, "expected header 'order,position,value'"));
+            }
+            header = true;
+            continue;
+        }
+        let [order, position, value] = <[String; 3]>::try_from(fields)
+            .map_err(|f| parse_error(line, format!("expected 3 fields, found {}", f.len())))?;
+        let order: u8 = order
+            .parse()
+            .ok()
+            .filter(|o| *o >= 1)
+            .ok_or_else(|| parse_error(line, format!("invalid order '{}'", order)))?;
+        let invalid_position = || parse_error(line, format!("invalid position '{}'", position));
+        let in_order = |p: &str| p.parse::<u8>().ok().filter(|p| (1..=order).contains(p));
+        let (values, index) = match position.split_once('-') {
+            Some((i, j)) => {
+                let (i, j) = (in_order(i), in_order(j));
+                let (Some(i), Some(j)) = (i, j) else {
+                    return Err(invalid_position());
+                };
+                let (i, j) = (i.min(j), i.max(j));
+                let index = pairs(order)
+                    .position(|p| p == (i, j))
+                    .ok_or_else(invalid_position)?;
+                (pack.connectives.entry(order).or_default(), index)
+            }
+            None => {
+                let p = in_order(&position).ok_or_else(invalid_position)?;
+                (pack.terms.entry(order).or_default(), (p - 1) as usize)
+            }
+        };
+        if !place(values, index, value) {
+            return Err(parse_error(
+                line,
+                format!("order {} position {} given twice", order, position),
+            ));
+        }
+    }
+    if !header {
+        return Err(VocabularyError::Empty);
+    }
+    Ok(pack)
+}
+
+/// Split a line on `separator` outside `quotes`, trimming and unquoting each field.
+/// A quote character inside a quoted field is written twice.
+fn split_fields(line: &str, separator: char, quotes: &[char]) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quote = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => {
+                if chars.peek() == Some(&q) {
+                    field.push(q);
+                    chars.next();
+                } else {
+                    quote = None;
+                }
+            }
+            Some(_) => field.push(c),
+            None if c == separator => fields.push(std::mem::take(&mut field).trim().to_string()),
+            None if quotes.contains(&c) && field.trim().is_empty() => {
+                field.clear();
+                quote = Some(c);
+            }
+            None => field.push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated quote".to_string());
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
+
+/// Quote characters of YAML strings
+const YAML_QUOTES: &[char] = &['"', '\''];
+
+/// A single YAML value, plain or quoted
+fn scalar(text: &str) -> Result<String, String> {
+    let mut fields = split_fields(text, '\n', YAML_QUOTES)?;
+    Ok(fields.remove(0))
+}
+
+/// Strip a `#` comment that is outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (idx, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return &line[..idx],
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn parse_yaml(text: &str) -> Result<VocabularyPack, VocabularyError> {
+    let mut pack = VocabularyPack::new();
+    // Whether the current section is `terms` (else `connectives`), and the
+    // order whose block list is open
+    let mut in_terms: Option<bool> = None;
+    let mut list: Option<u8> = None;
+    for (idx, raw) in text.lines().enumerate() {
+        let line = idx + 1;
+        let content = strip_comment(raw).trim_end();
+        if content.trim().is_empty() || content == "---" {
+            continue;
+        }
+        if content.starts_with('\t') {
+            return Err(parse_error(line, "indent with spaces, not tabs"));
+        }
+        let indented = content.starts_with(' ');
+        let content = content.trim_start();
+
+        if let Some(item) = content.strip_prefix('-') {
+            let (Some(terms), Some(order), true) = (in_terms, list, indented) else {
+                return Err(parse_error(line, "list item outside an order"));
+            };
+            let value = scalar(item).map_err(|m| parse_error(line, m))?;
+            section(&mut pack, terms)
+                .entry(order)
+                .or_default()
+                .push(value);
+            continue;
+        }
+
+        let Some((key, rest)) = content.split_once(':') else {
+            return Err(parse_error(line, "expected 'key: value'"));
+        };
+        let (key, rest) = (key.trim(), rest.trim());
+        list = None;
+        if !indented {
+            in_terms = match key {
+                "terms" => Some(true),
+                "connectives" => Some(false),
+                other => return Err(parse_error(line, format!("unknown key '{}'", other))),
+            };
+            if !rest.is_empty() && rest != "{}" {
+                return Err(parse_error(line, "expected orders on the following lines"));
+            }
+            continue;
+        }
+
+        let Some(terms) = in_terms else {
+            return Err(parse_error(line, "order outside 'terms' or 'connectives'"));
+        };
+        let order: u8 = key
+            .parse()
+            .ok()
+            .filter(|o| *o >= 1)
+            .ok_or_else(|| parse_error(line, format!("invalid order '{}'", key)))?;
+        let values = section(&mut pack, terms);
+        if values.contains_key(&order) {
+            return Err(parse_error(line, format!("order {} given twice", order)));
+        }
+        if rest.is_empty() {
+            values.insert(order, Vec::new());
+            list = Some(order);
+        } else if let Some(inner) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            let items = if inner.trim().is_empty() {
+                Vec::new()
+            } else {
+                split_fields(inner, ',', YAML_QUOTES).map_err(|m| parse_error(line, m))?
+            };
+            values.insert(order, items);
+        } else {
+            return Err(parse_error(
+                line,
+                "expected a list, e.g. [Cups, Wands, Swords]",
+            ));
+        }
+    }
+    Ok(pack)
+}
+
+fn section(pack: &mut VocabularyPack, terms: bool) -> &mut BTreeMap<u8, Vec<String>> {
+    if terms {
+        &mut pack.terms
+    } else {
+        &mut pack.connectives
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::build_graph;
+
+    #[test]
+    fn test_parse_formats() {
+        let csv = "order,position,value\n3,1,Cups\n3,3,\"Swords, drawn\"\n3,2-1,Pour\n";
+        let from_csv = parse_vocabulary(csv, VocabularyFormat::Csv).unwrap();
+
+        let yaml = "# Tarot suits\nterms:\n  3:\n    - Cups\n    -\n    - 'Swords, drawn'\n\
+                    connectives:\n  3: [Pour]\n";
+        let from_yaml = parse_vocabulary(yaml, VocabularyFormat::Yaml).unwrap();
+
+        let expected = VocabularyPack::new()
+            .with_terms(3, ["Cups", "", "Swords, drawn"])
+            .with_connectives(3, ["Pour"]);
+        assert_eq!(from_csv, expected);
+        assert_eq!(from_yaml, expected);
+
+        assert_eq!(
+            parse_vocabulary("order,position,value\n3,4,Coins", VocabularyFormat::Csv),
+            Err(parse_error(2, "invalid position '4'"))
+        );
+        assert_eq!(
+            parse_vocabulary("terms:\n  3: Cups\n", VocabularyFormat::Yaml),
+            Err(parse_error(
+                2,
+                "expected a list, e.g. [Cups, Wands, Swords]"
+            ))
+        );
+        assert_eq!(
+            VocabularyFormat::from_file_name("tarot.YML"),
+            Some(VocabularyFormat::Yaml)
+        );
+    }
+
+    #[test]
+    fn test_import_vocabulary() {
+        let mut graph = build_graph();
+        let pack = VocabularyPack::new()
+            .with_terms(3, ["Cups", "", "Swords"])
+            .with_terms(40, ["Lost"])
+            .with_connectives(3, ["Pour", "Cut"]);
+        let report = import_vocabulary(&mut graph, "Tarot", &pack).unwrap();
+
+        assert_eq!(report.language, Language::Custom("Tarot".to_string()));
+        assert_eq!((report.terms, report.connectives), (2, 2));
+        assert_eq!(report.skipped, vec!["1 terms for order 40: no such system"]);
+        assert!(graph.validate().is_empty());
+
+        assert_eq!(
+            import_vocabulary(&mut graph, "Hex", &pack),
+            Err(VocabularyError::ReservedName("Hex".to_string()))
+        );
+        // Built-in vocabularies are edited term by term, by signed-in users
+        let canonical = graph.term(3, 1).cloned();
+        for name in ["Canonical", "energy"] {
+            assert_eq!(
+                import_vocabulary(&mut graph, name, &pack),
+                Err(VocabularyError::ReservedName(name.to_string()))
+            );
+        }
+        assert_eq!(graph.term(3, 1).cloned(), canonical);
+        assert_eq!(
+            import_vocabulary(
+                &mut graph,
+                "Tarot",
+                &VocabularyPack::new().with_terms(3, ["a", "b", "c", "d"])
+            ),
+            Err(VocabularyError::TooManyValues {
+                order: 3,
+                expected: 3,
+                found: 4
+            })
+        );
+    }
+}
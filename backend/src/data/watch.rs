@@ -0,0 +1,52 @@
+//! Development hot-reload of external data files (requires the `dev` feature).
+//!
+//! Watches the data directory and, on any change, reloads the graph, logs the
+//! validation results and swaps the new graph into the shared store. A reload
+//! that fails to parse leaves the previous graph in place.
+
+use std::path::{Path, PathBuf};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::GraphStore;
+
+/// Start watching `dir`. The returned watcher must be kept alive for as long
+/// as reloading should continue.
+pub fn watch(dir: &Path, store: GraphStore) -> notify::Result<RecommendedWatcher> {
+    let watched: PathBuf = dir.to_path_buf();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) => {
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                reload(&watched, &store);
+            }
+        }
+        Err(e) => tracing::error!("Data watcher error: {}", e),
+    })?;
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    tracing::info!("Watching {} for data changes", dir.display());
+    Ok(watcher)
+}
+
+/// Reload the graph from `dir` into `store`, logging validation and audit results
+pub fn reload(dir: &Path, store: &GraphStore) {
+    match super::reload(dir, store) {
+        Ok(report) if report.problems.is_empty() => tracing::info!(
+            "Reloaded data: {} entries, {} links, validation passed",
+            report.entries,
+            report.links
+        ),
+        Ok(report) => {
+            tracing::warn!(
+                "Reloaded data with {} validation problem(s)",
+                report.problems.len()
+            );
+            for problem in &report.problems {
+                tracing::warn!("  {}", problem);
+            }
+        }
+        Err(e) => tracing::error!("Data reload failed, keeping previous graph: {}", e),
+    }
+}
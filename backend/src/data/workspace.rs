@@ -0,0 +1,205 @@
+//! Named graph workspaces.
+//!
+//! A workspace is an isolated, mutable copy of the graph, so that groups can
+//! edit without affecting the shared graph or each other. It is created from
+//! the registry's seed (the baseline graph, without persisted edits) the first
+//! time its name is used, and lives in memory: its edits are not persisted.
+//!
+//! The registry holds at most `limit` workspaces, and drops those unused for
+//! longer than its idle timeout when `evict_idle` is called.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::core::Graph;
+
+use super::store::GraphStore;
+
+/// Longest workspace name
+pub const MAX_WORKSPACE_NAME: usize = 64;
+
+/// Error raised when a workspace cannot be opened
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceError {
+    /// Names are 1 to 64 lowercase letters, digits, `-` or `_`
+    InvalidName(String),
+    /// The registry already holds its limit of workspaces (0 disables them)
+    LimitReached(usize),
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceError::InvalidName(name) => write!(
+                f,
+                "Invalid workspace name '{}': use 1 to {} lowercase letters, digits, '-' or '_'",
+                name, MAX_WORKSPACE_NAME
+            ),
+            WorkspaceError::LimitReached(0) => write!(f, "Workspaces are disabled"),
+            WorkspaceError::LimitReached(limit) => {
+                write!(f, "The server already holds {} workspaces", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+/// Check a workspace name
+pub fn validate_workspace_name(name: &str) -> Result<(), WorkspaceError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_WORKSPACE_NAME
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+    if valid {
+        Ok(())
+    } else {
+        Err(WorkspaceError::InvalidName(name.to_string()))
+    }
+}
+
+#[derive(Debug)]
+struct Workspace {
+    store: GraphStore,
+    last_used: Instant,
+}
+
+#[derive(Debug)]
+struct Registry {
+    seed: Graph,
+    limit: usize,
+    workspaces: Mutex<HashMap<String, Workspace>>,
+}
+
+/// Thread-safe registry of workspaces. Cloning shares the same registry.
+#[derive(Debug, Clone)]
+pub struct WorkspaceRegistry {
+    inner: Arc<Registry>,
+}
+
+impl WorkspaceRegistry {
+    /// A registry creating workspaces from `seed`, holding at most `limit` of them
+    pub fn new(seed: Graph, limit: usize) -> Self {
+        Self {
+            inner: Arc::new(Registry {
+                seed,
+                limit,
+                workspaces: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    fn workspaces(&self) -> MutexGuard<'_, HashMap<String, Workspace>> {
+        self.inner
+            .workspaces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The store of the named workspace, created from the seed if it does not exist
+    pub fn open(&self, name: &str) -> Result<GraphStore, WorkspaceError> {
+        validate_workspace_name(name)?;
+        let mut workspaces = self.workspaces();
+        if let Some(workspace) = workspaces.get_mut(name) {
+            workspace.last_used = Instant::now();
+            return Ok(workspace.store.clone());
+        }
+        if workspaces.len() >= self.inner.limit {
+            return Err(WorkspaceError::LimitReached(self.inner.limit));
+        }
+        let store = GraphStore::new(self.inner.seed.clone());
+        workspaces.insert(
+            name.to_string(),
+            Workspace {
+                store: store.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        tracing::info!("Created workspace '{}'", name);
+        Ok(store)
+    }
+
+    /// Names of the existing workspaces, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.workspaces().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Drop a workspace and its edits; returns whether it existed
+    pub fn remove(&self, name: &str) -> bool {
+        self.workspaces().remove(name).is_some()
+    }
+
+    /// Drop the workspaces unused for longer than `max_idle` as of `now`, returning
+    /// their names
+    pub fn evict_idle(&self, max_idle: Duration, now: Instant) -> Vec<String> {
+        let mut evicted = Vec::new();
+        self.workspaces().retain(|name, workspace| {
+            let idle = now.saturating_duration_since(workspace.last_used) > max_idle;
+            if idle {
+                evicted.push(name.clone());
+            }
+            !idle
+        });
+        evicted.sort();
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{add_annotation, build_graph};
+
+    #[test]
+    fn test_workspaces_are_isolated() {
+        let registry = WorkspaceRegistry::new(build_graph(), 4);
+        let alpha = registry.open("alpha").unwrap();
+        alpha
+            .update(|graph| add_annotation(graph, "term_3_1", "Only in alpha", "Ana", None))
+            .unwrap();
+
+        // Reopening returns the same workspace; others start from the seed
+        assert_eq!(registry.open("alpha").unwrap().revision(), alpha.revision());
+        let beta = registry.open("beta").unwrap();
+        assert_eq!(alpha.snapshot().all_annotations().len(), 1);
+        assert!(beta.snapshot().all_annotations().is_empty());
+        assert_eq!(registry.names(), ["alpha", "beta"]);
+
+        assert!(registry.remove("alpha"));
+        assert!(!registry.remove("alpha"));
+        let alpha = registry.open("alpha").unwrap();
+        assert!(alpha.snapshot().all_annotations().is_empty());
+    }
+
+    #[test]
+    fn test_workspace_limits() {
+        let registry = WorkspaceRegistry::new(Graph::new(), 1);
+        assert!(matches!(
+            registry.open("Study Group"),
+            Err(WorkspaceError::InvalidName(_))
+        ));
+        assert!(registry.open(&"a".repeat(MAX_WORKSPACE_NAME + 1)).is_err());
+
+        registry.open("first").unwrap();
+        assert_eq!(
+            registry.open("second").unwrap_err(),
+            WorkspaceError::LimitReached(1)
+        );
+
+        // Only workspaces idle for longer than the timeout are dropped
+        let later = Instant::now() + Duration::from_secs(60);
+        assert!(registry
+            .evict_idle(Duration::from_secs(120), later)
+            .is_empty());
+        assert_eq!(
+            registry.evict_idle(Duration::from_secs(30), later),
+            ["first"]
+        );
+        registry.open("second").unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+//! Operation allow-list for public deployments.
+//!
+//! With `allowed_operations_only` configured, the server executes only the
+//! frontend's operations (see `systematics_middleware::operations`), recognised
+//! by the SHA-256 hash of their exact text. A persisted query hash counts too, so
+//! the allow-list works with automatic persisted queries. Everything else,
+//! introspection included, fails with `OPERATION_NOT_ALLOWED`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{Request, ServerResult, Value};
+use sha2::{Digest, Sha256};
+use systematics_middleware::{operations, ErrorCode};
+
+use super::error::coded_server_error;
+
+/// Extension rejecting operations that are not on the allow-list
+#[derive(Clone, Default)]
+pub struct OperationAllowList {
+    /// Operation names by the SHA-256 hash of their document
+    operations: Arc<HashMap<String, &'static str>>,
+}
+
+impl OperationAllowList {
+    /// An empty allow-list, which rejects everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The allow-list of the frontend's operations
+    pub fn frontend() -> Self {
+        operations::frontend_operations()
+            .into_iter()
+            .fold(Self::new(), |list, (name, query)| list.allow(name, &query))
+    }
+
+    /// Allow an operation, given its exact document text
+    pub fn allow(mut self, name: &'static str, query: &str) -> Self {
+        let hash = format!("{:x}", Sha256::digest(query.as_bytes()));
+        Arc::make_mut(&mut self.operations).insert(hash, name);
+        self
+    }
+
+    /// Name of the allowed operation a request carries, by its text or persisted hash
+    fn allowed(&self, request: &Request) -> Option<&'static str> {
+        let hash = if request.query.is_empty() {
+            match request.extensions.get("persistedQuery") {
+                Some(Value::Object(persisted)) => match persisted.get("sha256Hash") {
+                    Some(Value::String(hash)) => hash.clone(),
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        } else {
+            format!("{:x}", Sha256::digest(request.query.as_bytes()))
+        };
+        self.operations.get(&hash).copied()
+    }
+}
+
+impl ExtensionFactory for OperationAllowList {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for OperationAllowList {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let Some(name) = self.allowed(&request) else {
+            return Err(coded_server_error(
+                ErrorCode::OperationNotAllowed,
+                "Operation is not on the allow-list",
+            ));
+        };
+        tracing::debug!(operation = name, "Allow-listed operation");
+        next.run(ctx, request).await
+    }
+}
@@ -0,0 +1,72 @@
+//! HTTP caching of query responses.
+//!
+//! Query fields carry `cache_control` hints (see `QueryRoot`). async-graphql
+//! merges the hints of the selected fields, where the shortest `maxAge` wins
+//! and `no_cache` overrides. The merged hint becomes the response's
+//! `Cache-Control` header, and [`CacheHints`] echoes it as
+//! `extensions.cacheControl`.
+//!
+//! Shared caches only store GET responses, so `/graphql` also executes
+//! queries sent as GET. Those requests are marked with [`GetRequest`], and
+//! mutations in them are refused.
+
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{Response, ServerResult, Value};
+use systematics_middleware::ErrorCode;
+
+use super::error::coded_server_error;
+
+/// Request data marking a request received as HTTP GET
+#[derive(Debug, Clone, Copy)]
+pub struct GetRequest;
+
+/// Extension reporting cache hints and refusing mutations in GET requests
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheHints;
+
+impl ExtensionFactory for CacheHints {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(CacheHints)
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for CacheHints {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let response = next.run(ctx, operation_name).await;
+        let hint = response.cache_control;
+        response.extension(
+            "cacheControl",
+            async_graphql::value!({
+                "version": 1,
+                "maxAge": hint.max_age.max(0),
+                "noCache": hint.max_age == -1,
+                "scope": if hint.public { "PUBLIC" } else { "PRIVATE" },
+            }),
+        )
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if info.parent_type == "MutationRoot" && ctx.data_opt::<GetRequest>().is_some() {
+            return Err(coded_server_error(
+                ErrorCode::MutationNotAllowed,
+                "Mutations must be sent with POST",
+            ));
+        }
+        next.run(ctx, info).await
+    }
+}
@@ -0,0 +1,215 @@
+//! Machine-readable GraphQL errors.
+//!
+//! Every error raised by a resolver or mutation carries `extensions.code`
+//! (an [`ErrorCode`] wire value) and, where it applies, `extensions.field`
+//! naming the offending argument and `extensions.hint` suggesting a fix:
+//!
+//! ```json
+//! { "message": "Order 99 is outside 1-64",
+//!   "extensions": { "code": "INVALID_ORDER", "field": "order" } }
+//! ```
+
+use async_graphql::{Error, ErrorExtensionValues, ErrorExtensions, ServerError};
+use systematics_middleware::ErrorCode;
+
+use crate::data::{
+    AnnotationError, CustomSystemError, InstanceError, RenameError, SessionError, VocabularyError,
+    MAX_CUSTOM_ORDER,
+};
+
+/// Build an error carrying `extensions.code`
+pub fn coded_error(code: ErrorCode, message: impl Into<String>) -> Error {
+    Error::new(message).extend_with(|_, e| e.set("code", code.as_str()))
+}
+
+/// Build a request-level error (raised before execution) carrying `extensions.code`
+pub fn coded_server_error(code: ErrorCode, message: impl Into<String>) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", code.as_str());
+    ServerError {
+        extensions: Some(extensions),
+        ..ServerError::new(message, None)
+    }
+}
+
+/// Adds the optional `field` and `hint` extensions to an error
+pub trait ErrorDetails {
+    /// Name the argument or input field the error refers to
+    fn field(self, field: &str) -> Self;
+    /// Suggest how to correct the request
+    fn hint(self, hint: impl Into<String>) -> Self;
+}
+
+impl ErrorDetails for Error {
+    fn field(self, field: &str) -> Self {
+        self.extend_with(|_, e| e.set("field", field))
+    }
+
+    fn hint(self, hint: impl Into<String>) -> Self {
+        let hint = hint.into();
+        self.extend_with(|_, e| e.set("hint", hint))
+    }
+}
+
+/// Validate an order argument (1 to `MAX_CUSTOM_ORDER`), failing with INVALID_ORDER
+pub fn order_arg(field: &str, value: i32) -> Result<u8, Error> {
+    in_range(value).ok_or_else(|| {
+        coded_error(
+            ErrorCode::InvalidOrder,
+            format!("Order {} is outside 1-{}", value, MAX_CUSTOM_ORDER),
+        )
+        .field(field)
+    })
+}
+
+/// Validate a position argument (1 to `MAX_CUSTOM_ORDER`), failing with INVALID_POSITION
+pub fn position_arg(field: &str, value: i32) -> Result<u8, Error> {
+    in_range(value).ok_or_else(|| {
+        coded_error(
+            ErrorCode::InvalidPosition,
+            format!("Position {} is outside 1-{}", value, MAX_CUSTOM_ORDER),
+        )
+        .field(field)
+    })
+}
+
+/// Validate a position argument against the positions of an order, failing with
+/// INVALID_POSITION
+pub fn position_in_order(field: &str, value: i32, order: u8) -> Result<u8, Error> {
+    let position = position_arg(field, value)?;
+    if position > order {
+        return Err(coded_error(
+            ErrorCode::InvalidPosition,
+            format!(
+                "Position {} is outside 1-{} for order {}",
+                value, order, order
+            ),
+        )
+        .field(field));
+    }
+    Ok(position)
+}
+
+/// Error for an order within range that has no system, failing with UNKNOWN_ORDER
+pub fn unknown_order(field: &str, order: u8) -> Error {
+    coded_error(
+        ErrorCode::UnknownOrder,
+        format!("Order {} does not exist", order),
+    )
+    .field(field)
+    .hint("Query v2 { systems { order } } for the available orders")
+}
+
+fn in_range(value: i32) -> Option<u8> {
+    u8::try_from(value)
+        .ok()
+        .filter(|v| (1..=MAX_CUSTOM_ORDER).contains(v))
+}
+
+impl ErrorExtensions for InstanceError {
+    fn extend(&self) -> Error {
+        let error = |code| coded_error(code, self.to_string());
+        match self {
+            InstanceError::EmptyName => error(ErrorCode::InvalidInput).field("name"),
+            InstanceError::UnknownOrder(_) => error(ErrorCode::UnknownOrder)
+                .field("order")
+                .hint("Query allSystems for the available orders"),
+            InstanceError::InstanceExists(_) => error(ErrorCode::AlreadyExists)
+                .field("name")
+                .hint("Choose a different instance name"),
+            InstanceError::LabelCount { expected, .. } => error(ErrorCode::InvalidInput)
+                .field("labels")
+                .hint(format!("Supply one label per position ({})", expected)),
+        }
+    }
+}
+
+impl ErrorExtensions for AnnotationError {
+    fn extend(&self) -> Error {
+        let error = |code| coded_error(code, self.to_string());
+        match self {
+            AnnotationError::EmptyText => error(ErrorCode::InvalidInput).field("text"),
+            AnnotationError::EmptyAuthor => error(ErrorCode::InvalidInput)
+                .field("author")
+                .hint("Sign in, or name the author"),
+            AnnotationError::UnknownTarget(_) => error(ErrorCode::UnknownEntry)
+                .field("entryId")
+                .hint("Annotate an entry or link ID, e.g. a term or connective"),
+        }
+    }
+}
+
+impl ErrorExtensions for VocabularyError {
+    fn extend(&self) -> Error {
+        let error = |code| coded_error(code, self.to_string());
+        match self {
+            VocabularyError::EmptyName | VocabularyError::ReservedName(_) => {
+                error(ErrorCode::InvalidInput)
+                    .field("name")
+                    .hint("Name the vocabulary, e.g. \"Tarot\"")
+            }
+            VocabularyError::UnsupportedFormat(_) => error(ErrorCode::InvalidInput)
+                .field("file")
+                .hint("Upload a .csv or .yaml file"),
+            VocabularyError::Parse { .. } | VocabularyError::Empty => {
+                error(ErrorCode::InvalidInput).field("file")
+            }
+            VocabularyError::TooManyValues { .. } => error(ErrorCode::InvalidInput)
+                .field("file")
+                .hint("Give one term per position and one connective per position pair"),
+        }
+    }
+}
+
+impl ErrorExtensions for RenameError {
+    fn extend(&self) -> Error {
+        let error = |code| coded_error(code, self.to_string());
+        match self {
+            RenameError::EmptyValue => error(ErrorCode::InvalidInput).field("value"),
+            RenameError::NotVocabulary(_) => error(ErrorCode::InvalidInput)
+                .field("language")
+                .hint("Rename terms and connectives in a vocabulary, e.g. ENERGY"),
+            RenameError::UnknownLocation { .. } => {
+                error(ErrorCode::InvalidPosition).field("position")
+            }
+            RenameError::NoConnective { .. } => error(ErrorCode::UnknownEntry)
+                .field("target")
+                .hint("Name a pair of positions joined by a connective"),
+            RenameError::CanonicalConnective => error(ErrorCode::InvalidInput)
+                .field("language")
+                .hint("Label connectives in another vocabulary, e.g. VALUES"),
+        }
+    }
+}
+
+impl ErrorExtensions for CustomSystemError {
+    fn extend(&self) -> Error {
+        let error = |code| coded_error(code, self.to_string());
+        match self {
+            CustomSystemError::OrderExists(_) => error(ErrorCode::AlreadyExists)
+                .field("order")
+                .hint("Custom systems use an unused order, e.g. 13 and above"),
+            CustomSystemError::OrderOutOfRange(_) => error(ErrorCode::InvalidOrder).field("order"),
+            CustomSystemError::TermCount { expected, .. } => error(ErrorCode::InvalidInput)
+                .field("terms")
+                .hint(format!("Supply one term per position ({})", expected)),
+            CustomSystemError::ConnectiveCount { expected, .. } => error(ErrorCode::InvalidInput)
+                .field("connectives")
+                .hint(format!("Supply none, or one per pair ({})", expected)),
+        }
+    }
+}
+
+impl ErrorExtensions for SessionError {
+    fn extend(&self) -> Error {
+        let error = coded_error(ErrorCode::SessionUnavailable, self.to_string());
+        match self {
+            SessionError::Unknown(_) => error
+                .field("id")
+                .hint("Start a new session with startSession"),
+            SessionError::LimitReached => {
+                error.hint("Commit or discard a session, or retry once idle ones expire")
+            }
+        }
+    }
+}
@@ -1,5 +1,19 @@
 //! GraphQL module for the Systematics property graph API.
 
+pub mod allowlist;
+pub mod cache;
+pub mod error;
+pub mod persisted;
+pub mod timing;
 pub mod types;
+pub mod wire;
 
-pub use types::{create_schema, QueryRoot, SystematicsSchema};
+pub use allowlist::OperationAllowList;
+pub use cache::{CacheHints, GetRequest};
+pub use persisted::PersistedQueries;
+pub use timing::{ResolverTimings, TraceRequested, TRACE_HEADER};
+
+pub use types::{
+    create_schema, create_schema_with_store, schema_sdl, MutationRoot, QueryRoot, QueryV2,
+    SchemaOptions, SystematicsSchema, SCHEMA_VERSION,
+};
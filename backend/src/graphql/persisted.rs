@@ -0,0 +1,236 @@
+//! Automatic persisted queries (APQ).
+//!
+//! Clients send `extensions.persistedQuery.sha256Hash` in place of the query
+//! text. An unknown hash is answered with `PersistedQueryNotFound`, and the
+//! client retries once with the full query, which registers it. Setting
+//! `persisted_only` rejects requests that do not use APQ, and registers
+//! nothing: only the frontend's operations, persisted at startup, are served.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::parser::{parse_query, types::ExecutableDocument};
+use async_graphql::{Request, ServerResult};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use systematics_middleware::{operations, ErrorCode};
+
+use super::error::coded_server_error;
+
+/// Maximum number of registered queries; further registrations run uncached
+const MAX_PERSISTED_QUERIES: usize = 1024;
+
+#[derive(Deserialize)]
+struct PersistedQuery {
+    version: i32,
+    #[serde(rename = "sha256Hash")]
+    sha256_hash: String,
+}
+
+/// Extension registering queries by SHA-256 hash. Cloning shares the registry.
+#[derive(Clone, Default)]
+pub struct PersistedQueries {
+    queries: Arc<Mutex<HashMap<String, ExecutableDocument>>>,
+    required: bool,
+}
+
+impl PersistedQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject requests that do not carry the hash of a persisted query, and
+    /// persist the frontend's operations, the only queries then served
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        if required {
+            for (_, query) in operations::frontend_operations() {
+                self = self.persist(&query);
+            }
+        }
+        self
+    }
+
+    /// Persist a query, given its exact document text
+    pub fn persist(self, query: &str) -> Self {
+        if let Ok(doc) = parse_query(query) {
+            self.register(format!("{:x}", Sha256::digest(query.as_bytes())), doc);
+        }
+        self
+    }
+
+    fn get(&self, hash: &str) -> Option<ExecutableDocument> {
+        self.queries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(hash)
+            .cloned()
+    }
+
+    fn register(&self, hash: String, doc: ExecutableDocument) {
+        let mut queries = self
+            .queries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if queries.len() < MAX_PERSISTED_QUERIES {
+            queries.insert(hash, doc);
+        }
+    }
+
+    /// Resolve the persisted query of a request, registering it when the text
+    /// is given (unless persisted queries are required)
+    fn resolve(&self, mut request: Request) -> ServerResult<Request> {
+        let Some(value) = request.extensions.remove("persistedQuery") else {
+            if self.required {
+                return Err(coded_server_error(
+                    ErrorCode::PersistedQueryRequired,
+                    "PersistedQueryRequired",
+                ));
+            }
+            return Ok(request);
+        };
+        let persisted: PersistedQuery = async_graphql::from_value(value).map_err(|_| {
+            coded_server_error(ErrorCode::InvalidInput, "Invalid persistedQuery extension")
+        })?;
+        if persisted.version != 1 {
+            return Err(coded_server_error(
+                ErrorCode::InvalidInput,
+                format!("Unsupported persistedQuery version {}", persisted.version),
+            ));
+        }
+
+        if request.query.is_empty() {
+            let doc = self.get(&persisted.sha256_hash).ok_or_else(|| {
+                coded_server_error(ErrorCode::PersistedQueryNotFound, "PersistedQueryNotFound")
+            })?;
+            request.set_parsed_query(doc);
+            return Ok(request);
+        }
+
+        let hash = format!("{:x}", Sha256::digest(request.query.as_bytes()));
+        if hash != persisted.sha256_hash {
+            return Err(coded_server_error(
+                ErrorCode::InvalidInput,
+                "persistedQuery hash does not match the query",
+            ));
+        }
+        if self.required {
+            // Only the queries persisted at startup run
+            let doc = self.get(&hash).ok_or_else(|| {
+                coded_server_error(ErrorCode::PersistedQueryNotFound, "PersistedQueryNotFound")
+            })?;
+            request.set_parsed_query(doc);
+            return Ok(request);
+        }
+        let doc = parse_query(&request.query)?;
+        self.register(hash, doc.clone());
+        request.set_parsed_query(doc);
+        Ok(request)
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for PersistedQueries {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        next.run(ctx, self.resolve(request)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::Value;
+    use serde_json::json;
+
+    const QUERY: &str = "{ systems { order } }";
+
+    fn hash(query: &str) -> String {
+        format!("{:x}", Sha256::digest(query.as_bytes()))
+    }
+
+    /// A request carrying a persisted query hash, and the text if given
+    fn request(query: &str, hash: &str) -> Request {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            Value::from_json(json!({ "version": 1, "sha256Hash": hash })).unwrap(),
+        );
+        request
+    }
+
+    fn code(result: ServerResult<Request>) -> String {
+        let error = result.expect_err("the request is refused");
+        let code = error.extensions.and_then(|e| e.get("code").cloned());
+        match code {
+            Some(Value::String(code)) => code,
+            other => panic!("no code: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resolve_registers_then_serves_by_hash() {
+        let persisted = PersistedQueries::new();
+        assert_eq!(
+            code(persisted.resolve(request("", &hash(QUERY)))),
+            "PERSISTED_QUERY_NOT_FOUND"
+        );
+        assert!(persisted.resolve(request(QUERY, &hash(QUERY))).is_ok());
+        assert!(persisted.resolve(request("", &hash(QUERY))).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_rejects_hash_mismatch() {
+        let persisted = PersistedQueries::new();
+        assert_eq!(
+            code(persisted.resolve(request(QUERY, &hash("{ other }")))),
+            "INVALID_INPUT"
+        );
+        assert!(persisted.get(&hash(QUERY)).is_none());
+    }
+
+    #[test]
+    fn test_resolve_unknown_hash() {
+        let persisted = PersistedQueries::new().required(true);
+        assert_eq!(
+            code(persisted.resolve(request("", &hash(QUERY)))),
+            "PERSISTED_QUERY_NOT_FOUND"
+        );
+    }
+
+    #[test]
+    fn test_required_serves_only_persisted_operations() {
+        let persisted = PersistedQueries::new().required(true);
+        assert_eq!(
+            code(persisted.resolve(Request::new(QUERY))),
+            "PERSISTED_QUERY_REQUIRED"
+        );
+
+        // A query with its own hash is not registered
+        assert_eq!(
+            code(persisted.resolve(request(QUERY, &hash(QUERY)))),
+            "PERSISTED_QUERY_NOT_FOUND"
+        );
+        assert!(persisted.get(&hash(QUERY)).is_none());
+
+        // The frontend's operations are served by hash, with or without text
+        let get_system = operations::get_system();
+        assert!(persisted.resolve(request("", &hash(&get_system))).is_ok());
+        assert!(persisted
+            .resolve(request(&get_system, &hash(&get_system)))
+            .is_ok());
+    }
+}
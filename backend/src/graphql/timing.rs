@@ -0,0 +1,142 @@
+//! Per-resolver timings for diagnosing slow nested queries.
+//!
+//! When a request carries the `X-Systematics-Trace` header, the server marks
+//! it with [`TraceRequested`] and this extension records how long each
+//! resolver took. Timings are returned under `extensions.tracing`, in the
+//! style of Apollo tracing (offsets and durations in nanoseconds), and logged
+//! at debug level. Requests without the header are not timed.
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{Response, ServerResult, Value};
+
+/// Request header that enables resolver timing
+pub const TRACE_HEADER: &str = "x-systematics-trace";
+
+/// Request data marking a request for resolver timing
+#[derive(Debug, Clone, Copy)]
+pub struct TraceRequested;
+
+/// Extension timing the resolvers of traced requests
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResolverTimings;
+
+impl ExtensionFactory for ResolverTimings {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(TimingExtension::default())
+    }
+}
+
+struct ResolverTiming {
+    path: Vec<String>,
+    parent_type: String,
+    field_name: String,
+    return_type: String,
+    start_offset: u64,
+    duration: u64,
+}
+
+/// Timings of one request; `start` is set only when the request is traced
+#[derive(Default)]
+struct TimingExtension {
+    start: Mutex<Option<Instant>>,
+    resolvers: Mutex<Vec<ResolverTiming>>,
+}
+
+fn nanos(since: Instant, until: Instant) -> u64 {
+    until.duration_since(since).as_nanos() as u64
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for TimingExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if ctx.data_opt::<TraceRequested>().is_none() {
+            return next.run(ctx, operation_name).await;
+        }
+        let start = Instant::now();
+        *self.start.lock().unwrap_or_else(|p| p.into_inner()) = Some(start);
+
+        let response = next.run(ctx, operation_name).await;
+        let duration = nanos(start, Instant::now());
+        let mut resolvers =
+            std::mem::take(&mut *self.resolvers.lock().unwrap_or_else(|p| p.into_inner()));
+        resolvers.sort_by_key(|r| r.start_offset);
+
+        tracing::debug!(
+            operation = operation_name.unwrap_or("anonymous"),
+            resolvers = resolvers.len(),
+            duration_us = duration / 1000,
+            "Traced GraphQL operation"
+        );
+        for r in &resolvers {
+            tracing::debug!(
+                path = %r.path.join("."),
+                duration_us = r.duration / 1000,
+                "Resolver"
+            );
+        }
+
+        let resolvers: Vec<Value> = resolvers
+            .into_iter()
+            .map(|r| {
+                async_graphql::value!({
+                    "path": r.path,
+                    "parentType": r.parent_type,
+                    "fieldName": r.field_name,
+                    "returnType": r.return_type,
+                    "startOffset": r.start_offset,
+                    "duration": r.duration,
+                })
+            })
+            .collect();
+        response.extension(
+            "tracing",
+            async_graphql::value!({
+                "version": 1,
+                "duration": duration,
+                "execution": { "resolvers": resolvers },
+            }),
+        )
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let Some(start) = *self.start.lock().unwrap_or_else(|p| p.into_inner()) else {
+            return next.run(ctx, info).await;
+        };
+        let path = info.path_node.to_string_vec();
+        let parent_type = info.parent_type.to_string();
+        let field_name = info.name.to_string();
+        let return_type = info.return_type.to_string();
+
+        let resolve_start = Instant::now();
+        let result = next.run(ctx, info).await;
+        let resolve_end = Instant::now();
+
+        self.resolvers
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .push(ResolverTiming {
+                path,
+                parent_type,
+                field_name,
+                return_type,
+                start_offset: nanos(start, resolve_start),
+                duration: nanos(resolve_start, resolve_end),
+            });
+        result
+    }
+}
@@ -1,45 +1,147 @@
 //! GraphQL types and schema for the Systematics property graph API.
 
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
+
 use crate::core::{
-    Character, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate, Entry, Graph,
-    Language, Link, LinkType, Location, Order, Position, SystemName, Term, TermDesignation,
+    Annotation, Character, Citation, CoherenceAttribute, Colour, ConnectiveDesignation, Coordinate,
+    Entry, Graph, Instance, InstanceLabel, Language, LineRole, Link, LinkType, Location, Order,
+    Position, ResearchCoverage, ResearchStatus, SearchHit, SearchHitKind, Sequence, SystemName,
+    Term, TermDesignation,
+};
+use crate::data::{
+    self, Edit, GraphStore, Revision, SessionCommit, SessionRegistry, VocabularyError,
+    VocabularyFormat, VocabularyImport,
 };
-use crate::data;
+use crate::graphql::allowlist::OperationAllowList;
+use crate::graphql::cache::CacheHints;
+use crate::graphql::error::{
+    coded_error, order_arg, position_arg, position_in_order, unknown_order, ErrorDetails,
+};
+use crate::graphql::persisted::PersistedQueries;
+use crate::graphql::timing::ResolverTimings;
+use crate::graphql::wire;
+use crate::http::{entity_tag, UserIdentity, Workspace};
+use async_graphql::futures_util::{self, Stream, StreamExt};
 use async_graphql::*;
+use systematics_middleware::{
+    ConnectiveView, CustomSystemInput, ErrorCode, GraphDelta, Page, Slice, API_VERSION,
+};
+
+/// Snapshot of the shared graph (or the selected workspace's) for the current request
+fn shared_graph(ctx: &Context<'_>) -> Graph {
+    ctx.data_unchecked::<GraphStore>().snapshot()
+}
+
+/// Snapshot of the shared graph, translated into `locale` when one is given
+fn localized_graph(ctx: &Context<'_>, locale: Option<&str>) -> Graph {
+    let graph = shared_graph(ctx);
+    match locale {
+        Some(locale) => graph.localized(locale),
+        None => graph,
+    }
+}
+
+/// Vocabulary named by `language`/`vocabulary` arguments, canonical when neither
+/// names one (or `CUSTOM` comes without a vocabulary name)
+fn vocabulary_language(language: Option<GqlLanguage>, vocabulary: Option<&str>) -> Language {
+    match (language, vocabulary) {
+        (Some(l), vocabulary) => l.resolve(vocabulary).unwrap_or(Language::Canonical),
+        (None, Some(name)) => Language::from_name(name),
+        (None, None) => Language::Canonical,
+    }
+}
+
+/// Version of the schema served; version 1 fields stay available (some
+/// deprecated) alongside the `v2` namespace
+pub const SCHEMA_VERSION: i32 = 2;
+
+/// Items in a page when `first` is not given
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// Most items a page can have
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Sequence of an order named by a `sequence` argument (its first sequence when
+/// none is named); naming an unknown sequence fails with INVALID_INPUT
+fn sequence_arg<'g>(
+    graph: &'g Graph,
+    order: u8,
+    name: Option<&str>,
+) -> Result<Option<&'g Sequence>> {
+    let sequence = graph.sequence(order, name);
+    if let (Some(name), None) = (name, sequence) {
+        let available: Vec<&str> = graph
+            .sequences(order)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        return Err(coded_error(
+            ErrorCode::InvalidInput,
+            format!("Order {} has no sequence '{}'", order, name),
+        )
+        .field("sequence")
+        .hint(if available.is_empty() {
+            "Omit sequence; this order has none".to_string()
+        } else {
+            format!("Use one of: {}", available.join(", "))
+        }));
+    }
+    Ok(sequence)
+}
 
-/// Root query object
+/// Root query object. Fields over data that changes only on reload (systems,
+/// orders, characters) carry a five-minute `cache_control` hint; instances
+/// and mutations are `no_cache`.
 #[derive(Clone, Default)]
 pub struct QueryRoot;
 
 #[Object]
 impl QueryRoot {
+    /// Schema version (see `v2` for the current query style)
+    async fn schema_version(&self) -> i32 {
+        SCHEMA_VERSION
+    }
+
+    /// Version 2 queries: missing systems are errors rather than nulls, and
+    /// lookups take optional filters rather than one field per filter
+    async fn v2(&self) -> QueryV2 {
+        QueryV2
+    }
+
+    /// Name of the workspace the request is served from (see the `workspace` query
+    /// parameter and `X-Workspace` header); null for the shared graph
+    async fn workspace(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.data_opt::<Workspace>().map(|w| w.name.clone())
+    }
+
     // ========================================================================
     // Graph Queries
     // ========================================================================
 
     /// Get the full graph with all entries and links
-    async fn graph(&self) -> GqlGraph {
-        GqlGraph::new(data::build_graph())
+    async fn graph(&self, ctx: &Context<'_>) -> GqlGraph {
+        GqlGraph::new(shared_graph(ctx))
     }
 
     // ========================================================================
     // Anchor Queries
     // ========================================================================
 
-    /// Get an Order anchor by value (1-12)
-    async fn order(&self, value: i32) -> Option<GqlOrder> {
-        if !(1..=12).contains(&value) {
-            return None;
-        }
-        let graph = data::build_graph();
-        graph
-            .order(value as u8)
-            .map(|o| GqlOrder::new(o.clone(), graph.clone()))
+    /// Get an Order anchor by value
+    async fn order(&self, ctx: &Context<'_>, value: i32) -> Result<Option<GqlOrder>> {
+        let value = order_arg("value", value)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .order(value)
+            .map(|o| GqlOrder::new(o.clone(), graph.clone())))
     }
 
     /// Get all Order anchors
-    async fn orders(&self) -> Vec<GqlOrder> {
-        let graph = data::build_graph();
+    #[graphql(cache_control(max_age = 300))]
+    async fn orders(&self, ctx: &Context<'_>) -> Vec<GqlOrder> {
+        let graph = shared_graph(ctx);
         graph
             .orders()
             .into_iter()
@@ -47,20 +149,18 @@ impl QueryRoot {
             .collect()
     }
 
-    /// Get a Position anchor by value (1-12)
-    async fn position(&self, value: i32) -> Option<GqlPosition> {
-        if !(1..=12).contains(&value) {
-            return None;
-        }
-        let graph = data::build_graph();
-        graph
-            .position(value as u8)
-            .map(|p| GqlPosition::new(p.clone(), graph.clone()))
+    /// Get a Position anchor by value
+    async fn position(&self, ctx: &Context<'_>, value: i32) -> Result<Option<GqlPosition>> {
+        let value = position_arg("value", value)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .position(value)
+            .map(|p| GqlPosition::new(p.clone(), graph.clone())))
     }
 
     /// Get all Position anchors
-    async fn positions(&self) -> Vec<GqlPosition> {
-        let graph = data::build_graph();
+    async fn positions(&self, ctx: &Context<'_>) -> Vec<GqlPosition> {
+        let graph = shared_graph(ctx);
         graph
             .positions()
             .into_iter()
@@ -69,19 +169,23 @@ impl QueryRoot {
     }
 
     /// Get a Location anchor by order and position
-    async fn location(&self, order: i32, position: i32) -> Option<GqlLocation> {
-        if !(1..=12).contains(&order) || position < 1 || position > order {
-            return None;
-        }
-        let graph = data::build_graph();
-        graph
-            .location(order as u8, position as u8)
-            .map(|l| GqlLocation::new(l.clone(), graph.clone()))
+    async fn location(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        position: i32,
+    ) -> Result<Option<GqlLocation>> {
+        let order = order_arg("order", order)?;
+        let position = position_in_order("position", position, order)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .location(order, position)
+            .map(|l| GqlLocation::new(l.clone(), graph.clone())))
     }
 
     /// Get all Location anchors
-    async fn locations(&self) -> Vec<GqlLocation> {
-        let graph = data::build_graph();
+    async fn locations(&self, ctx: &Context<'_>) -> Vec<GqlLocation> {
+        let graph = shared_graph(ctx);
         graph
             .locations()
             .into_iter()
@@ -90,85 +194,341 @@ impl QueryRoot {
     }
 
     /// Get all Locations for a given order
-    async fn locations_for_order(&self, order: i32) -> Vec<GqlLocation> {
-        let graph = data::build_graph();
-        graph
-            .locations_for_order(order as u8)
+    #[graphql(deprecation = "Use v2 { locations(order:) }")]
+    async fn locations_for_order(&self, ctx: &Context<'_>, order: i32) -> Result<Vec<GqlLocation>> {
+        let order = order_arg("order", order)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .locations_for_order(order)
             .into_iter()
             .map(|l| GqlLocation::new(l.clone(), graph.clone()))
-            .collect()
+            .collect())
     }
 
     /// Get all Locations for a given position (across all orders)
-    async fn locations_for_position(&self, position: i32) -> Vec<GqlLocation> {
-        let graph = data::build_graph();
-        graph
-            .locations_for_position(position as u8)
+    #[graphql(deprecation = "Use v2 { locations(position:) }")]
+    async fn locations_for_position(
+        &self,
+        ctx: &Context<'_>,
+        position: i32,
+    ) -> Result<Vec<GqlLocation>> {
+        let position = position_arg("position", position)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .locations_for_position(position)
             .into_iter()
             .map(|l| GqlLocation::new(l.clone(), graph.clone()))
-            .collect()
+            .collect())
     }
 
     // ========================================================================
     // System Queries
     // ========================================================================
 
-    /// Get system by order (1-12)
-    async fn system(&self, order: i32) -> Option<GqlSystemView> {
-        if !(1..=12).contains(&order) {
-            return None;
+    /// Get system by order (canonical 1-12, or a custom system's order),
+    /// with names translated into `locale` where available. Terms and connective
+    /// characters are in the vocabulary given by `language` (`vocabulary` names a
+    /// `CUSTOM` one), falling back to canonical.
+    #[graphql(cache_control(max_age = 300))]
+    async fn system(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        locale: Option<String>,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Result<Option<GqlSystemView>> {
+        let order = order_arg("order", order)?;
+        let graph = localized_graph(ctx, locale.as_deref());
+        if graph.order(order).is_none() {
+            return Ok(None);
         }
-        let graph = data::build_graph();
-        Some(GqlSystemView::new(order as u8, graph))
-    }
-
-    /// Get all systems (1-12)
-    async fn all_systems(&self) -> Vec<GqlSystemView> {
-        let graph = data::build_graph();
-        (1..=12)
-            .map(|order| GqlSystemView::new(order, graph.clone()))
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        Ok(Some(
+            GqlSystemView::new(order, graph).with_language(language),
+        ))
+    }
+
+    /// Get all systems, canonical and custom, by ascending order, in the
+    /// vocabulary given by `language`/`vocabulary` as for `system`
+    #[graphql(cache_control(max_age = 300))]
+    async fn all_systems(
+        &self,
+        ctx: &Context<'_>,
+        locale: Option<String>,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Vec<GqlSystemView> {
+        let graph = Arc::new(localized_graph(ctx, locale.as_deref()));
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        let mut orders: Vec<u8> = graph.orders().iter().map(|o| o.value).collect();
+        orders.sort_unstable();
+        orders
+            .into_iter()
+            .map(|order| GqlSystemView::new(order, graph.clone()).with_language(language.clone()))
             .collect()
     }
 
-    /// Get system by name (e.g., "Triad")
-    async fn system_by_name(&self, name: String) -> Option<GqlSystemView> {
-        let order = match name.to_lowercase().as_str() {
-            "monad" => 1,
-            "dyad" => 2,
-            "triad" => 3,
-            "tetrad" => 4,
-            "pentad" => 5,
-            "hexad" => 6,
-            "heptad" => 7,
-            "octad" => 8,
-            "ennead" => 9,
-            "decad" => 10,
-            "undecad" => 11,
-            "dodecad" => 12,
-            _ => return None,
+    /// Get the systems for the given orders, in the order requested (orders without a
+    /// system are skipped)
+    #[graphql(
+        deprecation = "Use v2 { systems(orders:) }, which reports unknown orders",
+        cache_control(max_age = 300)
+    )]
+    async fn systems(
+        &self,
+        ctx: &Context<'_>,
+        orders: Vec<i32>,
+        locale: Option<String>,
+    ) -> Result<Vec<GqlSystemView>> {
+        let orders = orders
+            .into_iter()
+            .map(|order| order_arg("orders", order))
+            .collect::<Result<Vec<u8>>>()?;
+        let graph = Arc::new(localized_graph(ctx, locale.as_deref()));
+        Ok(orders
+            .into_iter()
+            .filter(|&order| graph.order(order).is_some())
+            .map(|order| GqlSystemView::new(order, graph.clone()))
+            .collect())
+    }
+
+    /// Get system by name (e.g., "Triad", or a translated name when `locale` is given)
+    #[graphql(cache_control(max_age = 300))]
+    async fn system_by_name(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        locale: Option<String>,
+    ) -> Option<GqlSystemView> {
+        let source = shared_graph(ctx);
+        let graph = match locale.as_deref() {
+            Some(locale) => source.localized(locale),
+            None => source.clone(),
         };
-        let graph = data::build_graph();
+        let order = graph
+            .order_by_name(&name)
+            .or_else(|| source.order_by_name(&name))?;
         Some(GqlSystemView::new(order, graph))
     }
 
+    /// Get the lines and connectives joining two positions of an order, in either direction
+    async fn links_between(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        position_a: i32,
+        position_b: i32,
+    ) -> Result<Vec<GqlLink>> {
+        let order = order_arg("order", order)?;
+        let a = position_in_order("positionA", position_a, order)?;
+        let b = position_in_order("positionB", position_b, order)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .links_between(order, a, b)
+            .into_iter()
+            .map(|l| GqlLink::new(l.clone(), &graph))
+            .collect())
+    }
+
+    /// Get the shortest route between two positions of an order, as the links to
+    /// follow in sequence. `linkType` restricts the route to lines or connectives.
+    /// Null when no route exists.
+    #[graphql(cache_control(max_age = 300))]
+    async fn path(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        from_position: i32,
+        to_position: i32,
+        link_type: Option<GqlLinkType>,
+    ) -> Result<Option<Vec<GqlLink>>> {
+        let order = order_arg("order", order)?;
+        let from = position_in_order("fromPosition", from_position, order)?;
+        let to = position_in_order("toPosition", to_position, order)?;
+        if link_type == Some(GqlLinkType::Grouping) {
+            return Err(coded_error(
+                ErrorCode::InvalidInput,
+                "Groupings do not join positions into routes",
+            )
+            .field("linkType")
+            .hint("Use LINE or CONNECTIVE, or omit linkType"));
+        }
+        let link_type = link_type.map(LinkType::from);
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .shortest_path(order, from, to, link_type.as_ref())
+            .map(|path| {
+                path.into_iter()
+                    .map(|l| GqlLink::new(l.clone(), &graph))
+                    .collect()
+            }))
+    }
+
+    /// Compare two systems: the positions they share, the character values used by
+    /// both, and the connectives of system A projected onto system B. An order
+    /// without a system fails with UNKNOWN_ORDER.
+    #[graphql(cache_control(max_age = 300))]
+    async fn system_comparison(
+        &self,
+        ctx: &Context<'_>,
+        order_a: i32,
+        order_b: i32,
+    ) -> Result<GqlSystemComparison> {
+        let order_a = order_arg("orderA", order_a)?;
+        let order_b = order_arg("orderB", order_b)?;
+        let graph = shared_graph(ctx);
+        if graph.order(order_a).is_none() {
+            return Err(unknown_order("orderA", order_a));
+        }
+        if graph.order(order_b).is_none() {
+            return Err(unknown_order("orderB", order_b));
+        }
+        Ok(GqlSystemComparison::new(order_a, order_b, graph))
+    }
+
+    /// Get the structural groupings for an order (e.g., the Pentad's nested dyads)
+    async fn groupings(&self, ctx: &Context<'_>, order: i32) -> Result<Vec<GqlGrouping>> {
+        let order = order_arg("order", order)?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .groupings(order)
+            .into_iter()
+            .map(|l| GqlGrouping::new(l.clone(), &graph))
+            .collect())
+    }
+
+    // ========================================================================
+    // Instance Queries
+    // ========================================================================
+
+    /// Get all instances, optionally only those of one order
+    async fn instances(&self, ctx: &Context<'_>, order: Option<i32>) -> Result<Vec<GqlInstance>> {
+        let order = order.map(|o| order_arg("order", o)).transpose()?;
+        let graph = shared_graph(ctx);
+        Ok(graph
+            .instances(order)
+            .into_iter()
+            .map(|i| GqlInstance::new(i.clone(), &graph))
+            .collect())
+    }
+
+    /// Get an instance by ID (e.g., "instance_project_planning")
+    async fn instance(&self, ctx: &Context<'_>, id: String) -> Option<GqlInstance> {
+        let graph = shared_graph(ctx);
+        graph
+            .instance(&id)
+            .map(|i| GqlInstance::new(i.clone(), &graph))
+    }
+
+    // ========================================================================
+    // Annotation Queries
+    // ========================================================================
+
+    /// The signed-in user making the request (null without a valid bearer token)
+    #[graphql(cache_control(no_cache))]
+    async fn viewer(&self, ctx: &Context<'_>) -> Option<GqlUser> {
+        ctx.data_opt::<UserIdentity>().cloned().map(GqlUser)
+    }
+
+    /// Notes attached to an entry or link, in the order they were added
+    async fn annotations(&self, ctx: &Context<'_>, entry_id: String) -> Vec<GqlAnnotation> {
+        let graph = shared_graph(ctx);
+        graph
+            .annotations(&entry_id)
+            .into_iter()
+            .map(|a| GqlAnnotation::new(a.clone(), &graph))
+            .collect()
+    }
+
     // ========================================================================
     // Term Queries
     // ========================================================================
 
     /// Get term at a specific order and position
-    async fn term(&self, order: i32, position: i32) -> Option<GqlTerm> {
-        let graph = data::build_graph();
-        graph
-            .term(order as u8, position as u8)
+    async fn term(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        position: i32,
+        locale: Option<String>,
+    ) -> Result<Option<GqlTerm>> {
+        let order = order_arg("order", order)?;
+        let position = position_in_order("position", position, order)?;
+        let graph = localized_graph(ctx, locale.as_deref());
+        Ok(graph
+            .term(order, position)
+            .map(|t| GqlTerm::new(t.clone(), &graph)))
+    }
+
+    /// Get all terms for an order, optionally for one language.
+    /// `vocabulary` names a custom vocabulary (with `language: CUSTOM` or on its own).
+    async fn terms(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+        locale: Option<String>,
+    ) -> Result<Vec<GqlTerm>> {
+        let order = order_arg("order", order)?;
+        let graph = localized_graph(ctx, locale.as_deref());
+        let lang = match language {
+            Some(l) => match l.resolve(vocabulary.as_deref()) {
+                Some(lang) => Some(lang),
+                None => return Ok(vec![]),
+            },
+            None => vocabulary.as_deref().map(Language::from_name),
+        };
+        Ok(graph
+            .terms(order, lang)
+            .into_iter()
             .map(|t| GqlTerm::new(t.clone(), &graph))
+            .collect())
+    }
+
+    /// Terms of an order in the process order of a canonical sequence (e.g., the
+    /// Ennead's "Inner Lines"), defaulting to the order's first sequence. Orders
+    /// without a sequence are traversed by position.
+    #[graphql(cache_control(max_age = 300))]
+    async fn ordered_traversal(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        sequence: Option<String>,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+        locale: Option<String>,
+    ) -> Result<Vec<GqlTerm>> {
+        let order = order_arg("order", order)?;
+        let graph = localized_graph(ctx, locale.as_deref());
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        let sequence = sequence_arg(&graph, order, sequence.as_deref())?;
+        Ok(graph
+            .traversal(order, sequence, &language)
+            .into_iter()
+            .map(|t| GqlTerm::new(t.clone(), &graph))
+            .collect())
+    }
+
+    /// The colour of each position of an order (hex and name), with the rules
+    /// used to assign them, for consistent theming
+    #[graphql(cache_control(max_age = 300))]
+    async fn colour_palette(&self, ctx: &Context<'_>, order: i32) -> Result<GqlColourPalette> {
+        let order = order_arg("order", order)?;
+        let graph = shared_graph(ctx);
+        Ok(GqlColourPalette {
+            order,
+            colours: data::colour_palette(&graph, order),
+        })
     }
 
-    /// Get all terms for an order
-    async fn terms(&self, order: i32, language: Option<GqlLanguage>) -> Vec<GqlTerm> {
-        let graph = data::build_graph();
-        let lang = language.map(|l| l.into());
+    /// Every term, in any order or language, whose character has this value
+    /// (e.g., where "Will" appears)
+    async fn terms_by_value(&self, ctx: &Context<'_>, value: String) -> Vec<GqlTerm> {
+        let graph = shared_graph(ctx);
         graph
-            .terms(order as u8, lang)
+            .terms_by_value(&value)
             .into_iter()
             .map(|t| GqlTerm::new(t.clone(), &graph))
             .collect()
@@ -178,13 +538,114 @@ impl QueryRoot {
     // Character Queries
     // ========================================================================
 
-    /// Get all characters for a language
-    async fn characters(&self, language: GqlLanguage) -> Vec<GqlCharacter> {
-        let graph = data::build_graph();
+    /// Get all characters for a language (`vocabulary` names a `CUSTOM` one),
+    /// optionally only those whose value contains `valueContains` (case-insensitive)
+    /// and/or that an order uses for its terms or connectives
+    #[graphql(cache_control(max_age = 300))]
+    async fn characters(
+        &self,
+        ctx: &Context<'_>,
+        language: GqlLanguage,
+        vocabulary: Option<String>,
+        value_contains: Option<String>,
+        order_used_in: Option<i32>,
+    ) -> Result<Vec<GqlCharacter>> {
+        let order_used_in = order_used_in
+            .map(|o| order_arg("orderUsedIn", o))
+            .transpose()?;
+        let Some(language) = language.resolve(vocabulary.as_deref()) else {
+            return Ok(vec![]);
+        };
+        let graph = shared_graph(ctx);
+        let needle = value_contains.map(|v| v.to_lowercase());
+        let used = order_used_in.map(|order| graph.character_ids_in_order(order));
+        Ok(graph
+            .characters(language)
+            .into_iter()
+            .filter(|c| {
+                needle
+                    .as_deref()
+                    .is_none_or(|n| c.value.to_lowercase().contains(n))
+            })
+            .filter(|c| used.as_ref().is_none_or(|ids| ids.contains(c.id.as_str())))
+            .map(|c| GqlCharacter::new(c.clone(), &graph))
+            .collect())
+    }
+
+    /// Research coverage for an order's vocabulary, or for all characters if no order is given
+    async fn research_coverage(
+        &self,
+        ctx: &Context<'_>,
+        order: Option<i32>,
+    ) -> Result<GqlResearchCoverage> {
+        let order = order.map(|o| order_arg("order", o)).transpose()?;
+        let graph = shared_graph(ctx);
+        Ok(GqlResearchCoverage::new(graph.research_coverage(order)))
+    }
+
+    // ========================================================================
+    // Connective Queries
+    // ========================================================================
+
+    /// Connectives in every order labelled by a character, given by ID or value
+    /// (e.g., everywhere "Function" appears as a mutuality)
+    async fn connectives_by_character(&self, ctx: &Context<'_>, character: String) -> Vec<GqlLink> {
+        let graph = shared_graph(ctx);
         graph
-            .characters(language.into())
+            .connectives_by_character(&character)
             .into_iter()
-            .map(|c| GqlCharacter::new(c.clone()))
+            .map(|l| GqlLink::new(l.clone(), &graph))
+            .collect()
+    }
+
+    /// Locations linked to a location by a connective, with the linking character
+    /// (in `language`, falling back to canonical), ordered by position. An unknown
+    /// location has no neighbors.
+    #[graphql(cache_control(max_age = 300))]
+    async fn neighbors(
+        &self,
+        ctx: &Context<'_>,
+        location_id: String,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Vec<GqlNeighbor> {
+        let graph = shared_graph(ctx);
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        graph
+            .neighbors(&location_id)
+            .into_iter()
+            .map(|(location, link)| GqlNeighbor {
+                location: GqlLocation::new(location.clone(), graph.clone()),
+                connective: GqlLink::new(link.clone(), &graph).with_language(language.clone()),
+            })
+            .collect()
+    }
+
+    // ========================================================================
+    // Search Queries
+    // ========================================================================
+
+    /// Search characters, system names, coherence attributes and designations
+    /// (case-insensitive). `languages` restricts character matches; a `CUSTOM`
+    /// language takes its name from `vocabulary`.
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        text: String,
+        languages: Option<Vec<GqlLanguage>>,
+        vocabulary: Option<String>,
+    ) -> Vec<GqlSearchHit> {
+        let languages: Option<Vec<Language>> = languages.map(|langs| {
+            langs
+                .into_iter()
+                .filter_map(|l| l.resolve(vocabulary.as_deref()))
+                .collect()
+        });
+        let graph = shared_graph(ctx);
+        graph
+            .search(&text, languages.as_deref())
+            .into_iter()
+            .map(|h| GqlSearchHit::new(h, &graph))
             .collect()
     }
 
@@ -192,10 +653,35 @@ impl QueryRoot {
     // Slice Queries
     // ========================================================================
 
-    /// Get slice (all entries at order+position)
-    async fn slice(&self, order: i32, position: i32) -> GqlSlice {
-        let graph = data::build_graph();
-        GqlSlice::new(order as u8, position as u8, graph)
+    /// Get slice (term, coordinate, colour, connectives and lines at
+    /// order+position) in the middleware's wire format; `system.slice` also
+    /// has every entry there
+    async fn slice(&self, ctx: &Context<'_>, order: i32, position: i32) -> Result<Slice> {
+        let order = order_arg("order", order)?;
+        let position = position_in_order("position", position, order)?;
+        Ok(wire::slice(&shared_graph(ctx), order, position))
+    }
+
+    /// The slice at a position in every order that has it, by order
+    /// (e.g., "first-ness" across systems for position 1)
+    async fn position_across_orders(
+        &self,
+        ctx: &Context<'_>,
+        position: i32,
+        locale: Option<String>,
+    ) -> Result<Vec<GqlSlice>> {
+        let position = position_arg("position", position)?;
+        let graph = Arc::new(localized_graph(ctx, locale.as_deref()));
+        let mut orders: Vec<u8> = graph
+            .locations_for_position(position)
+            .into_iter()
+            .filter_map(|l| l.order_value())
+            .collect();
+        orders.sort();
+        Ok(orders
+            .into_iter()
+            .map(|order| GqlSlice::new(order, position, graph.clone()))
+            .collect())
     }
 
     // ========================================================================
@@ -223,6 +709,165 @@ impl QueryRoot {
             GqlLanguage::Society,
         ]
     }
+
+    /// Locales with translations available (e.g., "de", "fr")
+    async fn locales(&self, ctx: &Context<'_>) -> Vec<String> {
+        shared_graph(ctx).locales()
+    }
+
+    /// Names of all vocabularies in the graph, including runtime-registered ones
+    async fn vocabularies(&self, ctx: &Context<'_>) -> Vec<String> {
+        shared_graph(ctx)
+            .vocabularies()
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    }
+}
+
+/// Version 2 query namespace
+#[derive(Clone, Default)]
+pub struct QueryV2;
+
+#[Object]
+impl QueryV2 {
+    /// Get system by order; an order without a system fails with UNKNOWN_ORDER.
+    /// `language`/`vocabulary` select the vocabulary, falling back to canonical.
+    #[graphql(cache_control(max_age = 300))]
+    async fn system(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        locale: Option<String>,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Result<GqlSystemView> {
+        let order = order_arg("order", order)?;
+        let graph = localized_graph(ctx, locale.as_deref());
+        if graph.order(order).is_none() {
+            return Err(unknown_order("order", order));
+        }
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        Ok(GqlSystemView::new(order, graph).with_language(language))
+    }
+
+    /// Get the systems for the given orders in the order requested, or all systems by
+    /// ascending order; any order without a system fails with UNKNOWN_ORDER
+    #[graphql(cache_control(max_age = 300))]
+    async fn systems(
+        &self,
+        ctx: &Context<'_>,
+        orders: Option<Vec<i32>>,
+        locale: Option<String>,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Result<Vec<GqlSystemView>> {
+        let graph = Arc::new(localized_graph(ctx, locale.as_deref()));
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        let orders = match orders {
+            Some(orders) => orders
+                .into_iter()
+                .map(|order| {
+                    let order = order_arg("orders", order)?;
+                    match graph.order(order) {
+                        Some(_) => Ok(order),
+                        None => Err(unknown_order("orders", order)),
+                    }
+                })
+                .collect::<Result<Vec<u8>>>()?,
+            None => {
+                let mut orders: Vec<u8> = graph.orders().iter().map(|o| o.value).collect();
+                orders.sort_unstable();
+                orders
+            }
+        };
+        Ok(orders
+            .into_iter()
+            .map(|order| GqlSystemView::new(order, graph.clone()).with_language(language.clone()))
+            .collect())
+    }
+
+    /// Get Locations, optionally only those of an order and/or position
+    async fn locations(
+        &self,
+        ctx: &Context<'_>,
+        order: Option<i32>,
+        position: Option<i32>,
+    ) -> Result<Vec<GqlLocation>> {
+        let order = order.map(|o| order_arg("order", o)).transpose()?;
+        let position = match (order, position) {
+            (Some(order), Some(p)) => Some(position_in_order("position", p, order)?),
+            (None, Some(p)) => Some(position_arg("position", p)?),
+            (_, None) => None,
+        };
+        let graph = shared_graph(ctx);
+        let mut locations: Vec<&Location> = graph
+            .locations()
+            .into_iter()
+            .filter(|l| order.is_none_or(|o| l.order_value() == Some(o)))
+            .filter(|l| position.is_none_or(|p| l.position_value() == Some(p)))
+            .collect();
+        locations.sort_by_key(|l| (l.order_value(), l.position_value()));
+        Ok(locations
+            .into_iter()
+            .map(|l| GqlLocation::new(l.clone(), graph.clone()))
+            .collect())
+    }
+
+    /// Search as the v1 `search` does, a page at a time: up to `first` hits
+    /// (20 by default, at most 100) after the cursor `after` of the previous page
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        text: String,
+        languages: Option<Vec<GqlLanguage>>,
+        vocabulary: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<GqlSearchHitPage> {
+        let limit = page_size(first)?;
+        let offset = page_offset(after.as_deref())?;
+        let languages: Option<Vec<Language>> = languages.map(|langs| {
+            langs
+                .into_iter()
+                .filter_map(|l| l.resolve(vocabulary.as_deref()))
+                .collect()
+        });
+        let hits = shared_graph(ctx)
+            .search(&text, languages.as_deref())
+            .iter()
+            .map(wire::search_hit)
+            .collect();
+        Ok(Page::of(hits, offset, limit).into())
+    }
+}
+
+/// Page size from a `first` argument
+fn page_size(first: Option<i32>) -> Result<usize> {
+    match first {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(first @ 1..) if first as usize <= MAX_PAGE_SIZE => Ok(first as usize),
+        Some(first) => Err(coded_error(
+            ErrorCode::InvalidInput,
+            format!("first must be 1 to {}, got {}", MAX_PAGE_SIZE, first),
+        )
+        .field("first")),
+    }
+}
+
+/// Offset into a list from an `after` cursor, as `Page::of` sets it
+fn page_offset(after: Option<&str>) -> Result<usize> {
+    match after {
+        None => Ok(0),
+        Some(cursor) => cursor.parse().map_err(|_| {
+            coded_error(
+                ErrorCode::InvalidInput,
+                format!("'{}' is not a cursor", cursor),
+            )
+            .field("after")
+            .hint("Pass the cursor of the previous page, or omit after")
+        }),
+    }
 }
 
 // ============================================================================
@@ -236,41 +881,163 @@ pub enum GqlLanguage {
     Energy,
     Values,
     Society,
+    /// A runtime-registered vocabulary; its name is given separately
+    Custom,
     Hex,
     Name,
 }
 
-impl From<GqlLanguage> for Language {
-    fn from(l: GqlLanguage) -> Self {
-        match l {
-            GqlLanguage::Canonical => Language::Canonical,
-            GqlLanguage::Energy => Language::Energy,
-            GqlLanguage::Values => Language::Values,
-            GqlLanguage::Society => Language::Society,
-            GqlLanguage::Hex => Language::Hex,
-            GqlLanguage::Name => Language::Name,
+impl GqlLanguage {
+    /// Resolve to a core language; `Custom` takes its name from `vocabulary`
+    fn resolve(self, vocabulary: Option<&str>) -> Option<Language> {
+        match self {
+            GqlLanguage::Canonical => Some(Language::Canonical),
+            GqlLanguage::Energy => Some(Language::Energy),
+            GqlLanguage::Values => Some(Language::Values),
+            GqlLanguage::Society => Some(Language::Society),
+            GqlLanguage::Custom => vocabulary.map(Language::from_name),
+            GqlLanguage::Hex => Some(Language::Hex),
+            GqlLanguage::Name => Some(Language::Name),
         }
     }
 }
 
-impl From<Language> for GqlLanguage {
-    fn from(l: Language) -> Self {
+impl From<&Language> for GqlLanguage {
+    fn from(l: &Language) -> Self {
         match l {
             Language::Canonical => GqlLanguage::Canonical,
             Language::Energy => GqlLanguage::Energy,
             Language::Values => GqlLanguage::Values,
             Language::Society => GqlLanguage::Society,
+            Language::Custom(_) => GqlLanguage::Custom,
             Language::Hex => GqlLanguage::Hex,
             Language::Name => GqlLanguage::Name,
         }
     }
 }
 
+/// Entry type enum, mirroring the Entry variants
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlEntryType {
+    Order,
+    Position,
+    Location,
+    SystemName,
+    CoherenceAttribute,
+    TermDesignation,
+    ConnectiveDesignation,
+    Sequence,
+    Term,
+    Colour,
+    Coordinate,
+    Character,
+    Instance,
+    InstanceLabel,
+    Annotation,
+}
+
+impl From<&Entry> for GqlEntryType {
+    fn from(e: &Entry) -> Self {
+        match e {
+            Entry::Order(_) => GqlEntryType::Order,
+            Entry::Position(_) => GqlEntryType::Position,
+            Entry::Location(_) => GqlEntryType::Location,
+            Entry::SystemName(_) => GqlEntryType::SystemName,
+            Entry::CoherenceAttribute(_) => GqlEntryType::CoherenceAttribute,
+            Entry::TermDesignation(_) => GqlEntryType::TermDesignation,
+            Entry::ConnectiveDesignation(_) => GqlEntryType::ConnectiveDesignation,
+            Entry::Sequence(_) => GqlEntryType::Sequence,
+            Entry::Term(_) => GqlEntryType::Term,
+            Entry::Colour(_) => GqlEntryType::Colour,
+            Entry::Coordinate(_) => GqlEntryType::Coordinate,
+            Entry::Character(_) => GqlEntryType::Character,
+            Entry::Instance(_) => GqlEntryType::Instance,
+            Entry::InstanceLabel(_) => GqlEntryType::InstanceLabel,
+            Entry::Annotation(_) => GqlEntryType::Annotation,
+        }
+    }
+}
+
+/// What a search hit matched
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlSearchHitKind {
+    Term,
+    Connective,
+    Character,
+    SystemName,
+    CoherenceAttribute,
+    TermDesignation,
+    ConnectiveDesignation,
+}
+
+impl From<SearchHitKind> for GqlSearchHitKind {
+    fn from(k: SearchHitKind) -> Self {
+        match k {
+            SearchHitKind::Term => GqlSearchHitKind::Term,
+            SearchHitKind::Connective => GqlSearchHitKind::Connective,
+            SearchHitKind::Character => GqlSearchHitKind::Character,
+            SearchHitKind::SystemName => GqlSearchHitKind::SystemName,
+            SearchHitKind::CoherenceAttribute => GqlSearchHitKind::CoherenceAttribute,
+            SearchHitKind::TermDesignation => GqlSearchHitKind::TermDesignation,
+            SearchHitKind::ConnectiveDesignation => GqlSearchHitKind::ConnectiveDesignation,
+        }
+    }
+}
+
 /// Link type enum
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
 pub enum GqlLinkType {
     Line,
     Connective,
+    Grouping,
+}
+
+impl From<GqlLinkType> for LinkType {
+    fn from(link_type: GqlLinkType) -> Self {
+        match link_type {
+            GqlLinkType::Line => LinkType::Line,
+            GqlLinkType::Connective => LinkType::Connective,
+            GqlLinkType::Grouping => LinkType::Grouping,
+        }
+    }
+}
+
+/// Research status of vocabulary
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlResearchStatus {
+    Canonical,
+    Provisional,
+    NeedsResearch,
+}
+
+impl From<ResearchStatus> for GqlResearchStatus {
+    fn from(s: ResearchStatus) -> Self {
+        match s {
+            ResearchStatus::Canonical => GqlResearchStatus::Canonical,
+            ResearchStatus::Provisional => GqlResearchStatus::Provisional,
+            ResearchStatus::NeedsResearch => GqlResearchStatus::NeedsResearch,
+        }
+    }
+}
+
+/// Structural role of a line within its figure
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlLineRole {
+    Side,
+    Diagonal,
+    Circumference,
+    Chord,
+}
+
+impl From<LineRole> for GqlLineRole {
+    fn from(r: LineRole) -> Self {
+        match r {
+            LineRole::Side => GqlLineRole::Side,
+            LineRole::Diagonal => GqlLineRole::Diagonal,
+            LineRole::Circumference => GqlLineRole::Circumference,
+            LineRole::Chord => GqlLineRole::Chord,
+        }
+    }
 }
 
 // ============================================================================
@@ -279,17 +1046,24 @@ pub enum GqlLinkType {
 
 /// The full property graph
 pub struct GqlGraph {
-    graph: Graph,
+    graph: Arc<Graph>,
 }
 
 impl GqlGraph {
-    pub fn new(graph: Graph) -> Self {
-        Self { graph }
+    pub fn new(graph: impl Into<Arc<Graph>>) -> Self {
+        Self {
+            graph: graph.into(),
+        }
     }
 }
 
 #[Object]
 impl GqlGraph {
+    /// Version of the wire format this graph is sent in
+    async fn api_version(&self) -> i32 {
+        API_VERSION
+    }
+
     /// Total number of entries
     async fn entry_count(&self) -> i32 {
         self.graph.entries.len() as i32
@@ -300,15 +1074,46 @@ impl GqlGraph {
         self.graph.links.len() as i32
     }
 
-    /// All entries in the graph
-    async fn entries(&self) -> Vec<GqlEntry> {
+    /// Entries in the graph, optionally only those of the given types
+    /// and/or at an order and position
+    async fn entries(
+        &self,
+        entry_type: Option<Vec<GqlEntryType>>,
+        order: Option<i32>,
+        position: Option<i32>,
+    ) -> Vec<GqlEntry> {
         self.graph
             .entries
             .iter()
+            .filter(|e| {
+                entry_type
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&GqlEntryType::from(*e)))
+            })
+            .filter(|e| order.is_none_or(|o| e.order().map(i32::from) == Some(o)))
+            .filter(|e| position.is_none_or(|p| e.position().map(i32::from) == Some(p)))
             .map(|e| GqlEntry::new(e.clone(), &self.graph))
             .collect()
     }
 
+    /// Entries grouped by type, in the order each type first appears, to inspect
+    /// the composition of the graph without fetching every entry
+    async fn entries_by_type(&self) -> Vec<GqlEntryGroup> {
+        let mut groups: Vec<GqlEntryGroup> = Vec::new();
+        for (idx, entry) in self.graph.entries.iter().enumerate() {
+            let entry_type = GqlEntryType::from(entry);
+            match groups.iter_mut().find(|g| g.entry_type == entry_type) {
+                Some(group) => group.indices.push(idx),
+                None => groups.push(GqlEntryGroup {
+                    entry_type,
+                    indices: vec![idx],
+                    graph: self.graph.clone(),
+                }),
+            }
+        }
+        groups
+    }
+
     /// All links in the graph
     async fn links(&self) -> Vec<GqlLink> {
         self.graph
@@ -333,225 +1138,249 @@ impl GqlGraph {
     }
 }
 
+/// The entries of one type
+pub struct GqlEntryGroup {
+    entry_type: GqlEntryType,
+    /// Indices into the graph's entries
+    indices: Vec<usize>,
+    graph: Arc<Graph>,
+}
+
+#[Object]
+impl GqlEntryGroup {
+    async fn entry_type(&self) -> GqlEntryType {
+        self.entry_type
+    }
+
+    /// Number of entries of this type
+    async fn count(&self) -> i32 {
+        self.indices.len() as i32
+    }
+
+    /// Entries of this type, in graph order; `first` limits how many are returned
+    async fn entries(&self, first: Option<i32>) -> Result<Vec<GqlEntry>> {
+        let limit = match first {
+            Some(first) => usize::try_from(first).map_err(|_| {
+                coded_error(
+                    ErrorCode::InvalidInput,
+                    format!("first must not be negative, got {}", first),
+                )
+                .field("first")
+            })?,
+            None => self.indices.len(),
+        };
+        Ok(self
+            .indices
+            .iter()
+            .take(limit)
+            .map(|&idx| GqlEntry::new(self.graph.entries[idx].clone(), &self.graph))
+            .collect())
+    }
+}
+
 // ============================================================================
 // Entry Types
 // ============================================================================
 
-/// A graph entry (union type)
-pub struct GqlEntry {
-    entry: Entry,
-    graph: Graph,
+/// A graph entry. Select type-specific fields with inline fragments
+/// (e.g., `... on GqlTerm { character { value } }`).
+#[derive(Interface)]
+#[allow(clippy::duplicated_attributes)] // every `field` repeats `ty`, and the `asX` ones `deprecation`
+#[graphql(
+    field(name = "id", ty = "&str", desc = "Entry ID"),
+    field(
+        name = "entry_type",
+        ty = "&str",
+        desc = "Entry type name (e.g., \"Term\")"
+    ),
+    field(
+        name = "order_value",
+        ty = "Option<i32>",
+        desc = "Order value (null where there is none)"
+    ),
+    field(
+        name = "position_value",
+        ty = "Option<i32>",
+        desc = "Position value (null where there is none)"
+    ),
+    field(
+        name = "as_order",
+        ty = "Option<&GqlOrder>",
+        desc = "As Order (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_position",
+        ty = "Option<&GqlPosition>",
+        desc = "As Position (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_location",
+        ty = "Option<&GqlLocation>",
+        desc = "As Location (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_system_name",
+        ty = "Option<&GqlSystemName>",
+        desc = "As SystemName (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_coherence",
+        ty = "Option<&GqlCoherenceAttribute>",
+        desc = "As CoherenceAttribute (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_term_designation",
+        ty = "Option<&GqlTermDesignation>",
+        desc = "As TermDesignation (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_connective_designation",
+        ty = "Option<&GqlConnectiveDesignation>",
+        desc = "As ConnectiveDesignation (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_term",
+        ty = "Option<&GqlTerm>",
+        desc = "As Term (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_colour",
+        ty = "Option<&GqlColour>",
+        desc = "As Colour (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_coordinate",
+        ty = "Option<&GqlCoordinate>",
+        desc = "As Coordinate (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    ),
+    field(
+        name = "as_character",
+        ty = "Option<&GqlCharacter>",
+        desc = "As Character (if applicable)",
+        deprecation = "Select type-specific fields with an inline fragment"
+    )
+)]
+pub enum GqlEntry {
+    Order(GqlOrder),
+    Position(GqlPosition),
+    Location(GqlLocation),
+    SystemName(GqlSystemName),
+    CoherenceAttribute(GqlCoherenceAttribute),
+    TermDesignation(GqlTermDesignation),
+    ConnectiveDesignation(GqlConnectiveDesignation),
+    Sequence(GqlSequence),
+    Term(GqlTerm),
+    Colour(GqlColour),
+    Coordinate(GqlCoordinate),
+    Character(GqlCharacter),
+    Instance(GqlInstance),
+    InstanceLabel(GqlInstanceLabel),
+    Annotation(GqlAnnotation),
 }
 
 impl GqlEntry {
     pub fn new(entry: Entry, graph: &Graph) -> Self {
-        Self {
-            entry,
-            graph: graph.clone(),
+        match entry {
+            Entry::Order(o) => GqlEntry::Order(GqlOrder::new(o, graph.clone())),
+            Entry::Position(p) => GqlEntry::Position(GqlPosition::new(p, graph.clone())),
+            Entry::Location(l) => GqlEntry::Location(GqlLocation::new(l, graph.clone())),
+            Entry::SystemName(s) => GqlEntry::SystemName(GqlSystemName::new(s)),
+            Entry::CoherenceAttribute(c) => {
+                GqlEntry::CoherenceAttribute(GqlCoherenceAttribute::new(c))
+            }
+            Entry::TermDesignation(t) => GqlEntry::TermDesignation(GqlTermDesignation::new(t)),
+            Entry::ConnectiveDesignation(c) => {
+                GqlEntry::ConnectiveDesignation(GqlConnectiveDesignation::new(c))
+            }
+            Entry::Sequence(s) => GqlEntry::Sequence(GqlSequence::new(s, graph)),
+            Entry::Term(t) => GqlEntry::Term(GqlTerm::new(t, graph)),
+            Entry::Colour(c) => GqlEntry::Colour(GqlColour::new(c, graph)),
+            Entry::Coordinate(c) => GqlEntry::Coordinate(GqlCoordinate::new(c, graph)),
+            Entry::Character(c) => GqlEntry::Character(GqlCharacter::new(c, graph)),
+            Entry::Instance(i) => GqlEntry::Instance(GqlInstance::new(i, graph)),
+            Entry::InstanceLabel(l) => GqlEntry::InstanceLabel(GqlInstanceLabel::new(l, graph)),
+            Entry::Annotation(a) => GqlEntry::Annotation(GqlAnnotation::new(a, graph)),
         }
     }
 }
 
-#[Object]
-impl GqlEntry {
-    /// Entry ID
-    async fn id(&self) -> &str {
-        self.entry.id()
-    }
+// ============================================================================
+// Link Type
+// ============================================================================
 
-    /// Entry order (if applicable)
-    async fn order(&self) -> Option<i32> {
-        self.entry.order().map(|o| o as i32)
-    }
+/// A link between entries
+pub struct GqlLink {
+    link: Link,
+    graph: Graph,
+    /// Vocabulary the connective character is resolved in
+    language: Language,
+}
 
-    /// Entry position (if applicable)
-    async fn position(&self) -> Option<i32> {
-        self.entry.position().map(|p| p as i32)
+impl GqlLink {
+    pub fn new(link: Link, graph: &Graph) -> Self {
+        Self {
+            link,
+            graph: graph.clone(),
+            language: Language::Canonical,
+        }
     }
 
-    /// Is this an order-level entry? (references Order anchor)
-    async fn is_order_level_entry(&self) -> bool {
-        self.entry.is_order_level()
+    /// Resolve the connective character in `language`, falling back to canonical
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
     }
+}
 
-    /// Is this a location-level entry? (references Location anchor)
-    async fn is_location_level_entry(&self) -> bool {
-        self.entry.is_location_level()
+#[Object]
+impl GqlLink {
+    /// Link ID
+    async fn id(&self) -> &str {
+        &self.link.id
     }
 
-    /// Entry type name
-    async fn entry_type(&self) -> &str {
-        match &self.entry {
-            Entry::Order(_) => "Order",
-            Entry::Position(_) => "Position",
-            Entry::Location(_) => "Location",
-            Entry::SystemName(_) => "SystemName",
-            Entry::CoherenceAttribute(_) => "CoherenceAttribute",
-            Entry::TermDesignation(_) => "TermDesignation",
-            Entry::ConnectiveDesignation(_) => "ConnectiveDesignation",
-            Entry::Term(_) => "Term",
-            Entry::Colour(_) => "Colour",
-            Entry::Coordinate(_) => "Coordinate",
-            Entry::Character(_) => "Character",
-        }
+    /// Base (source) entry ID
+    async fn base_id(&self) -> Option<&str> {
+        self.link.base_single()
     }
 
-    /// Is this an anchor type?
-    async fn is_anchor(&self) -> bool {
-        self.entry.is_anchor()
+    /// Target entry ID
+    async fn target_id(&self) -> Option<&str> {
+        self.link.target_single()
     }
 
-    /// Is this an order-level entry?
-    async fn is_order_level(&self) -> bool {
-        self.entry.is_order_level()
+    /// Link type
+    async fn link_type(&self) -> GqlLinkType {
+        match &self.link.link_type {
+            LinkType::Line => GqlLinkType::Line,
+            LinkType::Connective => GqlLinkType::Connective,
+            LinkType::Grouping => GqlLinkType::Grouping,
+        }
     }
 
-    /// Is this a location-level entry?
-    async fn is_location_level(&self) -> bool {
-        self.entry.is_location_level()
+    /// Character ID (for connective links)
+    async fn character_id(&self) -> Option<&str> {
+        self.link.character_id()
     }
 
-    /// As Order (if applicable)
-    async fn as_order(&self) -> Option<GqlOrder> {
-        match &self.entry {
-            Entry::Order(o) => Some(GqlOrder::new(o.clone(), self.graph.clone())),
-            _ => None,
-        }
+    /// Optional tag
+    async fn tag(&self) -> Option<&str> {
+        self.link.tag.as_deref()
     }
 
-    /// As Position (if applicable)
-    async fn as_position(&self) -> Option<GqlPosition> {
-        match &self.entry {
-            Entry::Position(p) => Some(GqlPosition::new(p.clone(), self.graph.clone())),
-            _ => None,
-        }
-    }
-
-    /// As Location (if applicable)
-    async fn as_location(&self) -> Option<GqlLocation> {
-        match &self.entry {
-            Entry::Location(l) => Some(GqlLocation::new(l.clone(), self.graph.clone())),
-            _ => None,
-        }
-    }
-
-    /// As SystemName (if applicable)
-    async fn as_system_name(&self) -> Option<GqlSystemName> {
-        match &self.entry {
-            Entry::SystemName(s) => Some(GqlSystemName::new(s.clone())),
-            _ => None,
-        }
-    }
-
-    /// As CoherenceAttribute (if applicable)
-    async fn as_coherence(&self) -> Option<GqlCoherenceAttribute> {
-        match &self.entry {
-            Entry::CoherenceAttribute(c) => Some(GqlCoherenceAttribute::new(c.clone())),
-            _ => None,
-        }
-    }
-
-    /// As TermDesignation (if applicable)
-    async fn as_term_designation(&self) -> Option<GqlTermDesignation> {
-        match &self.entry {
-            Entry::TermDesignation(t) => Some(GqlTermDesignation::new(t.clone())),
-            _ => None,
-        }
-    }
-
-    /// As ConnectiveDesignation (if applicable)
-    async fn as_connective_designation(&self) -> Option<GqlConnectiveDesignation> {
-        match &self.entry {
-            Entry::ConnectiveDesignation(c) => Some(GqlConnectiveDesignation::new(c.clone())),
-            _ => None,
-        }
-    }
-
-    /// As Term (if applicable)
-    async fn as_term(&self) -> Option<GqlTerm> {
-        match &self.entry {
-            Entry::Term(t) => Some(GqlTerm::new(t.clone(), &self.graph)),
-            _ => None,
-        }
-    }
-
-    /// As Colour (if applicable)
-    async fn as_colour(&self) -> Option<GqlColour> {
-        match &self.entry {
-            Entry::Colour(c) => Some(GqlColour::new(c.clone(), &self.graph)),
-            _ => None,
-        }
-    }
-
-    /// As Coordinate (if applicable)
-    async fn as_coordinate(&self) -> Option<GqlCoordinate> {
-        match &self.entry {
-            Entry::Coordinate(c) => Some(GqlCoordinate::new(c.clone(), &self.graph)),
-            _ => None,
-        }
-    }
-
-    /// As Character (if applicable)
-    async fn as_character(&self) -> Option<GqlCharacter> {
-        match &self.entry {
-            Entry::Character(c) => Some(GqlCharacter::new(c.clone())),
-            _ => None,
-        }
-    }
-}
-
-// ============================================================================
-// Link Type
-// ============================================================================
-
-/// A link between entries
-pub struct GqlLink {
-    link: Link,
-    graph: Graph,
-}
-
-impl GqlLink {
-    pub fn new(link: Link, graph: &Graph) -> Self {
-        Self {
-            link,
-            graph: graph.clone(),
-        }
-    }
-}
-
-#[Object]
-impl GqlLink {
-    /// Link ID
-    async fn id(&self) -> &str {
-        &self.link.id
-    }
-
-    /// Base (source) entry ID
-    async fn base_id(&self) -> Option<&str> {
-        self.link.base_single()
-    }
-
-    /// Target entry ID
-    async fn target_id(&self) -> Option<&str> {
-        self.link.target_single()
-    }
-
-    /// Link type
-    async fn link_type(&self) -> GqlLinkType {
-        match &self.link.link_type {
-            LinkType::Line => GqlLinkType::Line,
-            LinkType::Connective => GqlLinkType::Connective,
-        }
-    }
-
-    /// Character ID (for connective links)
-    async fn character_id(&self) -> Option<&str> {
-        self.link.character_id()
-    }
-
-    /// Optional tag
-    async fn tag(&self) -> Option<&str> {
-        self.link.tag.as_deref()
+    /// Structural role (for line links): side/diagonal or circumference/chord
+    async fn role(&self) -> Option<GqlLineRole> {
+        self.link.line_role().map(|r| r.into())
     }
 
     /// Base entry
@@ -570,12 +1399,12 @@ impl GqlLink {
             .map(|e| GqlEntry::new(e.clone(), &self.graph))
     }
 
-    /// Character (for connective links)
+    /// Character naming this connective or grouping, in the requested vocabulary
+    /// where one names it (otherwise canonical)
     async fn character(&self) -> Option<GqlCharacter> {
-        self.link
-            .character_id()
-            .and_then(|id| self.graph.get_character(id))
-            .map(|c| GqlCharacter::new(c.clone()))
+        self.graph
+            .connective_character(&self.link, &self.language)
+            .map(|c| GqlCharacter::new(c.clone(), &self.graph))
     }
 
     /// Order of this link (derived from base entry)
@@ -661,58 +1490,104 @@ impl GqlLink {
 
     /// Get the corresponding line link (for connectives) or connective (for lines)
     async fn corresponding_links(&self) -> Vec<GqlLink> {
-        let base_id = match self.link.base_single() {
-            Some(id) => id,
-            None => return vec![],
-        };
-        let target_id = match self.link.target_single() {
-            Some(id) => id,
-            None => return vec![],
-        };
+        self.graph
+            .corresponding_links(&self.link)
+            .into_iter()
+            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .collect()
+    }
+}
 
-        let base_pos = self.graph.get_entry(base_id).and_then(|e| e.position());
-        let target_pos = self.graph.get_entry(target_id).and_then(|e| e.position());
-        let order = self.graph.get_entry(base_id).and_then(|e| e.order());
+// ============================================================================
+// Grouping Type
+// ============================================================================
 
-        match (order, base_pos, target_pos) {
-            (Some(ord), Some(bp), Some(tp)) => {
-                self.graph
-                    .links
-                    .iter()
-                    .filter(|l| {
-                        // Skip self
-                        if l.id == self.link.id {
-                            return false;
-                        }
-                        // Check if this link connects the same positions
-                        let l_base_id = match l.base_single() {
-                            Some(id) => id,
-                            None => return false,
-                        };
-                        let l_target_id = match l.target_single() {
-                            Some(id) => id,
-                            None => return false,
-                        };
-                        let l_base = self.graph.get_entry(l_base_id);
-                        let l_target = self.graph.get_entry(l_target_id);
-                        match (l_base, l_target) {
-                            (Some(lb), Some(lt)) => {
-                                lb.order() == Some(ord)
-                                    && lt.order() == Some(ord)
-                                    && ((lb.position() == Some(bp) && lt.position() == Some(tp))
-                                        || (lb.position() == Some(tp) && lt.position() == Some(bp)))
-                            }
-                            _ => false,
-                        }
-                    })
-                    .map(|l| GqlLink::new(l.clone(), &self.graph))
-                    .collect()
-            }
-            _ => vec![],
+/// A structural grouping of locations (backed by a Grouping link)
+pub struct GqlGrouping {
+    link: Link,
+    graph: Graph,
+}
+
+impl GqlGrouping {
+    pub fn new(link: Link, graph: &Graph) -> Self {
+        Self {
+            link,
+            graph: graph.clone(),
         }
     }
 }
 
+#[Object]
+impl GqlGrouping {
+    /// Grouping ID
+    async fn id(&self) -> &str {
+        &self.link.id
+    }
+
+    /// Order of this grouping (derived from its first member)
+    async fn order(&self) -> Option<i32> {
+        self.link
+            .base_single()
+            .and_then(|id| self.graph.get_entry(id))
+            .and_then(|e| e.order())
+            .map(|o| o as i32)
+    }
+
+    /// Label character ID
+    async fn character_id(&self) -> Option<&str> {
+        self.link.character_id()
+    }
+
+    /// Label character (e.g., "Range of Potential")
+    async fn character(&self) -> Option<GqlCharacter> {
+        self.link
+            .character_id()
+            .and_then(|id| self.graph.get_character(id))
+            .map(|c| GqlCharacter::new(c.clone(), &self.graph))
+    }
+
+    /// Member location IDs
+    async fn member_ids(&self) -> Vec<&str> {
+        self.link.bases().iter().map(|s| s.as_str()).collect()
+    }
+
+    /// Member positions
+    async fn member_positions(&self) -> Vec<i32> {
+        self.link
+            .bases()
+            .iter()
+            .filter_map(|id| self.graph.get_entry(id))
+            .filter_map(|e| e.position())
+            .map(|p| p as i32)
+            .collect()
+    }
+
+    /// Member locations
+    async fn members(&self) -> Vec<GqlLocation> {
+        self.link
+            .bases()
+            .iter()
+            .filter_map(|id| match self.graph.get_entry(id) {
+                Some(Entry::Location(l)) => Some(GqlLocation::new(l.clone(), self.graph.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// ID of the enclosing grouping (if nested)
+    async fn parent_id(&self) -> Option<&str> {
+        self.link.parent_grouping()
+    }
+
+    /// The enclosing grouping (if nested)
+    async fn parent(&self) -> Option<GqlGrouping> {
+        self.link
+            .parent_grouping()
+            .and_then(|id| self.graph.get_link(id))
+            .map(|l| GqlGrouping::new(l.clone(), &self.graph))
+    }
+}
+
 // ============================================================================
 // Specific Entry Types
 // ============================================================================
@@ -720,11 +1595,16 @@ impl GqlLink {
 /// Character entry
 pub struct GqlCharacter {
     character: Character,
+    /// Translations of the value, by locale
+    translations: HashMap<String, String>,
 }
 
 impl GqlCharacter {
-    pub fn new(character: Character) -> Self {
-        Self { character }
+    pub fn new(character: Character, graph: &Graph) -> Self {
+        Self {
+            translations: graph.translations(&character.id),
+            character,
+        }
     }
 }
 
@@ -734,13 +1614,112 @@ impl GqlCharacter {
         &self.character.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Character"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        None
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     async fn language(&self) -> GqlLanguage {
-        self.character.language.into()
+        (&self.character.language).into()
+    }
+
+    /// Vocabulary name (e.g., "Canonical", or a custom vocabulary's name)
+    async fn language_name(&self) -> String {
+        self.character.language.to_string()
+    }
+
+    /// Locale, if this character is a translation
+    async fn locale(&self) -> Option<&str> {
+        self.character.locale.as_deref()
+    }
+
+    /// Where this vocabulary element was published
+    async fn source(&self) -> Option<GqlCitation> {
+        self.character.source.clone().map(GqlCitation::new)
     }
 
     async fn value(&self) -> &str {
         &self.character.value
     }
+
+    /// Translations of the value by locale (e.g., "de" → "Wille"); empty where
+    /// there are none
+    #[graphql(name = "valueI18n")]
+    async fn value_i18n(&self) -> &HashMap<String, String> {
+        &self.translations
+    }
+
+    /// Research status of this vocabulary element
+    async fn status(&self) -> GqlResearchStatus {
+        self.character.status.into()
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        Some(self)
+    }
 }
 
 // ============================================================================
@@ -765,6 +1744,21 @@ impl GqlOrder {
         &self.order.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Order"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        Some(self.order.value as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     async fn value(&self) -> i32 {
         self.order.value as i32
     }
@@ -774,6 +1768,16 @@ impl GqlOrder {
         self.order.standard_name()
     }
 
+    /// Whether this is one of the twelve canonical systems
+    async fn is_canonical(&self) -> bool {
+        self.order.is_canonical()
+    }
+
+    /// User ID of the creator of a custom system
+    async fn created_by(&self) -> Option<&str> {
+        self.order.created_by.as_deref()
+    }
+
     /// System name entry for this order
     async fn system_name(&self) -> Option<GqlSystemName> {
         self.graph
@@ -781,13 +1785,22 @@ impl GqlOrder {
             .map(|s| GqlSystemName::new(s.clone()))
     }
 
-    /// Coherence attribute for this order
+    /// Primary coherence attribute for this order
     async fn coherence(&self) -> Option<GqlCoherenceAttribute> {
         self.graph
             .coherence(self.order.value)
             .map(|c| GqlCoherenceAttribute::new(c.clone()))
     }
 
+    /// All coherence attributes for this order, primary first
+    async fn coherences(&self) -> Vec<GqlCoherenceAttribute> {
+        self.graph
+            .coherences(self.order.value)
+            .into_iter()
+            .map(|c| GqlCoherenceAttribute::new(c.clone()))
+            .collect()
+    }
+
     /// Term designation for this order
     async fn term_designation(&self) -> Option<GqlTermDesignation> {
         self.graph
@@ -828,6 +1841,63 @@ impl GqlOrder {
             .map(|c| GqlCoordinate::new(c.clone(), &self.graph))
             .collect()
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 /// Position anchor type - abstract "n-th place" (1-12)
@@ -848,6 +1918,21 @@ impl GqlPosition {
         &self.position.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Position"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        None
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        Some(self.position.value as i32)
+    }
+
     async fn value(&self) -> i32 {
         self.position.value as i32
     }
@@ -860,26 +1945,88 @@ impl GqlPosition {
             .map(|l| GqlLocation::new(l.clone(), self.graph.clone()))
             .collect()
     }
-}
 
-/// Location anchor type - the pullback of Order × Position
-pub struct GqlLocation {
-    location: Location,
-    graph: Graph,
-}
+    // Accessors of the entry object the interface replaced
 
-impl GqlLocation {
-    pub fn new(location: Location, graph: Graph) -> Self {
-        Self { location, graph }
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
     }
-}
 
-#[Object]
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+/// Location anchor type - the pullback of Order × Position
+pub struct GqlLocation {
+    location: Location,
+    graph: Graph,
+}
+
+impl GqlLocation {
+    pub fn new(location: Location, graph: Graph) -> Self {
+        Self { location, graph }
+    }
+}
+
+#[Object]
 impl GqlLocation {
     async fn id(&self) -> &str {
         &self.location.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Location"
+    }
+
     /// Order reference ID
     async fn order_id(&self) -> &str {
         &self.location.order
@@ -927,6 +2074,15 @@ impl GqlLocation {
             .collect()
     }
 
+    /// Labels that instances bind to this location
+    async fn instance_labels(&self) -> Vec<GqlInstanceLabel> {
+        self.graph
+            .instance_labels_at_location(&self.location.id)
+            .into_iter()
+            .map(|l| GqlInstanceLabel::new(l.clone(), &self.graph))
+            .collect()
+    }
+
     /// The coordinate at this location
     async fn coordinate(&self) -> Option<GqlCoordinate> {
         let order = self.location.order_value()?;
@@ -947,7 +2103,7 @@ impl GqlLocation {
             .iter()
             .filter_map(|lang| {
                 self.graph
-                    .colour(order, position, *lang)
+                    .colour(order, position, lang.clone())
                     .map(|c| GqlColour::new(c.clone(), &self.graph))
             })
             .collect()
@@ -958,9 +2114,66 @@ impl GqlLocation {
         let order = self.location.order_value()?;
         let position = self.location.position_value()?;
         self.graph
-            .colour(order, position, language.into())
+            .colour(order, position, language.resolve(None)?)
             .map(|c| GqlColour::new(c.clone(), &self.graph))
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 // ============================================================================
@@ -988,6 +2201,21 @@ impl GqlTerm {
         &self.term.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Term"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.term.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        self.term.position_value().map(|v| v as i32)
+    }
+
     /// Location reference ID
     async fn location_id(&self) -> &str {
         &self.term.location
@@ -1007,11 +2235,23 @@ impl GqlTerm {
         &self.term.character
     }
 
+    /// Research status of this term's placement
+    async fn status(&self) -> GqlResearchStatus {
+        self.term.status.into()
+    }
+
+    /// Translations of the character's value by locale; empty where there
+    /// are none
+    #[graphql(name = "valueI18n")]
+    async fn value_i18n(&self) -> HashMap<String, String> {
+        self.graph.translations(&self.term.character)
+    }
+
     /// The character this term references
     async fn character(&self) -> Option<GqlCharacter> {
         self.graph
             .get_character(&self.term.character)
-            .map(|c| GqlCharacter::new(c.clone()))
+            .map(|c| GqlCharacter::new(c.clone(), &self.graph))
     }
 
     /// The location this term belongs to
@@ -1031,6 +2271,63 @@ impl GqlTerm {
             .map(|l| GqlLink::new(l.clone(), &self.graph))
             .collect()
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 /// Coordinate entry
@@ -1054,6 +2351,21 @@ impl GqlCoordinate {
         &self.coordinate.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Coordinate"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.coordinate.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        self.coordinate.position_value().map(|v| v as i32)
+    }
+
     /// Location reference ID
     async fn location_id(&self) -> &str {
         &self.coordinate.location
@@ -1089,6 +2401,63 @@ impl GqlCoordinate {
             .location(order, position)
             .map(|l| GqlLocation::new(l.clone(), self.graph.clone()))
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 /// Colour entry
@@ -1112,6 +2481,21 @@ impl GqlColour {
         &self.colour.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Colour"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.colour.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        self.colour.position_value().map(|v| v as i32)
+    }
+
     /// Location reference ID
     async fn location_id(&self) -> &str {
         &self.colour.location
@@ -1128,7 +2512,7 @@ impl GqlColour {
     }
 
     async fn language(&self) -> GqlLanguage {
-        self.colour.language.into()
+        (&self.colour.language).into()
     }
 
     async fn value(&self) -> &str {
@@ -1143,6 +2527,63 @@ impl GqlColour {
             .location(order, position)
             .map(|l| GqlLocation::new(l.clone(), self.graph.clone()))
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 // ============================================================================
@@ -1166,6 +2607,21 @@ impl GqlSystemName {
         &self.system_name.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "SystemName"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.system_name.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     /// Order reference ID
     async fn order_id(&self) -> &str {
         &self.system_name.order
@@ -1179,6 +2635,63 @@ impl GqlSystemName {
     async fn value(&self) -> &str {
         &self.system_name.value
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 /// CoherenceAttribute entry
@@ -1198,6 +2711,21 @@ impl GqlCoherenceAttribute {
         &self.coherence.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "CoherenceAttribute"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.coherence.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     /// Order reference ID
     async fn order_id(&self) -> &str {
         &self.coherence.order
@@ -1211,6 +2739,73 @@ impl GqlCoherenceAttribute {
     async fn value(&self) -> &str {
         &self.coherence.value
     }
+
+    /// Where this attribute was published
+    async fn source(&self) -> Option<GqlCitation> {
+        self.coherence.source.clone().map(GqlCitation::new)
+    }
+
+    /// Whether this is the order's primary attribute
+    async fn is_primary(&self) -> bool {
+        !self.coherence.alternate
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
 }
 
 /// TermDesignation entry
@@ -1230,6 +2825,21 @@ impl GqlTermDesignation {
         &self.term_designation.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "TermDesignation"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.term_designation.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     /// Order reference ID
     async fn order_id(&self) -> &str {
         &self.term_designation.order
@@ -1243,12 +2853,74 @@ impl GqlTermDesignation {
     async fn value(&self) -> &str {
         &self.term_designation.value
     }
-}
 
-/// ConnectiveDesignation entry
-pub struct GqlConnectiveDesignation {
-    connective_designation: ConnectiveDesignation,
-}
+    /// Where this designation was published
+    async fn source(&self) -> Option<GqlCitation> {
+        self.term_designation.source.clone().map(GqlCitation::new)
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+/// ConnectiveDesignation entry
+pub struct GqlConnectiveDesignation {
+    connective_designation: ConnectiveDesignation,
+}
 
 impl GqlConnectiveDesignation {
     pub fn new(connective_designation: ConnectiveDesignation) -> Self {
@@ -1264,6 +2936,21 @@ impl GqlConnectiveDesignation {
         &self.connective_designation.id
     }
 
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "ConnectiveDesignation"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.connective_designation.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
     /// Order reference ID
     async fn order_id(&self) -> &str {
         &self.connective_designation.order
@@ -1277,6 +2964,309 @@ impl GqlConnectiveDesignation {
     async fn value(&self) -> &str {
         &self.connective_designation.value
     }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        Some(self)
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+/// Canonical process order through the positions of a system
+pub struct GqlSequence {
+    sequence: Sequence,
+    graph: Graph,
+    /// Vocabulary of the terms, falling back to canonical
+    language: Language,
+}
+
+impl GqlSequence {
+    pub fn new(sequence: Sequence, graph: &Graph) -> Self {
+        Self {
+            sequence,
+            graph: graph.clone(),
+            language: Language::Canonical,
+        }
+    }
+
+    /// Resolve terms in `language`
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+}
+
+#[Object]
+impl GqlSequence {
+    async fn id(&self) -> &str {
+        &self.sequence.id
+    }
+
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Sequence"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.sequence.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
+    /// Order value (derived from order reference)
+    async fn order(&self) -> Option<i32> {
+        self.sequence.order_value().map(|v| v as i32)
+    }
+
+    async fn name(&self) -> &str {
+        &self.sequence.name
+    }
+
+    /// Positions visited, in process order
+    async fn positions(&self) -> Vec<i32> {
+        self.sequence
+            .position_values()
+            .into_iter()
+            .map(|p| p as i32)
+            .collect()
+    }
+
+    /// Terms at the positions visited, in process order
+    async fn terms(&self) -> Vec<GqlTerm> {
+        let Some(order) = self.sequence.order_value() else {
+            return vec![];
+        };
+        self.graph
+            .traversal(order, Some(&self.sequence), &self.language)
+            .into_iter()
+            .map(|t| GqlTerm::new(t.clone(), &self.graph))
+            .collect()
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+/// The colours of an order's positions
+pub struct GqlColourPalette {
+    order: u8,
+    colours: Vec<data::PaletteEntry>,
+}
+
+#[Object]
+impl GqlColourPalette {
+    async fn order(&self) -> i32 {
+        self.order as i32
+    }
+
+    /// Colours by position
+    async fn colours(&self) -> Vec<GqlPaletteColour> {
+        self.colours.iter().cloned().map(GqlPaletteColour).collect()
+    }
+
+    /// How colours are assigned to positions
+    async fn rules(&self) -> Vec<&'static str> {
+        data::PALETTE_RULES.to_vec()
+    }
+}
+
+/// The colour of one position
+pub struct GqlPaletteColour(data::PaletteEntry);
+
+#[Object]
+impl GqlPaletteColour {
+    async fn position(&self) -> i32 {
+        self.0.position as i32
+    }
+
+    /// Hex value (e.g., "#FF0000")
+    async fn hex(&self) -> &str {
+        &self.0.hex
+    }
+
+    /// Colour name (e.g., "Red"); null for a colour outside the palette
+    async fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+}
+
+// ============================================================================
+// Provenance
+// ============================================================================
+
+/// Publication a piece of content was taken from
+pub struct GqlCitation {
+    citation: Citation,
+}
+
+impl GqlCitation {
+    pub fn new(citation: Citation) -> Self {
+        Self { citation }
+    }
+}
+
+#[Object]
+impl GqlCitation {
+    async fn author(&self) -> &str {
+        &self.citation.author
+    }
+
+    async fn book(&self) -> &str {
+        &self.citation.book
+    }
+
+    /// Page number, where known
+    async fn page(&self) -> Option<i32> {
+        self.citation.page.map(|p| p as i32)
+    }
+}
+
+// ============================================================================
+// Research Coverage
+// ============================================================================
+
+/// Counts of vocabulary by research status
+pub struct GqlResearchCoverage {
+    coverage: ResearchCoverage,
+}
+
+impl GqlResearchCoverage {
+    pub fn new(coverage: ResearchCoverage) -> Self {
+        Self { coverage }
+    }
+}
+
+#[Object]
+impl GqlResearchCoverage {
+    async fn canonical(&self) -> i32 {
+        self.coverage.canonical as i32
+    }
+
+    async fn provisional(&self) -> i32 {
+        self.coverage.provisional as i32
+    }
+
+    async fn needs_research(&self) -> i32 {
+        self.coverage.needs_research as i32
+    }
+
+    async fn total(&self) -> i32 {
+        self.coverage.total() as i32
+    }
+
+    /// Fraction of items that are not placeholders (0.0-1.0)
+    async fn researched_ratio(&self) -> f64 {
+        self.coverage.researched_ratio()
+    }
 }
 
 // ============================================================================
@@ -1286,17 +3276,39 @@ impl GqlConnectiveDesignation {
 /// A view of a system at a given order
 pub struct GqlSystemView {
     order: u8,
-    graph: Graph,
+    graph: Arc<Graph>,
+    /// Vocabulary of terms and connective characters, falling back to canonical
+    language: Language,
 }
 
 impl GqlSystemView {
-    pub fn new(order: u8, graph: Graph) -> Self {
-        Self { order, graph }
+    /// Create a system view; pass an `Arc` to share one graph between several views
+    pub fn new(order: u8, graph: impl Into<Arc<Graph>>) -> Self {
+        Self {
+            order,
+            graph: graph.into(),
+            language: Language::Canonical,
+        }
+    }
+
+    /// Resolve terms and connective characters in `language`
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    fn link(&self, link: &Link) -> GqlLink {
+        GqlLink::new(link.clone(), &self.graph).with_language(self.language.clone())
     }
 }
 
 #[Object]
 impl GqlSystemView {
+    /// Version of the wire format this view is sent in
+    async fn api_version(&self) -> i32 {
+        API_VERSION
+    }
+
     async fn order(&self) -> i32 {
         self.order as i32
     }
@@ -1305,10 +3317,30 @@ impl GqlSystemView {
         self.graph.system_name(self.order).map(|s| s.value.clone())
     }
 
+    /// Whether this is one of the twelve canonical systems (false for custom systems)
+    async fn is_canonical(&self) -> bool {
+        Order::new(self.order).is_canonical()
+    }
+
+    /// User ID of the creator of a custom system
+    async fn created_by(&self) -> Option<String> {
+        self.graph.order(self.order)?.created_by.clone()
+    }
+
+    /// Primary coherence attribute
     async fn coherence(&self) -> Option<String> {
         self.graph.coherence(self.order).map(|c| c.value.clone())
     }
 
+    /// All coherence attributes, primary first
+    async fn coherences(&self) -> Vec<String> {
+        self.graph
+            .coherences(self.order)
+            .into_iter()
+            .map(|c| c.value.clone())
+            .collect()
+    }
+
     async fn term_designation(&self) -> Option<String> {
         self.graph
             .term_designation(self.order)
@@ -1321,9 +3353,36 @@ impl GqlSystemView {
             .map(|c| c.value.clone())
     }
 
-    async fn terms(&self) -> Vec<GqlTerm> {
+    /// Canonical process orders through the positions (e.g., the Ennead's inner lines)
+    async fn sequences(&self) -> Vec<GqlSequence> {
         self.graph
-            .terms(self.order, None)
+            .sequences(self.order)
+            .into_iter()
+            .map(|s| GqlSequence::new(s.clone(), &self.graph).with_language(self.language.clone()))
+            .collect()
+    }
+
+    /// Terms in one vocabulary, if `language`/`vocabulary` name one. Otherwise one
+    /// term per position in the system's vocabulary, canonical where it has none.
+    async fn terms(
+        &self,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Vec<GqlTerm> {
+        let terms = match (language, vocabulary.as_deref()) {
+            (Some(l), vocabulary) => match l.resolve(vocabulary) {
+                Some(lang) => self.graph.terms(self.order, Some(lang)),
+                None => return vec![],
+            },
+            (None, Some(name)) => self
+                .graph
+                .terms(self.order, Some(Language::from_name(name))),
+            (None, None) if self.language == Language::Canonical => {
+                self.graph.terms(self.order, Some(Language::Canonical))
+            }
+            (None, None) => self.graph.terms_with_fallback(self.order, &self.language),
+        };
+        terms
             .into_iter()
             .map(|t| GqlTerm::new(t.clone(), &self.graph))
             .collect()
@@ -1349,7 +3408,7 @@ impl GqlSystemView {
         self.graph
             .connectives(self.order, None, None)
             .into_iter()
-            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .map(|l| self.link(l))
             .collect()
     }
 
@@ -1357,7 +3416,63 @@ impl GqlSystemView {
         self.graph
             .lines(self.order)
             .into_iter()
-            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .map(|l| self.link(l))
+            .collect()
+    }
+
+    /// Connectives resolved for labelling edges: each with its character, the
+    /// connective designation and the terms it joins
+    async fn connective_views(&self) -> Vec<ConnectiveView> {
+        self.graph
+            .connectives(self.order, None, None)
+            .into_iter()
+            .map(|l| wire::connective_view(l, &self.graph, &self.language))
+            .collect()
+    }
+
+    /// n×n connective matrix: row i, column j holds the character value of the
+    /// connective between positions i+1 and j+1 (empty if unnamed, null where
+    /// there is none)
+    async fn adjacency_matrix(&self) -> Vec<Vec<Option<String>>> {
+        self.graph
+            .connective_matrix(self.order)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| {
+                        let link = cell?;
+                        Some(
+                            self.graph
+                                .connective_character(link, &self.language)
+                                .map(|c| c.value.clone())
+                                .unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Research coverage of this system's terms and connectives
+    async fn research_coverage(&self) -> GqlResearchCoverage {
+        GqlResearchCoverage::new(self.graph.research_coverage(Some(self.order)))
+    }
+
+    /// Structural groupings (e.g., the Pentad's nested dyads)
+    async fn groupings(&self) -> Vec<GqlGrouping> {
+        self.graph
+            .groupings(self.order)
+            .into_iter()
+            .map(|l| GqlGrouping::new(l.clone(), &self.graph))
+            .collect()
+    }
+
+    /// Instances applying this system to a domain
+    async fn instances(&self) -> Vec<GqlInstance> {
+        self.graph
+            .instances(Some(self.order))
+            .into_iter()
+            .map(|i| GqlInstance::new(i.clone(), &self.graph))
             .collect()
     }
 
@@ -1367,27 +3482,36 @@ impl GqlSystemView {
             .graph
             .connectives(self.order, None, None)
             .into_iter()
-            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .map(|l| self.link(l))
             .collect();
         all_links.extend(
             self.graph
                 .lines(self.order)
                 .into_iter()
-                .map(|l| GqlLink::new(l.clone(), &self.graph)),
+                .map(|l| self.link(l)),
         );
         all_links
     }
 
     /// Get slice at a specific position
-    async fn slice(&self, position: i32) -> GqlSlice {
-        GqlSlice::new(self.order, position as u8, self.graph.clone())
+    async fn slice(&self, position: i32) -> Result<GqlSlice> {
+        let position = position_in_order("position", position, self.order)?;
+        Ok(GqlSlice::new(self.order, position, self.graph.clone()))
     }
 
-    /// All slices for this system
-    async fn slices(&self) -> Vec<GqlSlice> {
-        (1..=self.order)
+    /// Slices at the given positions, in the order requested, or all slices by position
+    async fn slices(&self, positions: Option<Vec<i32>>) -> Result<Vec<GqlSlice>> {
+        let positions = match positions {
+            Some(positions) => positions
+                .into_iter()
+                .map(|p| position_in_order("positions", p, self.order))
+                .collect::<Result<Vec<u8>>>()?,
+            None => (1..=self.order).collect(),
+        };
+        Ok(positions
+            .into_iter()
             .map(|pos| GqlSlice::new(self.order, pos, self.graph.clone()))
-            .collect()
+            .collect())
     }
 }
 
@@ -1395,19 +3519,29 @@ impl GqlSystemView {
 // Slice View
 // ============================================================================
 
+/// A typed member of a slice
+#[derive(Union)]
+pub enum GqlSliceMember {
+    Term(GqlTerm),
+    Coordinate(GqlCoordinate),
+    Colour(GqlColour),
+    Location(GqlLocation),
+}
+
 /// A slice - all entries at a specific order+position
 pub struct GqlSlice {
     order: u8,
     position: u8,
-    graph: Graph,
+    graph: Arc<Graph>,
 }
 
 impl GqlSlice {
-    pub fn new(order: u8, position: u8, graph: Graph) -> Self {
+    /// Create a slice view; pass an `Arc` to share one graph between several slices
+    pub fn new(order: u8, position: u8, graph: impl Into<Arc<Graph>>) -> Self {
         Self {
             order,
             position,
-            graph,
+            graph: graph.into(),
         }
     }
 }
@@ -1422,14 +3556,48 @@ impl GqlSlice {
         self.position as i32
     }
 
-    async fn entries(&self) -> Vec<GqlEntry> {
-        self.graph
+    /// Name of the system this slice belongs to
+    async fn system_name(&self) -> Option<String> {
+        self.graph.system_name(self.order).map(|s| s.value.clone())
+    }
+
+    /// Primary coherence attribute of the system this slice belongs to
+    async fn coherence(&self) -> Option<String> {
+        self.graph.coherence(self.order).map(|c| c.value.clone())
+    }
+
+    async fn entries(&self) -> Vec<GqlEntry> {
+        self.graph
             .slice(self.order, self.position)
             .into_iter()
             .map(|e| GqlEntry::new(e.clone(), &self.graph))
             .collect()
     }
 
+    /// The location and its terms, coordinate and colours, as typed members
+    async fn members(&self) -> Vec<GqlSliceMember> {
+        self.graph
+            .slice(self.order, self.position)
+            .into_iter()
+            .filter_map(|e| match e {
+                Entry::Location(l) => Some(GqlSliceMember::Location(GqlLocation::new(
+                    l.clone(),
+                    (*self.graph).clone(),
+                ))),
+                Entry::Term(t) => Some(GqlSliceMember::Term(GqlTerm::new(t.clone(), &self.graph))),
+                Entry::Coordinate(c) => Some(GqlSliceMember::Coordinate(GqlCoordinate::new(
+                    c.clone(),
+                    &self.graph,
+                ))),
+                Entry::Colour(c) => Some(GqlSliceMember::Colour(GqlColour::new(
+                    c.clone(),
+                    &self.graph,
+                ))),
+                _ => None,
+            })
+            .collect()
+    }
+
     async fn term(&self) -> Option<GqlTerm> {
         self.graph
             .term(self.order, self.position)
@@ -1443,7 +3611,10 @@ impl GqlSlice {
     }
 
     async fn colour(&self, language: Option<GqlLanguage>) -> Option<GqlColour> {
-        let lang = language.map(|l| l.into()).unwrap_or(Language::Hex);
+        let lang = match language {
+            Some(l) => l.resolve(None)?,
+            None => Language::Hex,
+        };
         self.graph
             .colour(self.order, self.position, lang)
             .map(|c| GqlColour::new(c.clone(), &self.graph))
@@ -1457,23 +3628,1124 @@ impl GqlSlice {
             .map(|(t, _)| GqlTerm::new(t.clone(), &self.graph))
             .collect()
     }
+
+    /// Connectives whose base or target is this location
+    async fn connectives(&self) -> Vec<GqlLink> {
+        let location_id = format!("loc_{}_{}", self.order, self.position);
+        self.graph
+            .connectives_for_location(&location_id)
+            .into_iter()
+            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .collect()
+    }
+
+    /// Lines whose base or target is this location's coordinate
+    async fn lines(&self) -> Vec<GqlLink> {
+        self.graph
+            .lines_at(self.order, self.position)
+            .into_iter()
+            .map(|l| GqlLink::new(l.clone(), &self.graph))
+            .collect()
+    }
+}
+
+// ============================================================================
+// Instance Types
+// ============================================================================
+
+/// Instance: a system applied to a domain, with a label at each location
+pub struct GqlInstance {
+    instance: Instance,
+    graph: Graph,
+}
+
+impl GqlInstance {
+    pub fn new(instance: Instance, graph: &Graph) -> Self {
+        Self {
+            instance,
+            graph: graph.clone(),
+        }
+    }
+}
+
+#[Object(cache_control(no_cache))]
+impl GqlInstance {
+    async fn id(&self) -> &str {
+        &self.instance.id
+    }
+
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Instance"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.instance.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
+    async fn name(&self) -> &str {
+        &self.instance.name
+    }
+
+    /// Order value of the system this instance applies
+    async fn order(&self) -> Option<i32> {
+        self.instance.order_value().map(|v| v as i32)
+    }
+
+    /// The system this instance applies
+    async fn system(&self) -> Option<GqlSystemView> {
+        let order = self.instance.order_value()?;
+        self.graph.order(order)?;
+        Some(GqlSystemView::new(order, self.graph.clone()))
+    }
+
+    /// Labels by position
+    async fn labels(&self) -> Vec<GqlInstanceLabel> {
+        self.graph
+            .instance_labels(&self.instance.id)
+            .into_iter()
+            .map(|l| GqlInstanceLabel::new(l.clone(), &self.graph))
+            .collect()
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+/// Instance label at one location
+pub struct GqlInstanceLabel {
+    label: InstanceLabel,
+    graph: Graph,
+}
+
+impl GqlInstanceLabel {
+    pub fn new(label: InstanceLabel, graph: &Graph) -> Self {
+        Self {
+            label,
+            graph: graph.clone(),
+        }
+    }
+}
+
+#[Object(cache_control(no_cache))]
+impl GqlInstanceLabel {
+    async fn id(&self) -> &str {
+        &self.label.id
+    }
+
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "InstanceLabel"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        self.label.order_value().map(|v| v as i32)
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        self.label.position_value().map(|v| v as i32)
+    }
+
+    /// Instance reference ID
+    async fn instance_id(&self) -> &str {
+        &self.label.instance
+    }
+
+    /// Location reference ID
+    async fn location_id(&self) -> &str {
+        &self.label.location
+    }
+
+    async fn order(&self) -> Option<i32> {
+        self.label.order_value().map(|v| v as i32)
+    }
+
+    async fn position(&self) -> Option<i32> {
+        self.label.position_value().map(|v| v as i32)
+    }
+
+    async fn value(&self) -> &str {
+        &self.label.value
+    }
+
+    /// The canonical term this label stands alongside
+    async fn term(&self) -> Option<GqlTerm> {
+        let order = self.label.order_value()?;
+        let position = self.label.position_value()?;
+        self.graph
+            .term(order, position)
+            .map(|t| GqlTerm::new(t.clone(), &self.graph))
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+// ============================================================================
+// Annotation Types
+// ============================================================================
+
+/// A user note attached to an entry or link
+pub struct GqlAnnotation {
+    annotation: Annotation,
+    graph: Graph,
+}
+
+impl GqlAnnotation {
+    pub fn new(annotation: Annotation, graph: &Graph) -> Self {
+        Self {
+            annotation,
+            graph: graph.clone(),
+        }
+    }
+}
+
+#[Object(cache_control(no_cache))]
+impl GqlAnnotation {
+    async fn id(&self) -> &str {
+        &self.annotation.id
+    }
+
+    /// Entry type name (e.g., "Term")
+    async fn entry_type(&self) -> &str {
+        "Annotation"
+    }
+
+    /// Order value, as on every entry (null where there is none)
+    async fn order_value(&self) -> Option<i32> {
+        None
+    }
+
+    /// Position value, as on every entry (null where there is none)
+    async fn position_value(&self) -> Option<i32> {
+        None
+    }
+
+    /// ID of the annotated entry or link
+    async fn entry_id(&self) -> &str {
+        &self.annotation.target
+    }
+
+    async fn text(&self) -> &str {
+        &self.annotation.text
+    }
+
+    async fn author(&self) -> &str {
+        &self.annotation.author
+    }
+
+    /// User ID of the author (null unless added by a signed-in user)
+    async fn author_id(&self) -> Option<&str> {
+        self.annotation.author_id.as_deref()
+    }
+
+    /// The annotated entry (null when a link is annotated)
+    async fn entry(&self) -> Option<GqlEntry> {
+        self.graph
+            .get_entry(&self.annotation.target)
+            .map(|e| GqlEntry::new(e.clone(), &self.graph))
+    }
+
+    /// The annotated link (null when an entry is annotated)
+    async fn link(&self) -> Option<GqlLink> {
+        self.graph
+            .get_link(&self.annotation.target)
+            .map(|l| GqlLink::new(l.clone(), &self.graph))
+    }
+
+    // Accessors of the entry object the interface replaced
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_order(&self) -> Option<&GqlOrder> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_position(&self) -> Option<&GqlPosition> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_location(&self) -> Option<&GqlLocation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_system_name(&self) -> Option<&GqlSystemName> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coherence(&self) -> Option<&GqlCoherenceAttribute> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term_designation(&self) -> Option<&GqlTermDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_connective_designation(&self) -> Option<&GqlConnectiveDesignation> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_term(&self) -> Option<&GqlTerm> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_colour(&self) -> Option<&GqlColour> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_coordinate(&self) -> Option<&GqlCoordinate> {
+        None
+    }
+
+    #[graphql(deprecation = "Select type-specific fields with an inline fragment")]
+    async fn as_character(&self) -> Option<&GqlCharacter> {
+        None
+    }
+}
+
+// ============================================================================
+// User Types
+// ============================================================================
+
+/// A signed-in user, identified by a bearer token
+pub struct GqlUser(UserIdentity);
+
+#[Object(cache_control(no_cache))]
+impl GqlUser {
+    /// Stable user ID (the token subject)
+    async fn id(&self) -> &str {
+        &self.0.subject
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    async fn email(&self) -> Option<&str> {
+        self.0.email.as_deref()
+    }
+
+    /// Name that annotations are attributed to
+    async fn display_name(&self) -> &str {
+        self.0.display_name()
+    }
+}
+
+// ============================================================================
+// Vocabulary Import Types
+// ============================================================================
+
+/// Report of an imported vocabulary pack
+pub struct GqlVocabularyImport(VocabularyImport);
+
+#[Object(cache_control(no_cache))]
+impl GqlVocabularyImport {
+    /// The registered vocabulary; pass it as `language` (with `vocabulary` for CUSTOM)
+    async fn language(&self) -> GqlLanguage {
+        GqlLanguage::from(&self.0.language)
+    }
+
+    /// Name of the vocabulary
+    async fn vocabulary(&self) -> String {
+        self.0.language.to_string()
+    }
+
+    /// Number of term values applied
+    async fn terms_imported(&self) -> i32 {
+        self.0.terms as i32
+    }
+
+    /// Number of connective names applied
+    async fn connectives_imported(&self) -> i32 {
+        self.0.connectives as i32
+    }
+
+    /// Values that were not applied, with the reason
+    async fn skipped(&self) -> &[String] {
+        &self.0.skipped
+    }
+}
+
+/// Report of a committed session
+pub struct GqlSessionCommit(SessionCommit);
+
+#[Object(cache_control(no_cache))]
+impl GqlSessionCommit {
+    /// Number of drafted edits applied
+    async fn applied(&self) -> i32 {
+        self.0.applied as i32
+    }
+
+    /// Drafted edits that no longer apply, with the reason
+    async fn skipped(&self) -> &[String] {
+        &self.0.skipped
+    }
+}
+
+// ============================================================================
+// Comparison Types
+// ============================================================================
+
+/// Two systems compared position by position
+pub struct GqlSystemComparison {
+    order_a: u8,
+    order_b: u8,
+    graph: Arc<Graph>,
+}
+
+impl GqlSystemComparison {
+    pub fn new(order_a: u8, order_b: u8, graph: impl Into<Arc<Graph>>) -> Self {
+        Self {
+            order_a,
+            order_b,
+            graph: graph.into(),
+        }
+    }
+}
+
+#[Object]
+impl GqlSystemComparison {
+    async fn order_a(&self) -> i32 {
+        self.order_a as i32
+    }
+
+    async fn order_b(&self) -> i32 {
+        self.order_b as i32
+    }
+
+    async fn system_a(&self) -> GqlSystemView {
+        GqlSystemView::new(self.order_a, self.graph.clone())
+    }
+
+    async fn system_b(&self) -> GqlSystemView {
+        GqlSystemView::new(self.order_b, self.graph.clone())
+    }
+
+    /// Positions both systems have, by ascending position
+    async fn aligned_positions(&self) -> Vec<GqlAlignedPosition> {
+        let graph: &Graph = &self.graph;
+        graph
+            .compare_systems(self.order_a, self.order_b)
+            .aligned
+            .into_iter()
+            .map(|(position, a, b)| GqlAlignedPosition {
+                position,
+                location_a: GqlLocation::new(a.clone(), graph.clone()),
+                location_b: GqlLocation::new(b.clone(), graph.clone()),
+                term_a: graph.term(self.order_a, position).cloned(),
+                term_b: graph.term(self.order_b, position).cloned(),
+                graph: graph.clone(),
+            })
+            .collect()
+    }
+
+    /// Character values naming terms or connectives of both systems (sorted)
+    async fn shared_values(&self) -> Vec<String> {
+        self.graph
+            .compare_systems(self.order_a, self.order_b)
+            .shared_values
+    }
+
+    /// Connectives of system A paired with the connective of system B joining the
+    /// same positions
+    async fn projections(&self) -> Vec<GqlProjection> {
+        let graph: &Graph = &self.graph;
+        graph
+            .compare_systems(self.order_a, self.order_b)
+            .projections
+            .into_iter()
+            .map(|(a, b)| GqlProjection {
+                link_a: GqlLink::new(a.clone(), graph),
+                link_b: GqlLink::new(b.clone(), graph),
+            })
+            .collect()
+    }
+}
+
+/// A position present in both compared systems
+pub struct GqlAlignedPosition {
+    position: u8,
+    location_a: GqlLocation,
+    location_b: GqlLocation,
+    term_a: Option<Term>,
+    term_b: Option<Term>,
+    graph: Graph,
+}
+
+#[Object]
+impl GqlAlignedPosition {
+    async fn position(&self) -> i32 {
+        self.position as i32
+    }
+
+    async fn location_a(&self) -> &GqlLocation {
+        &self.location_a
+    }
+
+    async fn location_b(&self) -> &GqlLocation {
+        &self.location_b
+    }
+
+    async fn term_a(&self) -> Option<GqlTerm> {
+        self.term_a
+            .as_ref()
+            .map(|t| GqlTerm::new(t.clone(), &self.graph))
+    }
+
+    async fn term_b(&self) -> Option<GqlTerm> {
+        self.term_b
+            .as_ref()
+            .map(|t| GqlTerm::new(t.clone(), &self.graph))
+    }
+}
+
+/// A connective of system A and its counterpart in system B
+pub struct GqlProjection {
+    link_a: GqlLink,
+    link_b: GqlLink,
+}
+
+#[Object]
+impl GqlProjection {
+    async fn link_a(&self) -> &GqlLink {
+        &self.link_a
+    }
+
+    async fn link_b(&self) -> &GqlLink {
+        &self.link_b
+    }
+}
+
+// ============================================================================
+// Neighbor Types
+// ============================================================================
+
+/// A location linked to another by a connective
+pub struct GqlNeighbor {
+    location: GqlLocation,
+    connective: GqlLink,
+}
+
+#[Object]
+impl GqlNeighbor {
+    /// The linked location
+    async fn location(&self) -> &GqlLocation {
+        &self.location
+    }
+
+    /// The connective linking the two locations
+    async fn connective(&self) -> &GqlLink {
+        &self.connective
+    }
+
+    /// Character naming the connective (null when unnamed)
+    async fn character(&self) -> Option<GqlCharacter> {
+        let link = &self.connective;
+        link.graph
+            .connective_character(&link.link, &link.language)
+            .map(|c| GqlCharacter::new(c.clone(), &link.graph))
+    }
+}
+
+// ============================================================================
+// Search Types
+// ============================================================================
+
+/// A search match, with the order/position that owns it
+pub struct GqlSearchHit {
+    hit: SearchHit,
+    graph: Graph,
+}
+
+impl GqlSearchHit {
+    pub fn new(hit: SearchHit, graph: &Graph) -> Self {
+        Self {
+            hit,
+            graph: graph.clone(),
+        }
+    }
+}
+
+#[Object]
+impl GqlSearchHit {
+    async fn kind(&self) -> GqlSearchHitKind {
+        self.hit.kind.into()
+    }
+
+    /// ID of the matched entry, or of the link for connectives
+    async fn id(&self) -> &str {
+        &self.hit.id
+    }
+
+    /// The matched text
+    async fn value(&self) -> &str {
+        &self.hit.value
+    }
+
+    async fn order(&self) -> Option<i32> {
+        self.hit.order.map(|v| v as i32)
+    }
+
+    /// Position of a term (null for order-level hits and connectives)
+    async fn position(&self) -> Option<i32> {
+        self.hit.position.map(|v| v as i32)
+    }
+
+    /// The matched entry (null for connectives)
+    async fn entry(&self) -> Option<GqlEntry> {
+        self.graph
+            .get_entry(&self.hit.id)
+            .map(|e| GqlEntry::new(e.clone(), &self.graph))
+    }
+
+    /// The matched connective (null for entries)
+    async fn link(&self) -> Option<GqlLink> {
+        self.graph
+            .get_link(&self.hit.id)
+            .map(|l| GqlLink::new(l.clone(), &self.graph))
+    }
+}
+
+/// A page of search hits in the middleware's wire format (`Page<SearchHit>`)
+#[derive(SimpleObject)]
+pub struct GqlSearchHitPage {
+    items: Vec<systematics_middleware::SearchHit>,
+    /// Hits in the whole search
+    total: i32,
+    has_next: bool,
+    /// Cursor to pass as `after` for the next page; null on the last
+    cursor: Option<String>,
+}
+
+impl From<Page<systematics_middleware::SearchHit>> for GqlSearchHitPage {
+    fn from(page: Page<systematics_middleware::SearchHit>) -> Self {
+        Self {
+            items: page.items,
+            total: page.total,
+            has_next: page.has_next,
+            cursor: page.cursor,
+        }
+    }
+}
+
+// ============================================================================
+// Mutations
+// ============================================================================
+
+/// Root mutation object. Changes apply to the shared graph; they survive data
+/// reloads and restarts when the store persists its edits, and are lost otherwise.
+/// In a workspace, changes apply to its copy of the graph and are never persisted;
+/// in a session, they apply to its draft until it is committed.
+#[derive(Clone, Default)]
+pub struct MutationRoot;
+
+#[Object(cache_control(no_cache))]
+impl MutationRoot {
+    /// Create an instance of the system at `order`, with one label per position. Only
+    /// signed-in users may create instances.
+    async fn create_instance(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        order: i32,
+        labels: Vec<String>,
+    ) -> Result<GqlInstance> {
+        signed_in(ctx)?;
+        let order = order_arg("order", order)?;
+        let store = ctx.data_unchecked::<GraphStore>();
+        let edit = Edit::Instance {
+            name: name.clone(),
+            order,
+            labels: labels.clone(),
+        };
+        let (instance, graph) = store
+            .apply(edit, |graph| {
+                data::add_instance(graph, &name, order, &labels).map(|i| (i, graph.clone()))
+            })
+            .map_err(|e| e.extend())?;
+        Ok(GqlInstance::new(instance, &graph))
+    }
+
+    /// Attach a note to an entry or link (e.g., a term or connective). Only signed-in
+    /// users may annotate, and notes are attributed to them; `author` is ignored.
+    async fn annotate_entry(
+        &self,
+        ctx: &Context<'_>,
+        entry_id: String,
+        text: String,
+        #[graphql(name = "author")] _author: Option<String>,
+    ) -> Result<GqlAnnotation> {
+        let identity = signed_in(ctx)?;
+        let author = identity.display_name().to_string();
+        let author_id = Some(identity.subject.as_str());
+        let store = ctx.data_unchecked::<GraphStore>();
+        let edit = Edit::Annotation {
+            target: entry_id.clone(),
+            text: text.clone(),
+            author: author.clone(),
+            author_id: author_id.map(str::to_string),
+        };
+        let (annotation, graph) = store
+            .apply(edit, |graph| {
+                data::add_annotation(graph, &entry_id, &text, &author, author_id)
+                    .map(|a| (a, graph.clone()))
+            })
+            .map_err(|e| e.extend())?;
+        Ok(GqlAnnotation::new(annotation, &graph))
+    }
+
+    /// Import a vocabulary pack uploaded as CSV or YAML (multipart request) and register it
+    /// as vocabulary `name`, defaulting to the file name without its extension. The name
+    /// may not be a built-in language's. Only signed-in users may import.
+    async fn import_vocabulary(
+        &self,
+        ctx: &Context<'_>,
+        file: Upload,
+        name: Option<String>,
+    ) -> Result<GqlVocabularyImport> {
+        signed_in(ctx)?;
+        let upload = file.value(ctx)?;
+        let format = VocabularyFormat::from_file_name(&upload.filename)
+            .or_else(|| {
+                upload
+                    .content_type
+                    .as_deref()
+                    .and_then(VocabularyFormat::from_content_type)
+            })
+            .ok_or_else(|| VocabularyError::UnsupportedFormat(upload.filename.clone()).extend())?;
+        let name = name.unwrap_or_else(|| {
+            let file_name = upload.filename.as_str();
+            let stem = file_name
+                .rsplit_once('.')
+                .map_or(file_name, |(stem, _)| stem);
+            stem.to_string()
+        });
+        let mut text = String::new();
+        upload.into_read().read_to_string(&mut text).map_err(|_| {
+            coded_error(ErrorCode::InvalidInput, "Vocabulary file is not UTF-8 text").field("file")
+        })?;
+        let pack = data::parse_vocabulary(&text, format).map_err(|e| e.extend())?;
+        let store = ctx.data_unchecked::<GraphStore>();
+        let edit = Edit::Vocabulary {
+            name: name.clone(),
+            pack: pack.clone(),
+        };
+        let report = store
+            .apply(edit, |graph| data::import_vocabulary(graph, &name, &pack))
+            .map_err(|e| e.extend())?;
+        Ok(GqlVocabularyImport(report))
+    }
+
+    /// Rename the term at a position of a system in a vocabulary (the canonical one by
+    /// default, or as for `system`), returning the system in that vocabulary. Only
+    /// signed-in users may rename.
+    async fn rename_term(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        position: i32,
+        value: String,
+        language: Option<GqlLanguage>,
+        vocabulary: Option<String>,
+    ) -> Result<GqlSystemView> {
+        signed_in(ctx)?;
+        let order = order_arg("order", order)?;
+        let position = position_in_order("position", position, order)?;
+        let language = vocabulary_language(language, vocabulary.as_deref());
+        let store = ctx.data_unchecked::<GraphStore>();
+        let edit = Edit::Term {
+            order,
+            position,
+            language: language.clone(),
+            value: value.clone(),
+        };
+        let graph = store
+            .apply(edit, |graph| {
+                data::rename_term(graph, order, position, &language, &value).map(|_| graph.clone())
+            })
+            .map_err(|e| e.extend())?;
+        Ok(GqlSystemView::new(order, graph).with_language(language))
+    }
+
+    /// Name the connective between two positions of a system (either way round) in a
+    /// vocabulary other than the canonical one, returning the system in that vocabulary.
+    /// Only signed-in users may label connectives.
+    #[allow(clippy::too_many_arguments)]
+    async fn label_connective(
+        &self,
+        ctx: &Context<'_>,
+        order: i32,
+        base: i32,
+        target: i32,
+        value: String,
+        language: GqlLanguage,
+        vocabulary: Option<String>,
+    ) -> Result<GqlSystemView> {
+        signed_in(ctx)?;
+        let order = order_arg("order", order)?;
+        let base = position_in_order("base", base, order)?;
+        let target = position_in_order("target", target, order)?;
+        let language = vocabulary_language(Some(language), vocabulary.as_deref());
+        let store = ctx.data_unchecked::<GraphStore>();
+        let edit = Edit::ConnectiveLabel {
+            order,
+            base,
+            target,
+            language: language.clone(),
+            value: value.clone(),
+        };
+        let graph = store
+            .apply(edit, |graph| {
+                data::label_connective(graph, order, base, target, &language, &value)
+                    .map(|_| graph.clone())
+            })
+            .map_err(|e| e.extend())?;
+        Ok(GqlSystemView::new(order, graph).with_language(language))
+    }
+
+    /// Create a custom system, generating its locations, geometry, colours and lines.
+    /// Only signed-in users may create systems, and they are attributed to them.
+    async fn create_custom_system(
+        &self,
+        ctx: &Context<'_>,
+        input: CustomSystemInput,
+    ) -> Result<GqlSystemView> {
+        let def = data::CustomSystem {
+            created_by: Some(signed_in(ctx)?.subject.clone()),
+            ..custom_system(input)?
+        };
+        let store = ctx.data_unchecked::<GraphStore>();
+        let graph = store
+            .apply(Edit::CustomSystem(def.clone()), |graph| {
+                data::add_custom_system(graph, &def).map(|_| graph.clone())
+            })
+            .map_err(|e| e.extend())?;
+        Ok(GqlSystemView::new(def.order, graph))
+    }
+
+    /// Start a draft session over the graph the request is served from, returning its
+    /// ID. Requests sending the ID in the `X-Session` header read and edit the draft,
+    /// leaving the graph unchanged until the session is committed.
+    async fn start_session(&self, ctx: &Context<'_>) -> Result<ID> {
+        let store = ctx.data_unchecked::<GraphStore>();
+        session_registry(ctx)?
+            .start(store)
+            .map(ID)
+            .map_err(|e| e.extend())
+    }
+
+    /// End a session, applying its drafted edits (and persisting them where the
+    /// graph's edits are persisted). Only signed-in users may commit.
+    async fn commit_session(&self, ctx: &Context<'_>, id: ID) -> Result<GqlSessionCommit> {
+        signed_in(ctx)?;
+        session_registry(ctx)?
+            .commit(&id)
+            .map(GqlSessionCommit)
+            .map_err(|e| e.extend())
+    }
+
+    /// End a session, dropping its drafted edits; returns whether the session existed
+    async fn discard_session(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        Ok(session_registry(ctx)?.discard(&id))
+    }
+}
+
+/// The signed-in user of a request, failing with UNAUTHENTICATED for anonymous ones
+fn signed_in<'a>(ctx: &Context<'a>) -> Result<&'a UserIdentity> {
+    ctx.data_opt::<UserIdentity>().ok_or_else(|| {
+        coded_error(ErrorCode::Unauthenticated, "Sign in to edit")
+            .hint("Send a bearer token from the configured issuer")
+    })
+}
+
+/// The server's draft sessions (absent from schemas built without a server, e.g. for SDL)
+fn session_registry<'a>(ctx: &Context<'a>) -> Result<&'a SessionRegistry> {
+    ctx.data_opt::<SessionRegistry>()
+        .ok_or_else(|| coded_error(ErrorCode::SessionUnavailable, "Sessions are not available"))
+}
+
+/// Definition of a custom system from the input the frontend sends
+fn custom_system(input: CustomSystemInput) -> Result<data::CustomSystem> {
+    let order = order_arg("order", input.order)?;
+    Ok(data::CustomSystem {
+        order,
+        name: input.name,
+        coherence: input.coherence,
+        alternate_coherences: input.alternate_coherences,
+        term_designation: input.term_designation,
+        connective_designation: input.connective_designation,
+        terms: input.terms,
+        connectives: input.connectives,
+        created_by: None,
+    })
+}
+
+// ============================================================================
+// Subscriptions
+// ============================================================================
+
+/// Revisions of the served graph: the current one, then one per change
+pub fn revisions(store: &GraphStore) -> impl Stream<Item = Revision> {
+    let mut changes = store.subscribe();
+    let current = changes.borrow_and_update().clone();
+    futures_util::stream::once(async move { current }).chain(futures_util::stream::unfold(
+        changes,
+        |mut changes| async move {
+            changes.changed().await.ok()?;
+            let revision = changes.borrow_and_update().clone();
+            Some((revision, changes))
+        },
+    ))
+}
+
+/// Root subscription object, served over WebSocket at `/graphql/ws`
+#[derive(Clone, Default)]
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// The graph's revision, now and after every mutation or data reload that changes it
+    async fn graph_changed(&self, ctx: &Context<'_>) -> impl Stream<Item = GqlRevision> {
+        revisions(ctx.data_unchecked::<GraphStore>()).map(GqlRevision)
+    }
+
+    /// The changes of each revision after subscribing: entries and links added,
+    /// updated and removed
+    async fn graph_delta(&self, ctx: &Context<'_>) -> impl Stream<Item = GraphDelta> {
+        let store = ctx.data_unchecked::<GraphStore>().clone();
+        let mut before = store.snapshot();
+        revisions(&store).skip(1).filter_map(move |_| {
+            let after = store.snapshot();
+            let delta = wire::delta(&before, &after);
+            before = after;
+            async move { (!delta.is_empty()).then_some(delta) }
+        })
+    }
+
+    /// Notes added after subscribing, optionally only those on one entry or link
+    async fn annotation_added(
+        &self,
+        ctx: &Context<'_>,
+        entry_id: Option<String>,
+    ) -> impl Stream<Item = GqlAnnotation> {
+        let store = ctx.data_unchecked::<GraphStore>().clone();
+        let mut seen = store.snapshot().all_annotations().len();
+        revisions(&store).skip(1).flat_map(move |_| {
+            let graph = store.snapshot();
+            let added: Vec<GqlAnnotation> = graph
+                .all_annotations()
+                .into_iter()
+                .skip(seen)
+                .filter(|a| entry_id.as_ref().is_none_or(|id| &a.target == id))
+                .map(|a| GqlAnnotation::new(a.clone(), &graph))
+                .collect();
+            seen = graph.all_annotations().len();
+            futures_util::stream::iter(added)
+        })
+    }
+}
+
+/// A revision of the served graph
+pub struct GqlRevision(Revision);
+
+#[Object]
+impl GqlRevision {
+    /// Content hash of the graph
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    /// HTTP entity tag of GET responses served from this revision
+    async fn etag(&self) -> String {
+        entity_tag(&self.0)
+    }
+
+    /// When the graph last changed, as an HTTP date
+    async fn modified_at(&self) -> String {
+        httpdate::fmt_http_date(self.0.modified)
+    }
 }
 
 // ============================================================================
 // Schema
 // ============================================================================
 
-pub type SystematicsSchema = async_graphql::Schema<
-    QueryRoot,
-    async_graphql::EmptyMutation,
-    async_graphql::EmptySubscription,
->;
+pub type SystematicsSchema = async_graphql::Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+/// Schema configuration (see `Config::schema_options`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaOptions {
+    /// Answer introspection queries (and serve the SDL)
+    pub introspection: bool,
+    /// Reject requests without a persisted query hash
+    pub persisted_only: bool,
+    /// Execute only the frontend's operations
+    pub allowed_operations_only: bool,
+}
 
+impl Default for SchemaOptions {
+    fn default() -> Self {
+        Self {
+            introspection: true,
+            persisted_only: false,
+            allowed_operations_only: false,
+        }
+    }
+}
+
+/// Create the schema over the built-in canonical graph
 pub fn create_schema() -> SystematicsSchema {
-    async_graphql::Schema::build(
-        QueryRoot,
-        async_graphql::EmptyMutation,
-        async_graphql::EmptySubscription,
+    create_schema_with_store(
+        GraphStore::new(data::build_graph()),
+        SchemaOptions::default(),
     )
-    .finish()
+}
+
+/// Render the schema as SDL (for codegen tools and the frontend build)
+pub fn schema_sdl() -> String {
+    create_schema().sdl()
+}
+
+/// Create the schema over a shared graph store (e.g., one that is hot-reloaded).
+/// Automatic persisted queries are accepted, and required if `options.persisted_only`;
+/// with `options.allowed_operations_only`, only the frontend's operations run.
+pub fn create_schema_with_store(store: GraphStore, options: SchemaOptions) -> SystematicsSchema {
+    let mut builder =
+        async_graphql::Schema::build(QueryRoot, MutationRoot, SubscriptionRoot).data(store);
+    // Checked before persisted queries resolve, so unlisted queries are never registered
+    if options.allowed_operations_only {
+        builder = builder.extension(OperationAllowList::frontend());
+    }
+    builder = builder
+        .extension(PersistedQueries::new().required(options.persisted_only))
+        .extension(ResolverTimings)
+        .extension(CacheHints);
+    if !options.introspection {
+        builder = builder.disable_introspection();
+    }
+    builder.finish()
 }
@@ -0,0 +1,284 @@
+//! Graph entries as the middleware's wire types.
+//!
+//! Fields answered with a middleware type (see `systematics_middleware`) are
+//! built here, eagerly, from a graph snapshot; their values match those of the
+//! corresponding `Gql*` objects, so clients decode either alike.
+
+use std::collections::{HashMap, HashSet};
+
+use systematics_middleware as wire;
+
+use crate::core::{
+    Character, Citation, Colour, Coordinate, Entry, Graph, Language, LineRole, Link, LinkType,
+    ResearchStatus, SearchHit, SearchHitKind, Term,
+};
+
+fn language(language: &Language) -> wire::Language {
+    match language {
+        Language::Canonical => wire::Language::Canonical,
+        Language::Energy => wire::Language::Energy,
+        Language::Values => wire::Language::Values,
+        Language::Society => wire::Language::Society,
+        Language::Custom(_) => wire::Language::Custom,
+        Language::Hex => wire::Language::Hex,
+        Language::Name => wire::Language::Name,
+    }
+}
+
+fn status(status: ResearchStatus) -> wire::ResearchStatus {
+    match status {
+        ResearchStatus::Canonical => wire::ResearchStatus::Canonical,
+        ResearchStatus::Provisional => wire::ResearchStatus::Provisional,
+        ResearchStatus::NeedsResearch => wire::ResearchStatus::NeedsResearch,
+    }
+}
+
+fn link_type(link_type: &LinkType) -> wire::LinkType {
+    match link_type {
+        LinkType::Line => wire::LinkType::Line,
+        LinkType::Connective => wire::LinkType::Connective,
+        LinkType::Grouping => wire::LinkType::Grouping,
+    }
+}
+
+fn line_role(role: LineRole) -> wire::LineRole {
+    match role {
+        LineRole::Side => wire::LineRole::Side,
+        LineRole::Diagonal => wire::LineRole::Diagonal,
+        LineRole::Circumference => wire::LineRole::Circumference,
+        LineRole::Chord => wire::LineRole::Chord,
+    }
+}
+
+fn search_hit_kind(kind: SearchHitKind) -> wire::SearchHitKind {
+    match kind {
+        SearchHitKind::Term => wire::SearchHitKind::Term,
+        SearchHitKind::Connective => wire::SearchHitKind::Connective,
+        SearchHitKind::Character => wire::SearchHitKind::Character,
+        SearchHitKind::SystemName => wire::SearchHitKind::SystemName,
+        SearchHitKind::CoherenceAttribute => wire::SearchHitKind::CoherenceAttribute,
+        SearchHitKind::TermDesignation => wire::SearchHitKind::TermDesignation,
+        SearchHitKind::ConnectiveDesignation => wire::SearchHitKind::ConnectiveDesignation,
+    }
+}
+
+fn citation(citation: &Citation) -> wire::Citation {
+    wire::Citation {
+        author: citation.author.clone(),
+        book: citation.book.clone(),
+        page: citation.page.map(|p| p as i32),
+    }
+}
+
+/// A character, with its translations
+pub fn character(character: &Character, graph: &Graph) -> wire::Character {
+    wire::Character {
+        id: character.id.clone(),
+        language: language(&character.language),
+        language_name: Some(character.language.to_string()),
+        value: character.value.clone(),
+        value_i18n: graph.translations(&character.id),
+        status: status(character.status),
+        locale: character.locale.clone(),
+        source: character.source.as_ref().map(citation),
+    }
+}
+
+/// A term, with the character it references
+pub fn term(term: &Term, graph: &Graph) -> wire::Term {
+    wire::Term {
+        id: term.id.clone(),
+        order: term.order_value().map_or(0, i32::from),
+        position: term.position_value().map_or(0, i32::from),
+        character_id: term.character.clone(),
+        character: graph
+            .get_character(&term.character)
+            .map(|c| character(c, graph)),
+        value_i18n: graph.translations(&term.character),
+        status: status(term.status),
+    }
+}
+
+/// A coordinate
+pub fn coordinate(coordinate: &Coordinate) -> wire::Coordinate {
+    wire::Coordinate {
+        id: coordinate.id.clone(),
+        order: coordinate.order_value().map_or(0, i32::from),
+        position: coordinate.position_value().map_or(0, i32::from),
+        x: coordinate.value.x,
+        y: coordinate.value.y,
+        z: coordinate.value.z,
+    }
+}
+
+/// A colour
+pub fn colour(colour: &Colour) -> wire::Colour {
+    wire::Colour {
+        id: colour.id.clone(),
+        order: colour.order_value().map_or(0, i32::from),
+        position: colour.position_value().map_or(0, i32::from),
+        language: language(&colour.language),
+        value: colour.value.clone(),
+    }
+}
+
+/// Coordinate of a link end: the entry itself for lines, otherwise the
+/// coordinate at the entry's location
+fn end_coordinate(entry: Option<&Entry>, graph: &Graph) -> Option<wire::Coordinate> {
+    match entry? {
+        Entry::Coordinate(c) => Some(coordinate(c)),
+        entry => graph
+            .coordinate(entry.order()?, entry.position()?)
+            .map(coordinate),
+    }
+}
+
+/// A line or connective, with its canonical character and end coordinates
+pub fn link(link: &Link, graph: &Graph) -> wire::Link {
+    let base = link.base_single().and_then(|id| graph.get_entry(id));
+    let target = link.target_single().and_then(|id| graph.get_entry(id));
+    wire::Link {
+        id: link.id.clone(),
+        base_id: link.base_single().unwrap_or_default().to_string(),
+        target_id: link.target_single().unwrap_or_default().to_string(),
+        link_type: link_type(&link.link_type),
+        character_id: link.character_id().map(str::to_string),
+        tag: link.tag.clone(),
+        role: link.line_role().map(line_role),
+        order: base.and_then(|e| e.order()).map(i32::from),
+        base_position: base.and_then(|e| e.position()).map(i32::from),
+        target_position: target.and_then(|e| e.position()).map(i32::from),
+        character: graph
+            .connective_character(link, &Language::Canonical)
+            .map(|c| character(c, graph)),
+        base_coordinate: end_coordinate(base, graph),
+        target_coordinate: end_coordinate(target, graph),
+    }
+}
+
+/// A connective resolved for labelling: its character and the terms it joins
+/// in `language` (falling back to canonical), and the order's connective
+/// designation
+pub fn connective_view(link: &Link, graph: &Graph, language: &Language) -> wire::ConnectiveView {
+    let base = link.base_single().and_then(|id| graph.get_entry(id));
+    let target = link.target_single().and_then(|id| graph.get_entry(id));
+    let order = base.and_then(|e| e.order());
+    let terms = order.map_or_else(Vec::new, |o| graph.terms_with_fallback(o, language));
+    let term = |end: Option<&Entry>| {
+        let position = end?.position()?;
+        let term = terms
+            .iter()
+            .find(|t| t.position_value() == Some(position))?;
+        graph
+            .get_character(&term.character)
+            .map(|c| c.value.clone())
+    };
+    wire::ConnectiveView {
+        id: link.id.clone(),
+        order: order.map_or(0, i32::from),
+        base_position: base.and_then(|e| e.position()).map_or(0, i32::from),
+        target_position: target.and_then(|e| e.position()).map_or(0, i32::from),
+        character: graph
+            .connective_character(link, language)
+            .map(|c| character(c, graph)),
+        designation: order
+            .and_then(|o| graph.connective_designation(o))
+            .map(|d| d.value.clone()),
+        base_term: term(base),
+        target_term: term(target),
+    }
+}
+
+/// The slice at an order and position: its term, coordinate, hex colour, and
+/// the connectives and lines meeting there
+pub fn slice(graph: &Graph, order: u8, position: u8) -> wire::Slice {
+    let location_id = format!("loc_{}_{}", order, position);
+    wire::Slice {
+        order: order as i32,
+        position: position as i32,
+        system_name: graph.system_name(order).map(|s| s.value.clone()),
+        coherence: graph.coherence(order).map(|c| c.value.clone()),
+        term: graph.term(order, position).map(|t| term(t, graph)),
+        coordinate: graph.coordinate(order, position).map(coordinate),
+        colour: graph.colour(order, position, Language::Hex).map(colour),
+        connectives: graph
+            .connectives_for_location(&location_id)
+            .into_iter()
+            .map(|l| link(l, graph))
+            .collect(),
+        lines: graph
+            .lines_at(order, position)
+            .into_iter()
+            .map(|l| link(l, graph))
+            .collect(),
+    }
+}
+
+/// A search match
+pub fn search_hit(hit: &SearchHit) -> wire::SearchHit {
+    wire::SearchHit {
+        kind: search_hit_kind(hit.kind),
+        id: hit.id.clone(),
+        value: hit.value.clone(),
+        order: hit.order.map(i32::from),
+        position: hit.position.map(i32::from),
+    }
+}
+
+/// The changes from `before` to `after`: entries and links compared by ID,
+/// carried as wire types resolved in `after`
+pub fn delta(before: &Graph, after: &Graph) -> wire::GraphDelta {
+    let mut delta = wire::GraphDelta {
+        api_version: wire::API_VERSION,
+        from_revision: before.content_hash(),
+        to_revision: after.content_hash(),
+        ..wire::GraphDelta::default()
+    };
+
+    let entries: HashMap<&str, &Entry> = before.entries.iter().map(|e| (e.id(), e)).collect();
+    for entry in &after.entries {
+        let changes = match entries.get(entry.id()) {
+            None => &mut delta.added,
+            Some(&previous) if previous != entry => &mut delta.updated,
+            Some(_) => continue,
+        };
+        match entry {
+            Entry::Term(t) => changes.terms.push(term(t, after)),
+            Entry::Coordinate(c) => changes.coordinates.push(coordinate(c)),
+            Entry::Colour(c) => changes.colours.push(colour(c)),
+            Entry::Character(c) => changes.characters.push(character(c, after)),
+            entry => delta.other.push(entry.id().to_string()),
+        }
+    }
+
+    let links: HashMap<&str, &Link> = before.links.iter().map(|l| (l.id.as_str(), l)).collect();
+    for l in &after.links {
+        let changes = match links.get(l.id.as_str()) {
+            None => &mut delta.added,
+            Some(&previous) if previous != l => &mut delta.updated,
+            Some(_) => continue,
+        };
+        match l.link_type {
+            LinkType::Line | LinkType::Connective => changes.links.push(link(l, after)),
+            LinkType::Grouping => delta.other.push(l.id.clone()),
+        }
+    }
+
+    let entry_ids: HashSet<&str> = after.entries.iter().map(Entry::id).collect();
+    let link_ids: HashSet<&str> = after.links.iter().map(|l| l.id.as_str()).collect();
+    delta.removed = before
+        .entries
+        .iter()
+        .map(|e| e.id())
+        .filter(|id| !entry_ids.contains(id))
+        .chain(
+            before
+                .links
+                .iter()
+                .map(|l| l.id.as_str())
+                .filter(|id| !link_ids.contains(id)),
+        )
+        .map(str::to_string)
+        .collect();
+    delta
+}
@@ -0,0 +1,423 @@
+//! gRPC service (`grpc` feature).
+//!
+//! For integrations that prefer protobuf to GraphQL, the API also serves the
+//! `systematics.v1.Systematics` service of `proto/systematics.proto`, on the
+//! same address over HTTP/2 (with or without TLS):
+//!
+//! - `GetSystem`: one system by order; `NOT_FOUND` if there is none, and
+//!   `INVALID_ARGUMENT` for an order out of range
+//! - `ListSystems`: every system, canonical and custom, by ascending order
+//! - `StreamChanges`: the graph's revision, now and after every change
+//!
+//! Systems are read with the frontend's GraphQL operations and sent as the
+//! middleware types those decode to, so both APIs answer alike. The service
+//! only reads, and serves the shared graph.
+
+use std::pin::Pin;
+use std::time::UNIX_EPOCH;
+
+use async_graphql::futures_util::{Stream, StreamExt};
+use async_graphql::Variables;
+use serde::de::DeserializeOwned;
+use systematics_middleware::{self as wire, operations, ErrorCode, GraphQLError};
+use tonic::server::NamedService;
+use tonic::{Request, Response, Status};
+
+use crate::data::{GraphStore, Revision};
+use crate::graphql::types::revisions;
+use crate::graphql::{create_schema_with_store, SchemaOptions, SystematicsSchema};
+
+/// Code generated from `proto/systematics.proto`
+pub mod proto {
+    tonic::include_proto!("systematics.v1");
+}
+
+use proto::systematics_server::{Systematics, SystematicsServer};
+
+/// The gRPC service over a graph store
+#[derive(Clone)]
+pub struct SystematicsService {
+    schema: SystematicsSchema,
+    store: GraphStore,
+}
+
+impl SystematicsService {
+    pub fn new(store: GraphStore) -> Self {
+        // Only the frontend's operations run, so the schema needs no restrictions
+        let options = SchemaOptions {
+            introspection: false,
+            persisted_only: false,
+            allowed_operations_only: false,
+        };
+        Self {
+            schema: create_schema_with_store(store.clone(), options),
+            store,
+        }
+    }
+
+    /// Router serving the service at `/systematics.v1.Systematics/<method>`
+    pub fn into_router(self) -> axum::Router {
+        let path = format!("/{}/*method", SystematicsServer::<Self>::NAME);
+        axum::Router::new().route_service(&path, SystematicsServer::new(self))
+    }
+
+    /// Run a GraphQL operation, decoding the `field` of its result
+    async fn query<T: DeserializeOwned>(
+        &self,
+        document: String,
+        variables: serde_json::Value,
+        field: &str,
+    ) -> Result<T, Status> {
+        let request =
+            async_graphql::Request::new(document).variables(Variables::from_json(variables));
+        let response = self.schema.execute(request).await;
+        if let Some(error) = response.errors.first() {
+            return Err(status(error));
+        }
+        let mut data = response
+            .data
+            .into_json()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        serde_json::from_value(data[field].take()).map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+/// Status answering a GraphQL error, by its code
+fn status(error: &async_graphql::ServerError) -> Status {
+    let code = serde_json::to_value(error)
+        .ok()
+        .and_then(|json| serde_json::from_value::<GraphQLError>(json).ok())
+        .and_then(|error| error.extensions.code);
+    match code {
+        Some(ErrorCode::InvalidOrder | ErrorCode::InvalidInput) => {
+            Status::invalid_argument(&error.message)
+        }
+        Some(ErrorCode::UnknownOrder) => Status::not_found(&error.message),
+        _ => Status::internal(&error.message),
+    }
+}
+
+/// Change message of a revision
+fn change(revision: Revision) -> proto::Change {
+    let modified_at = revision
+        .modified
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64);
+    proto::Change {
+        hash: revision.hash,
+        modified_at,
+    }
+}
+
+#[tonic::async_trait]
+impl Systematics for SystematicsService {
+    async fn get_system(
+        &self,
+        request: Request<proto::GetSystemRequest>,
+    ) -> Result<Response<proto::SystemView>, Status> {
+        let order = request.into_inner().order;
+        let system: Option<wire::SystemView> = self
+            .query(
+                operations::get_system(),
+                serde_json::json!({ "order": order }),
+                "system",
+            )
+            .await?;
+        match system {
+            Some(system) => Ok(Response::new(system.into())),
+            None => Err(Status::not_found(format!("No system of order {}", order))),
+        }
+    }
+
+    async fn list_systems(
+        &self,
+        _request: Request<proto::ListSystemsRequest>,
+    ) -> Result<Response<proto::ListSystemsResponse>, Status> {
+        let systems: Vec<wire::SystemView> = self
+            .query(
+                operations::get_all_systems(),
+                serde_json::json!({}),
+                "allSystems",
+            )
+            .await?;
+        Ok(Response::new(proto::ListSystemsResponse {
+            systems: systems.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    type StreamChangesStream = Pin<Box<dyn Stream<Item = Result<proto::Change, Status>> + Send>>;
+
+    async fn stream_changes(
+        &self,
+        _request: Request<proto::StreamChangesRequest>,
+    ) -> Result<Response<Self::StreamChangesStream>, Status> {
+        let changes = revisions(&self.store).map(change).map(Ok);
+        Ok(Response::new(Box::pin(changes)))
+    }
+}
+
+// ============================================================================
+// Middleware types to protobuf messages
+// ============================================================================
+
+impl From<wire::Language> for proto::Language {
+    fn from(language: wire::Language) -> Self {
+        match language {
+            wire::Language::Canonical => Self::Canonical,
+            wire::Language::Energy => Self::Energy,
+            wire::Language::Values => Self::Values,
+            wire::Language::Society => Self::Society,
+            wire::Language::Custom => Self::Custom,
+            wire::Language::Hex => Self::Hex,
+            wire::Language::Name => Self::Name,
+            wire::Language::Unknown => Self::Unspecified,
+        }
+    }
+}
+
+impl From<wire::ResearchStatus> for proto::ResearchStatus {
+    fn from(status: wire::ResearchStatus) -> Self {
+        match status {
+            wire::ResearchStatus::Canonical => Self::Canonical,
+            wire::ResearchStatus::Provisional => Self::Provisional,
+            wire::ResearchStatus::NeedsResearch => Self::NeedsResearch,
+            wire::ResearchStatus::Unknown => Self::Unspecified,
+        }
+    }
+}
+
+impl From<wire::LinkType> for proto::LinkType {
+    fn from(link_type: wire::LinkType) -> Self {
+        match link_type {
+            wire::LinkType::Line => Self::Line,
+            wire::LinkType::Connective => Self::Connective,
+            wire::LinkType::Grouping => Self::Grouping,
+            wire::LinkType::Unknown => Self::Unspecified,
+        }
+    }
+}
+
+impl From<wire::LineRole> for proto::LineRole {
+    fn from(role: wire::LineRole) -> Self {
+        match role {
+            wire::LineRole::Side => Self::Side,
+            wire::LineRole::Diagonal => Self::Diagonal,
+            wire::LineRole::Circumference => Self::Circumference,
+            wire::LineRole::Chord => Self::Chord,
+            wire::LineRole::Unknown => Self::Unspecified,
+        }
+    }
+}
+
+impl From<wire::Character> for proto::Character {
+    fn from(character: wire::Character) -> Self {
+        Self {
+            id: character.id,
+            language: proto::Language::from(character.language).into(),
+            language_name: character.language_name,
+            value: character.value,
+            status: proto::ResearchStatus::from(character.status).into(),
+        }
+    }
+}
+
+impl From<wire::Term> for proto::Term {
+    fn from(term: wire::Term) -> Self {
+        Self {
+            id: term.id,
+            order: term.order,
+            position: term.position,
+            character_id: term.character_id,
+            character: term.character.map(Into::into),
+            status: proto::ResearchStatus::from(term.status).into(),
+        }
+    }
+}
+
+impl From<wire::Coordinate> for proto::Coordinate {
+    fn from(coordinate: wire::Coordinate) -> Self {
+        Self {
+            id: coordinate.id,
+            order: coordinate.order,
+            position: coordinate.position,
+            x: coordinate.x,
+            y: coordinate.y,
+            z: coordinate.z,
+        }
+    }
+}
+
+impl From<wire::Colour> for proto::Colour {
+    fn from(colour: wire::Colour) -> Self {
+        Self {
+            id: colour.id,
+            order: colour.order,
+            position: colour.position,
+            language: proto::Language::from(colour.language).into(),
+            value: colour.value,
+        }
+    }
+}
+
+impl From<wire::Link> for proto::Link {
+    fn from(link: wire::Link) -> Self {
+        Self {
+            id: link.id,
+            base_id: link.base_id,
+            target_id: link.target_id,
+            link_type: proto::LinkType::from(link.link_type).into(),
+            character_id: link.character_id,
+            tag: link.tag,
+            role: link
+                .role
+                .map_or(proto::LineRole::Unspecified, Into::into)
+                .into(),
+            order: link.order,
+            base_position: link.base_position,
+            target_position: link.target_position,
+            character: link.character.map(Into::into),
+            base_coordinate: link.base_coordinate.map(Into::into),
+            target_coordinate: link.target_coordinate.map(Into::into),
+        }
+    }
+}
+
+impl From<wire::Grouping> for proto::Grouping {
+    fn from(grouping: wire::Grouping) -> Self {
+        Self {
+            id: grouping.id,
+            order: grouping.order,
+            character_id: grouping.character_id,
+            character: grouping.character.map(Into::into),
+            member_positions: grouping.member_positions,
+            parent_id: grouping.parent_id,
+        }
+    }
+}
+
+impl From<wire::SystemView> for proto::SystemView {
+    fn from(system: wire::SystemView) -> Self {
+        Self {
+            order: system.order,
+            name: system.name,
+            is_canonical: system.is_canonical,
+            coherence: system.coherence,
+            coherences: system.coherences,
+            term_designation: system.term_designation,
+            connective_designation: system.connective_designation,
+            terms: system.terms.into_iter().map(Into::into).collect(),
+            coordinates: system.coordinates.into_iter().map(Into::into).collect(),
+            colours: system.colours.into_iter().map(Into::into).collect(),
+            connectives: system.connectives.into_iter().map(Into::into).collect(),
+            lines: system.lines.into_iter().map(Into::into).collect(),
+            groupings: system.groupings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{add_annotation, build_graph};
+    use systematics_middleware::fixtures::sample_triad;
+
+    fn service() -> SystematicsService {
+        SystematicsService::new(GraphStore::new(build_graph()))
+    }
+
+    #[tokio::test]
+    async fn test_get_system() {
+        let system = service()
+            .get_system(Request::new(proto::GetSystemRequest { order: 3 }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(system.name.as_deref(), Some("Triad"));
+        assert!(system.is_canonical);
+        assert_eq!(system.terms.len(), 3);
+        assert_eq!(
+            system.connectives[0].link_type(),
+            proto::LinkType::Connective
+        );
+
+        let unknown = service()
+            .get_system(Request::new(proto::GetSystemRequest { order: 40 }))
+            .await
+            .unwrap_err();
+        assert_eq!(unknown.code(), tonic::Code::NotFound);
+        let invalid = service()
+            .get_system(Request::new(proto::GetSystemRequest { order: 0 }))
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_system_matches_fixture() {
+        let served: wire::SystemView = service()
+            .query(
+                operations::get_system(),
+                serde_json::json!({ "order": 3 }),
+                "system",
+            )
+            .await
+            .unwrap();
+        let sample = sample_triad();
+        let ids = |links: &[wire::Link]| links.iter().map(|l| l.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&served.lines), ids(&sample.lines));
+        assert_eq!(ids(&served.connectives), ids(&sample.connectives));
+        assert_eq!(served.coordinates, sample.coordinates);
+        assert_eq!(served.colours, sample.colours);
+        assert_eq!(served.term_designation, sample.term_designation);
+        let labels = |system: &wire::SystemView| {
+            system
+                .connective_views
+                .iter()
+                .map(|c| (c.title(), c.label().map(str::to_string)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&served), labels(&sample));
+
+        let system = proto::SystemView::from(sample);
+        assert_eq!(
+            system.terms[1].character.as_ref().unwrap().value,
+            "Function"
+        );
+        assert_eq!(system.lines[0].role(), proto::LineRole::Side);
+        assert_eq!(
+            system.connectives[0].character_id.as_deref(),
+            Some("char_canonical_act1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_systems() {
+        let systems = service()
+            .list_systems(Request::new(proto::ListSystemsRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .systems;
+        let orders: Vec<i32> = systems.iter().map(|s| s.order).collect();
+        assert_eq!(orders, (1..=12).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_stream_changes() {
+        let service = service();
+        let mut changes = service
+            .stream_changes(Request::new(proto::StreamChangesRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        let first = changes.next().await.unwrap().unwrap();
+        assert_eq!(first.hash, service.store.revision().hash);
+
+        service
+            .store
+            .update(|graph| add_annotation(graph, "term_3_1", "Note", "Ana", None))
+            .unwrap();
+        let second = changes.next().await.unwrap().unwrap();
+        assert_ne!(second.hash, first.hash);
+    }
+}
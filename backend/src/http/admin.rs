@@ -0,0 +1,282 @@
+//! Administration endpoints.
+//!
+//! `POST /admin/reload` re-reads the external data files (`data_dir`),
+//! re-validates them and swaps the result into the shared graph, replaying
+//! the persisted edits, and answers with the report: entry and link counts,
+//! the validation and audit problems, and the new revision. Files that fail to
+//! load leave the graph in place and are answered `422` with a
+//! `RELOAD_FAILED` error. Workspaces keep the data they were created from.
+//!
+//! `GET /export/graph.json` downloads a backup: the whole shared graph,
+//! including user content (instances, annotations, vocabularies and custom
+//! systems). `POST /import` restores one, replacing the shared graph; the
+//! restore is recorded as an edit, so it is persisted and later edits build on
+//! it (reloaded data files no longer show through it). It answers with the
+//! same report as a reload.
+//!
+//! The routes exist only when `SYSTEMATICS_ADMIN_TOKEN` is set, and require
+//! `Authorization: Bearer <admin token>`; user tokens (see `auth`) are not
+//! accepted. Other requests are answered `401` with an `UNAUTHENTICATED` error.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use systematics_middleware::ErrorCode;
+
+use super::auth::bearer_token;
+use super::error_response;
+use crate::core::Graph;
+use crate::data::{self, audit, Edit, GraphStore};
+
+/// Environment variable holding the admin token; enables the admin routes
+pub const ADMIN_TOKEN_ENV: &str = "SYSTEMATICS_ADMIN_TOKEN";
+
+/// Largest backup accepted by `POST /import`
+pub const MAX_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Shortest admin token accepted
+pub const MIN_ADMIN_TOKEN: usize = 16;
+
+/// Error raised when the admin token is unusable
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminTokenTooShort;
+
+impl fmt::Display for AdminTokenTooShort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} must be at least {} characters",
+            ADMIN_TOKEN_ENV, MIN_ADMIN_TOKEN
+        )
+    }
+}
+
+impl std::error::Error for AdminTokenTooShort {}
+
+/// State of the admin routes: the token, and the store and data directory to reload
+#[derive(Debug, Clone)]
+pub struct Admin {
+    token: Arc<str>,
+    store: GraphStore,
+    data_dir: Option<PathBuf>,
+}
+
+impl Admin {
+    pub fn new(
+        token: &str,
+        store: GraphStore,
+        data_dir: Option<PathBuf>,
+    ) -> Result<Self, AdminTokenTooShort> {
+        if token.len() < MIN_ADMIN_TOKEN {
+            return Err(AdminTokenTooShort);
+        }
+        Ok(Self {
+            token: token.into(),
+            store,
+            data_dir,
+        })
+    }
+
+    /// Admin settings from the environment, or `None` when no token is set
+    pub fn from_env(
+        store: GraphStore,
+        data_dir: Option<PathBuf>,
+    ) -> Result<Option<Self>, AdminTokenTooShort> {
+        match std::env::var(ADMIN_TOKEN_ENV) {
+            Ok(token) if !token.is_empty() => Self::new(&token, store, data_dir).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether `presented` is the admin token, compared in constant time
+    fn accepts(&self, presented: &str) -> bool {
+        let (expected, presented) = (self.token.as_bytes(), presented.as_bytes());
+        expected.len() == presented.len()
+            && expected
+                .iter()
+                .zip(presented)
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// Router of the admin endpoints
+pub fn admin_router(admin: Admin) -> Router {
+    Router::new()
+        .route("/admin/reload", post(reload))
+        .route("/export/graph.json", get(export_graph))
+        .route(
+            "/import",
+            post(import_graph).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+        )
+        .route_layer(middleware::from_fn_with_state(admin.clone(), require_admin))
+        .with_state(admin)
+}
+
+/// Middleware admitting requests bearing the admin token
+async fn require_admin(State(admin): State<Admin>, req: Request, next: Next) -> Response {
+    let authorized = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(bearer_token)
+        .is_some_and(|token| admin.accepts(token));
+    if authorized {
+        return next.run(req).await;
+    }
+    error_response(
+        StatusCode::UNAUTHORIZED,
+        ErrorCode::Unauthenticated,
+        "Admin endpoints require the admin token",
+        None,
+        vec![(
+            header::WWW_AUTHENTICATE,
+            HeaderValue::from_static("Bearer realm=\"admin\""),
+        )],
+    )
+}
+
+/// Report of a graph swapped in by a reload or restore
+fn swap_report(store: &GraphStore, entries: usize, links: usize, problems: &[String]) -> Response {
+    Json(serde_json::json!({
+        "entries": entries,
+        "links": links,
+        "problems": problems,
+        "revision": store.revision().hash,
+    }))
+    .into_response()
+}
+
+/// Download the shared graph as JSON
+async fn export_graph(State(admin): State<Admin>) -> Response {
+    let graph = admin.store.snapshot();
+    // Serializing the whole graph blocks
+    match tokio::task::spawn_blocking(move || serde_json::to_vec(&graph)).await {
+        Ok(Ok(json)) => (
+            [
+                (header::CONTENT_TYPE, "application/json"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"graph.json\"",
+                ),
+                (header::CACHE_CONTROL, "no-store"),
+            ],
+            json,
+        )
+            .into_response(),
+        Ok(Err(e)) => internal_error(&e.to_string()),
+        Err(e) => internal_error(&e.to_string()),
+    }
+}
+
+/// Replace the shared graph with a backup
+async fn import_graph(State(admin): State<Admin>, body: Bytes) -> Response {
+    let store = admin.store.clone();
+    let restored = tokio::task::spawn_blocking(move || {
+        let graph: Graph = serde_json::from_slice(&body).map_err(|e| e.to_string())?;
+        if graph.entries.is_empty() {
+            return Err("The backup has no entries".to_string());
+        }
+        let mut problems = graph.validate();
+        problems.extend(audit(&graph));
+        let (entries, links) = (graph.entries.len(), graph.links.len());
+        let edit = Edit::Restore(Box::new(graph));
+        store.apply(edit.clone(), |graph| edit.apply(graph))?;
+        Ok((entries, links, problems))
+    })
+    .await;
+    match restored {
+        Ok(Ok((entries, links, problems))) => {
+            tracing::info!(
+                "Restored a backup: {} entries, {} links, {} problem(s)",
+                entries,
+                links,
+                problems.len()
+            );
+            swap_report(&admin.store, entries, links, &problems)
+        }
+        Ok(Err(e)) => error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InvalidInput,
+            &format!("Invalid backup: {}", e),
+            Some("Send a graph downloaded from /export/graph.json".into()),
+            Vec::new(),
+        ),
+        Err(e) => internal_error(&e.to_string()),
+    }
+}
+
+fn internal_error(message: &str) -> Response {
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        ErrorCode::Unknown,
+        message,
+        None,
+        Vec::new(),
+    )
+}
+
+/// Reload the data directory into the shared graph
+async fn reload(State(admin): State<Admin>) -> Response {
+    let Some(dir) = admin.data_dir.clone() else {
+        return error_response(
+            StatusCode::CONFLICT,
+            ErrorCode::ReloadFailed,
+            "No data directory is configured",
+            Some("Set data_dir (SYSTEMATICS_DATA_DIR) and restart".to_string()),
+            Vec::new(),
+        );
+    };
+    let store = admin.store.clone();
+    // Reading and validating the files blocks
+    let reloaded = tokio::task::spawn_blocking(move || data::reload(&dir, &store)).await;
+    match reloaded {
+        Ok(Ok(report)) => {
+            tracing::info!(
+                "Reloaded data on request: {} entries, {} links, {} problem(s)",
+                report.entries,
+                report.links,
+                report.problems.len()
+            );
+            swap_report(&admin.store, report.entries, report.links, &report.problems)
+        }
+        Ok(Err(e)) => {
+            tracing::error!("Data reload failed, keeping previous graph: {}", e);
+            error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCode::ReloadFailed,
+                &e.to_string(),
+                Some("Fix the file and reload again; the previous data is still served".into()),
+                Vec::new(),
+            )
+        }
+        Err(e) => internal_error(&e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Graph;
+
+    #[test]
+    fn test_admin_token() {
+        let store = GraphStore::new(Graph::new());
+        assert_eq!(
+            Admin::new("short", store.clone(), None).unwrap_err(),
+            AdminTokenTooShort
+        );
+        let admin = Admin::new("correct-horse-battery", store, None).unwrap();
+        assert!(admin.accepts("correct-horse-battery"));
+        assert!(!admin.accepts("correct-horse-battere"));
+        assert!(!admin.accepts("correct-horse"));
+    }
+}
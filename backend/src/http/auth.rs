@@ -0,0 +1,309 @@
+//! Optional bearer-token (JWT) authentication.
+//!
+//! When `SYSTEMATICS_JWT_ISSUER` is set, a request carrying
+//! `Authorization: Bearer <token>` must present a token from that issuer
+//! (and for `SYSTEMATICS_JWT_AUDIENCE`, when set), signed with either the
+//! shared secret `SYSTEMATICS_JWT_SECRET` (HS256) or a key from the JWKS
+//! file named by `SYSTEMATICS_JWT_JWKS` (an OIDC provider's published keys,
+//! e.g. RS256). Invalid tokens are answered `401` with an `UNAUTHENTICATED`
+//! error; requests without a token proceed anonymously.
+//!
+//! The identity of a valid token is added to the request as [`UserIdentity`],
+//! which the GraphQL resolvers read to attribute annotations and custom
+//! systems. WebSocket clients may instead send the token in the
+//! connection-init payload (see [`connection_identity`]).
+
+use std::fmt;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use systematics_middleware::ErrorCode;
+
+use super::error_response;
+
+/// Environment variable naming the accepted token issuer; enables authentication
+pub const JWT_ISSUER_ENV: &str = "SYSTEMATICS_JWT_ISSUER";
+
+/// Environment variable naming the required token audience (optional)
+pub const JWT_AUDIENCE_ENV: &str = "SYSTEMATICS_JWT_AUDIENCE";
+
+/// Environment variable holding the HS256 shared secret
+pub const JWT_SECRET_ENV: &str = "SYSTEMATICS_JWT_SECRET";
+
+/// Environment variable naming a JWKS file with the issuer's public keys
+pub const JWT_JWKS_ENV: &str = "SYSTEMATICS_JWT_JWKS";
+
+/// The authenticated user of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserIdentity {
+    /// Stable user ID (the token's `sub` claim)
+    pub subject: String,
+    /// Display name (`name`, else `preferred_username`)
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl UserIdentity {
+    /// Name to attribute content to: the display name, email or subject
+    pub fn display_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .or(self.email.as_deref())
+            .unwrap_or(&self.subject)
+    }
+}
+
+/// Error raised when authentication is misconfigured or a token is rejected
+#[derive(Debug)]
+pub enum AuthError {
+    /// An issuer is configured without a secret or JWKS
+    MissingKey,
+    Jwks(String),
+    /// The token names a key or algorithm the server does not accept
+    UnknownKey,
+    InvalidToken(jsonwebtoken::errors::Error),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingKey => write!(
+                f,
+                "{} is set, but neither {} nor {}",
+                JWT_ISSUER_ENV, JWT_SECRET_ENV, JWT_JWKS_ENV
+            ),
+            AuthError::Jwks(e) => write!(f, "Invalid JWKS: {}", e),
+            AuthError::UnknownKey => write!(f, "Token signed with an unknown key"),
+            AuthError::InvalidToken(e) => write!(f, "Invalid token: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+enum Keys {
+    Secret(DecodingKey),
+    Jwks(JwkSet),
+}
+
+/// Accepted issuer, audience and signing keys
+pub struct AuthConfig {
+    issuer: String,
+    audience: Option<String>,
+    keys: Keys,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    name: Option<String>,
+    preferred_username: Option<String>,
+    email: Option<String>,
+}
+
+impl AuthConfig {
+    /// Accept HS256 tokens signed with a shared secret
+    pub fn with_secret(issuer: &str, audience: Option<&str>, secret: &[u8]) -> Self {
+        Self::new(
+            issuer,
+            audience,
+            Keys::Secret(DecodingKey::from_secret(secret)),
+        )
+    }
+
+    /// Accept tokens signed with a key of an OIDC provider's JWKS
+    pub fn with_jwks(issuer: &str, audience: Option<&str>, jwks: JwkSet) -> Self {
+        Self::new(issuer, audience, Keys::Jwks(jwks))
+    }
+
+    fn new(issuer: &str, audience: Option<&str>, keys: Keys) -> Self {
+        Self {
+            issuer: issuer.to_string(),
+            audience: audience.map(str::to_string),
+            keys,
+        }
+    }
+
+    /// Configuration from the environment, or `None` when no issuer is set
+    pub fn from_env() -> Result<Option<Self>, AuthError> {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let Some(issuer) = var(JWT_ISSUER_ENV) else {
+            return Ok(None);
+        };
+        let audience = var(JWT_AUDIENCE_ENV);
+        if let Some(path) = var(JWT_JWKS_ENV) {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| AuthError::Jwks(format!("{}: {}", path, e)))?;
+            let jwks = serde_json::from_str(&text)
+                .map_err(|e| AuthError::Jwks(format!("{}: {}", path, e)))?;
+            return Ok(Some(Self::with_jwks(&issuer, audience.as_deref(), jwks)));
+        }
+        match var(JWT_SECRET_ENV) {
+            Some(secret) => Ok(Some(Self::with_secret(
+                &issuer,
+                audience.as_deref(),
+                secret.as_bytes(),
+            ))),
+            None => Err(AuthError::MissingKey),
+        }
+    }
+
+    /// The accepted issuer
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// Validate a token's signature, expiry, issuer and audience
+    pub fn validate(&self, token: &str) -> Result<UserIdentity, AuthError> {
+        let header = decode_header(token).map_err(AuthError::InvalidToken)?;
+        let (key, algorithm) = match &self.keys {
+            Keys::Secret(key) => (key.clone(), Algorithm::HS256),
+            Keys::Jwks(jwks) => {
+                let jwk = match &header.kid {
+                    Some(kid) => jwks.find(kid),
+                    None => jwks.keys.first(),
+                };
+                let jwk = jwk.ok_or(AuthError::UnknownKey)?;
+                // The algorithm must be asymmetric, so a public key is never used as an
+                // HMAC secret
+                if matches!(
+                    header.alg,
+                    Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+                ) {
+                    return Err(AuthError::UnknownKey);
+                }
+                let key = DecodingKey::from_jwk(jwk).map_err(AuthError::InvalidToken)?;
+                (key, header.alg)
+            }
+        };
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[&self.issuer]);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        let claims = decode::<Claims>(token, &key, &validation)
+            .map_err(AuthError::InvalidToken)?
+            .claims;
+        Ok(UserIdentity {
+            subject: claims.sub,
+            name: claims.name.or(claims.preferred_username),
+            email: claims.email,
+        })
+    }
+}
+
+/// Token of an `Authorization` value of the form `Bearer <token>`
+pub(super) fn bearer_token(value: &str) -> Option<&str> {
+    value.strip_prefix("Bearer ").map(str::trim)
+}
+
+/// Identity for a WebSocket connection, from the `Authorization` (or `authorization`)
+/// entry of its connection-init payload, e.g. `{"Authorization": "Bearer <token>"}`.
+/// A payload without one is anonymous.
+pub fn connection_identity(
+    config: &AuthConfig,
+    payload: &serde_json::Value,
+) -> Result<Option<UserIdentity>, AuthError> {
+    let value = ["Authorization", "authorization"]
+        .iter()
+        .find_map(|key| payload.get(key)?.as_str());
+    match value.and_then(bearer_token) {
+        Some(token) => config.validate(token).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Middleware validating bearer tokens and adding the [`UserIdentity`] to the request
+pub async fn authenticate(
+    State(config): State<Arc<AuthConfig>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(bearer_token);
+    let Some(token) = token else {
+        return next.run(req).await;
+    };
+    match config.validate(token) {
+        Ok(identity) => {
+            req.extensions_mut().insert(identity);
+            next.run(req).await
+        }
+        Err(e) => {
+            tracing::debug!("Rejected token: {}", e);
+            error_response(
+                StatusCode::UNAUTHORIZED,
+                ErrorCode::Unauthenticated,
+                &e.to_string(),
+                Some("Sign in again, or send the request without a token".to_string()),
+                vec![(
+                    header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Bearer error=\"invalid_token\""),
+                )],
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    const SECRET: &[u8] = b"study-group-secret";
+
+    fn token(issuer: &str, expires: u64) -> String {
+        let claims = serde_json::json!({
+            "sub": "user-1",
+            "preferred_username": "ana",
+            "iss": issuer,
+            "aud": "systematics",
+            "exp": expires,
+        });
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_token() {
+        let config = AuthConfig::with_secret("https://id.example", Some("systematics"), SECRET);
+        let later = jsonwebtoken::get_current_timestamp() + 600;
+
+        let identity = config
+            .validate(&token("https://id.example", later))
+            .unwrap();
+        assert_eq!(identity.subject, "user-1");
+        assert_eq!(identity.display_name(), "ana");
+
+        // Wrong issuer, expired, or signed with another secret
+        assert!(config
+            .validate(&token("https://evil.example", later))
+            .is_err());
+        assert!(config.validate(&token("https://id.example", 1)).is_err());
+        let other = AuthConfig::with_secret("https://id.example", None, b"other");
+        assert!(other.validate(&token("https://id.example", later)).is_err());
+
+        // WebSocket connection-init payloads
+        let payload = serde_json::json!({
+            "Authorization": format!("Bearer {}", token("https://id.example", later)),
+        });
+        let identity = connection_identity(&config, &payload).unwrap();
+        assert_eq!(identity.unwrap().subject, "user-1");
+        let anonymous = connection_identity(&config, &serde_json::json!({})).unwrap();
+        assert!(anonymous.is_none());
+    }
+}
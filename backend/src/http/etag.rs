@@ -0,0 +1,132 @@
+//! Conditional GET for query responses.
+//!
+//! A GET response is determined by its URL, the served graph and the schema
+//! version, so it is tagged with an `ETag` built from the graph's content hash
+//! and the schema version, and a `Last-Modified` time of the graph's last
+//! change. A request whose `If-None-Match` (or, failing that,
+//! `If-Modified-Since`) still matches is answered `304 Not Modified` without
+//! executing the query.
+//!
+//! Requests served from a draft session or a workspace are validated against
+//! its graph instead of the shared one.
+//!
+//! Responses marked `no-cache`, `no-store` or `private`, and requests with an
+//! `Authorization` header (whose responses may be user-specific), are left
+//! untagged.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::{Session, Workspace};
+use crate::data::{GraphStore, Revision};
+use crate::graphql::SCHEMA_VERSION;
+
+/// Entity tag of the responses served from a revision
+pub fn entity_tag(revision: &Revision) -> String {
+    format!("\"{}-v{}\"", &revision.hash[..16], SCHEMA_VERSION)
+}
+
+/// Whether the request's validators match the current entity tag or modification time
+fn not_modified(headers: &HeaderMap, etag: &str, modified: SystemTime) -> bool {
+    if let Some(tags) = headers.get(header::IF_NONE_MATCH) {
+        let tags = tags.to_str().unwrap_or_default();
+        return tags
+            .split(',')
+            .map(|t| t.trim().trim_start_matches("W/"))
+            .any(|t| t == etag || t == "*");
+    }
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| httpdate::parse_http_date(v.to_str().ok()?).ok())
+        .is_some_and(|since| whole_seconds(modified) <= since)
+}
+
+/// HTTP dates have one-second resolution
+fn whole_seconds(time: SystemTime) -> SystemTime {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Whether shared caches may store and revalidate a response
+fn cacheable(headers: &HeaderMap) -> bool {
+    let cache_control = headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    !["no-cache", "no-store", "private"]
+        .iter()
+        .any(|directive| cache_control.contains(directive))
+}
+
+/// Middleware tagging GET responses and answering matching revalidations with 304
+pub async fn conditional_get(
+    State(store): State<GraphStore>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.method() != Method::GET || req.headers().contains_key(header::AUTHORIZATION) {
+        return next.run(req).await;
+    }
+    let extensions = req.extensions();
+    let revision = match (extensions.get::<Session>(), extensions.get::<Workspace>()) {
+        (Some(session), _) => session.store.revision(),
+        (None, Some(workspace)) => workspace.store.revision(),
+        (None, None) => store.revision(),
+    };
+    let etag = entity_tag(&revision);
+    let validators = [
+        (
+            header::ETAG,
+            HeaderValue::from_str(&etag).expect("hex entity tag"),
+        ),
+        (
+            header::LAST_MODIFIED,
+            HeaderValue::from_str(&httpdate::fmt_http_date(revision.modified)).expect("HTTP date"),
+        ),
+    ];
+
+    if not_modified(req.headers(), &etag, revision.modified) {
+        return (StatusCode::NOT_MODIFIED, validators).into_response();
+    }
+    let mut response = next.run(req).await;
+    if response.status() == StatusCode::OK && cacheable(response.headers()) {
+        response.headers_mut().extend(validators);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_modified() {
+        let modified = UNIX_EPOCH + Duration::from_millis(1_700_000_000_500);
+        let etag = "\"0123456789abcdef-v2\"";
+
+        let mut headers = HeaderMap::new();
+        assert!(!not_modified(&headers, etag, modified));
+
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Tue, 14 Nov 2023 22:13:20 GMT"),
+        );
+        assert!(not_modified(&headers, etag, modified));
+
+        // If-None-Match takes precedence over If-Modified-Since
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""));
+        assert!(!not_modified(&headers, etag, modified));
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"stale\", W/\"0123456789abcdef-v2\""),
+        );
+        assert!(not_modified(&headers, etag, modified));
+    }
+}
@@ -0,0 +1,77 @@
+//! Frontend embedded in the binary (`embed` feature).
+//!
+//! The built frontend (`frontend/dist`, built with `trunk build --release`
+//! before the backend) is compiled into release builds, so a single file can
+//! be deployed and run from any directory. Debug builds read the same files
+//! from disk at request time.
+//!
+//! Paths without a file fall back to `index.html` for client-side routing.
+//! Files are tagged with their content hash. `index.html` is revalidated on
+//! every load so deployments take effect at once; other files are cached for
+//! an hour.
+
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "../frontend/dist"]
+#[allow_missing = true]
+struct Dist;
+
+/// Page served for paths without a file
+const INDEX: &str = "index.html";
+
+/// Whether the frontend was embedded (it was built before the backend)
+pub fn embedded_frontend() -> bool {
+    Dist::get(INDEX).is_some()
+}
+
+/// Fallback handler serving the embedded frontend
+pub async fn serve_embedded(uri: Uri, headers: HeaderMap) -> Response {
+    let path = uri.path().trim_start_matches('/');
+    let (path, file) = match Dist::get(path) {
+        Some(file) if !path.is_empty() => (path, file),
+        _ => match Dist::get(INDEX) {
+            Some(file) => (INDEX, file),
+            None => return StatusCode::NOT_FOUND.into_response(),
+        },
+    };
+
+    let hash = file.metadata.sha256_hash();
+    let etag = format!(
+        "\"{}\"",
+        hash[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    );
+    let cache_control = if path == INDEX {
+        "no-cache"
+    } else {
+        "public, max-age=3600"
+    };
+    let validators = [
+        (
+            header::ETAG,
+            HeaderValue::from_str(&etag).expect("hex entity tag"),
+        ),
+        (
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(cache_control),
+        ),
+    ];
+    let matches = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag));
+    if matches {
+        return (StatusCode::NOT_MODIFIED, validators).into_response();
+    }
+    (
+        [(header::CONTENT_TYPE, file.metadata.mimetype().to_string())],
+        validators,
+        file.data,
+    )
+        .into_response()
+}
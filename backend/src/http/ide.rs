@@ -0,0 +1,108 @@
+//! GraphiQL, the in-browser GraphQL IDE.
+//!
+//! Served at `ide_path` (`/graphiql` by default) while `ide` is enabled, as it
+//! is in development builds. The page sends queries and mutations to
+//! `/graphql` and subscriptions over `/graphql/ws`, and opens with a tab per
+//! canonical system querying its terms and connectives, as starting points.
+//! Its scripts load from unpkg, pinned to GraphiQL 2.
+
+use crate::core::Order;
+
+/// GraphiQL release loaded by the page
+const GRAPHIQL_VERSION: &str = "2.4.7";
+
+/// Preset tab querying the system of `order`
+fn preset(order: &Order) -> serde_json::Value {
+    let name = order.standard_name().unwrap_or("System");
+    let query = format!(
+        r#"# The {name} (order {order}): its terms and connectives
+query {name} {{
+  system(order: {order}) {{
+    name
+    coherence
+    terms {{
+      position
+      character {{ value }}
+    }}
+    connectives {{
+      basePosition
+      targetPosition
+      character {{ value }}
+    }}
+  }}
+}}
+"#,
+        name = name,
+        order = order.value
+    );
+    serde_json::json!({ "query": query })
+}
+
+/// GraphiQL page sending operations to `endpoint` and subscriptions to
+/// `subscription_endpoint` (paths on the page's origin)
+pub fn graphiql_page(endpoint: &str, subscription_endpoint: &str) -> String {
+    let tabs: Vec<serde_json::Value> = (1..=12).map(|n| preset(&Order::new(n))).collect();
+    // Embedded in a script element, which must not be closed early
+    let json = |value: serde_json::Value| value.to_string().replace("</", "<\\/");
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Systematics GraphiQL</title>
+    <style>
+      body {{ height: 100%; margin: 0; width: 100%; overflow: hidden; }}
+      #graphiql {{ height: 100vh; }}
+    </style>
+    <script crossorigin src="https://unpkg.com/react@18/umd/react.production.min.js"></script>
+    <script crossorigin src="https://unpkg.com/react-dom@18/umd/react-dom.production.min.js"></script>
+    <link rel="stylesheet" href="https://unpkg.com/graphiql@{version}/graphiql.min.css" />
+  </head>
+  <body>
+    <div id="graphiql">Loading…</div>
+    <script crossorigin src="https://unpkg.com/graphiql@{version}/graphiql.min.js"></script>
+    <script>
+      const url = (path, socket) => {{
+        const url = new URL(path, window.location.origin);
+        if (socket) {{
+          url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+        }}
+        return url.toString();
+      }};
+      const fetcher = GraphiQL.createFetcher({{
+        url: url({endpoint}),
+        subscriptionUrl: url({subscription_endpoint}, true),
+      }});
+      ReactDOM.createRoot(document.getElementById('graphiql')).render(
+        React.createElement(GraphiQL, {{
+          fetcher,
+          defaultTabs: {tabs},
+          defaultEditorToolsVisibility: true,
+        }}),
+      );
+    </script>
+  </body>
+</html>
+"#,
+        version = GRAPHIQL_VERSION,
+        endpoint = json(endpoint.into()),
+        subscription_endpoint = json(subscription_endpoint.into()),
+        tabs = json(tabs.into()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphiql_page() {
+        let page = graphiql_page("/graphql", "/graphql/ws");
+        assert!(page.contains("graphiql@2.4.7/graphiql.min.js"));
+        assert!(page.contains(r#"url("/graphql/ws", true)"#));
+        assert!(page.contains("query Triad {"));
+        assert!(page.contains("system(order: 12)"));
+    }
+}
@@ -0,0 +1,80 @@
+//! HTTP layers wrapped around the GraphQL routes: bearer-token authentication
+//! (`auth`), conditional GET (`etag`), per-client rate limiting
+//! (`ratelimit`), workspace selection (`workspace`) and draft sessions
+//! (`session`), and around the whole app: request IDs and access logs
+//! (`request_id`). With the `embed` feature, `frontend` serves the frontend
+//! compiled into the binary. `admin` serves the token-protected admin routes,
+//! and `ide` the GraphiQL page; `negotiate` picks the format of a resource
+//! from the `Accept` header.
+//!
+//! Requests rejected by a layer are answered with a GraphQL-shaped error
+//! body, so clients read `extensions.code` as they would for any other error.
+
+pub mod admin;
+pub mod auth;
+pub mod etag;
+#[cfg(feature = "embed")]
+pub mod frontend;
+pub mod ide;
+pub mod negotiate;
+pub mod ratelimit;
+pub mod request_id;
+pub mod session;
+pub mod workspace;
+
+pub use admin::{admin_router, Admin, ADMIN_TOKEN_ENV};
+pub use auth::{authenticate, connection_identity, AuthConfig, AuthError, UserIdentity};
+pub use etag::{conditional_get, entity_tag};
+#[cfg(feature = "embed")]
+pub use frontend::{embedded_frontend, serve_embedded};
+pub use ide::graphiql_page;
+pub use negotiate::negotiate;
+pub use ratelimit::{rate_limit, ClientKeys, RateLimit, RateLimiter, API_KEY_HEADER};
+pub use request_id::{request_id, tag_errors, with_request_ids, REQUEST_ID_HEADER};
+pub use session::{select_session, Session, SESSION_HEADER, SESSION_PARAM};
+pub use workspace::{select_workspace, Workspace, WORKSPACE_HEADER, WORKSPACE_PARAM};
+
+use std::collections::HashMap;
+
+use axum::extract::Query;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use systematics_middleware::ErrorCode;
+
+/// Response with `status` and a single coded error, plus any extra headers
+fn error_response(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+    hint: Option<String>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+) -> Response {
+    let mut extensions = serde_json::json!({ "code": code.as_str() });
+    if let Some(hint) = hint {
+        extensions["hint"] = hint.into();
+    }
+    let body = serde_json::json!({
+        "errors": [{ "message": message, "extensions": extensions }],
+    });
+    let mut response = (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        body.to_string(),
+    )
+        .into_response();
+    response.headers_mut().extend(headers);
+    response
+}
+
+/// Value selected by the query parameter `param` or else the header `name`, if any
+fn selector(uri: &Uri, headers: &HeaderMap, param: &str, name: &HeaderName) -> Option<String> {
+    let from_query = Query::<HashMap<String, String>>::try_from_uri(uri)
+        .ok()
+        .and_then(|Query(mut params)| params.remove(param));
+    from_query.or_else(|| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+    })
+}
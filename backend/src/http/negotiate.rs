@@ -0,0 +1,108 @@
+//! Content negotiation.
+//!
+//! A resource available in several media types is served in the one the
+//! `Accept` header prefers. Each offered type takes the `q` value (1 by
+//! default) of the most specific range matching it (`text/turtle`, then
+//! `text/*`, then `*/*`); the highest wins, ties going to the type whose range
+//! comes first, then to the type offered first. Without an `Accept` header the
+//! first offered type is served.
+
+/// A media range of an `Accept` header, with its weight
+struct MediaRange<'a> {
+    range: &'a str,
+    q: f32,
+}
+
+impl MediaRange<'_> {
+    /// How specifically the range matches `media_type` (higher is more
+    /// specific), or `None` if it does not
+    fn specificity(&self, media_type: &str) -> Option<u8> {
+        let (kind, _) = media_type.split_once('/')?;
+        if self.range.eq_ignore_ascii_case(media_type) {
+            Some(2)
+        } else if self
+            .range
+            .strip_suffix("/*")
+            .is_some_and(|range| range.eq_ignore_ascii_case(kind))
+        {
+            Some(1)
+        } else if self.range == "*/*" {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+fn media_ranges(accept: &str) -> Vec<MediaRange<'_>> {
+    accept
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';').map(str::trim);
+            let range = params.next().filter(|range| !range.is_empty())?;
+            let q = params
+                .filter_map(|param| param.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some(MediaRange { range, q })
+        })
+        .collect()
+}
+
+/// The offered media type `accept` prefers, or `None` when it accepts none
+pub fn negotiate<'a>(accept: Option<&str>, offered: &[&'a str]) -> Option<&'a str> {
+    let ranges = match accept.map(str::trim) {
+        Some(accept) if !accept.is_empty() => media_ranges(accept),
+        _ => return offered.first().copied(),
+    };
+    offered
+        .iter()
+        .enumerate()
+        .filter_map(|(offer, &media_type)| {
+            // The most specific matching range decides; the first of equals
+            let (index, range, _) = ranges
+                .iter()
+                .enumerate()
+                .filter_map(|(index, range)| Some((index, range, range.specificity(media_type)?)))
+                .max_by_key(|&(index, _, specificity)| (specificity, std::cmp::Reverse(index)))?;
+            (range.q > 0.0).then_some((media_type, range.q, index, offer))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.2.cmp(&a.2)).then(b.3.cmp(&a.3)))
+        .map(|(media_type, ..)| media_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFERED: [&str; 3] = ["application/json", "text/turtle", "image/svg+xml"];
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(None, &OFFERED), Some("application/json"));
+        assert_eq!(negotiate(Some("*/*"), &OFFERED), Some("application/json"));
+        assert_eq!(
+            negotiate(Some("text/turtle"), &OFFERED),
+            Some("text/turtle")
+        );
+        assert_eq!(
+            negotiate(Some("image/*, */*;q=0.1"), &OFFERED),
+            Some("image/svg+xml")
+        );
+        // Weights, then the client's order
+        assert_eq!(
+            negotiate(Some("application/json;q=0.5, text/turtle"), &OFFERED),
+            Some("text/turtle")
+        );
+        assert_eq!(
+            negotiate(Some("image/svg+xml, text/turtle"), &OFFERED),
+            Some("image/svg+xml")
+        );
+        // A specific range overrides a wildcard, including to refuse a type
+        assert_eq!(
+            negotiate(Some("*/*, application/json;q=0"), &OFFERED),
+            Some("text/turtle")
+        );
+        assert_eq!(negotiate(Some("text/html"), &OFFERED), None);
+    }
+}
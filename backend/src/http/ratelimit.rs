@@ -0,0 +1,244 @@
+//! Per-client rate limiting.
+//!
+//! Each client has a token bucket holding up to `burst` requests, refilled at
+//! `per_second`. Clients are keyed by the `X-Api-Key` header when it carries
+//! one of the configured API keys, otherwise by IP address. Behind
+//! `trusted_proxies` proxies (Shuttle has one) the IP is that many entries from
+//! the right of `X-Forwarded-For`, the ones the proxies appended; entries to
+//! their left are sent by the client and not trusted. With no trusted proxies
+//! it is the peer address. A request arriving with an empty bucket is answered
+//! `429 Too Many Requests` with a `Retry-After` header and a `RATE_LIMITED`
+//! error.
+//!
+//! Limits come from the `rate_limit` configuration (see `config`).
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use systematics_middleware::ErrorCode;
+
+use super::error_response;
+
+/// Request header identifying a client by API key
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Tracked clients above which idle (full) buckets are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Burst and steady limits per client
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub burst: u32,
+    pub per_second: f64,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            burst: 30,
+            per_second: 5.0,
+        }
+    }
+}
+
+/// How clients are told apart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientKeys {
+    /// API keys that identify a client; other keys are ignored
+    pub api_keys: HashSet<String>,
+    /// Proxies in front of the server, each appending to `X-Forwarded-For`
+    pub trusted_proxies: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets by client key. Cloning shares the buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    keys: Arc<ClientKeys>,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            keys: Arc::default(),
+            buckets: Arc::default(),
+        }
+    }
+
+    /// Tell clients apart by `keys` rather than by peer address only
+    pub fn with_client_keys(mut self, keys: ClientKeys) -> Self {
+        self.keys = Arc::new(keys);
+        self
+    }
+
+    /// Take a token for `client` at time `now`, or return how long until one is available
+    pub fn check(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let RateLimit { burst, per_second } = self.limit;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|p| p.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|_, b| refill(b, now, self.limit) < burst as f64);
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: burst as f64,
+            updated: now,
+        });
+        bucket.tokens = refill(bucket, now, self.limit);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Tokens in a bucket at `now`, capped at the burst size
+fn refill(bucket: &Bucket, now: Instant, limit: RateLimit) -> f64 {
+    let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+    (bucket.tokens + elapsed * limit.per_second).min(limit.burst as f64)
+}
+
+/// Key identifying the client of a request
+fn client_key(headers: &HeaderMap, peer: Option<SocketAddr>, keys: &ClientKeys) -> String {
+    if let Some(key) = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+        if keys.api_keys.contains(key) {
+            return format!("key:{}", key);
+        }
+    }
+    let forwarded = || {
+        let value = headers.get("x-forwarded-for")?.to_str().ok()?;
+        let hops: Vec<&str> = value.split(',').map(str::trim).collect();
+        // Fewer entries than proxies: the left-most is still a proxy's
+        let index = hops.len().saturating_sub(keys.trusted_proxies);
+        Some(hops[index].to_string())
+    };
+    let ip = match keys.trusted_proxies {
+        0 => None,
+        _ => forwarded(),
+    };
+    match (ip, peer) {
+        (Some(ip), _) => format!("ip:{}", ip),
+        (None, Some(addr)) => format!("ip:{}", addr.ip()),
+        (None, None) => "ip:".to_string(),
+    }
+}
+
+/// Middleware rejecting requests from clients over their limit
+pub async fn rate_limit(State(limiter): State<RateLimiter>, req: Request, next: Next) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    let client = client_key(req.headers(), peer, &limiter.keys);
+    match limiter.check(&client, Instant::now()) {
+        Ok(()) => next.run(req).await,
+        Err(wait) => {
+            let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+            tracing::debug!(client, retry_after, "Rate limited");
+            error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorCode::RateLimited,
+                "Too many requests",
+                Some(format!("Retry after {} seconds", retry_after)),
+                vec![(header::RETRY_AFTER, HeaderValue::from(retry_after))],
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_refills_at_steady_rate() {
+        let limiter = RateLimiter::new(RateLimit {
+            burst: 2,
+            per_second: 1.0,
+        });
+        let start = Instant::now();
+
+        assert!(limiter.check("a", start).is_ok());
+        assert!(limiter.check("a", start).is_ok());
+        assert_eq!(limiter.check("a", start), Err(Duration::from_secs(1)));
+
+        // Clients have separate buckets
+        assert!(limiter.check("b", start).is_ok());
+
+        // Half a second refills half a token
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.check("a", later), Err(Duration::from_millis(500)));
+        assert!(limiter
+            .check("a", later + Duration::from_millis(500))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_client_key() {
+        let keys = ClientKeys {
+            api_keys: HashSet::from(["secret".to_string()]),
+            trusted_proxies: 1,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        assert_eq!(client_key(&headers, None, &keys), "ip:10.0.0.1");
+
+        let behind_two = ClientKeys {
+            trusted_proxies: 2,
+            ..keys.clone()
+        };
+        assert_eq!(client_key(&headers, None, &behind_two), "ip:203.0.113.7");
+
+        // With no trusted proxy, the header is the client's own claim
+        let peer: SocketAddr = "192.0.2.1:5000".parse().unwrap();
+        let direct = ClientKeys::default();
+        assert_eq!(client_key(&headers, Some(peer), &direct), "ip:192.0.2.1");
+
+        headers.insert(API_KEY_HEADER, "secret".parse().unwrap());
+        assert_eq!(client_key(&headers, Some(peer), &keys), "key:secret");
+        assert_eq!(client_key(&headers, Some(peer), &direct), "ip:192.0.2.1");
+    }
+
+    #[test]
+    fn test_rotating_keys_and_spoofed_addresses_share_a_bucket() {
+        let keys = ClientKeys {
+            api_keys: HashSet::from(["secret".to_string()]),
+            trusted_proxies: 1,
+        };
+        let limiter = RateLimiter::new(RateLimit {
+            burst: 2,
+            per_second: 1.0,
+        })
+        .with_client_keys(keys.clone());
+        let now = Instant::now();
+
+        // The proxy appends the client's address after whatever it claims
+        let request = |i: usize| {
+            let mut headers = HeaderMap::new();
+            headers.insert(API_KEY_HEADER, format!("key-{}", i).parse().unwrap());
+            headers.insert(
+                "x-forwarded-for",
+                format!("198.51.100.{}, 203.0.113.7", i).parse().unwrap(),
+            );
+            client_key(&headers, None, &keys)
+        };
+        assert!(limiter.check(&request(1), now).is_ok());
+        assert!(limiter.check(&request(2), now).is_ok());
+        assert!(limiter.check(&request(3), now).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Request IDs and access logs.
+//!
+//! Every request carries an `X-Request-Id`: the client's, when it sends one,
+//! or else a generated UUID. The ID is echoed on the response, recorded on the
+//! request's tracing span (so every line logged while serving it carries it),
+//! and added to GraphQL errors as `extensions.requestId`, so a report from a
+//! user can be matched with the server's logs.
+//!
+//! Each response is logged once, with its status and latency, as the access
+//! log; with the `json` log format these are structured fields.
+
+use std::time::Duration;
+
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderName};
+use axum::response::Response;
+use axum::Router;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use tracing::Span;
+
+/// Header carrying the request ID
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The request ID in `headers`, if any
+pub fn request_id(headers: &HeaderMap) -> Option<&str> {
+    headers.get(REQUEST_ID_HEADER).and_then(|v| v.to_str().ok())
+}
+
+/// Add the request ID to the extensions of each error of a GraphQL response
+pub fn tag_errors(response: &mut async_graphql::Response, id: &str) {
+    for error in &mut response.errors {
+        error
+            .extensions
+            .get_or_insert_with(Default::default)
+            .set("requestId", id);
+    }
+}
+
+/// Span of a request, named after its method and path
+fn request_span(req: &Request) -> Span {
+    tracing::info_span!(
+        "request",
+        method = %req.method(),
+        path = %req.uri().path(),
+        request_id = request_id(req.headers()).unwrap_or_default(),
+    )
+}
+
+/// Access log line of a response
+fn log_response(response: &Response, latency: Duration, _span: &Span) {
+    tracing::info!(
+        status = response.status().as_u16(),
+        latency_ms = latency.as_millis() as u64,
+        "Served"
+    );
+}
+
+/// Assign request IDs to the requests of `router`, and log their responses
+pub fn with_request_ids(router: Router) -> Router {
+    let trace = TraceLayer::new_for_http()
+        .make_span_with(request_span)
+        .on_request(())
+        .on_response(log_response);
+    // Outermost last: the ID is set before the span is made, and copied to the
+    // response after it is logged
+    router
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(trace)
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::ServerError;
+
+    #[test]
+    fn test_tag_errors() {
+        let mut response = async_graphql::Response::from_errors(vec![
+            ServerError::new("First", None),
+            ServerError::new("Second", None),
+        ]);
+        tag_errors(&mut response, "4f1c");
+        let json = serde_json::to_value(&response).unwrap();
+        for error in json["errors"].as_array().unwrap() {
+            assert_eq!(error["extensions"]["requestId"], "4f1c");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Draft session selection.
+//!
+//! A request names a draft session, started with the `startSession` mutation,
+//! with the `session` query parameter or the `X-Session` header; the parameter
+//! wins when both are given. The session's draft store is added to the request
+//! as a [`Session`] extension, and takes precedence over the shared graph and
+//! any workspace: queries read the draft and mutations edit it, until
+//! `commitSession` or `discardSession` ends the session.
+//!
+//! An unknown (committed, discarded or expired) session is answered with a
+//! `SESSION_UNAVAILABLE` error. Every response varies on the header, so caches
+//! keep drafts apart from the graph they were started from.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::Response;
+use systematics_middleware::ErrorCode;
+
+use super::{error_response, selector};
+use crate::data::{GraphStore, SessionRegistry};
+
+/// Request header naming a draft session
+pub const SESSION_HEADER: HeaderName = HeaderName::from_static("x-session");
+
+/// Query parameter naming a draft session
+pub const SESSION_PARAM: &str = "session";
+
+/// The draft session a request is served from
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub store: GraphStore,
+}
+
+/// ID of the session selected by the query string or headers, if any
+fn session_id(uri: &Uri, headers: &HeaderMap) -> Option<String> {
+    selector(uri, headers, SESSION_PARAM, &SESSION_HEADER).filter(|id| !id.is_empty())
+}
+
+/// Middleware adding the selected [`Session`] to the request
+pub async fn select_session(
+    State(registry): State<SessionRegistry>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if let Some(id) = session_id(req.uri(), req.headers()) {
+        match registry.draft(&id) {
+            Ok(store) => {
+                req.extensions_mut().insert(Session { id, store });
+            }
+            Err(e) => {
+                return error_response(
+                    StatusCode::NOT_FOUND,
+                    ErrorCode::SessionUnavailable,
+                    &e.to_string(),
+                    Some("Start a new session with startSession".into()),
+                    Vec::new(),
+                );
+            }
+        }
+    }
+    let mut response = next.run(req).await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_name(SESSION_HEADER));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_id() {
+        let mut headers = HeaderMap::new();
+        let plain = Uri::from_static("/graphql?query=%7B+a+%7D");
+        let selected = Uri::from_static("/graphql?session=0f3a&query=%7B+a+%7D");
+        assert_eq!(session_id(&plain, &headers), None);
+
+        headers.insert(SESSION_HEADER, HeaderValue::from_static(""));
+        assert_eq!(session_id(&plain, &headers), None);
+        headers.insert(SESSION_HEADER, HeaderValue::from_static("9bd2"));
+        assert_eq!(session_id(&plain, &headers).as_deref(), Some("9bd2"));
+        assert_eq!(session_id(&selected, &headers).as_deref(), Some("0f3a"));
+    }
+}
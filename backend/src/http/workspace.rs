@@ -0,0 +1,96 @@
+//! Workspace selection.
+//!
+//! A request names a workspace with the `workspace` query parameter (e.g.
+//! `/graphql?workspace=study-group`) or the `X-Workspace` header; the
+//! parameter wins when both are given. The workspace is opened (created on
+//! first use) and added to the request as a [`Workspace`] extension, whose
+//! store the handlers and `conditional_get` use instead of the shared graph.
+//! Requests naming none are served from the shared graph.
+//!
+//! An invalid name, or a new workspace beyond the registry's limit, is
+//! answered with a `WORKSPACE_UNAVAILABLE` error. Every response varies on the
+//! header, so caches keep the workspaces' responses apart.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::Response;
+use systematics_middleware::ErrorCode;
+
+use super::{error_response, selector};
+use crate::data::{GraphStore, WorkspaceError, WorkspaceRegistry};
+
+/// Request header naming a workspace
+pub const WORKSPACE_HEADER: HeaderName = HeaderName::from_static("x-workspace");
+
+/// Query parameter naming a workspace
+pub const WORKSPACE_PARAM: &str = "workspace";
+
+/// The workspace a request is served from
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub name: String,
+    pub store: GraphStore,
+}
+
+/// Name of the workspace selected by the query string or headers, if any
+fn workspace_name(uri: &Uri, headers: &HeaderMap) -> Option<String> {
+    selector(uri, headers, WORKSPACE_PARAM, &WORKSPACE_HEADER)
+}
+
+/// Middleware adding the selected [`Workspace`] to the request
+pub async fn select_workspace(
+    State(registry): State<WorkspaceRegistry>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if let Some(name) = workspace_name(req.uri(), req.headers()) {
+        match registry.open(&name) {
+            Ok(store) => {
+                req.extensions_mut().insert(Workspace { name, store });
+            }
+            Err(e) => {
+                let (status, hint) = match e {
+                    WorkspaceError::InvalidName(_) => (StatusCode::BAD_REQUEST, None),
+                    WorkspaceError::LimitReached(_) => (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        Some("Use an existing workspace, or retry once idle ones expire".into()),
+                    ),
+                };
+                return error_response(
+                    status,
+                    ErrorCode::WorkspaceUnavailable,
+                    &e.to_string(),
+                    hint,
+                    Vec::new(),
+                );
+            }
+        }
+    }
+    let mut response = next.run(req).await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_name(WORKSPACE_HEADER));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_name() {
+        let mut headers = HeaderMap::new();
+        let plain = Uri::from_static("/graphql?query=%7B+a+%7D");
+        let selected = Uri::from_static("/graphql?query=%7B+a+%7D&workspace=study-group");
+        assert_eq!(workspace_name(&plain, &headers), None);
+
+        headers.insert(WORKSPACE_HEADER, HeaderValue::from_static(" seminar "));
+        assert_eq!(workspace_name(&plain, &headers).as_deref(), Some("seminar"));
+        // The query parameter wins over the header
+        assert_eq!(
+            workspace_name(&selected, &headers).as_deref(),
+            Some("study-group")
+        );
+    }
+}
@@ -3,8 +3,16 @@
 //! This crate provides a GraphQL API for exploring systematic structures
 //! from orders 1-12 (Monad through Dodecad).
 
+pub mod config;
 pub mod core;
 pub mod data;
 pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod http;
+pub mod webhook;
 
-pub use graphql::{create_schema, SystematicsSchema};
+pub use graphql::{
+    create_schema, create_schema_with_store, schema_sdl, SchemaOptions, SystematicsSchema,
+    SCHEMA_VERSION,
+};
@@ -1,106 +1,1019 @@
-use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
+use async_graphql::Data;
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::{
-    extract::State,
-    response::{Html, IntoResponse},
+    extract::{self, ws::WebSocketUpgrade, Extension, FromRef, Query, RawQuery, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware,
+    response::{Html, IntoResponse, Redirect, Response},
     routing::get,
     Router,
 };
-use systematics_backend::create_schema;
-use tower_http::cors::{Any, CorsLayer};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use systematics_backend::config::{Config, LogFormat};
+use systematics_backend::core::Graph;
+use systematics_backend::data::{
+    self, ExportError, ExportFormat, GraphStore, RenderError, RenderOptions, SessionRegistry,
+    WorkspaceRegistry,
+};
+use systematics_backend::graphql::{GetRequest, TraceRequested, TRACE_HEADER};
+#[cfg(feature = "grpc")]
+use systematics_backend::grpc::SystematicsService;
+#[cfg(feature = "embed")]
+use systematics_backend::http;
+use systematics_backend::http::{
+    admin_router, authenticate, conditional_get, connection_identity, graphiql_page, negotiate,
+    rate_limit, request_id, select_session, select_workspace, tag_errors, with_request_ids, Admin,
+    AuthConfig, RateLimiter, Session, UserIdentity, Workspace, REQUEST_ID_HEADER,
+};
+use systematics_backend::webhook::Webhooks;
+use systematics_backend::{
+    create_schema_with_store, SchemaOptions, SystematicsSchema, SCHEMA_VERSION,
+};
+use systematics_middleware::binary::BinaryFormat;
+use systematics_middleware::{operations, ErrorCode, GraphQLError, GraphView, SystemView};
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer};
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[cfg(not(feature = "shuttle"))]
+use clap::Parser;
+use std::future::IntoFuture;
 use std::net::SocketAddr;
+#[cfg(not(feature = "shuttle"))]
+use std::path::Path;
+#[cfg(not(feature = "shuttle"))]
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
 
 use tower_http::services::{ServeDir, ServeFile};
 
+#[cfg(not(feature = "shuttle"))]
+mod cli;
+
+#[cfg(not(feature = "shuttle"))]
+use cli::{Cli, Command};
+
+/// Response header carrying the schema version, so clients can detect upgrades
+const SCHEMA_VERSION_HEADER: HeaderName = HeaderName::from_static("x-schema-version");
+
+/// Router state: the schema, store and draft sessions, where the IDE is served (if
+/// it is), and the token settings (for WebSocket connections)
+#[derive(Clone)]
+struct ApiState {
+    schema: SystematicsSchema,
+    /// Schema the REST endpoints read wire types with, free of the public
+    /// schema's restrictions (allow-list, persisted queries)
+    reader: SystematicsSchema,
+    store: GraphStore,
+    sessions: SessionRegistry,
+    ide_path: Option<String>,
+    auth: Option<Arc<AuthConfig>>,
+}
+
+impl FromRef<ApiState> for SystematicsSchema {
+    fn from_ref(state: &ApiState) -> Self {
+        state.schema.clone()
+    }
+}
+
+impl FromRef<ApiState> for SessionRegistry {
+    fn from_ref(state: &ApiState) -> Self {
+        state.sessions.clone()
+    }
+}
+
+/// Where a request is served from: its draft session and workspace, if any
+struct Selection {
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+}
+
+impl Selection {
+    /// Add the selected stores to request data; a session's draft shadows the
+    /// workspace's store, which shadows the shared one in the schema data
+    fn insert_into(self, data: &mut Data) {
+        if let Some(Extension(workspace)) = self.workspace {
+            data.insert(workspace.store.clone());
+            data.insert(workspace);
+        }
+        if let Some(Extension(session)) = self.session {
+            data.insert(session.store.clone());
+            data.insert(session);
+        }
+    }
+}
+
+/// Execute a request, applying the request headers, authenticated user, draft
+/// sessions and selected workspace, tagging errors with the request ID, and adding
+/// the version header
+async fn execute(
+    state: &ApiState,
+    headers: &HeaderMap,
+    identity: Option<Extension<UserIdentity>>,
+    selection: Selection,
+    mut req: async_graphql::Request,
+) -> impl IntoResponse {
+    if headers.contains_key(TRACE_HEADER) {
+        req = req.data(TraceRequested);
+    }
+    if let Some(Extension(identity)) = identity {
+        req = req.data(identity);
+    }
+    req = req.data(state.sessions.clone());
+    selection.insert_into(&mut req.data);
+    let mut response = state.schema.execute(req).await;
+    if let Some(id) = request_id(headers) {
+        tag_errors(&mut response, id);
+    }
+    let response: GraphQLResponse = response.into();
+    (
+        [(SCHEMA_VERSION_HEADER, SCHEMA_VERSION.to_string())],
+        response,
+    )
+}
+
 async fn graphql_handler(
-    State(schema): State<systematics_backend::SystematicsSchema>,
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    identity: Option<Extension<UserIdentity>>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
     req: GraphQLRequest,
-) -> GraphQLResponse {
-    schema.execute(req.into_inner()).await.into()
+) -> impl IntoResponse {
+    let selection = Selection { workspace, session };
+    execute(&state, &headers, identity, selection, req.into_inner()).await
+}
+
+/// Execute a query sent as GET (cacheable by browsers and CDNs), or redirect to the
+/// IDE (when enabled) if there is no query string
+async fn graphql_get(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    identity: Option<Extension<UserIdentity>>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+    RawQuery(query): RawQuery,
+) -> Response {
+    let Some(query) = query else {
+        return match &state.ide_path {
+            Some(path) => Redirect::to(path).into_response(),
+            None => (StatusCode::BAD_REQUEST, "Missing query").into_response(),
+        };
+    };
+    match async_graphql::http::parse_query_string(&query) {
+        Ok(req) => execute(
+            &state,
+            &headers,
+            identity,
+            Selection { workspace, session },
+            req.data(GetRequest),
+        )
+        .await
+        .into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
 }
 
-async fn graphql_playground() -> impl IntoResponse {
-    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+/// Serve subscriptions over WebSocket (graphql-ws and graphql-transport-ws protocols).
+/// The user is taken from the upgrade request's bearer token or, when tokens are
+/// configured, from the connection-init payload; an invalid token closes the connection.
+/// A workspace or draft session is selected by the upgrade request, for the whole
+/// connection.
+async fn graphql_ws(
+    State(state): State<ApiState>,
+    identity: Option<Extension<UserIdentity>>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let mut data = Data::default();
+    if let Some(Extension(identity)) = identity {
+        data.insert(identity);
+    }
+    data.insert(state.sessions.clone());
+    Selection { workspace, session }.insert_into(&mut data);
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| {
+            GraphQLWebSocket::new(socket, state.schema, protocol)
+                .with_data(data)
+                .on_connection_init(move |payload| connection_init(state.auth, payload))
+                .serve()
+        })
 }
 
-/// Initialize tracing subscriber
-fn init_tracing() {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "systematics_backend=debug,tower_http=debug".into()),
+/// Connection-init hook: authenticate the connection from its payload
+async fn connection_init(
+    auth: Option<Arc<AuthConfig>>,
+    payload: serde_json::Value,
+) -> async_graphql::Result<Data> {
+    let mut data = Data::default();
+    if let Some(auth) = auth {
+        match connection_identity(&auth, &payload) {
+            Ok(Some(identity)) => data.insert(identity),
+            Ok(None) => {}
+            Err(e) => return Err(async_graphql::Error::new(e.to_string())),
+        }
+    }
+    Ok(data)
+}
+
+/// Serve the schema SDL for codegen tools
+async fn graphql_sdl(State(schema): State<SystematicsSchema>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/graphql; charset=utf-8")],
+        schema.sdl(),
+    )
+}
+
+/// Query parameters of `/render/{order}.png`
+#[derive(Debug, Deserialize)]
+struct RenderQuery {
+    /// Width in pixels
+    width: Option<u32>,
+    /// Scale of the 400-pixel drawing
+    scale: Option<f64>,
+    /// Draw the term labels (the default)
+    label: Option<bool>,
+}
+
+/// Snapshot of the graph a request is served from: its draft session's, its
+/// workspace's, or the shared one
+fn served_graph(
+    state: &ApiState,
+    session: Option<Extension<Session>>,
+    workspace: Option<Extension<Workspace>>,
+) -> Graph {
+    match (session, workspace) {
+        (Some(Extension(session)), _) => session.store.snapshot(),
+        (None, Some(Extension(workspace))) => workspace.store.snapshot(),
+        (None, None) => state.store.snapshot(),
+    }
+}
+
+/// Response refusing a request that accepts none of the `offered` media types
+fn not_acceptable(offered: &[&str]) -> Response {
+    (
+        StatusCode::NOT_ACCEPTABLE,
+        [(header::VARY, "accept")],
+        format!("Available as {}", offered.join(", ")),
+    )
+        .into_response()
+}
+
+/// Run one of the middleware's operations on the graph a request is served
+/// from, decoding the `field` of its result
+async fn read_wire<T: DeserializeOwned>(
+    state: &ApiState,
+    selection: Selection,
+    document: String,
+    variables: serde_json::Value,
+    field: &str,
+) -> Result<T, Response> {
+    let mut req = async_graphql::Request::new(document)
+        .variables(async_graphql::Variables::from_json(variables));
+    selection.insert_into(&mut req.data);
+    let response = state.reader.execute(req).await;
+    if let Some(error) = response.errors.first() {
+        let code = serde_json::to_value(error)
+            .ok()
+            .and_then(|json| serde_json::from_value::<GraphQLError>(json).ok())
+            .and_then(|error| error.extensions.code);
+        let status = match code {
+            Some(ErrorCode::InvalidOrder | ErrorCode::UnknownOrder) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        return Err((status, error.message.clone()).into_response());
+    }
+    let decoded = response
+        .data
+        .into_json()
+        .map_err(|e| e.to_string())
+        .and_then(|mut data| serde_json::from_value(data[field].take()).map_err(|e| e.to_string()));
+    decoded.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e).into_response())
+}
+
+/// A wire type as JSON, or in the binary format of `media_type`
+fn wire_response<T: Serialize>(value: &T, media_type: &'static str) -> Response {
+    let encoded = match BinaryFormat::from_media_type(media_type) {
+        Some(format) => format.encode(value).map_err(|e| e.to_string()),
+        None => serde_json::to_vec(value).map_err(|e| e.to_string()),
+    };
+    match encoded {
+        Ok(bytes) => (
+            [
+                (header::CONTENT_TYPE, media_type),
+                (header::CACHE_CONTROL, "public, max-age=300"),
+                (header::VARY, "accept"),
+            ],
+            bytes,
         )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
 }
 
-/// Build the GraphQL API router (shared between local and Shuttle)
-fn build_api_router() -> Router {
-    let schema = create_schema();
+/// Serve a system in the format the `Accept` header prefers: JSON (the default),
+/// Turtle, GraphML, DOT or SVG, e.g. `/systems/4` with `Accept: text/turtle`.
+/// CBOR and MessagePack carry the middleware's `SystemView`, as the `system`
+/// query returns it.
+async fn system_resource(
+    State(state): State<ApiState>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+    headers: HeaderMap,
+    extract::Path(order): extract::Path<u8>,
+) -> Response {
+    let offered: Vec<&str> = ExportFormat::ALL
+        .iter()
+        .map(ExportFormat::media_type)
+        .chain(BinaryFormat::ALL.iter().map(BinaryFormat::media_type))
+        .collect();
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+    let Some(chosen) = negotiate(accept, &offered) else {
+        return not_acceptable(&offered);
+    };
+    if BinaryFormat::from_media_type(chosen).is_some() {
+        let selection = Selection { workspace, session };
+        let variables = serde_json::json!({ "order": order });
+        let system: Option<SystemView> = match read_wire(
+            &state,
+            selection,
+            operations::get_system(),
+            variables,
+            "system",
+        )
+        .await
+        {
+            Ok(system) => system,
+            Err(response) => return response,
+        };
+        return match system {
+            Some(system) => wire_response(&system, chosen),
+            None => (
+                StatusCode::NOT_FOUND,
+                ExportError::UnknownOrder(order).to_string(),
+            )
+                .into_response(),
+        };
+    }
+    let format = ExportFormat::ALL
+        .into_iter()
+        .find(|f| f.media_type() == chosen)
+        .expect("offered media types are export formats or binary");
 
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    let graph = served_graph(&state, session, workspace);
+    let exported =
+        tokio::task::spawn_blocking(move || data::export(&graph, format, Some(order))).await;
+    match exported {
+        Ok(Ok(text)) => (
+            [
+                (header::CONTENT_TYPE, format.media_type()),
+                (header::CACHE_CONTROL, "public, max-age=300"),
+                (header::VARY, "accept"),
+            ],
+            text,
+        )
+            .into_response(),
+        Ok(Err(e @ ExportError::UnknownOrder(_))) => {
+            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+        }
+        Ok(Err(e)) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Serve the whole graph as the middleware's `GraphView`, in JSON (the
+/// default), CBOR or MessagePack as the `Accept` header prefers
+async fn graph_resource(
+    State(state): State<ApiState>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+    headers: HeaderMap,
+) -> Response {
+    let offered: Vec<&str> = std::iter::once("application/json")
+        .chain(BinaryFormat::ALL.iter().map(BinaryFormat::media_type))
+        .collect();
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+    let Some(chosen) = negotiate(accept, &offered) else {
+        return not_acceptable(&offered);
+    };
+    let selection = Selection { workspace, session };
+    let variables = serde_json::json!({});
+    match read_wire::<GraphView>(
+        &state,
+        selection,
+        operations::get_graph(),
+        variables,
+        "graph",
+    )
+    .await
+    {
+        Ok(graph) => wire_response(&graph, chosen),
+        Err(response) => response,
+    }
+}
+
+/// Render a system as a PNG image, e.g. `/render/4.png?width=1200&label=false`
+async fn render_system(
+    State(state): State<ApiState>,
+    workspace: Option<Extension<Workspace>>,
+    session: Option<Extension<Session>>,
+    extract::Path(file): extract::Path<String>,
+    Query(query): Query<RenderQuery>,
+) -> Response {
+    let Some(order) = file
+        .strip_suffix(".png")
+        .and_then(|order| order.parse::<u8>().ok())
+    else {
+        return (StatusCode::NOT_FOUND, "Expected /render/{order}.png").into_response();
+    };
+    let mut options = match (query.width, query.scale) {
+        (Some(_), Some(_)) => {
+            return (StatusCode::BAD_REQUEST, "Give width or scale, not both").into_response()
+        }
+        (Some(width), None) => RenderOptions {
+            width,
+            ..RenderOptions::default()
+        },
+        (None, Some(scale)) => RenderOptions::scaled(scale),
+        (None, None) => RenderOptions::default(),
+    };
+    options.labels = query.label.unwrap_or(true);
+
+    // Rasterizing is CPU-bound, so it runs off the async workers
+    let graph = served_graph(&state, session, workspace);
+    let rendered =
+        tokio::task::spawn_blocking(move || data::render_png(&graph, order, &options)).await;
+    match rendered {
+        Ok(Ok(png)) => (
+            [
+                (header::CONTENT_TYPE, "image/png"),
+                (header::CACHE_CONTROL, "public, max-age=300"),
+            ],
+            png,
+        )
+            .into_response(),
+        Ok(Err(e @ RenderError::UnknownOrder(_))) => {
+            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+        }
+        Ok(Err(e @ RenderError::InvalidWidth(_))) => {
+            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+        }
+        Ok(Err(e)) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Initialize tracing subscriber, logging to `writer` in `format`
+fn init_tracing<W>(writer: W, format: LogFormat)
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "systematics_backend=debug,tower_http=debug".into());
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Text => registry
+            .with(tracing_subscriber::fmt::layer().with_writer(writer))
+            .init(),
+        LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().json().with_writer(writer))
+            .init(),
+    }
+}
 
-    Router::new()
-        .route("/graphql", get(graphql_playground).post(graphql_handler))
-        .layer(cors)
-        .with_state(schema)
+/// Load the configuration, refusing to start when it is invalid
+fn load_config() -> Config {
+    Config::load().unwrap_or_else(|e| panic!("Invalid configuration: {}", e))
 }
 
-// Local development runtime (tokio)
+/// Load the configuration from the file given on the command line, or the default one
+#[cfg(not(feature = "shuttle"))]
+fn load_config_from(file: Option<&Path>) -> Config {
+    match file {
+        Some(file) => Config::load_file(file)
+            .unwrap_or_else(|e| panic!("Invalid configuration in {}: {}", file.display(), e)),
+        None => load_config(),
+    }
+}
+
+/// Load the initial graph, overlaying external data files when a data directory is configured
+fn load_initial_graph(config: &Config) -> systematics_backend::core::Graph {
+    let Some(dir) = &config.data_dir else {
+        return data::build_graph();
+    };
+    match data::load_graph(dir) {
+        Ok(graph) => {
+            for problem in graph.validate() {
+                tracing::warn!("Data validation: {}", problem);
+            }
+            for problem in data::audit(&graph) {
+                tracing::warn!("Data audit: {}", problem);
+            }
+            tracing::info!("Loaded external data from {}", dir.display());
+            graph
+        }
+        Err(e) => {
+            tracing::error!("{}; falling back to built-in data", e);
+            data::build_graph()
+        }
+    }
+}
+
+/// Create the store over the initial graph (see `open_store`)
+#[cfg(not(feature = "shuttle"))]
+fn load_store(config: &Config) -> GraphStore {
+    open_store(config, load_initial_graph(config))
+}
+
+/// Create the store over `baseline`. With the `sqlite` feature and a `database`
+/// configured, edits are persisted to that SQLite file and the ones recorded
+/// earlier are replayed; a database that cannot be opened stops startup.
+#[cfg(not(feature = "shuttle"))]
+fn open_store(config: &Config, baseline: systematics_backend::core::Graph) -> GraphStore {
+    let Some(path) = &config.database else {
+        return GraphStore::new(baseline);
+    };
+    #[cfg(feature = "sqlite")]
+    match data::SqliteLog::open(path).and_then(|log| GraphStore::persistent(baseline, log)) {
+        Ok(store) => {
+            tracing::info!(
+                "Persisting edits to {} ({} recorded)",
+                path.display(),
+                store.edit_count()
+            );
+            store
+        }
+        Err(e) => panic!("Cannot open database {}: {}", path.display(), e),
+    }
+    #[cfg(not(feature = "sqlite"))]
+    {
+        tracing::warn!(
+            "A database ({}) is configured, but this build lacks the sqlite feature; \
+             edits are not persisted",
+            path.display()
+        );
+        GraphStore::new(baseline)
+    }
+}
+
+/// Resolve on Ctrl-C or SIGTERM, waking `stopping` so the drain timeout starts
+async fn shutdown_signal(stopping: Arc<Notify>) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+    tracing::info!("Shutting down; waiting for in-flight requests");
+    stopping.notify_one();
+}
+
+/// Run a server shutting down on `shutdown_signal(stopping)`. In-flight requests
+/// get `drain` to finish before they are dropped; then the store's pending edits
+/// are flushed.
+async fn run_until_shutdown(
+    server: impl IntoFuture<Output = std::io::Result<()>>,
+    stopping: Arc<Notify>,
+    store: GraphStore,
+    drain: Duration,
+) -> std::io::Result<()> {
+    let deadline = async {
+        stopping.notified().await;
+        tokio::time::sleep(drain).await;
+    };
+    tokio::select! {
+        result = server.into_future() => result?,
+        () = deadline => tracing::warn!(
+            "Requests still in flight after {}s; dropping them",
+            drain.as_secs()
+        ),
+    }
+    match tokio::task::spawn_blocking(move || store.flush()).await {
+        Ok(Ok(())) => tracing::info!("Shutdown complete"),
+        Ok(Err(e)) => tracing::error!("Failed to flush edits: {}", e),
+        Err(e) => tracing::error!("Failed to flush edits: {}", e),
+    }
+    Ok(())
+}
+
+/// Create the workspace registry, seeded with `baseline`, and drop idle workspaces
+/// every minute
+fn workspace_registry(
+    config: &Config,
+    baseline: systematics_backend::core::Graph,
+) -> WorkspaceRegistry {
+    let registry = WorkspaceRegistry::new(baseline, config.max_workspaces);
+    if config.max_workspaces > 0 {
+        let idle = config.workspace_idle_timeout();
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                for name in registry.evict_idle(idle, std::time::Instant::now()) {
+                    tracing::info!("Dropped idle workspace '{}'", name);
+                }
+            }
+        });
+    }
+    registry
+}
+
+/// Create the draft session registry, and discard idle sessions every minute
+fn session_registry(config: &Config) -> SessionRegistry {
+    let registry = SessionRegistry::default();
+    let idle = config.session_idle_timeout();
+    let sessions = registry.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            let dropped = sessions.evict_idle(idle, std::time::Instant::now());
+            if dropped > 0 {
+                tracing::info!("Discarded {} idle draft sessions", dropped);
+            }
+        }
+    });
+    registry
+}
+
+/// Load the admin settings, refusing to start when the token is unusable
+fn load_admin(store: &GraphStore, config: &Config) -> Option<Admin> {
+    match Admin::from_env(store.clone(), config.data_dir.clone()) {
+        Ok(Some(admin)) => {
+            tracing::info!(
+                "Admin endpoints served at /admin/reload, /export/graph.json and /import"
+            );
+            Some(admin)
+        }
+        Ok(None) => None,
+        Err(e) => panic!("Invalid admin settings: {}", e),
+    }
+}
+
+/// Load the token authentication settings, refusing to start when they are invalid
+fn load_auth() -> Option<Arc<AuthConfig>> {
+    match AuthConfig::from_env() {
+        Ok(Some(config)) => {
+            tracing::info!("Accepting bearer tokens from {}", config.issuer());
+            Some(Arc::new(config))
+        }
+        Ok(None) => None,
+        Err(e) => panic!("Invalid authentication settings: {}", e),
+    }
+}
+
+/// CORS policy: any origin, or the configured ones (none when empty), with
+/// credentials when enabled. Credentialed responses cannot use wildcards, so the
+/// methods are listed and the requested headers are echoed.
+fn cors_layer(config: &Config) -> CorsLayer {
+    let origin = if config.cors_any_origin() {
+        tracing::info!("CORS: any origin");
+        AllowOrigin::any()
+    } else {
+        if config.cors_origins.is_empty() {
+            tracing::info!("CORS: cross-origin requests refused");
+        } else {
+            tracing::info!("CORS: {}", config.cors_origins.join(", "));
+        }
+        let origins: Vec<HeaderValue> = config
+            .cors_origins
+            .iter()
+            .map(|origin| origin.parse().expect("origins are validated on load"))
+            .collect();
+        AllowOrigin::list(origins)
+    };
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers(AllowHeaders::mirror_request())
+        .allow_credentials(config.cors_credentials)
+        .expose_headers([REQUEST_ID_HEADER])
+}
+
+/// Serve the frontend outside the API routes, falling back to `index.html` for
+/// client-side routing: from `static_dir` or, when that does not exist and the
+/// frontend is embedded (`embed` feature), from the binary
+fn with_frontend(router: Router, config: &Config) -> Router {
+    #[cfg(feature = "embed")]
+    if !config.static_dir.is_dir() && http::embedded_frontend() {
+        tracing::info!("Static files served from the binary");
+        return router.fallback(http::serve_embedded);
+    }
+    tracing::info!("Static files served from {}", config.static_dir.display());
+    let static_files = ServeDir::new(&config.static_dir)
+        .not_found_service(ServeFile::new(config.static_dir.join("index.html")));
+    router.fallback_service(static_files)
+}
+
+/// Build the API router shared by the local and Shuttle servers: GraphQL, the
+/// REST and render endpoints, and the admin and gRPC routes when enabled. Edits
+/// of `store` are sent to the configured webhooks.
+fn build_api_router(
+    store: GraphStore,
+    workspaces: WorkspaceRegistry,
+    config: &Config,
+    auth: Option<Arc<AuthConfig>>,
+) -> Router {
+    let schema = create_schema_with_store(store.clone(), config.schema_options());
+    // The REST endpoints run only the middleware's operations
+    let reader = create_schema_with_store(
+        store.clone(),
+        SchemaOptions {
+            introspection: false,
+            persisted_only: false,
+            allowed_operations_only: false,
+        },
+    );
+    let sessions = session_registry(config);
+    if !config.webhooks.is_empty() {
+        tracing::info!("Notifying {} webhook(s) of edits", config.webhooks.len());
+        Webhooks::new(config.webhooks.clone()).spawn(&store);
+    }
+    let cors = cors_layer(config);
+
+    let mut router = Router::new()
+        .route("/graphql", get(graphql_get).post(graphql_handler))
+        .route("/graphql/ws", get(graphql_ws))
+        .route("/render/:file", get(render_system))
+        .route("/systems/:order", get(system_resource))
+        .route("/graph", get(graph_resource));
+    if config.introspection {
+        router = router.route("/schema.graphql", get(graphql_sdl));
+    }
+    router = router.route_layer(middleware::from_fn_with_state(
+        store.clone(),
+        conditional_get,
+    ));
+    router = router.route_layer(middleware::from_fn_with_state(workspaces, select_workspace));
+    router = router.route_layer(middleware::from_fn_with_state(
+        sessions.clone(),
+        select_session,
+    ));
+    if let Some(auth) = auth.clone() {
+        router = router.route_layer(middleware::from_fn_with_state(auth, authenticate));
+    }
+    if let Some(limit) = config.rate_limit() {
+        router = router.route_layer(middleware::from_fn_with_state(
+            RateLimiter::new(limit).with_client_keys(config.client_keys()),
+            rate_limit,
+        ));
+    }
+    if config.ide {
+        let page = Html(graphiql_page("/graphql", "/graphql/ws"));
+        router = router.route(
+            &config.ide_path,
+            get(move || std::future::ready(page.clone())),
+        );
+    }
+    let admin = load_admin(&store, config);
+    #[cfg(feature = "grpc")]
+    let grpc = SystematicsService::new(store.clone()).into_router();
+    let router = router.layer(cors).with_state(ApiState {
+        schema,
+        reader,
+        store,
+        sessions,
+        ide_path: config.ide.then(|| config.ide_path.clone()),
+        auth,
+    });
+    let router = match admin {
+        Some(admin) => router.merge(admin_router(admin)),
+        None => router,
+    };
+    #[cfg(feature = "grpc")]
+    let router = {
+        tracing::info!("gRPC service enabled");
+        router.merge(grpc)
+    };
+    router
+}
+
+// Local runtime (tokio): the command-line interface, serving by default
 #[cfg(not(feature = "shuttle"))]
 #[tokio::main]
-async fn main() {
-    init_tracing();
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or(Command::Serve);
+    let config = load_config_from(cli.config.as_deref());
+    // Data commands write their results to standard output, so they log to stderr
+    match command {
+        Command::Serve => init_tracing(std::io::stdout, config.log_format),
+        _ => init_tracing(std::io::stderr, config.log_format),
+    }
 
-    // Build API routes
-    let api_router = build_api_router();
+    match command {
+        Command::Serve => {
+            serve(config).await;
+            ExitCode::SUCCESS
+        }
+        Command::Validate => cli::validate(&config),
+        Command::Export {
+            format,
+            order,
+            output,
+        } => cli::export(&config, format, order, output.as_deref()),
+        Command::Import { file, name } => cli::import(&config, &file, name),
+    }
+}
 
-    // Serve static files from frontend/dist
-    // Fallback to index.html for SPA routing
-    let static_files = ServeDir::new("frontend/dist")
-        .not_found_service(ServeFile::new("frontend/dist/index.html"));
+/// Run the local server until it is stopped
+#[cfg(not(feature = "shuttle"))]
+async fn serve(config: Config) {
+    let baseline = load_initial_graph(&config);
+    let workspaces = workspace_registry(&config, baseline.clone());
+    let store = open_store(&config, baseline);
+
+    // Hot-reload external data in development builds
+    #[cfg(feature = "dev")]
+    let _watcher = config.data_dir.as_ref().and_then(|dir| {
+        data::watch::watch(dir, store.clone())
+            .map_err(|e| tracing::error!("Failed to watch {}: {}", dir.display(), e))
+            .ok()
+    });
+
+    // Build API routes
+    let api_router = build_api_router(store.clone(), workspaces, &config, load_auth());
 
-    // Combine routes: API takes precedence, then static files
-    let app = Router::new()
-        .nest("/", api_router)
-        .fallback_service(static_files);
+    // Combine routes: API takes precedence, then the frontend
+    let app = with_request_ids(with_frontend(Router::new().nest("/", api_router), &config));
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8000));
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("GraphQL API configured at /graphql");
-    tracing::info!("Static files served from frontend/dist");
-    tracing::info!("Server running at http://{}", addr);
+    tracing::info!("GraphQL subscriptions served at /graphql/ws");
+    if config.ide {
+        tracing::info!("GraphiQL served at {}", config.ide_path);
+    }
+    if config.introspection {
+        tracing::info!("Schema SDL served at /schema.graphql");
+    } else {
+        tracing::info!("Introspection disabled");
+    }
+    match config.rate_limit() {
+        Some(limit) => tracing::info!(
+            "Rate limit: {} requests per client, refilled at {}/s",
+            limit.burst,
+            limit.per_second
+        ),
+        None => tracing::info!("Rate limiting disabled"),
+    }
+
+    let stopping = Arc::new(Notify::new());
+    #[cfg(not(feature = "tls"))]
+    if let Some((cert, _)) = config.tls() {
+        panic!(
+            "HTTPS is configured ({}), but this build lacks the tls feature",
+            cert.display()
+        );
+    }
+    #[cfg(feature = "tls")]
+    if let Some((cert, key)) = config.tls() {
+        tracing::info!("Server running at https://{}", addr);
+        let server = https_server(app, addr, cert, key, stopping.clone()).await;
+        run_until_shutdown(server, stopping, store, config.shutdown_timeout())
+            .await
+            .unwrap();
+        return;
+    }
 
+    tracing::info!("Server running at http://{}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(stopping.clone()));
+    run_until_shutdown(server, stopping, store, config.shutdown_timeout())
+        .await
+        .unwrap();
+}
+
+/// Serve `app` over HTTPS with the certificate and key in PEM files, shutting down
+/// on `shutdown_signal(stopping)`; refuses to start when they cannot be loaded
+#[cfg(all(feature = "tls", not(feature = "shuttle")))]
+async fn https_server(
+    app: Router,
+    addr: SocketAddr,
+    cert: &Path,
+    key: &Path,
+    stopping: Arc<Notify>,
+) -> impl IntoFuture<Output = std::io::Result<()>> {
+    // ring is the only crypto provider compiled in; an error means it is installed
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let tls = axum_server::tls_rustls::RustlsConfig::from_pem_file(cert, key)
+        .await
+        .unwrap_or_else(|e| panic!("Invalid TLS certificate or key: {}", e));
+    let handle = axum_server::Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal(stopping).await;
+        shutdown.graceful_shutdown(None);
+    });
+    axum_server::bind_rustls(addr, tls)
+        .handle(handle)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+}
+
+/// The Shuttle service: the app, served with the same graceful shutdown as locally
+#[cfg(feature = "shuttle")]
+struct SystematicsService {
+    app: Router,
+    store: GraphStore,
+    drain: Duration,
+}
+
+#[cfg(feature = "shuttle")]
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for SystematicsService {
+    async fn bind(self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let stopping = Arc::new(Notify::new());
+        let server = axum::serve(
+            listener,
+            self.app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal(stopping.clone()));
+        run_until_shutdown(server, stopping, self.store, self.drain).await?;
+        Ok(())
+    }
 }
 
 // Production deployment runtime (Shuttle)
 #[cfg(feature = "shuttle")]
 #[shuttle_runtime::main]
-async fn main() -> shuttle_axum::ShuttleAxum {
-    init_tracing();
+async fn main(
+    #[shuttle_shared_db::Postgres] pool: sqlx::PgPool,
+) -> Result<SystematicsService, shuttle_runtime::Error> {
+    let config = load_config();
+    init_tracing(std::io::stdout, config.log_format);
 
-    // Build API routes
-    let api_router = build_api_router();
+    // Edits are kept in the shared database, so they survive redeploys
+    let log = data::PgLog::connect(pool)
+        .await
+        .map_err(|e| shuttle_runtime::Error::Database(e.to_string()))?;
+    let baseline = load_initial_graph(&config);
+    let workspaces = workspace_registry(&config, baseline.clone());
+    let store = GraphStore::persistent(baseline, log)
+        .map_err(|e| shuttle_runtime::Error::Database(e.to_string()))?;
+    tracing::info!(
+        "Persisting edits to Postgres ({} recorded)",
+        store.edit_count()
+    );
 
-    // Serve static files from frontend/dist
-    // Fallback to index.html for SPA routing
-    let static_files = ServeDir::new("frontend/dist")
-        .not_found_service(ServeFile::new("frontend/dist/index.html"));
+    // Build API routes
+    let api_router = build_api_router(store.clone(), workspaces, &config, load_auth());
 
-    // Combine routes: API takes precedence, then static files
-    let app = Router::new()
-        .nest("/", api_router)
-        .fallback_service(static_files);
+    // Combine routes: API takes precedence, then the frontend
+    let app = with_request_ids(with_frontend(Router::new().nest("/", api_router), &config));
 
     tracing::info!("GraphQL API configured at /graphql");
-    tracing::info!("Static files served from frontend/dist");
+    tracing::info!("GraphQL subscriptions served at /graphql/ws");
+    if config.ide {
+        tracing::info!("GraphiQL served at {}", config.ide_path);
+    }
+    if config.introspection {
+        tracing::info!("Schema SDL served at /schema.graphql");
+    } else {
+        tracing::info!("Introspection disabled");
+    }
+    match config.rate_limit() {
+        Some(limit) => tracing::info!(
+            "Rate limit: {} requests per client, refilled at {}/s",
+            limit.burst,
+            limit.per_second
+        ),
+        None => tracing::info!("Rate limiting disabled"),
+    }
 
-    Ok(app.into())
+    Ok(SystematicsService {
+        app,
+        store,
+        drain: config.shutdown_timeout(),
+    })
 }
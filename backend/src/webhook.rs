@@ -0,0 +1,274 @@
+//! Webhook notifications of edits.
+//!
+//! After each successful edit of the shared graph (an instance, annotation,
+//! vocabulary, custom system or restored backup), every configured URL is sent
+//! a `POST` with a JSON event, so external tools (chat bots, the CI of a data
+//! repository) can react:
+//!
+//! ```json
+//! { "id": "5c0a…", "event": "annotation", "summary": "annotation on term_3_1",
+//!   "timestamp": 1760000000,
+//!   "data": { "target": "term_3_1", "text": "…", "author": "Ana", "author_id": null },
+//!   "delta": { "fromRevision": "…", "toRevision": "…", "added": { … }, … } }
+//! ```
+//!
+//! `event` is the edit's kind, and `data` its recorded form (absent for
+//! restores, which carry the whole graph). `delta` is the middleware's
+//! `GraphDelta`: the entries and links changed since the previous event. Deliveries failing with a network
+//! error, a `5xx` or `429` are retried with exponential backoff; other
+//! responses end them. Deliveries run in the background, so events may arrive
+//! out of order; `timestamp` (Unix seconds) and `id` order and deduplicate them.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{Client, StatusCode};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use systematics_middleware::GraphDelta;
+
+use crate::data::{Edit, GraphStore};
+use crate::graphql::wire;
+
+/// Header naming the event kind
+pub const EVENT_HEADER: &str = "x-systematics-event";
+
+/// Attempts per delivery, including the first
+const ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; each further retry doubles it
+const BACKOFF: Duration = Duration::from_secs(1);
+
+/// Time allowed for a webhook to answer
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook URLs and their delivery policy
+#[derive(Debug, Clone)]
+pub struct Webhooks {
+    client: Client,
+    urls: Arc<[String]>,
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl Webhooks {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self::with_policy(urls, ATTEMPTS, BACKOFF)
+    }
+
+    fn with_policy(urls: Vec<String>, attempts: u32, backoff: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("systematics/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("default TLS backend");
+        Self {
+            client,
+            urls: urls.into(),
+            attempts,
+            backoff,
+        }
+    }
+
+    /// Deliver an event to every URL for each edit applied to `store` from now on
+    pub fn spawn(self, store: &GraphStore) -> JoinHandle<()> {
+        let mut edits = store.applied_edits();
+        let mut before = store.snapshot();
+        let store = store.clone();
+        tokio::spawn(async move {
+            loop {
+                match edits.recv().await {
+                    Ok(edit) => {
+                        // Edits are sent under the store's write lock, so this
+                        // graph includes the edit (and perhaps later ones)
+                        let after = store.snapshot();
+                        let delta = wire::delta(&before, &after);
+                        before = after;
+                        let event = Arc::new(event(&edit, &delta));
+                        for url in self.urls.iter() {
+                            let (hooks, url, event) = (self.clone(), url.clone(), event.clone());
+                            tokio::spawn(async move { hooks.deliver(&url, &event).await });
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("Webhooks fell behind; {} edit(s) not notified", missed)
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Post `event` to `url`, retrying failures that may pass; returns whether it
+    /// was accepted
+    async fn deliver(&self, url: &str, event: &serde_json::Value) -> bool {
+        let kind = event["event"].as_str().unwrap_or_default();
+        let mut delay = self.backoff;
+        for attempt in 1..=self.attempts {
+            let sent = self
+                .client
+                .post(url)
+                .header(EVENT_HEADER, kind)
+                .json(event)
+                .send()
+                .await;
+            let problem = match sent {
+                Ok(response) if response.status().is_success() => return true,
+                Ok(response) if !retryable(response.status()) => {
+                    tracing::warn!(
+                        "Webhook {} refused {} event: {}",
+                        url,
+                        kind,
+                        response.status()
+                    );
+                    return false;
+                }
+                Ok(response) => response.status().to_string(),
+                Err(e) => e.to_string(),
+            };
+            if attempt == self.attempts {
+                tracing::warn!(
+                    "Webhook {} failed {} times; dropping {} event: {}",
+                    url,
+                    attempt,
+                    kind,
+                    problem
+                );
+            } else {
+                tracing::debug!(
+                    "Webhook {} failed ({}); retrying in {:?}",
+                    url,
+                    problem,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+        false
+    }
+}
+
+/// Whether a delivery answered with `status` may succeed later
+fn retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// JSON event describing an edit, and the changes it made
+fn event(edit: &Edit, delta: &GraphDelta) -> serde_json::Value {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let mut event = serde_json::json!({
+        "id": uuid::Uuid::new_v4().simple().to_string(),
+        "event": edit.kind(),
+        "summary": edit.describe(),
+        "timestamp": timestamp,
+        "delta": delta,
+    });
+    if !matches!(edit, Edit::Restore(_)) {
+        // The recorded form is `{"kind": …, "data": …}`
+        if let Ok(serde_json::Value::Object(mut recorded)) = serde_json::to_value(edit) {
+            if let Some(data) = recorded.remove("data") {
+                event["data"] = data;
+            }
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{add_annotation, build_graph};
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use std::sync::Mutex;
+
+    fn note() -> Edit {
+        Edit::Annotation {
+            target: "term_3_1".to_string(),
+            text: "Compare with Affirmation".to_string(),
+            author: "Ana".to_string(),
+            author_id: None,
+        }
+    }
+
+    #[test]
+    fn test_event() {
+        let event = event(&note(), &GraphDelta::default());
+        assert_eq!(event["event"], "annotation");
+        assert_eq!(event["summary"], "annotation on term_3_1");
+        assert_eq!(event["data"]["author"], "Ana");
+
+        let restore = super::event(
+            &Edit::Restore(Box::new(build_graph())),
+            &GraphDelta::default(),
+        );
+        assert_eq!(restore["event"], "restore");
+        assert!(restore.get("data").is_none());
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!retryable(StatusCode::NOT_FOUND));
+    }
+
+    /// Receiver answering 503 to the first delivery, then recording events
+    async fn receiver() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let received: Arc<Mutex<Vec<serde_json::Value>>> = Arc::default();
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(received): State<Arc<Mutex<Vec<serde_json::Value>>>>,
+                     Json(event): Json<serde_json::Value>| async move {
+                        let mut received = received.lock().unwrap();
+                        received.push(event);
+                        if received.len() == 1 {
+                            StatusCode::SERVICE_UNAVAILABLE
+                        } else {
+                            StatusCode::NO_CONTENT
+                        }
+                    },
+                ),
+            )
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, received)
+    }
+
+    #[tokio::test]
+    async fn test_edits_are_delivered_with_retries() {
+        let (url, received) = receiver().await;
+        let store = GraphStore::new(build_graph());
+        Webhooks::with_policy(vec![url], 3, Duration::from_millis(10)).spawn(&store);
+
+        store
+            .apply(note(), |graph| {
+                add_annotation(graph, "term_3_1", "Compare with Affirmation", "Ana", None)
+            })
+            .unwrap();
+
+        for _ in 0..200 {
+            if received.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        // The retry resends the same event
+        assert_eq!(received[0], received[1]);
+        assert_eq!(received[1]["data"]["target"], "term_3_1");
+        // The note is the only change, and not one a delta carries
+        let delta: GraphDelta = serde_json::from_value(received[1]["delta"].clone()).unwrap();
+        assert_eq!(delta.other.len(), 1);
+        assert!(delta.added.is_empty() && delta.updated.is_empty() && delta.removed.is_empty());
+        assert_ne!(delta.from_revision, delta.to_revision);
+    }
+}
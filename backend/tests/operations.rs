@@ -0,0 +1,225 @@
+//! The middleware's GraphQL operations (`systematics_middleware::operations`)
+//! must validate against this server's schema, so clients sending them never
+//! meet a field the server does not have.
+
+use async_graphql::{Request, Variables};
+use serde_json::json;
+use std::time::Duration;
+use systematics_backend::create_schema;
+use systematics_backend::http::UserIdentity;
+use systematics_middleware::{
+    operations, AnnotationView, CustomSystemInput, InstanceView, Page, SearchHit, SystemView,
+};
+
+/// A Tridecad with numbered terms
+fn tridecad() -> CustomSystemInput {
+    CustomSystemInput::new(13, "Tridecad", (1..=13).map(|p| format!("Term {}", p)))
+}
+
+/// Variables every operation accepts some of
+fn variables() -> Variables {
+    Variables::from_json(json!({
+        "order": 3,
+        "orders": [3],
+        "name": "Triad",
+        "position": 1,
+        "value": 3,
+        "input": tridecad(),
+        "text": "a",
+        "first": 5,
+        "base": 1,
+        "target": 2,
+        "term": "Intent",
+        "label": "Trust",
+        "language": "ENERGY",
+        "entryId": "loc_3_1",
+    }))
+}
+
+/// A signed-in user, whom editing mutations require
+fn editor() -> UserIdentity {
+    UserIdentity {
+        subject: "user-1".to_string(),
+        name: Some("Ana".to_string()),
+        email: None,
+    }
+}
+
+#[tokio::test]
+async fn test_queries_and_mutations_validate() {
+    let schema = create_schema();
+    for (name, document) in operations::all_operations() {
+        if document.starts_with("subscription") {
+            continue;
+        }
+        let response = schema
+            .execute(Request::new(document).variables(variables()).data(editor()))
+            .await;
+        assert!(
+            response.errors.is_empty(),
+            "{}: {:?}",
+            name,
+            response.errors
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_subscriptions_validate() {
+    use async_graphql::futures_util::StreamExt;
+
+    let schema = create_schema();
+    for (name, document) in operations::all_operations() {
+        if !document.starts_with("subscription") {
+            continue;
+        }
+        // An invalid document yields its errors at once; a valid one waits
+        // for the next revision of the graph
+        let mut stream = schema.execute_stream(Request::new(document).variables(variables()));
+        let first = tokio::time::timeout(Duration::from_millis(100), stream.next()).await;
+        if let Ok(Some(response)) = first {
+            assert!(
+                response.errors.is_empty(),
+                "{}: {:?}",
+                name,
+                response.errors
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_custom_systems_and_instances_decode() {
+    let schema = create_schema();
+    let created = schema
+        .execute(
+            Request::new(operations::create_custom_system())
+                .variables(variables())
+                .data(editor()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    let system: SystemView =
+        serde_json::from_value(created.data.into_json().unwrap()["createCustomSystem"].take())
+            .unwrap();
+    assert_eq!(system.name.as_deref(), Some("Tridecad"));
+    assert_eq!(system.term_at(13), Some("Term 13"));
+
+    schema
+        .execute(
+            Request::new(
+                r#"mutation { createInstance(name: "Week", order: 3, labels: ["Plan", "Do", "Review"]) { id } }"#,
+            )
+            .data(editor()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    let listed = schema
+        .execute(Request::new(operations::get_instances()).variables(variables()))
+        .await
+        .into_result()
+        .unwrap();
+    let instances: Vec<InstanceView> =
+        serde_json::from_value(listed.data.into_json().unwrap()["instances"].take()).unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].name, "Week");
+    assert_eq!(instances[0].label_at(3), Some("Review"));
+}
+
+#[tokio::test]
+async fn test_annotations_decode() {
+    let schema = create_schema();
+    let added = schema
+        .execute(
+            Request::new(operations::annotate_entry())
+                .variables(variables())
+                .data(editor()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    let note: AnnotationView =
+        serde_json::from_value(added.data.into_json().unwrap()["annotateEntry"].take()).unwrap();
+    assert_eq!(note.author, "Ana");
+    assert_eq!(note.author_id.as_deref(), Some("user-1"));
+
+    let listed = schema
+        .execute(Request::new(operations::get_annotations()).variables(variables()))
+        .await
+        .into_result()
+        .unwrap();
+    let notes: Vec<AnnotationView> =
+        serde_json::from_value(listed.data.into_json().unwrap()["annotations"].take()).unwrap();
+    assert_eq!(notes, vec![note]);
+    assert_eq!(notes[0].entry_id, "loc_3_1");
+}
+
+#[tokio::test]
+async fn test_renames_decode() {
+    let schema = create_schema();
+
+    // Anonymous edits are refused
+    let anonymous = schema
+        .execute(Request::new(operations::rename_term()).variables(variables()))
+        .await;
+    assert_eq!(
+        anonymous.errors[0].extensions.as_ref().unwrap().get("code"),
+        Some(&async_graphql::Value::from("UNAUTHENTICATED"))
+    );
+
+    let renamed = schema
+        .execute(
+            Request::new(operations::rename_term())
+                .variables(variables())
+                .data(editor()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    let system: SystemView =
+        serde_json::from_value(renamed.data.into_json().unwrap()["renameTerm"].take()).unwrap();
+    assert_eq!(system.term_at(1), Some("Intent"));
+
+    let labelled = schema
+        .execute(
+            Request::new(operations::label_connective())
+                .variables(variables())
+                .data(editor()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+    let system: SystemView =
+        serde_json::from_value(labelled.data.into_json().unwrap()["labelConnective"].take())
+            .unwrap();
+    assert_eq!(
+        system.connective_view_between(1, 2).and_then(|c| c.label()),
+        Some("Trust")
+    );
+}
+
+#[tokio::test]
+async fn test_search_pages_decode() {
+    let schema = create_schema();
+    let mut hits = Vec::new();
+    let mut after: Option<String> = None;
+    let total = loop {
+        let request = Request::new(operations::search_page()).variables(Variables::from_json(
+            json!({ "text": "a", "first": 5, "after": after }),
+        ));
+        let response = schema.execute(request).await.into_result().unwrap();
+        let page: Page<SearchHit> =
+            serde_json::from_value(response.data.into_json().unwrap()["v2"]["search"].take())
+                .unwrap();
+        assert!(page.items.len() <= 5);
+        hits.extend(page.items.iter().cloned());
+        match page.next_cursor() {
+            Some(cursor) => after = Some(cursor.to_string()),
+            None => break page.total,
+        }
+    };
+    assert!(total > 5);
+    assert_eq!(hits.len(), total as usize);
+}
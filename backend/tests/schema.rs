@@ -0,0 +1,158 @@
+//! Queries against the schema as clients send them: translated names, the
+//! fields every entry shares, the coded errors of out-of-range arguments and
+//! anonymous edits; and the SDL checked in for codegen.
+
+use async_graphql::Request;
+use serde_json::{json, Value};
+use systematics_backend::{create_schema, schema_sdl};
+
+/// Data of a query that must succeed
+async fn execute(query: &str) -> Value {
+    let response = create_schema().execute(Request::new(query)).await;
+    assert!(
+        response.errors.is_empty(),
+        "{}: {:?}",
+        query,
+        response.errors
+    );
+    response.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn test_system_in_a_locale() {
+    let data = execute(
+        r#"{ system(order: 3, locale: "de") { name coherence terms { position character { value } } } }"#,
+    )
+    .await;
+    let system = &data["system"];
+    assert_eq!(system["name"], "Triade");
+    assert_eq!(system["coherence"], "Dynamik");
+    let terms: Vec<&str> = system["terms"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["character"]["value"].as_str().unwrap())
+        .collect();
+    assert_eq!(terms, vec!["Wille", "Funktion", "Sein"]);
+
+    let data = execute(r#"{ system(order: 3, locale: "fr") { name } }"#).await;
+    assert_eq!(data["system"]["name"], json!("Triade"));
+}
+
+#[tokio::test]
+async fn test_untranslated_locale_falls_back() {
+    let data = execute(r#"{ system(order: 3, locale: "xx") { name } }"#).await;
+    assert_eq!(data["system"]["name"], "Triad");
+}
+
+#[tokio::test]
+async fn test_entry_interface_fields() {
+    let data = execute(
+        r#"{ graph { entries(order: 3, position: 2) { id entryType orderValue positionValue } } }"#,
+    )
+    .await;
+    let entries = data["graph"]["entries"].as_array().unwrap();
+    let location = entries.iter().find(|e| e["id"] == "loc_3_2").unwrap();
+    assert_eq!(location["entryType"], "Location");
+    assert_eq!(location["orderValue"], 3);
+    assert_eq!(location["positionValue"], 2);
+
+    let data =
+        execute(r#"{ graph { entry(id: "order_3") { entryType orderValue positionValue } } }"#)
+            .await;
+    assert_eq!(
+        data["graph"]["entry"],
+        json!({ "entryType": "Order", "orderValue": 3, "positionValue": null })
+    );
+}
+
+#[tokio::test]
+async fn test_deprecated_entry_accessors_still_resolve() {
+    let data = execute(
+        r#"{ graph { entry(id: "loc_3_2") { asLocation { order { value } position { value } } asTerm { id } } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["graph"]["entry"],
+        json!({
+            "asLocation": { "order": { "value": 3 }, "position": { "value": 2 } },
+            "asTerm": null
+        })
+    );
+}
+
+/// Code of the one error a query fails with
+async fn error_code(query: &str) -> String {
+    let response = create_schema().execute(Request::new(query)).await;
+    assert_eq!(response.errors.len(), 1, "{}: {:?}", query, response.errors);
+    let extensions = response.errors[0].extensions.as_ref().unwrap();
+    match extensions.get("code") {
+        Some(async_graphql::Value::String(code)) => code.clone(),
+        other => panic!("{}: no code: {:?}", query, other),
+    }
+}
+
+#[tokio::test]
+async fn test_out_of_range_arguments_are_coded() {
+    assert_eq!(
+        error_code("{ order(value: 99) { value } }").await,
+        "INVALID_ORDER"
+    );
+    assert_eq!(
+        error_code("{ location(order: 3, position: 7) { id } }").await,
+        "INVALID_POSITION"
+    );
+    assert_eq!(
+        error_code("{ term(order: 3, position: 4) { id } }").await,
+        "INVALID_POSITION"
+    );
+    assert_eq!(
+        error_code("{ v2 { locations(order: 3, position: 4) { id } } }").await,
+        "INVALID_POSITION"
+    );
+
+    // In range, a location that exists
+    let data = execute("{ location(order: 3, position: 3) { id } }").await;
+    assert_eq!(data["location"]["id"], "loc_3_3");
+}
+
+#[tokio::test]
+async fn test_anonymous_edits_are_refused() {
+    for mutation in [
+        r#"mutation { createInstance(name: "Week", order: 3, labels: ["Plan", "Do", "Review"]) { id } }"#,
+        r#"mutation { annotateEntry(entryId: "term_3_1", text: "Draft", author: "Ana") { id } }"#,
+        r#"mutation { renameTerm(order: 3, position: 1, value: "Intent") { order } }"#,
+        r#"mutation { labelConnective(order: 3, base: 1, target: 2, value: "Trust", language: VALUES) { order } }"#,
+        r#"mutation { createCustomSystem(input: { order: 13, name: "Tridecad", terms: [] }) { order } }"#,
+        r#"mutation { commitSession(id: "6a47eb5f") { applied } }"#,
+    ] {
+        assert_eq!(
+            error_code(mutation).await,
+            "UNAUTHENTICATED",
+            "{}",
+            mutation
+        );
+    }
+
+    // Nothing was written
+    let data = execute(r#"{ instances { id } annotations(entryId: "term_3_1") { id } }"#).await;
+    assert_eq!(data, json!({ "instances": [], "annotations": [] }));
+}
+
+/// SDL checked in for codegen tools and the frontend build
+const CHECKED_IN_SDL: &str = include_str!("../../middleware/schema/schema.graphql");
+
+#[test]
+fn test_checked_in_sdl_is_current() {
+    let sdl = schema_sdl();
+    let stale = sdl
+        .lines()
+        .zip(CHECKED_IN_SDL.lines())
+        .position(|(served, checked_in)| served != checked_in);
+    assert!(
+        sdl == CHECKED_IN_SDL,
+        "middleware/schema/schema.graphql differs from the schema (first at line {}); \
+         regenerate it with `curl -s http://127.0.0.1:8000/schema.graphql > middleware/schema/schema.graphql`",
+        stale.map_or(sdl.lines().count().min(CHECKED_IN_SDL.lines().count()), |i| i) + 1
+    );
+}
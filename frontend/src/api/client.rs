@@ -1,15 +1,68 @@
+use futures::{SinkExt, StreamExt};
 use gloo_net::http::Request;
+use gloo_net::websocket::{futures::WebSocket, Message, WebSocketError};
+use gloo_timers::callback::Timeout;
+use gloo_timers::future::TimeoutFuture;
 use serde::{Deserialize, Serialize};
-use systematics_middleware::{ApiError, Coordinate, SystemView};
+use sha2::{Digest, Sha256};
+use systematics_middleware::{
+    is_supported_version, transform_coordinates_to_viewport, AnnotationView, ApiError,
+    CustomSystemInput, ErrorCode, GraphDelta, GraphQLError, Language, Page, SearchHit,
+    SearchHitKind, Slice, SystemView, Viewport, API_VERSION,
+};
+use systematics_middleware::{mock, operations};
+use web_sys::AbortController;
+
+/// Time allowed for a response before the request is aborted
+const REQUEST_TIMEOUT_MS: u32 = 15_000;
+
+/// Attempts at a request failing with a retryable error
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry when the server names none; doubled for each
+/// further retry
+const RETRY_DELAY_MS: u32 = 500;
+
+/// Orders of the systems served in demo mode
+const DEMO_ORDERS: std::ops::RangeInclusive<i32> = 1..=12;
+/// Search hits fetched at a time
+const SEARCH_PAGE_SIZE: i32 = 20;
+
+/// Drawing area of the graph view (800x800 with margins)
+const VIEWPORT: Viewport = Viewport {
+    width: 800.0,
+    height: 800.0,
+    margin: 100.0,
+};
 
 /// GraphQL request structure
 #[derive(Serialize)]
-struct GraphQLRequest {
-    query: String,
+struct GraphQLRequest<'a> {
+    /// Omitted when sending only the persisted query hash
     #[serde(skip_serializing_if = "Option::is_none")]
-    variables: Option<serde_json::Value>,
+    query: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variables: Option<&'a serde_json::Value>,
+    extensions: RequestExtensions<'a>,
+}
+
+#[derive(Serialize)]
+struct RequestExtensions<'a> {
+    #[serde(rename = "persistedQuery")]
+    persisted_query: PersistedQuery<'a>,
 }
 
+/// Automatic persisted query: the SHA-256 hash of the query text
+#[derive(Serialize)]
+struct PersistedQuery<'a> {
+    version: i32,
+    #[serde(rename = "sha256Hash")]
+    sha256_hash: &'a str,
+}
+
+/// Error message returned for an unknown hash by servers that send no code
+const PERSISTED_QUERY_NOT_FOUND: &str = "PersistedQueryNotFound";
+
 /// GraphQL response structure
 #[derive(Deserialize, Debug)]
 struct GraphQLResponse<T> {
@@ -17,13 +70,7 @@ struct GraphQLResponse<T> {
     errors: Option<Vec<GraphQLError>>,
 }
 
-#[derive(Deserialize, Debug)]
-struct GraphQLError {
-    message: String,
-}
-
 /// System query response (for system(order:) query)
-#[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct SystemQueryResponse {
     system: Option<SystemView>,
@@ -31,6 +78,7 @@ struct SystemQueryResponse {
 
 /// System by name query response
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 struct SystemByNameQueryResponse {
     #[serde(rename = "systemByName")]
     system_by_name: Option<SystemView>,
@@ -43,141 +91,166 @@ struct AllSystemsQueryResponse {
     all_systems: Vec<SystemView>,
 }
 
+/// Systems query response (for systems(orders:) query)
+#[allow(dead_code)]
+#[derive(Deserialize, Debug)]
+struct SystemsQueryResponse {
+    systems: Vec<SystemView>,
+}
+
+/// Slice query response
+#[derive(Deserialize, Debug)]
+struct SliceQueryResponse {
+    slice: Slice,
+}
+
+/// Search page query response (for v2 { search } query)
+#[derive(Deserialize, Debug)]
+struct SearchPageQueryResponse {
+    v2: SearchPageV2,
+}
+
+#[derive(Deserialize, Debug)]
+struct SearchPageV2 {
+    search: Page<SearchHit>,
+}
+
+/// Rename term mutation response
+#[derive(Deserialize, Debug)]
+struct RenameTermResponse {
+    #[serde(rename = "renameTerm")]
+    rename_term: SystemView,
+}
+
+/// Label connective mutation response
+#[derive(Deserialize, Debug)]
+struct LabelConnectiveResponse {
+    #[serde(rename = "labelConnective")]
+    label_connective: SystemView,
+}
+
+/// Create custom system mutation response
+#[derive(Deserialize, Debug)]
+struct CreateCustomSystemResponse {
+    #[serde(rename = "createCustomSystem")]
+    create_custom_system: SystemView,
+}
+
+/// Annotations query response
+#[derive(Deserialize, Debug)]
+struct AnnotationsQueryResponse {
+    annotations: Vec<AnnotationView>,
+}
+
+/// Annotate entry mutation response
+#[derive(Deserialize, Debug)]
+struct AnnotateEntryResponse {
+    #[serde(rename = "annotateEntry")]
+    annotate_entry: AnnotationView,
+}
+
+/// Graph delta subscription response
+#[derive(Deserialize, Debug)]
+struct GraphDeltaResponse {
+    #[serde(rename = "graphDelta")]
+    graph_delta: GraphDelta,
+}
+
+/// Message of the graphql-transport-ws protocol
+#[derive(Serialize, Deserialize, Debug)]
+struct SocketMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<serde_json::Value>,
+}
+
+impl SocketMessage {
+    fn new(kind: &str, payload: Option<serde_json::Value>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            id: (kind == "subscribe").then(|| "1".to_string()),
+            payload,
+        }
+    }
+}
+
+/// What a graph delta does to a fetched system (see `GraphQLClient::apply_delta`)
+pub enum DeltaOutcome {
+    /// The delta changes nothing of the system
+    Unchanged,
+    /// The system with the delta applied
+    Applied(Box<SystemView>),
+    /// The delta cannot be applied in place; fetch the system again
+    Refetch,
+}
+
 /// GraphQL API client for systematics data
 #[derive(Clone)]
 pub struct GraphQLClient {
     endpoint: String,
+    /// Serve generated systems (`middleware::mock`) instead of querying
+    demo: bool,
+    /// Bearer token sent with mutations, which need a signed-in user
+    token: Option<String>,
 }
 
 impl GraphQLClient {
     /// Create a new GraphQL client with the specified endpoint
     pub fn new(endpoint: String) -> Self {
-        Self { endpoint }
+        Self {
+            endpoint,
+            demo: false,
+            token: None,
+        }
     }
 
-    /// GraphQL fragment for system fields (reduces duplication)
-    const SYSTEM_FIELDS: &'static str = r#"
-        order
-        name
-        coherence
-        termDesignation
-        connectiveDesignation
-        terms {
-            id
-            order
-            position
-            characterId
-            character {
-                id
-                language
-                value
-            }
-        }
-        coordinates {
-            id
-            order
-            position
-            x
-            y
-            z
-        }
-        colours {
-            id
-            order
-            position
-            language
-            value
-        }
-        lines {
-            id
-            baseId
-            targetId
-            linkType
-            characterId
-            tag
-            order
-            basePosition
-            targetPosition
-            baseCoordinate {
-                id
-                order
-                position
-                x
-                y
-                z
-            }
-            targetCoordinate {
-                id
-                order
-                position
-                x
-                y
-                z
-            }
-        }
-        connectives {
-            id
-            baseId
-            targetId
-            linkType
-            characterId
-            tag
-            order
-            basePosition
-            targetPosition
-            character {
-                id
-                language
-                value
-            }
-            baseCoordinate {
-                id
-                order
-                position
-                x
-                y
-                z
-            }
-            targetCoordinate {
-                id
-                order
-                position
-                x
-                y
-                z
-            }
+    /// Create a client for offline and demo use, which sends no requests and
+    /// serves generated systems of orders 1-12
+    pub fn demo() -> Self {
+        Self {
+            endpoint: String::new(),
+            demo: true,
+            token: None,
         }
-    "#;
+    }
 
-    /// Fetch a single system by order (1-12)
-    #[allow(dead_code)]
-    pub async fn fetch_system_by_order(&self, order: i32) -> Result<SystemView, ApiError> {
-        let query = format!(
-            r#"
-            query GetSystem($order: Int!) {{
-                system(order: $order) {{
-                    {}
-                }}
-            }}
-        "#,
-            Self::SYSTEM_FIELDS
-        );
+    /// Use a bearer token for mutations (or none, signing out). Queries are
+    /// sent without it, so a rejected token does not stop browsing.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Whether edits can be sent: a server to send them to, and a token
+    pub fn can_edit(&self) -> bool {
+        !self.demo && self.token.is_some()
+    }
+
+    /// Fetch a single system by order (1-12) with its terms in a vocabulary
+    /// (demo systems have only their generated terms)
+    pub async fn fetch_system_by_order(
+        &self,
+        order: i32,
+        language: Language,
+    ) -> Result<SystemView, ApiError> {
+        if self.demo {
+            return self.prepare_system(mock::system_view(order));
+        }
+        let query = operations::get_system();
 
         let variables = serde_json::json!({
-            "order": order
+            "order": order,
+            "language": language,
         });
 
         let response: GraphQLResponse<SystemQueryResponse> =
             self.execute_query(&query, Some(variables)).await?;
 
         if let Some(errors) = response.errors {
-            return Err(ApiError::ParseError(
-                errors
-                    .iter()
-                    .map(|e| e.message.clone())
-                    .collect::<Vec<_>>()
-                    .join(", "),
-            ));
+            return Err(ApiError::GraphQL(errors));
         }
 
         let data = response
@@ -188,21 +261,53 @@ impl GraphQLClient {
             .system
             .ok_or_else(|| ApiError::NotFound(format!("System with order {} not found", order)))?;
 
-        Ok(self.transform_coordinates(system))
+        self.prepare_system(system)
     }
 
-    /// Fetch a single system by name (uses systemByName API query)
+    /// Fetch only the systems for the given orders (e.g., two for a comparison view)
+    #[allow(dead_code)]
+    pub async fn fetch_systems(&self, orders: &[i32]) -> Result<Vec<SystemView>, ApiError> {
+        if self.demo {
+            return mock::system_views(orders.iter().copied())
+                .into_iter()
+                .map(|system| self.prepare_system(system))
+                .collect();
+        }
+        let query = operations::get_systems();
+
+        let variables = serde_json::json!({
+            "orders": orders
+        });
+
+        let response: GraphQLResponse<SystemsQueryResponse> =
+            self.execute_query(&query, Some(variables)).await?;
+
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+
+        let data = response
+            .data
+            .ok_or_else(|| ApiError::NotFound("No systems found".to_string()))?;
+
+        data.systems
+            .into_iter()
+            .map(|system| self.prepare_system(system))
+            .collect()
+    }
+
+    /// Fetch a single system by name (uses systemByName API query); routes
+    /// find systems by slug among those already loaded instead
+    #[allow(dead_code)]
     pub async fn fetch_system(&self, system_name: &str) -> Result<SystemView, ApiError> {
-        let query = format!(
-            r#"
-            query GetSystemByName($name: String!) {{
-                systemByName(name: $name) {{
-                    {}
-                }}
-            }}
-        "#,
-            Self::SYSTEM_FIELDS
-        );
+        if self.demo {
+            let system = mock::system_views(DEMO_ORDERS)
+                .into_iter()
+                .find(|s| s.name.as_deref() == Some(system_name))
+                .ok_or_else(|| ApiError::NotFound(format!("System '{}' not found", system_name)))?;
+            return self.prepare_system(system);
+        }
+        let query = operations::get_system_by_name();
 
         let variables = serde_json::json!({
             "name": system_name
@@ -212,13 +317,7 @@ impl GraphQLClient {
             self.execute_query(&query, Some(variables)).await?;
 
         if let Some(errors) = response.errors {
-            return Err(ApiError::ParseError(
-                errors
-                    .iter()
-                    .map(|e| e.message.clone())
-                    .collect::<Vec<_>>()
-                    .join(", "),
-            ));
+            return Err(ApiError::GraphQL(errors));
         }
 
         let data = response
@@ -229,33 +328,24 @@ impl GraphQLClient {
             .system_by_name
             .ok_or_else(|| ApiError::NotFound(format!("System '{}' not found", system_name)))?;
 
-        Ok(self.transform_coordinates(system))
+        self.prepare_system(system)
     }
 
     /// Fetch all available systems (orders 1-12)
     pub async fn fetch_all_systems(&self) -> Result<Vec<SystemView>, ApiError> {
-        let query = format!(
-            r#"
-            query GetAllSystems {{
-                allSystems {{
-                    {}
-                }}
-            }}
-        "#,
-            Self::SYSTEM_FIELDS
-        );
+        if self.demo {
+            return mock::system_views(DEMO_ORDERS)
+                .into_iter()
+                .map(|system| self.prepare_system(system))
+                .collect();
+        }
+        let query = operations::get_all_systems();
 
         let response: GraphQLResponse<AllSystemsQueryResponse> =
             self.execute_query(&query, None).await?;
 
         if let Some(errors) = response.errors {
-            return Err(ApiError::ParseError(
-                errors
-                    .iter()
-                    .map(|e| e.message.clone())
-                    .collect::<Vec<_>>()
-                    .join(", "),
-            ));
+            return Err(ApiError::GraphQL(errors));
         }
 
         let data = response
@@ -274,7 +364,7 @@ impl GraphQLClient {
             .all_systems
             .into_iter()
             .map(|sys| {
-                let transformed = self.transform_coordinates(sys);
+                let transformed = self.prepare_system(sys)?;
                 web_sys::console::log_1(
                     &format!(
                         "Loaded system: {} (order {})",
@@ -283,134 +373,502 @@ impl GraphQLClient {
                     )
                     .into(),
                 );
-                transformed
+                Ok(transformed)
             })
-            .collect();
+            .collect::<Result<_, ApiError>>()?;
 
         Ok(systems)
     }
 
-    /// Execute a GraphQL query
+    /// Search terms, connectives and order attributes (names, coherences,
+    /// designations) of every system for `text`, a page at a time from the
+    /// cursor `after`
+    pub async fn search(
+        &self,
+        text: &str,
+        after: Option<String>,
+    ) -> Result<Page<SearchHit>, ApiError> {
+        if self.demo {
+            let offset = after.and_then(|a| a.parse().ok()).unwrap_or(0);
+            return Ok(Page::of(
+                demo_search(text),
+                offset,
+                SEARCH_PAGE_SIZE as usize,
+            ));
+        }
+        let query = operations::search_page();
+
+        let variables = serde_json::json!({
+            "text": text,
+            "first": SEARCH_PAGE_SIZE,
+            "after": after,
+        });
+
+        let response: GraphQLResponse<SearchPageQueryResponse> =
+            self.execute_query(&query, Some(variables)).await?;
+
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+
+        let data = response
+            .data
+            .ok_or_else(|| ApiError::NotFound(format!("No results for '{}'", text)))?;
+
+        Ok(data.v2.search)
+    }
+
+    /// Fetch the slice at a position of a system: its term, coordinate,
+    /// colour, and the connectives and lines meeting there
+    pub async fn fetch_slice(&self, order: i32, position: i32) -> Result<Slice, ApiError> {
+        if self.demo {
+            return Ok(mock::slice(&mock::system_view(order), position));
+        }
+        let query = operations::get_slice();
+
+        let variables = serde_json::json!({
+            "order": order,
+            "position": position
+        });
+
+        let response: GraphQLResponse<SliceQueryResponse> =
+            self.execute_query(&query, Some(variables)).await?;
+
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+
+        let data = response.data.ok_or_else(|| {
+            ApiError::NotFound(format!("No slice at order {} position {}", order, position))
+        })?;
+
+        Ok(data.slice)
+    }
+
+    /// Rename the term at a position of a system in a vocabulary, returning
+    /// the system in that vocabulary as the server now has it
+    pub async fn rename_term(
+        &self,
+        order: i32,
+        position: i32,
+        value: &str,
+        language: Language,
+    ) -> Result<SystemView, ApiError> {
+        let variables = serde_json::json!({
+            "order": order,
+            "position": position,
+            "term": value,
+            "language": language,
+        });
+        let response: GraphQLResponse<RenameTermResponse> = self
+            .execute_mutation(&operations::rename_term(), variables)
+            .await?;
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+        let data = response.data.ok_or_else(|| {
+            ApiError::NotFound(format!("No term at order {} position {}", order, position))
+        })?;
+        self.prepare_system(data.rename_term)
+    }
+
+    /// Label the connective between two positions of a system in a
+    /// non-canonical vocabulary, returning the system in that vocabulary
+    pub async fn label_connective(
+        &self,
+        order: i32,
+        (base, target): (i32, i32),
+        value: &str,
+        language: Language,
+    ) -> Result<SystemView, ApiError> {
+        let variables = serde_json::json!({
+            "order": order,
+            "base": base,
+            "target": target,
+            "label": value,
+            "language": language,
+        });
+        let response: GraphQLResponse<LabelConnectiveResponse> = self
+            .execute_mutation(&operations::label_connective(), variables)
+            .await?;
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+        let data = response.data.ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No connective at order {} between {} and {}",
+                order, base, target
+            ))
+        })?;
+        self.prepare_system(data.label_connective)
+    }
+
+    /// Create a custom system, returning it as the server built it
+    pub async fn create_custom_system(
+        &self,
+        input: &CustomSystemInput,
+    ) -> Result<SystemView, ApiError> {
+        let variables = serde_json::json!({ "input": input });
+        let response: GraphQLResponse<CreateCustomSystemResponse> = self
+            .execute_mutation(&operations::create_custom_system(), variables)
+            .await?;
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+        let data = response
+            .data
+            .ok_or_else(|| ApiError::NotFound(format!("System '{}' not created", input.name)))?;
+        self.prepare_system(data.create_custom_system)
+    }
+
+    /// Fetch the notes attached to an entry or link on the server (demo
+    /// systems have none)
+    pub async fn fetch_annotations(&self, entry_id: &str) -> Result<Vec<AnnotationView>, ApiError> {
+        if self.demo {
+            return Ok(Vec::new());
+        }
+        let variables = serde_json::json!({ "entryId": entry_id });
+        let response: GraphQLResponse<AnnotationsQueryResponse> = self
+            .execute_query(&operations::get_annotations(), Some(variables))
+            .await?;
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+        let data = response
+            .data
+            .ok_or_else(|| ApiError::NotFound(format!("No annotations of '{}'", entry_id)))?;
+        Ok(data.annotations)
+    }
+
+    /// Attach a note to an entry or link as the signed-in user, returning
+    /// the annotation it became
+    pub async fn annotate_entry(
+        &self,
+        entry_id: &str,
+        text: &str,
+    ) -> Result<AnnotationView, ApiError> {
+        let variables = serde_json::json!({ "entryId": entry_id, "text": text });
+        let response: GraphQLResponse<AnnotateEntryResponse> = self
+            .execute_mutation(&operations::annotate_entry(), variables)
+            .await?;
+        if let Some(errors) = response.errors {
+            return Err(ApiError::GraphQL(errors));
+        }
+        let data = response
+            .data
+            .ok_or_else(|| ApiError::NotFound(format!("'{}' not annotated", entry_id)))?;
+        Ok(data.annotate_entry)
+    }
+
+    /// Subscribe to the graph's changes over WebSocket (graphql-transport-ws),
+    /// calling `on_delta` with each one until the server closes the connection.
+    /// Generated systems never change, so in demo mode this never returns.
+    pub async fn watch_deltas(&self, mut on_delta: impl FnMut(GraphDelta)) -> Result<(), ApiError> {
+        if self.demo {
+            return futures::future::pending().await;
+        }
+        let network = |e: WebSocketError| ApiError::NetworkError(e.to_string());
+        let parse = |e: serde_json::Error| ApiError::ParseError(e.to_string());
+        let mut socket =
+            WebSocket::open_with_protocol(&self.websocket_endpoint(), "graphql-transport-ws")
+                .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+        let query = operations::graph_delta();
+        for message in [
+            SocketMessage::new("connection_init", None),
+            SocketMessage::new("subscribe", Some(serde_json::json!({ "query": query }))),
+        ] {
+            let text = serde_json::to_string(&message).map_err(parse)?;
+            socket.send(Message::Text(text)).await.map_err(network)?;
+        }
+
+        while let Some(message) = socket.next().await {
+            let Message::Text(text) = message.map_err(network)? else {
+                continue;
+            };
+            let message: SocketMessage = serde_json::from_str(&text).map_err(parse)?;
+            match message.kind.as_str() {
+                "next" => {
+                    let response: GraphQLResponse<GraphDeltaResponse> =
+                        serde_json::from_value(message.payload.unwrap_or_default())
+                            .map_err(parse)?;
+                    if let Some(errors) = response.errors {
+                        return Err(ApiError::GraphQL(errors));
+                    }
+                    if let Some(data) = response.data {
+                        on_delta(data.graph_delta);
+                    }
+                }
+                "error" => {
+                    let errors = serde_json::from_value(message.payload.unwrap_or_default())
+                        .map_err(parse)?;
+                    return Err(ApiError::GraphQL(errors));
+                }
+                "ping" => {
+                    let pong =
+                        serde_json::to_string(&SocketMessage::new("pong", None)).map_err(parse)?;
+                    socket.send(Message::Text(pong)).await.map_err(network)?;
+                }
+                "complete" => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a graph delta to a system as fetched (see `prepare_system`). The
+    /// delta's coordinates are in API space, and the entries it does not
+    /// carry may be the system's, so either makes the system be fetched again.
+    pub fn apply_delta(&self, delta: &GraphDelta, system: &SystemView) -> DeltaOutcome {
+        let moved = [&delta.added, &delta.updated]
+            .iter()
+            .any(|entries| entries.coordinates.iter().any(|c| c.order == system.order));
+        if moved || !delta.other.is_empty() {
+            return DeltaOutcome::Refetch;
+        }
+
+        let mut updated = system.clone();
+        if !delta.apply_to(&mut updated) {
+            return DeltaOutcome::Unchanged;
+        }
+        match updated.validate() {
+            Ok(()) => DeltaOutcome::Applied(Box::new(updated)),
+            Err(e) => {
+                web_sys::console::warn_1(&format!("{}; fetching the system again", e).into());
+                DeltaOutcome::Refetch
+            }
+        }
+    }
+
+    /// WebSocket endpoint of subscriptions: the GraphQL endpoint's `/ws`, over
+    /// ws or wss
+    fn websocket_endpoint(&self) -> String {
+        let endpoint = format!("{}/ws", self.endpoint);
+        if let Some(rest) = endpoint.strip_prefix("http") {
+            return format!("ws{}", rest);
+        }
+        // A relative endpoint, on the page's host
+        let location = web_sys::window().map(|w| w.location());
+        let host = location
+            .as_ref()
+            .and_then(|l| l.host().ok())
+            .unwrap_or_default();
+        let scheme = match location.and_then(|l| l.protocol().ok()).as_deref() {
+            Some("https:") => "wss",
+            _ => "ws",
+        };
+        format!("{}://{}{}", scheme, host, endpoint)
+    }
+
+    /// Execute a GraphQL query as an automatic persisted query: the hash is
+    /// sent first, and the full query only if the server does not know it yet
     async fn execute_query<T: for<'de> Deserialize<'de>>(
         &self,
         query: &str,
         variables: Option<serde_json::Value>,
     ) -> Result<GraphQLResponse<T>, ApiError> {
-        let request_body = GraphQLRequest {
-            query: query.to_string(),
-            variables,
+        self.execute(query, variables, None).await
+    }
+
+    /// Execute a mutation as signed in, with the bearer token. Demo systems
+    /// are generated, so there is nothing to edit in demo mode.
+    async fn execute_mutation<T: for<'de> Deserialize<'de>>(
+        &self,
+        mutation: &str,
+        variables: serde_json::Value,
+    ) -> Result<GraphQLResponse<T>, ApiError> {
+        if self.demo {
+            return Err(ApiError::NotFound(
+                "No server to save edits to in demo mode".to_string(),
+            ));
+        }
+        self.execute(mutation, Some(variables), self.token.as_deref())
+            .await
+    }
+
+    /// Send a query or mutation as an automatic persisted query, with an
+    /// `Authorization` header if given a token
+    async fn execute<T: for<'de> Deserialize<'de>>(
+        &self,
+        query: &str,
+        variables: Option<serde_json::Value>,
+        token: Option<&str>,
+    ) -> Result<GraphQLResponse<T>, ApiError> {
+        let hash = format!("{:x}", Sha256::digest(query.as_bytes()));
+        let mut request_body = GraphQLRequest {
+            query: None,
+            variables: variables.as_ref(),
+            extensions: RequestExtensions {
+                persisted_query: PersistedQuery {
+                    version: 1,
+                    sha256_hash: &hash,
+                },
+            },
+        };
+
+        let response: GraphQLResponse<T> = self.post(&request_body, token).await?;
+        let not_found = response.errors.as_ref().is_some_and(|errors| {
+            errors.iter().any(|e| {
+                e.extensions.code == Some(ErrorCode::PersistedQueryNotFound)
+                    || e.message == PERSISTED_QUERY_NOT_FOUND
+            })
+        });
+        if !not_found {
+            return Ok(response);
+        }
+
+        request_body.query = Some(query);
+        self.post(&request_body, token).await
+    }
+
+    /// POST a request body to the GraphQL endpoint, retrying errors that may
+    /// pass (see `ApiError::is_retryable`) after the server's `Retry-After`
+    /// delay, or else a doubling backoff
+    async fn post<T: for<'de> Deserialize<'de>>(
+        &self,
+        request_body: &GraphQLRequest<'_>,
+        token: Option<&str>,
+    ) -> Result<GraphQLResponse<T>, ApiError> {
+        let mut attempt = 1;
+        loop {
+            match self.post_once(request_body, token).await {
+                Err(e) if e.is_retryable() && attempt < MAX_ATTEMPTS => {
+                    let delay = e
+                        .retry_after()
+                        .map_or(RETRY_DELAY_MS << (attempt - 1), |seconds| seconds * 1000);
+                    web_sys::console::warn_1(&format!("{}; retrying in {} ms", e, delay).into());
+                    TimeoutFuture::new(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// POST a request body to the GraphQL endpoint once, aborting it after
+    /// `REQUEST_TIMEOUT_MS`
+    async fn post_once<T: for<'de> Deserialize<'de>>(
+        &self,
+        request_body: &GraphQLRequest<'_>,
+        token: Option<&str>,
+    ) -> Result<GraphQLResponse<T>, ApiError> {
+        let controller =
+            AbortController::new().map_err(|e| ApiError::NetworkError(format!("{:?}", e)))?;
+        let signal = controller.signal();
+        // Cancelled when dropped, once the response is read
+        let _timeout = Timeout::new(REQUEST_TIMEOUT_MS, move || controller.abort());
+        let timed_out = |e: gloo_net::Error, error: fn(String) -> ApiError| {
+            if signal.aborted() {
+                ApiError::Timeout
+            } else {
+                error(e.to_string())
+            }
         };
 
-        let response = Request::post(&self.endpoint)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
+        let mut request = Request::post(&self.endpoint).header("Content-Type", "application/json");
+        if let Some(token) = token {
+            request = request.header("Authorization", &format!("Bearer {}", token));
+        }
+        let response = request
+            .abort_signal(Some(&signal))
+            .json(request_body)
             .map_err(|e| ApiError::ParseError(e.to_string()))?
             .send()
             .await
-            .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+            .map_err(|e| timed_out(e, ApiError::NetworkError))?;
 
         if !response.ok() {
-            return Err(ApiError::NetworkError(format!(
-                "Request failed with status: {}",
-                response.status()
-            )));
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|value| value.trim().parse().ok());
+            // Errors the server explains, as for rate limiting, come with a body
+            let errors = response
+                .json::<GraphQLResponse<serde_json::Value>>()
+                .await
+                .ok()
+                .and_then(|body| body.errors)
+                .unwrap_or_default();
+            return Err(ApiError::Http {
+                status: response.status(),
+                retry_after,
+                errors,
+            });
         }
 
         response
             .json::<GraphQLResponse<T>>()
             .await
-            .map_err(|e| ApiError::ParseError(e.to_string()))
+            .map_err(|e| timed_out(e, ApiError::ParseError))
     }
 
-    /// Transform coordinates from API space to viewport space (800x800 with margins)
-    fn transform_coordinates(&self, mut system: SystemView) -> SystemView {
-        let viewport_width = 800.0;
-        let viewport_height = 800.0;
-        let margin = 100.0;
+    /// Check a fetched system, then transform its coordinates from API space
+    /// to viewport space (800x800 with margins)
+    fn prepare_system(&self, mut system: SystemView) -> Result<SystemView, ApiError> {
+        // Every fetched system passes through here; a broken one fails the
+        // fetch rather than rendering a broken diagram
+        warn_if_unsupported(&system);
+        system.validate()?;
 
         // Transform main coordinates array only
         // Links will look up coordinates by position from this array
-        system.coordinates = transform_coordinates_to_viewport(
-            system.coordinates,
-            viewport_width,
-            viewport_height,
-            margin,
-        );
+        system.coordinates = transform_coordinates_to_viewport(system.coordinates, &VIEWPORT);
 
-        system
+        Ok(system)
     }
 }
 
-/// Transform coordinates from API space to viewport space
-///
-/// The API may return coordinates in any scale (e.g., 0-1, 0-10, or even 0,0,0 for single points).
-/// This function scales and centers them to fit within the viewport with margins.
-fn transform_coordinates_to_viewport(
-    coords: Vec<Coordinate>,
-    viewport_width: f64,
-    viewport_height: f64,
-    margin: f64,
-) -> Vec<Coordinate> {
-    if coords.is_empty() {
-        return coords;
-    }
-
-    // For a single point, center it in the viewport
-    if coords.len() == 1 {
-        let mut coord = coords.into_iter().next().unwrap();
-        coord.x = viewport_width / 2.0;
-        coord.y = viewport_height / 2.0;
-        return vec![coord];
+/// Log a warning for a system sent in a newer wire format than this bundle
+/// reads; it is still shown, but fields may be missing or misread
+fn warn_if_unsupported(system: &SystemView) {
+    if !is_supported_version(system.api_version) {
+        web_sys::console::warn_1(
+            &format!(
+                "System {} sent in wire format version {}, newer than this app's {}; reload to update",
+                system.order, system.api_version, API_VERSION
+            )
+            .into(),
+        );
     }
+}
 
-    // Find bounding box to determine scale
-    let mut min_x = f64::INFINITY;
-    let mut max_x = f64::NEG_INFINITY;
-    let mut min_y = f64::INFINITY;
-    let mut max_y = f64::NEG_INFINITY;
-
-    for coord in &coords {
-        min_x = min_x.min(coord.x);
-        max_x = max_x.max(coord.x);
-        min_y = min_y.min(coord.y);
-        max_y = max_y.max(coord.y);
+/// Hits for `text` among the demo systems: their names, coherences, terms
+/// and connectives, matched in any case
+fn demo_search(text: &str) -> Vec<SearchHit> {
+    let needle = text.to_lowercase();
+    let matches = |value: &str| value.to_lowercase().contains(&needle);
+    let hit = |kind, id: &str, value: &str, order, position| SearchHit {
+        kind,
+        id: id.to_string(),
+        value: value.to_string(),
+        order: Some(order),
+        position,
+    };
+    let mut hits = Vec::new();
+    for system in mock::system_views(DEMO_ORDERS) {
+        let order = system.order;
+        for (kind, value) in [
+            (SearchHitKind::SystemName, system.name.as_deref()),
+            (
+                SearchHitKind::CoherenceAttribute,
+                system.coherence.as_deref(),
+            ),
+        ] {
+            if let Some(value) = value.filter(|v| matches(v)) {
+                hits.push(hit(kind, &format!("demo_{}", order), value, order, None));
+            }
+        }
+        for term in &system.terms {
+            if let Some(c) = term.character.as_ref().filter(|c| matches(&c.value)) {
+                let kind = SearchHitKind::Term;
+                hits.push(hit(kind, &term.id, &c.value, order, Some(term.position)));
+            }
+        }
+        for view in &system.connective_views {
+            if let Some(c) = view.character.as_ref().filter(|c| matches(&c.value)) {
+                let kind = SearchHitKind::Connective;
+                hits.push(hit(kind, &view.id, &c.value, order, None));
+            }
+        }
     }
-
-    // Calculate the full extent needed to contain all points
-    let center_x = (min_x + max_x) / 2.0;
-    let center_y = (min_y + max_y) / 2.0;
-
-    let extent_x = (max_x - min_x).max(0.0001);
-    let extent_y = (max_y - min_y).max(0.0001);
-
-    // Use the larger extent for both axes to preserve aspect ratio
-    let max_extent = extent_x.max(extent_y);
-
-    // Calculate available space (viewport minus margins on both sides)
-    let available_width = viewport_width - 2.0 * margin;
-    let available_height = viewport_height - 2.0 * margin;
-
-    // Use smaller dimension to ensure graph fits in viewport
-    let available_size = available_width.min(available_height);
-
-    // Scale to fit available space
-    let scale = available_size / max_extent;
-
-    // Viewport center
-    let viewport_center_x = viewport_width / 2.0;
-    let viewport_center_y = viewport_height / 2.0;
-
-    // Transform all coordinates:
-    // 1. Translate to center at origin
-    // 2. Scale
-    // 3. Flip Y-axis (mathematical coords: y+ = up, SVG coords: y+ = down)
-    // 4. Translate to viewport center
-    coords
-        .into_iter()
-        .map(|mut coord| {
-            coord.x = (coord.x - center_x) * scale + viewport_center_x;
-            coord.y = -(coord.y - center_y) * scale + viewport_center_y; // Negate Y for SVG
-            coord
-        })
-        .collect()
+    hits
 }
@@ -1,9 +1,22 @@
-use crate::api::client::GraphQLClient;
+use crate::api::client::{DeltaOutcome, GraphQLClient};
+use crate::components::edit_panel::{EditFailure, EditPanel, EditTarget};
 use crate::components::graph_view::ApiGraphView;
+use crate::components::graph_view_3d::ApiGraphView3d;
+use crate::components::note_list::NoteList;
+use crate::components::position_detail::PositionDetail;
+use crate::components::print_view::PrintView;
+use crate::components::search_box::SearchBox;
 use crate::components::system_selector::{SystemDisplay, SystemSelector};
-use systematics_middleware::SystemView;
+use crate::notes::{LocalNote, NoteStore};
+use crate::routes::{slug, system_slug, Route, ViewQuery};
+use gloo_timers::future::TimeoutFuture;
+use systematics_middleware::{
+    AnnotationView, ApiError, CustomSystemInput, ErrorCode, GraphDelta, Language, Page, SearchHit,
+    SearchHitKind, Slice, SystemView,
+};
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
+use yew_router::prelude::*;
 
 /// Detect GraphQL endpoint based on current browser location
 /// - Development (localhost:8080): Points to http://localhost:8000/graphql
@@ -27,21 +40,139 @@ fn get_graphql_endpoint() -> String {
     "/graphql".to_string()
 }
 
+/// Whether to run without a backend, on generated systems: asked for with
+/// `?demo` in the page URL, and assumed when the browser is offline
+fn is_demo_mode() -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let requested = window
+        .location()
+        .search()
+        .is_ok_and(|search| search.contains("demo"));
+    requested || !window.navigator().on_line()
+}
+
+/// Local storage key of the bearer token edits are sent with
+const TOKEN_KEY: &str = "systematics-token";
+
+/// The browser's local storage, where it is available
+pub(crate) fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+/// Bearer token kept from an earlier sign-in
+fn stored_token() -> Option<String> {
+    local_storage().and_then(|storage| storage.get_item(TOKEN_KEY).ok().flatten())
+}
+
+/// Keep a bearer token for later visits, or forget it
+fn store_token(token: Option<&str>) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let _ = match token {
+        Some(token) => storage.set_item(TOKEN_KEY, token),
+        None => storage.remove_item(TOKEN_KEY),
+    };
+}
+
+/// Delay before subscribing again after the graph delta subscription ends
+const RESUBSCRIBE_DELAY_MS: u32 = 5_000;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Breadcrumb {
     pub system_name: String,
 }
 
+/// Search across systems: the text searched and the hits fetched so far
+#[derive(Default)]
+struct SearchState {
+    text: String,
+    hits: Vec<SearchHit>,
+    /// Hits in all pages
+    total: i32,
+    /// Cursor of the next page, if any
+    cursor: Option<String>,
+    loading: bool,
+    error: Option<String>,
+}
+
 pub enum ApiAppMsg {
     SelectSystem(String),
     SystemsLoaded(Vec<SystemView>),
+    /// No GraphQL server answered for the systems; the app carries on with
+    /// the built-in demo systems
+    BackendUnreachable(String),
+    /// Try the backend again, from a fresh start
+    RetryBackend,
     SystemLoaded(Box<SystemView>),
     LoadError(String),
     NavigateToSystem(String),
     NavigateBack,
     ToggleEdgeLabels,
+    Toggle3d,
+    SelectPosition(Option<i32>),
+    /// Select the line between two positions, or clear the selection
+    SelectEdge(Option<(i32, i32)>),
+    SelectLanguage(Language),
+    /// Search every system for a text (empty to clear the search)
+    Search(String),
+    SearchMore,
+    /// A page of hits for a text, to follow those listed if `more`
+    SearchLoaded(String, Box<Page<SearchHit>>, bool),
+    SearchFailed(String, String),
+    PickSearchHit(SearchHit),
+    /// The URL changed, by the app or the browser's back and forward
+    LocationChanged,
+    SliceLoaded(Box<Slice>),
+    GraphChanged(Box<GraphDelta>),
+    SystemRefreshed(Box<SystemView>),
+    ToggleEdit,
+    /// Keep a bearer token to send edits with
+    SignIn(String),
+    SignOut,
+    /// Rename the term at a position of the selected system
+    RenameTerm(i32, String),
+    /// Label the connective between two positions of the selected system
+    LabelConnective((i32, i32), String),
+    CreateCustomSystem(CustomSystemInput),
+    /// The selected system as the server has it after an edit, in the
+    /// vocabulary edited
+    EditSaved(Box<SystemView>, Language),
+    /// An edit was refused; `signed_out` if for the token
+    EditFailed {
+        failure: EditFailure,
+        signed_out: bool,
+    },
+    SystemCreated(Box<SystemView>),
+    /// Write a note on an entry or link (a term's location or a connective)
+    AddNote(String, String),
+    RemoveNote(u32),
+    /// A note was sent to the server, as the annotation with this ID
+    NoteSynced(u32, String),
+    /// Notes on an entry or link on the server
+    SharedNotesLoaded(String, Vec<AnnotationView>),
+    /// Open the print view of the selected system, or of every system
+    Print(bool),
+    ClosePrint,
+    /// Every system, in a vocabulary, for the print view
+    PrintSystemsLoaded(Language, Vec<SystemView>),
 }
 
+/// The app within a browser router, which keeps the view in the URL
+#[function_component(App)]
+pub fn app() -> Html {
+    html! {
+        <BrowserRouter>
+            <ApiApp />
+        </BrowserRouter>
+    }
+}
+
+/// The system explorer. What is shown (system, edge labels, vocabulary and
+/// selected node) follows the URL: interactions push a new URL, and each URL,
+/// pushed or restored with back and forward, is applied as it comes.
 pub struct ApiApp {
     systems: Vec<SystemView>,
     selected_system: Option<SystemView>,
@@ -50,6 +181,38 @@ pub struct ApiApp {
     graphql_client: GraphQLClient,
     breadcrumbs: Vec<Breadcrumb>,
     show_edge_labels: bool,
+    /// Render the WebGL 3D view instead of the SVG graph
+    show_3d: bool,
+    /// Vocabulary of the selected system's terms
+    language: Language,
+    /// Selected node of the selected system
+    selected_position: Option<i32>,
+    /// Highlighted line of the selected system, by its two positions
+    highlighted_edge: Option<(i32, i32)>,
+    search: SearchState,
+    /// Slice at the selected position of the selected system
+    selected_slice: Option<Slice>,
+    demo: bool,
+    /// Why the built-in demo systems are shown, when the backend could not be
+    /// reached
+    offline: Option<String>,
+    /// Show the edit panel for the selected system
+    editing: bool,
+    /// Edits shown before the server has answered them
+    pending_edits: usize,
+    /// The selected system as last confirmed, restored if a pending edit is
+    /// refused
+    confirmed_system: Option<SystemView>,
+    edit_failure: Option<EditFailure>,
+    /// Notes kept in this browser
+    notes: NoteStore,
+    /// Notes being sent to the server, by ID
+    syncing: Vec<u32>,
+    /// Notes on the server for the selected term or connective, by its ID
+    shared_notes: Option<(String, Vec<AnnotationView>)>,
+    /// Every system in the vocabulary of the print view, when not canonical
+    print_systems: Option<(Language, Vec<SystemView>)>,
+    _location_listener: Option<LocationHandle>,
 }
 
 impl Component for ApiApp {
@@ -58,25 +221,31 @@ impl Component for ApiApp {
 
     fn create(ctx: &Context<Self>) -> Self {
         // GraphQL endpoint - auto-detected based on environment
-        let graphql_endpoint = get_graphql_endpoint();
-        let graphql_client = GraphQLClient::new(graphql_endpoint);
+        let demo = is_demo_mode();
+        let graphql_client = if demo {
+            GraphQLClient::demo()
+        } else {
+            GraphQLClient::new(get_graphql_endpoint()).with_token(stored_token())
+        };
 
-        // Load all systems on initialization
+        // Keep the selected system current as the graph changes
         let link = ctx.link().clone();
         let client = graphql_client.clone();
-
         spawn_local(async move {
-            match client.fetch_all_systems().await {
-                Ok(systems) => {
-                    link.send_message(ApiAppMsg::SystemsLoaded(systems));
-                }
-                Err(e) => {
-                    link.send_message(ApiAppMsg::LoadError(e.to_string()));
+            loop {
+                let on_delta = |delta| link.send_message(ApiAppMsg::GraphChanged(Box::new(delta)));
+                if let Err(e) = client.watch_deltas(on_delta).await {
+                    web_sys::console::warn_1(&format!("Graph updates stopped: {}", e).into());
                 }
+                TimeoutFuture::new(RESUBSCRIBE_DELAY_MS).await;
             }
         });
 
-        Self {
+        let location_listener = ctx
+            .link()
+            .add_location_listener(ctx.link().callback(|_| ApiAppMsg::LocationChanged));
+
+        let mut app = Self {
             systems: vec![],
             selected_system: None,
             loading: true,
@@ -84,7 +253,28 @@ impl Component for ApiApp {
             graphql_client,
             breadcrumbs: vec![],
             show_edge_labels: false,
-        }
+            show_3d: false,
+            language: Language::Canonical,
+            selected_position: None,
+            highlighted_edge: None,
+            search: SearchState::default(),
+            selected_slice: None,
+            demo,
+            offline: None,
+            editing: false,
+            pending_edits: 0,
+            confirmed_system: None,
+            edit_failure: None,
+            notes: NoteStore::load(),
+            syncing: Vec::new(),
+            shared_notes: None,
+            print_systems: None,
+            _location_listener: location_listener,
+        };
+        // Load all systems on initialization
+        app.load_systems(ctx);
+        app.sync_notes(ctx);
+        app
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -92,25 +282,8 @@ impl Component for ApiApp {
             ApiAppMsg::SelectSystem(name) => {
                 // Clear breadcrumbs when manually selecting from sidebar
                 self.breadcrumbs.clear();
-                self.loading = true;
-                self.error = None;
-
-                // Fetch the selected system
-                let link = ctx.link().clone();
-                let client = self.graphql_client.clone();
-
-                spawn_local(async move {
-                    match client.fetch_system(&name).await {
-                        Ok(system) => {
-                            link.send_message(ApiAppMsg::SystemLoaded(Box::new(system)));
-                        }
-                        Err(e) => {
-                            link.send_message(ApiAppMsg::LoadError(e.to_string()));
-                        }
-                    }
-                });
-
-                true
+                self.navigate(ctx, &name, self.view_query(None));
+                false
             }
             ApiAppMsg::NavigateToSystem(name) => {
                 // Add current system to breadcrumbs before navigating
@@ -122,50 +295,15 @@ impl Component for ApiApp {
                             .unwrap_or_else(|| current.display_name()),
                     });
                 }
-
-                self.loading = true;
-                self.error = None;
-
-                // Fetch the target system
-                let link = ctx.link().clone();
-                let client = self.graphql_client.clone();
-
-                spawn_local(async move {
-                    match client.fetch_system(&name).await {
-                        Ok(system) => {
-                            link.send_message(ApiAppMsg::SystemLoaded(Box::new(system)));
-                        }
-                        Err(e) => {
-                            link.send_message(ApiAppMsg::LoadError(e.to_string()));
-                        }
-                    }
-                });
-
-                true
+                self.navigate(ctx, &name, self.view_query(None));
+                false
             }
             ApiAppMsg::NavigateBack => {
-                if let Some(breadcrumb) = self.breadcrumbs.pop() {
-                    self.loading = true;
-                    self.error = None;
-
-                    // Fetch the previous system
-                    let link = ctx.link().clone();
-                    let client = self.graphql_client.clone();
-                    let name = breadcrumb.system_name;
-
-                    spawn_local(async move {
-                        match client.fetch_system(&name).await {
-                            Ok(system) => {
-                                link.send_message(ApiAppMsg::SystemLoaded(Box::new(system)));
-                            }
-                            Err(e) => {
-                                link.send_message(ApiAppMsg::LoadError(e.to_string()));
-                            }
-                        }
-                    });
+                // The breadcrumb is popped as its system's URL is applied
+                if let Some(breadcrumb) = self.breadcrumbs.last() {
+                    self.navigate(ctx, &breadcrumb.system_name, self.view_query(None));
                 }
-
-                true
+                false
             }
             ApiAppMsg::SystemsLoaded(systems) => {
                 self.loading = false;
@@ -179,17 +317,34 @@ impl Component for ApiApp {
                     );
                 }
 
-                // Select the first system by default
-                if let Some(first_system) = systems.first() {
-                    self.selected_system = Some(first_system.clone());
-                }
-
                 self.systems = systems;
+                self.apply_location(ctx)
+            }
+            ApiAppMsg::BackendUnreachable(error) => {
+                web_sys::console::warn_1(
+                    &format!("Backend unreachable ({}); showing the demo systems", error).into(),
+                );
+                self.graphql_client = GraphQLClient::demo();
+                self.offline = Some(error);
+                self.editing = false;
+                self.load_systems(ctx);
                 true
             }
+            ApiAppMsg::RetryBackend => {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().reload();
+                }
+                false
+            }
             ApiAppMsg::SystemLoaded(system) => {
                 self.loading = false;
+                // Answers to edits of the system left are ignored
+                self.pending_edits = 0;
+                self.confirmed_system = None;
+                self.edit_failure = None;
                 self.selected_system = Some(*system);
+                self.load_slice(ctx);
+                self.load_shared_notes(ctx);
                 true
             }
             ApiAppMsg::LoadError(error) => {
@@ -198,9 +353,297 @@ impl Component for ApiApp {
                 true
             }
             ApiAppMsg::ToggleEdgeLabels => {
+                if self.selected_system.is_some() {
+                    let query = ViewQuery {
+                        labels: (!self.show_edge_labels).then(|| "on".to_string()),
+                        ..self.view_query(self.selected_position)
+                    }
+                    .with_edge(self.highlighted_edge);
+                    self.navigate_here(ctx, query);
+                    return false;
+                }
                 self.show_edge_labels = !self.show_edge_labels;
                 true
             }
+            ApiAppMsg::Toggle3d => {
+                self.show_3d = !self.show_3d;
+                true
+            }
+            ApiAppMsg::SelectPosition(position) => {
+                self.navigate_here(ctx, self.view_query(position));
+                false
+            }
+            ApiAppMsg::SelectEdge(edge) => {
+                self.navigate_here(ctx, self.view_query(None).with_edge(edge));
+                false
+            }
+            ApiAppMsg::SelectLanguage(language) => {
+                let query = ViewQuery::new(
+                    self.show_edge_labels,
+                    language,
+                    self.selected_position,
+                    self.demo,
+                )
+                .with_edge(self.highlighted_edge);
+                self.navigate_here(ctx, query);
+                false
+            }
+            ApiAppMsg::Search(text) => {
+                self.search = SearchState {
+                    loading: !text.is_empty(),
+                    text,
+                    ..SearchState::default()
+                };
+                self.load_search(ctx, None);
+                true
+            }
+            ApiAppMsg::SearchMore => {
+                let Some(cursor) = self.search.cursor.clone() else {
+                    return false;
+                };
+                self.search.loading = true;
+                self.load_search(ctx, Some(cursor));
+                true
+            }
+            ApiAppMsg::SearchLoaded(text, page, more) => {
+                // Ignore hits for a text since replaced
+                if text != self.search.text {
+                    return false;
+                }
+                let page = *page;
+                self.search.loading = false;
+                self.search.total = page.total;
+                self.search.cursor = page.next_cursor().map(str::to_string);
+                if !more {
+                    self.search.hits.clear();
+                }
+                self.search.hits.extend(page.items);
+                true
+            }
+            ApiAppMsg::SearchFailed(text, error) => {
+                if text != self.search.text {
+                    return false;
+                }
+                self.search.loading = false;
+                self.search.error = Some(error);
+                true
+            }
+            ApiAppMsg::PickSearchHit(hit) => {
+                let Some(system) = hit
+                    .order
+                    .and_then(|order| self.systems.iter().find(|s| s.order == order))
+                else {
+                    return false;
+                };
+                // Connectives are found by link ID among the system's views
+                let edge = match hit.kind {
+                    SearchHitKind::Connective => system
+                        .connective_views
+                        .iter()
+                        .find(|view| view.id == hit.id)
+                        .map(|view| (view.base_position, view.target_position)),
+                    _ => None,
+                };
+                let position = hit.position.filter(|_| edge.is_none());
+                self.breadcrumbs.clear();
+                let query = self.view_query(position).with_edge(edge);
+                self.navigate(ctx, &system_slug(system), query);
+                false
+            }
+            ApiAppMsg::LocationChanged => self.apply_location(ctx),
+            ApiAppMsg::GraphChanged(delta) => {
+                let Some(system) = &self.selected_system else {
+                    return false;
+                };
+                match self.graphql_client.apply_delta(&delta, system) {
+                    DeltaOutcome::Unchanged => false,
+                    DeltaOutcome::Applied(system) => {
+                        self.selected_system = Some(*system);
+                        true
+                    }
+                    DeltaOutcome::Refetch => {
+                        let link = ctx.link().clone();
+                        let client = self.graphql_client.clone();
+                        let order = system.order;
+                        let language = self.language;
+                        spawn_local(async move {
+                            match client.fetch_system_by_order(order, language).await {
+                                Ok(system) => {
+                                    link.send_message(ApiAppMsg::SystemRefreshed(Box::new(system)))
+                                }
+                                Err(e) => web_sys::console::log_1(
+                                    &format!("Failed to refresh system: {}", e).into(),
+                                ),
+                            }
+                        });
+                        false
+                    }
+                }
+            }
+            ApiAppMsg::SystemRefreshed(system) => {
+                // Ignore a refresh that arrives after its system was left
+                let current = self.selected_system.as_ref().map(|s| s.order);
+                if current != Some(system.order) {
+                    return false;
+                }
+                self.selected_system = Some(*system);
+                true
+            }
+            ApiAppMsg::ToggleEdit => {
+                self.editing = !self.editing;
+                self.edit_failure = None;
+                true
+            }
+            ApiAppMsg::SignIn(token) => {
+                store_token(Some(&token));
+                self.graphql_client = self.graphql_client.clone().with_token(Some(token));
+                self.sync_notes(ctx);
+                self.load_shared_notes(ctx);
+                true
+            }
+            ApiAppMsg::SignOut => {
+                store_token(None);
+                self.graphql_client = self.graphql_client.clone().with_token(None);
+                self.shared_notes = None;
+                true
+            }
+            ApiAppMsg::RenameTerm(position, value) => {
+                let language = self.language;
+                if !self.edit_shown(|system| system.rename_term(position, language, &value)) {
+                    return false;
+                }
+                let client = self.graphql_client.clone();
+                let order = self.selected_system.as_ref().map_or(0, |s| s.order);
+                self.save_edit(ctx, EditTarget::Term(position), async move {
+                    client.rename_term(order, position, &value, language).await
+                });
+                true
+            }
+            ApiAppMsg::LabelConnective(edge, value) => {
+                let language = self.language;
+                if !self
+                    .edit_shown(|system| system.label_connective(edge.0, edge.1, language, &value))
+                {
+                    return false;
+                }
+                let client = self.graphql_client.clone();
+                let order = self.selected_system.as_ref().map_or(0, |s| s.order);
+                self.save_edit(ctx, EditTarget::Connective(edge.0, edge.1), async move {
+                    client.label_connective(order, edge, &value, language).await
+                });
+                true
+            }
+            ApiAppMsg::CreateCustomSystem(input) => {
+                self.edit_failure = None;
+                let link = ctx.link().clone();
+                let client = self.graphql_client.clone();
+                spawn_local(async move {
+                    match client.create_custom_system(&input).await {
+                        Ok(system) => link.send_message(ApiAppMsg::SystemCreated(Box::new(system))),
+                        Err(e) => link.send_message(ApiAppMsg::EditFailed {
+                            signed_out: e.code() == Some(ErrorCode::Unauthenticated),
+                            failure: EditFailure::new(EditTarget::NewSystem, &e),
+                        }),
+                    }
+                });
+                true
+            }
+            ApiAppMsg::EditSaved(system, language) => {
+                let current = self.selected_system.as_ref().map(|s| s.order);
+                if current != Some(system.order) || language != self.language {
+                    return false;
+                }
+                self.pending_edits = self.pending_edits.saturating_sub(1);
+                if self.pending_edits == 0 {
+                    // The server's copy replaces the one edited in place
+                    self.confirmed_system = None;
+                    self.selected_system = Some(*system);
+                    self.load_slice(ctx);
+                } else {
+                    self.confirmed_system = Some(*system);
+                }
+                true
+            }
+            ApiAppMsg::EditFailed {
+                failure,
+                signed_out,
+            } => {
+                if failure.target != EditTarget::NewSystem {
+                    self.pending_edits = self.pending_edits.saturating_sub(1);
+                    if let Some(system) = self.confirmed_system.clone() {
+                        self.selected_system = Some(system);
+                    }
+                    if self.pending_edits == 0 {
+                        self.confirmed_system = None;
+                    }
+                }
+                if signed_out {
+                    store_token(None);
+                    self.graphql_client = self.graphql_client.clone().with_token(None);
+                }
+                self.edit_failure = Some(failure);
+                true
+            }
+            ApiAppMsg::SystemCreated(system) => {
+                self.edit_failure = None;
+                let name = system_slug(&system);
+                self.systems.push(*system);
+                self.systems.sort_by_key(|s| s.order);
+                self.breadcrumbs.clear();
+                self.navigate(ctx, &name, self.view_query(None));
+                true
+            }
+            ApiAppMsg::AddNote(entry_id, text) => {
+                self.notes.add(&entry_id, &text);
+                self.sync_notes(ctx);
+                true
+            }
+            ApiAppMsg::RemoveNote(id) => {
+                self.notes.remove(id);
+                true
+            }
+            ApiAppMsg::NoteSynced(id, remote_id) => {
+                self.syncing.retain(|&s| s != id);
+                self.notes.mark_synced(id, remote_id);
+                true
+            }
+            ApiAppMsg::SharedNotesLoaded(entry_id, annotations) => {
+                if self.noted_entry().as_deref() != Some(entry_id.as_str()) {
+                    return false;
+                }
+                self.shared_notes = Some((entry_id, annotations));
+                true
+            }
+            ApiAppMsg::Print(all) => {
+                let route = match (&self.selected_system, all) {
+                    (Some(system), false) => Route::Print {
+                        name: system_slug(system),
+                    },
+                    _ => Route::PrintAll,
+                };
+                self.push(ctx, &route, &self.view_query(self.selected_position));
+                false
+            }
+            ApiAppMsg::ClosePrint => {
+                match &self.selected_system {
+                    Some(_) => self.navigate_here(ctx, self.view_query(self.selected_position)),
+                    None => self.push(ctx, &Route::Home, &self.view_query(None)),
+                }
+                false
+            }
+            ApiAppMsg::PrintSystemsLoaded(language, systems) => {
+                self.print_systems = Some((language, systems));
+                true
+            }
+            ApiAppMsg::SliceLoaded(slice) => {
+                // Ignore a slice that arrives after its system or node was left
+                let current = self.selected_system.as_ref().map(|s| s.order);
+                if current != Some(slice.order) || self.selected_position != Some(slice.position) {
+                    return false;
+                }
+                self.selected_slice = Some(*slice);
+                true
+            }
         }
     }
 
@@ -209,6 +652,22 @@ impl Component for ApiApp {
         let on_navigate = ctx.link().callback(ApiAppMsg::NavigateToSystem);
         let on_back = ctx.link().callback(|_| ApiAppMsg::NavigateBack);
         let on_toggle_edge_labels = ctx.link().callback(|_| ApiAppMsg::ToggleEdgeLabels);
+        let on_toggle_3d = ctx.link().callback(|_| ApiAppMsg::Toggle3d);
+        let on_select_position = ctx.link().callback(ApiAppMsg::SelectPosition);
+        let on_select_edge = ctx.link().callback(ApiAppMsg::SelectEdge);
+        let on_close_detail = ctx.link().callback(|_| ApiAppMsg::SelectPosition(None));
+        let on_select_language = ctx.link().callback(ApiAppMsg::SelectLanguage);
+        let on_search = ctx.link().callback(ApiAppMsg::Search);
+        let on_search_more = ctx.link().callback(|_| ApiAppMsg::SearchMore);
+        let on_pick = ctx.link().callback(ApiAppMsg::PickSearchHit);
+        // Demo systems are generated, so there is nothing to save edits to
+        let on_toggle_edit = (!self.demo && self.offline.is_none())
+            .then(|| ctx.link().callback(|_| ApiAppMsg::ToggleEdit));
+        match ctx.link().route::<Route>() {
+            Some(Route::Print { .. }) => return self.render_print(ctx, false),
+            Some(Route::PrintAll) => return self.render_print(ctx, true),
+            _ => {}
+        }
 
         html! {
             <div class="app">
@@ -224,6 +683,7 @@ impl Component for ApiApp {
                                         name: sys.name.clone().unwrap_or_else(|| sys.display_name().to_lowercase()),
                                         display_name: sys.display_name(),
                                         k_notation: sys.k_notation(),
+                                        is_canonical: sys.is_canonical,
                                     }
                                 }).collect();
 
@@ -239,6 +699,12 @@ impl Component for ApiApp {
                                         on_select={ on_select }
                                         show_edge_labels={ self.show_edge_labels }
                                         on_toggle_edge_labels={ Some(on_toggle_edge_labels.clone()) }
+                                        show_3d={ self.show_3d }
+                                        on_toggle_3d={ Some(on_toggle_3d.clone()) }
+                                        language={ self.language }
+                                        on_select_language={ Some(on_select_language.clone()) }
+                                        editing={ self.editing }
+                                        { on_toggle_edit }
                                     />
                                 }
                             }
@@ -246,6 +712,27 @@ impl Component for ApiApp {
                     </aside>
 
                     <main class="main-view">
+                        if let Some(ref reason) = self.offline {
+                            <div class="offline-banner" role="status">
+                                <span title={ reason.clone() }>
+                                    { "The server could not be reached, so the built-in demo systems are shown, with generated labels." }
+                                </span>
+                                <button class="offline-retry" onclick={ ctx.link().callback(|_| ApiAppMsg::RetryBackend) }>
+                                    { "Try again" }
+                                </button>
+                            </div>
+                        }
+                        <SearchBox
+                            hits={ self.search.hits.clone() }
+                            total={ self.search.total }
+                            has_more={ self.search.cursor.is_some() }
+                            loading={ self.search.loading }
+                            error={ self.search.error.clone() }
+                            { on_search }
+                            on_more={ on_search_more }
+                            { on_pick }
+                        />
+
                         // Breadcrumb trail
                         if !self.breadcrumbs.is_empty() {
                             <nav class="breadcrumbs">
@@ -276,15 +763,69 @@ impl Component for ApiApp {
                                         <p>{ error }</p>
                                     </div>
                                 }
-                            } else if self.loading {
+                            } else if self.loading && self.selected_system.is_none() {
                                 html! { <div class="loading">{"Loading system..."}</div> }
                             } else if let Some(ref system) = self.selected_system {
+                                // The shown system stays while the next loads, so
+                                // the graph view can morph from it
                                 html! {
-                                    <ApiGraphView
-                                        system={ system.clone() }
-                                        on_navigate={ Some(on_navigate) }
-                                        show_edge_labels={ self.show_edge_labels }
-                                    />
+                                    <>
+                                        if self.show_3d {
+                                            <ApiGraphView3d
+                                                system={ system.clone() }
+                                                selected_position={ self.selected_position }
+                                            />
+                                        } else {
+                                            <ApiGraphView
+                                                system={ system.clone() }
+                                                on_navigate={ Some(on_navigate) }
+                                                show_edge_labels={ self.show_edge_labels }
+                                                selected_position={ self.selected_position }
+                                                selected_edge={ self.highlighted_edge }
+                                                on_select_position={ Some(on_select_position) }
+                                                on_select_edge={ Some(on_select_edge) }
+                                                noted_positions={ (1..=system.order).filter(|&p| self.notes.has(&system.location_id(p))).collect::<Vec<_>>() }
+                                                noted_edges={ system.connective_views.iter().filter(|c| self.notes.has(&c.id)).map(|c| (c.base_position, c.target_position)).collect::<Vec<_>>() }
+                                                on_print={ Some(ctx.link().callback(|_| ApiAppMsg::Print(false))) }
+                                            />
+                                        }
+                                        if let Some(ref slice) = self.selected_slice {
+                                            <PositionDetail
+                                                slice={ slice.clone() }
+                                                on_close={ on_close_detail }
+                                                notes={ self.notes.on(&system.location_id(slice.position)) }
+                                                shared_notes={ self.shared_notes_on(&system.location_id(slice.position)) }
+                                                noted_links={ slice.connectives.iter().filter(|l| self.notes.has(&l.id)).map(|l| l.id.clone()).collect::<Vec<_>>() }
+                                                on_add_note={ self.on_add_note(ctx, system.location_id(slice.position)) }
+                                                on_remove_note={ ctx.link().callback(ApiAppMsg::RemoveNote) }
+                                            />
+                                        } else if let Some(view) = self.highlighted_edge.and_then(|(a, b)| system.connective_view_between(a, b)) {
+                                            <aside class="position-detail">
+                                                <header>
+                                                    <h3>{ view.title() }</h3>
+                                                    <button
+                                                        class="position-detail-close"
+                                                        onclick={ ctx.link().callback(|_| ApiAppMsg::SelectEdge(None)) }
+                                                    >
+                                                        { "×" }
+                                                    </button>
+                                                </header>
+                                                if let Some(label) = view.label() {
+                                                    <p class="position-term">{ label }</p>
+                                                }
+                                                <NoteList
+                                                    subject={ system.line_description(view.base_position, view.target_position) }
+                                                    notes={ self.notes.on(&view.id) }
+                                                    shared={ self.shared_notes_on(&view.id) }
+                                                    on_add={ self.on_add_note(ctx, view.id.clone()) }
+                                                    on_remove={ ctx.link().callback(ApiAppMsg::RemoveNote) }
+                                                />
+                                            </aside>
+                                        }
+                                        if self.editing {
+                                            { self.render_edit_panel(ctx, system) }
+                                        }
+                                    </>
                                 }
                             } else {
                                 html! { <div class="loading">{"Select a system"}</div> }
@@ -296,3 +837,387 @@ impl Component for ApiApp {
         }
     }
 }
+
+impl ApiApp {
+    /// The current view, with a node selected at `position`
+    fn view_query(&self, position: Option<i32>) -> ViewQuery {
+        ViewQuery::new(self.show_edge_labels, self.language, position, self.demo)
+    }
+
+    /// Push the URL of a system's view, which is then applied
+    fn navigate(&self, ctx: &Context<Self>, name: &str, query: ViewQuery) {
+        let route = Route::System { name: slug(name) };
+        self.push(ctx, &route, &query);
+    }
+
+    /// Push a URL, which is then applied
+    fn push(&self, ctx: &Context<Self>, route: &Route, query: &ViewQuery) {
+        let Some(navigator) = ctx.link().navigator() else {
+            return;
+        };
+        if let Err(e) = navigator.push_with_query(route, query) {
+            web_sys::console::warn_1(&format!("Failed to update the URL: {}", e).into());
+        }
+    }
+
+    /// Push the URL of another view of the selected system
+    fn navigate_here(&self, ctx: &Context<Self>, query: ViewQuery) {
+        if let Some(system) = &self.selected_system {
+            self.navigate(ctx, &system_slug(system), query);
+        }
+    }
+
+    /// Show what the URL names, fetching what is not loaded yet. Applied once
+    /// the systems are loaded, since routes name systems by slug.
+    fn apply_location(&mut self, ctx: &Context<Self>) -> bool {
+        let query = ctx
+            .link()
+            .location()
+            .and_then(|location| location.query::<ViewQuery>().ok())
+            .unwrap_or_default();
+        self.show_edge_labels = query.show_edge_labels();
+        self.highlighted_edge = query.edge();
+        if self.systems.is_empty() {
+            return true;
+        }
+
+        let route = ctx.link().route::<Route>();
+        if route == Some(Route::PrintAll) {
+            self.load_print_systems(ctx, query.language());
+            // The system shown before stays selected, to go back to
+            if self.selected_system.is_some() {
+                return true;
+            }
+        }
+        let target = match route {
+            Some(Route::System { name }) | Some(Route::Print { name }) => {
+                match self.systems.iter().find(|s| system_slug(s) == name) {
+                    Some(system) => system.clone(),
+                    None => {
+                        self.error = Some(format!("System '{}' not found", name));
+                        return true;
+                    }
+                }
+            }
+            _ => match self.systems.first() {
+                Some(system) => system.clone(),
+                None => return true,
+            },
+        };
+        self.error = None;
+
+        // Arriving at the last breadcrumb's system goes back along the trail
+        if self
+            .breadcrumbs
+            .last()
+            .is_some_and(|crumb| slug(&crumb.system_name) == system_slug(&target))
+        {
+            self.breadcrumbs.pop();
+        }
+
+        let language = query.language();
+        let shown = self.selected_system.as_ref().map(|s| s.order) == Some(target.order)
+            && self.language == language;
+        self.language = language;
+        if !shown {
+            self.selected_position = query.position;
+            self.selected_slice = None;
+            if self.selected_system.is_none() && language == Language::Canonical {
+                // The loaded systems are canonical, so the first needs no fetch
+                self.selected_system = Some(target);
+                self.load_slice(ctx);
+            } else {
+                self.load_system(ctx, target.order);
+            }
+        } else if self.selected_position != query.position {
+            self.selected_position = query.position;
+            self.load_slice(ctx);
+        }
+        self.load_shared_notes(ctx);
+        true
+    }
+
+    /// Every system on its own page, or the selected one, for printing
+    fn render_print(&self, ctx: &Context<Self>, all: bool) -> Html {
+        if let Some(ref error) = self.error {
+            return html! {
+                <div class="error">
+                    <h2>{"Error"}</h2>
+                    <p>{ error }</p>
+                </div>
+            };
+        }
+        let systems = match (all, &self.print_systems) {
+            (true, _) if self.language == Language::Canonical => self.systems.clone(),
+            (true, Some((language, systems))) if *language == self.language => systems.clone(),
+            (true, _) => Vec::new(),
+            (false, _) => self.selected_system.iter().cloned().collect(),
+        };
+        html! {
+            <PrintView
+                { systems }
+                { all }
+                language={ self.language }
+                show_edge_labels={ self.show_edge_labels }
+                on_scope={ ctx.link().callback(ApiAppMsg::Print) }
+                on_close={ ctx.link().callback(|_| ApiAppMsg::ClosePrint) }
+            />
+        }
+    }
+
+    /// Fetch every system in a vocabulary for the print view; the loaded
+    /// systems are canonical
+    fn load_print_systems(&mut self, ctx: &Context<Self>, language: Language) {
+        let loaded = self.print_systems.as_ref().map(|(l, _)| *l) == Some(language);
+        if language == Language::Canonical || loaded {
+            return;
+        }
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+        let orders: Vec<i32> = self.systems.iter().map(|s| s.order).collect();
+        spawn_local(async move {
+            let fetches = orders
+                .into_iter()
+                .map(|order| client.fetch_system_by_order(order, language));
+            match futures::future::try_join_all(fetches).await {
+                Ok(systems) => link.send_message(ApiAppMsg::PrintSystemsLoaded(language, systems)),
+                Err(e) => link.send_message(ApiAppMsg::LoadError(e.to_string())),
+            }
+        });
+    }
+
+    /// Edit panel for the selected system
+    fn render_edit_panel(&self, ctx: &Context<Self>, system: &SystemView) -> Html {
+        let link = ctx.link();
+        html! {
+            <EditPanel
+                system={ system.clone() }
+                language={ self.language }
+                orders={ self.systems.iter().map(|s| s.order).collect::<Vec<_>>() }
+                signed_in={ self.graphql_client.can_edit() }
+                pending={ self.pending_edits }
+                failure={ self.edit_failure.clone() }
+                on_sign_in={ link.callback(ApiAppMsg::SignIn) }
+                on_sign_out={ link.callback(|_| ApiAppMsg::SignOut) }
+                on_rename={ link.callback(|(position, value)| ApiAppMsg::RenameTerm(position, value)) }
+                on_label={ link.callback(|(edge, value)| ApiAppMsg::LabelConnective(edge, value)) }
+                on_create={ link.callback(ApiAppMsg::CreateCustomSystem) }
+                on_close={ link.callback(|_| ApiAppMsg::ToggleEdit) }
+            />
+        }
+    }
+
+    /// Show an edit to the selected system before the server answers it,
+    /// keeping the last confirmed copy to restore if it is refused. False if
+    /// the edit does not apply to the system.
+    fn edit_shown(&mut self, edit: impl FnOnce(&mut SystemView) -> bool) -> bool {
+        let Some(system) = &mut self.selected_system else {
+            return false;
+        };
+        let confirmed = system.clone();
+        if !edit(system) {
+            return false;
+        }
+        if self.pending_edits == 0 {
+            self.confirmed_system = Some(confirmed);
+        }
+        self.pending_edits += 1;
+        self.edit_failure = None;
+        true
+    }
+
+    /// Send an edit shown by `edit_shown`, answering with the saved system
+    /// or the failure
+    fn save_edit(
+        &self,
+        ctx: &Context<Self>,
+        target: EditTarget,
+        edit: impl std::future::Future<Output = Result<SystemView, ApiError>> + 'static,
+    ) {
+        let link = ctx.link().clone();
+        let language = self.language;
+        spawn_local(async move {
+            match edit.await {
+                Ok(system) => link.send_message(ApiAppMsg::EditSaved(Box::new(system), language)),
+                Err(e) => link.send_message(ApiAppMsg::EditFailed {
+                    signed_out: e.code() == Some(ErrorCode::Unauthenticated),
+                    failure: EditFailure::new(target, &e),
+                }),
+            }
+        });
+    }
+
+    /// ID the notes on the selection are attached to: the selected node's
+    /// location, or else the highlighted line's connective
+    fn noted_entry(&self) -> Option<String> {
+        let system = self.selected_system.as_ref()?;
+        match (self.selected_position, self.highlighted_edge) {
+            (Some(position), _) => Some(system.location_id(position)),
+            (None, Some((a, b))) => system.connective_view_between(a, b).map(|c| c.id.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// Notes on an entry or link on the server, other than those sent from
+    /// this browser
+    fn shared_notes_on(&self, entry_id: &str) -> Vec<AnnotationView> {
+        let sent = self.notes.remote_ids();
+        match &self.shared_notes {
+            Some((id, annotations)) if id == entry_id => annotations
+                .iter()
+                .filter(|a| !sent.contains(&a.id.as_str()))
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_add_note(&self, ctx: &Context<Self>, entry_id: String) -> Callback<String> {
+        ctx.link()
+            .callback(move |text| ApiAppMsg::AddNote(entry_id.clone(), text))
+    }
+
+    /// Fetch the server's notes on the selection, for signed-in users
+    fn load_shared_notes(&mut self, ctx: &Context<Self>) {
+        let entry_id = match self.noted_entry() {
+            Some(id) if self.graphql_client.can_edit() => id,
+            _ => {
+                self.shared_notes = None;
+                return;
+            }
+        };
+        if self
+            .shared_notes
+            .as_ref()
+            .is_some_and(|(id, _)| *id == entry_id)
+        {
+            return;
+        }
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+        spawn_local(async move {
+            match client.fetch_annotations(&entry_id).await {
+                Ok(annotations) => {
+                    link.send_message(ApiAppMsg::SharedNotesLoaded(entry_id, annotations))
+                }
+                Err(e) => web_sys::console::warn_1(&format!("Failed to load notes: {}", e).into()),
+            }
+        });
+    }
+
+    /// Send the notes not yet on the server, when signed in. A note that
+    /// fails to send stays local, and is tried again on the next visit or
+    /// sign-in.
+    fn sync_notes(&mut self, ctx: &Context<Self>) {
+        if !self.graphql_client.can_edit() {
+            return;
+        }
+        let notes: Vec<LocalNote> = self
+            .notes
+            .unsynced()
+            .into_iter()
+            .filter(|n| !self.syncing.contains(&n.id))
+            .collect();
+        for note in notes {
+            self.syncing.push(note.id);
+            let link = ctx.link().clone();
+            let client = self.graphql_client.clone();
+            spawn_local(async move {
+                match client.annotate_entry(&note.entry_id, &note.text).await {
+                    Ok(annotation) => {
+                        link.send_message(ApiAppMsg::NoteSynced(note.id, annotation.id))
+                    }
+                    Err(e) => {
+                        web_sys::console::warn_1(&format!("Failed to save a note: {}", e).into())
+                    }
+                }
+            });
+        }
+    }
+
+    /// Fetch a page of hits for the searched text, after `cursor` or else the
+    /// first
+    fn load_search(&self, ctx: &Context<Self>, cursor: Option<String>) {
+        let text = self.search.text.clone();
+        if text.is_empty() {
+            return;
+        }
+        let more = cursor.is_some();
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+
+        spawn_local(async move {
+            match client.search(&text, cursor).await {
+                Ok(page) => {
+                    link.send_message(ApiAppMsg::SearchLoaded(text, Box::new(page), more));
+                }
+                Err(e) => {
+                    link.send_message(ApiAppMsg::SearchFailed(text, e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Fetch the list of systems, falling back to the demo systems when the
+    /// backend cannot be reached
+    fn load_systems(&self, ctx: &Context<Self>) {
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+
+        spawn_local(async move {
+            match client.fetch_all_systems().await {
+                Ok(systems) => {
+                    link.send_message(ApiAppMsg::SystemsLoaded(systems));
+                }
+                Err(e) if e.is_unreachable() => {
+                    link.send_message(ApiAppMsg::BackendUnreachable(e.to_string()));
+                }
+                Err(e) => {
+                    link.send_message(ApiAppMsg::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Fetch a system in the current vocabulary
+    fn load_system(&mut self, ctx: &Context<Self>, order: i32) {
+        self.loading = true;
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+        let language = self.language;
+
+        spawn_local(async move {
+            match client.fetch_system_by_order(order, language).await {
+                Ok(system) => {
+                    link.send_message(ApiAppMsg::SystemLoaded(Box::new(system)));
+                }
+                Err(e) => {
+                    link.send_message(ApiAppMsg::LoadError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Fetch the slice at the selected position, if any
+    fn load_slice(&mut self, ctx: &Context<Self>) {
+        self.selected_slice = None;
+        let (Some(position), Some(system)) = (self.selected_position, &self.selected_system) else {
+            return;
+        };
+
+        let link = ctx.link().clone();
+        let client = self.graphql_client.clone();
+        let order = system.order;
+
+        spawn_local(async move {
+            match client.fetch_slice(order, position).await {
+                Ok(slice) => {
+                    link.send_message(ApiAppMsg::SliceLoaded(Box::new(slice)));
+                }
+                Err(e) => {
+                    web_sys::console::log_1(&format!("Failed to load slice: {}", e).into());
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,490 @@
+use std::collections::HashMap;
+
+use systematics_middleware::{ApiError, CustomSystemInput, Language, SystemView};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+/// What an edit changes, so that its failure is shown beside it
+#[derive(Clone, Debug, PartialEq)]
+pub enum EditTarget {
+    Term(i32),
+    Connective(i32, i32),
+    NewSystem,
+}
+
+/// Why the server refused an edit: its message, and the argument it named
+/// (`extensions.field`) with a hint, where it gave them
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditFailure {
+    pub target: EditTarget,
+    pub message: String,
+    pub field: Option<String>,
+    pub hint: Option<String>,
+}
+
+impl EditFailure {
+    pub fn new(target: EditTarget, error: &ApiError) -> Self {
+        let first = error.errors().first();
+        Self {
+            target,
+            message: first.map_or_else(|| error.to_string(), |e| e.message.clone()),
+            field: first.and_then(|e| e.extensions.field.clone()),
+            hint: first.and_then(|e| e.extensions.hint.clone()),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EditPanelProps {
+    pub system: SystemView,
+    /// Vocabulary the edits are made in
+    pub language: Language,
+    /// Orders of the loaded systems, which a new system cannot take
+    pub orders: Vec<i32>,
+    /// Whether a token is kept to send edits with
+    pub signed_in: bool,
+    /// Edits sent and not yet answered
+    pub pending: usize,
+    #[prop_or_default]
+    pub failure: Option<EditFailure>,
+    pub on_sign_in: Callback<String>,
+    pub on_sign_out: Callback<()>,
+    /// Called with a position and its new term
+    pub on_rename: Callback<(i32, String)>,
+    /// Called with a connective's two positions and its new label
+    pub on_label: Callback<((i32, i32), String)>,
+    pub on_create: Callback<CustomSystemInput>,
+    pub on_close: Callback<()>,
+}
+
+pub enum EditPanelMsg {
+    Token(String),
+    SignIn,
+    Rename(i32, String),
+    Label((i32, i32), String),
+    NewName(String),
+    NewOrder(String),
+    NewTerms(String),
+    Create,
+}
+
+/// Panel for editing the selected system as a signed-in user: its terms and
+/// connective labels in the current vocabulary, and new custom systems.
+/// Input is checked here before it is sent; the server's refusals are shown
+/// beside the input they concern.
+pub struct EditPanel {
+    token: String,
+    new_name: String,
+    new_order: String,
+    /// Terms of the new system, one per line
+    new_terms: String,
+    /// Problems with the input, by the key of the input (see `term_key`)
+    invalid: HashMap<String, String>,
+}
+
+fn term_key(position: i32) -> String {
+    format!("term-{}", position)
+}
+
+fn connective_key((base, target): (i32, i32)) -> String {
+    format!("connective-{}-{}", base, target)
+}
+
+impl Component for EditPanel {
+    type Message = EditPanelMsg;
+    type Properties = EditPanelProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            token: String::new(),
+            new_name: String::new(),
+            new_order: String::new(),
+            new_terms: String::new(),
+            invalid: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            EditPanelMsg::Token(token) => {
+                self.token = token;
+                false
+            }
+            EditPanelMsg::SignIn => {
+                let token = self.token.trim();
+                if token.is_empty() {
+                    self.invalid
+                        .insert("token".to_string(), "Paste a token to sign in".to_string());
+                } else {
+                    self.invalid.remove("token");
+                    props.on_sign_in.emit(token.to_string());
+                    self.token.clear();
+                }
+                true
+            }
+            EditPanelMsg::Rename(position, value) => {
+                let key = term_key(position);
+                let value = value.trim();
+                if value.is_empty() {
+                    self.invalid
+                        .insert(key, "A term cannot be empty".to_string());
+                } else {
+                    self.invalid.remove(&key);
+                    if props.system.term_at(position) != Some(value) {
+                        props.on_rename.emit((position, value.to_string()));
+                    }
+                }
+                true
+            }
+            EditPanelMsg::Label(edge, value) => {
+                let key = connective_key(edge);
+                let value = value.trim();
+                if value.is_empty() {
+                    self.invalid
+                        .insert(key, "A label cannot be empty".to_string());
+                } else {
+                    self.invalid.remove(&key);
+                    let current = props
+                        .system
+                        .connective_view_between(edge.0, edge.1)
+                        .and_then(|c| c.label());
+                    if current != Some(value) {
+                        props.on_label.emit((edge, value.to_string()));
+                    }
+                }
+                true
+            }
+            EditPanelMsg::NewName(name) => {
+                self.new_name = name;
+                false
+            }
+            EditPanelMsg::NewOrder(order) => {
+                self.new_order = order;
+                false
+            }
+            EditPanelMsg::NewTerms(terms) => {
+                self.new_terms = terms;
+                false
+            }
+            EditPanelMsg::Create => {
+                if let Some(input) = self.new_system(&props.orders) {
+                    props.on_create.emit(input);
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let on_close = {
+            let on_close = props.on_close.clone();
+            Callback::from(move |_| on_close.emit(()))
+        };
+
+        html! {
+            <aside class="edit-panel" aria-label="Edit the system">
+                <header>
+                    <h3>{ format!("Editing {}", props.system.display_name()) }</h3>
+                    <button class="position-detail-close" onclick={ on_close } title="Stop editing">
+                        { "×" }
+                    </button>
+                </header>
+                if props.signed_in {
+                    { self.render_signed_in(ctx) }
+                } else {
+                    { self.render_sign_in(ctx) }
+                }
+            </aside>
+        }
+    }
+}
+
+impl EditPanel {
+    /// Token field for signing in; edits are refused without one
+    fn render_sign_in(&self, ctx: &Context<Self>) -> Html {
+        let oninput = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            EditPanelMsg::Token(input.value())
+        });
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            EditPanelMsg::SignIn
+        });
+        html! {
+            <form class="edit-sign-in" { onsubmit }>
+                <p class="edit-note">{ "Sign in with a bearer token from the configured issuer to edit." }</p>
+                <input
+                    type="password"
+                    class="edit-input"
+                    placeholder="Bearer token"
+                    aria-label="Bearer token"
+                    autocomplete="off"
+                    { oninput }
+                />
+                { self.problem("token") }
+                <button type="submit" class="edit-button">{ "Sign in" }</button>
+            </form>
+        }
+    }
+
+    fn render_signed_in(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let system = &props.system;
+        let on_sign_out = {
+            let on_sign_out = props.on_sign_out.clone();
+            Callback::from(move |_| on_sign_out.emit(()))
+        };
+        // Canonical connectives are curated, so labels go in other vocabularies
+        let labelling = props.language != Language::Canonical;
+
+        html! {
+            <>
+                <p class="edit-status" role="status">
+                    {
+                        match props.pending {
+                            0 => "Signed in; changes are saved as you make them".to_string(),
+                            1 => "Saving 1 change…".to_string(),
+                            n => format!("Saving {} changes…", n),
+                        }
+                    }
+                    <button class="edit-link" onclick={ on_sign_out }>{ "Sign out" }</button>
+                </p>
+
+                <h4>{ format!("Terms ({})", language_name(props.language)) }</h4>
+                <ul class="edit-list">
+                    { for (1..=system.order).map(|position| self.render_term(ctx, position)) }
+                </ul>
+
+                <h4>{ format!("Connective labels ({})", language_name(props.language)) }</h4>
+                if labelling {
+                    <ul class="edit-list">
+                        { for system.connective_views.iter().map(|view| {
+                            self.render_connective(ctx, (view.base_position, view.target_position))
+                        }) }
+                    </ul>
+                } else {
+                    <p class="edit-note">
+                        { "Canonical connectives are curated; pick another vocabulary to label them." }
+                    </p>
+                }
+
+                { self.render_new_system(ctx) }
+            </>
+        }
+    }
+
+    fn render_term(&self, ctx: &Context<Self>, position: i32) -> Html {
+        let system = &ctx.props().system;
+        let onchange = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            EditPanelMsg::Rename(position, input.value())
+        });
+        let key = term_key(position);
+        let failure = self.failure_for(ctx, &EditTarget::Term(position));
+        html! {
+            <li>
+                <label>
+                    <span class="edit-position">{ position }</span>
+                    <input
+                        class={ classes!("edit-input", self.is_invalid(&key, failure).then_some("invalid")) }
+                        value={ system.term_at(position).unwrap_or_default().to_string() }
+                        aria-label={ format!("Term at position {}", position) }
+                        { onchange }
+                    />
+                </label>
+                { self.problem(&key) }
+                { render_failure(failure) }
+            </li>
+        }
+    }
+
+    fn render_connective(&self, ctx: &Context<Self>, edge: (i32, i32)) -> Html {
+        let system = &ctx.props().system;
+        let onchange = ctx.link().callback(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            EditPanelMsg::Label(edge, input.value())
+        });
+        let key = connective_key(edge);
+        let failure = self.failure_for(ctx, &EditTarget::Connective(edge.0, edge.1));
+        let label = system
+            .connective_view_between(edge.0, edge.1)
+            .and_then(|c| c.label())
+            .unwrap_or_default()
+            .to_string();
+        let name = system.connective_label(edge.0, edge.1);
+        html! {
+            <li>
+                <label>
+                    <span class="edit-position">{ &name }</span>
+                    <input
+                        class={ classes!("edit-input", self.is_invalid(&key, failure).then_some("invalid")) }
+                        value={ label }
+                        aria-label={ format!("Label of {}", name) }
+                        { onchange }
+                    />
+                </label>
+                { self.problem(&key) }
+                { render_failure(failure) }
+            </li>
+        }
+    }
+
+    /// Form for a custom system: a name, an unused order, and a term for each
+    /// position
+    fn render_new_system(&self, ctx: &Context<Self>) -> Html {
+        let failure = self.failure_for(ctx, &EditTarget::NewSystem);
+        let field = failure.and_then(|f| f.field.as_deref());
+        let invalid =
+            |key: &str| (self.invalid.contains_key(key) || field == Some(key)).then_some("invalid");
+        let on_name = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            EditPanelMsg::NewName(input.value())
+        });
+        let on_order = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            EditPanelMsg::NewOrder(input.value())
+        });
+        let on_terms = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            EditPanelMsg::NewTerms(input.value())
+        });
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            EditPanelMsg::Create
+        });
+        html! {
+            <form class="edit-new-system" { onsubmit }>
+                <h4>{ "New system" }</h4>
+                <input
+                    class={ classes!("edit-input", invalid("name")) }
+                    placeholder="Name"
+                    aria-label="Name of the new system"
+                    value={ self.new_name.clone() }
+                    oninput={ on_name }
+                />
+                { self.problem("name") }
+                <input
+                    type="number"
+                    min="1"
+                    class={ classes!("edit-input", invalid("order")) }
+                    placeholder="Order (e.g., 13)"
+                    aria-label="Order of the new system"
+                    value={ self.new_order.clone() }
+                    oninput={ on_order }
+                />
+                { self.problem("order") }
+                <textarea
+                    class={ classes!("edit-input", invalid("terms")) }
+                    rows="4"
+                    placeholder="Terms, one per line"
+                    aria-label="Terms of the new system, one per line"
+                    value={ self.new_terms.clone() }
+                    oninput={ on_terms }
+                />
+                { self.problem("terms") }
+                { render_failure(failure) }
+                <button type="submit" class="edit-button">{ "Create system" }</button>
+            </form>
+        }
+    }
+
+    /// The custom system the form describes, or none, marking what is wrong
+    /// with it
+    fn new_system(&mut self, orders: &[i32]) -> Option<CustomSystemInput> {
+        for key in ["name", "order", "terms"] {
+            self.invalid.remove(key);
+        }
+        let name = self.new_name.trim();
+        if name.is_empty() {
+            self.invalid
+                .insert("name".to_string(), "Name the system".to_string());
+        }
+        let order = match self.new_order.trim().parse::<i32>() {
+            Ok(order) if order < 1 => {
+                self.invalid.insert(
+                    "order".to_string(),
+                    "The order must be 1 or more".to_string(),
+                );
+                None
+            }
+            Ok(order) if orders.contains(&order) => {
+                self.invalid.insert(
+                    "order".to_string(),
+                    format!("There is already a system of order {}", order),
+                );
+                None
+            }
+            Ok(order) => Some(order),
+            Err(_) => {
+                self.invalid.insert(
+                    "order".to_string(),
+                    "Enter the order as a number".to_string(),
+                );
+                None
+            }
+        };
+        let terms: Vec<&str> = self
+            .new_terms
+            .lines()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+        let order = order?;
+        let input = CustomSystemInput::new(order, name, terms.iter().copied());
+        if !input.is_complete() {
+            self.invalid.insert(
+                "terms".to_string(),
+                format!(
+                    "Give {} terms, one per position ({} so far)",
+                    order,
+                    terms.len()
+                ),
+            );
+        }
+        self.invalid.is_empty().then_some(input)
+    }
+
+    /// The server's refusal of the last edit, if it concerned `target`
+    fn failure_for<'a>(
+        &self,
+        ctx: &'a Context<Self>,
+        target: &EditTarget,
+    ) -> Option<&'a EditFailure> {
+        ctx.props().failure.as_ref().filter(|f| f.target == *target)
+    }
+
+    fn is_invalid(&self, key: &str, failure: Option<&EditFailure>) -> bool {
+        self.invalid.contains_key(key) || failure.is_some()
+    }
+
+    /// What is wrong with an input, as checked before sending it
+    fn problem(&self, key: &str) -> Html {
+        match self.invalid.get(key) {
+            Some(problem) => html! { <p class="edit-error" role="alert">{ problem }</p> },
+            None => html! {},
+        }
+    }
+}
+
+/// Why the server refused an edit, and how to correct it
+fn render_failure(failure: Option<&EditFailure>) -> Html {
+    let Some(failure) = failure else {
+        return html! {};
+    };
+    html! {
+        <p class="edit-error" role="alert">
+            { &failure.message }
+            if let Some(ref hint) = failure.hint {
+                <span class="edit-hint">{ hint }</span>
+            }
+        </p>
+    }
+}
+
+/// Vocabulary name as shown (e.g., "Energy")
+fn language_name(language: Language) -> String {
+    let slug = language.slug();
+    format!("{}{}", slug[..1].to_uppercase(), &slug[1..])
+}
@@ -1,4 +1,9 @@
-use systematics_middleware::SystemView;
+use std::collections::HashMap;
+
+use gloo_timers::callback::Interval;
+
+use systematics_middleware::{EnneagramFigure, LineRole, SystemView};
+use wasm_bindgen::{JsCast, JsValue};
 use yew::prelude::*;
 
 /// Default colors for rendering
@@ -6,6 +11,68 @@ const DEFAULT_NODE_COLOR: &str = "#4A90E2";
 const DEFAULT_EDGE_COLOR: &str = "#888888";
 const SELECTED_NODE_COLOR: &str = "#FF6B6B";
 const SELECTED_EDGE_COLOR: &str = "#FF6B6B";
+const LABEL_COLOR: &str = "#333";
+const EDGE_LABEL_COLOR: &str = "#2563eb";
+const NEEDS_RESEARCH_COLOR: &str = "#999";
+const TRIANGLE_EDGE_COLOR: &str = "#7c3aed";
+const PROCESS_EDGE_COLOR: &str = "#0f766e";
+const NOTE_MARKER_COLOR: &str = "#f59e0b";
+
+/// How far points may stray from their mean distance to the centre, as a
+/// fraction of it, and still be drawn as lying on one circle
+const CIRCLE_TOLERANCE: f64 = 0.02;
+
+/// How far the enneagram's process lines bow from straight, as a fraction of
+/// their length
+const PROCESS_BOW: f64 = 0.08;
+
+/// Side of the square drawing area, in SVG units
+const VIEW_SIZE: f64 = 800.0;
+
+/// Size of the position numbers and term labels in print, relative to screen
+const PRINT_LABEL_SCALE: f64 = 1.3;
+
+/// Zoom limits, as magnifications of the whole drawing
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 8.0;
+
+/// Magnification per wheel step
+const WHEEL_ZOOM: f64 = 1.15;
+
+/// Pixels a pointer moves before a press becomes a pan rather than a click
+const PAN_THRESHOLD: f64 = 4.0;
+
+/// Keys handled on a focused node or line
+const FOCUS_KEYS: [&str; 9] = [
+    "ArrowRight",
+    "ArrowDown",
+    "ArrowLeft",
+    "ArrowUp",
+    "Home",
+    "End",
+    "Enter",
+    " ",
+    "Escape",
+];
+
+/// Length of the morph from one system's layout to the next, and the time
+/// between its frames, in milliseconds
+const MORPH_MS: f64 = 600.0;
+const MORPH_FRAME_MS: u32 = 16;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Rules of the stylesheet that apply to the drawing, embedded in exported
+/// files so they look as on screen without it
+const EXPORT_STYLE: &str = "
+svg { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; }
+.graph-backdrop { fill: #ffffff; }
+.edge { opacity: 0.6; }
+.edge-triangle, .edge-process { opacity: 0.85; }
+.symbolic-circle { opacity: 0.3; }
+.graph-legend text { font-size: 12px; fill: #4a5568; }
+.graph-legend .legend-title { font-size: 14px; font-weight: 600; fill: #2d3748; }
+";
 
 #[derive(Properties, PartialEq)]
 pub struct ApiGraphViewProps {
@@ -14,31 +81,158 @@ pub struct ApiGraphViewProps {
     pub on_navigate: Option<Callback<String>>,
     #[prop_or_default]
     pub show_edge_labels: bool,
+    /// Called with the position of a node when it is selected, and with
+    /// `None` when the selection is cleared
+    #[prop_or_default]
+    pub on_select_position: Option<Callback<Option<i32>>>,
+    /// Position of the selected node, when the app keeps the selection (e.g.,
+    /// restored from a link)
+    #[prop_or_default]
+    pub selected_position: Option<i32>,
+    /// Positions of a line to highlight (e.g., a connective found by search)
+    #[prop_or_default]
+    pub selected_edge: Option<(i32, i32)>,
+    /// Called with the positions of a line when it is selected, and with
+    /// `None` when the selection is cleared
+    #[prop_or_default]
+    pub on_select_edge: Option<Callback<Option<(i32, i32)>>>,
+    /// Positions whose terms have notes, marked on their nodes
+    #[prop_or_default]
+    pub noted_positions: Vec<i32>,
+    /// Connectives with notes, by their two positions in either order, marked
+    /// on their lines
+    #[prop_or_default]
+    pub noted_edges: Vec<(i32, i32)>,
+    /// Called from the toolbar's "Print" button
+    #[prop_or_default]
+    pub on_print: Option<Callback<()>>,
+    /// Laid out for printing: no toolbar, keyboard hints or table, and larger
+    /// labels
+    #[prop_or_default]
+    pub print: bool,
+}
+
+/// Node or line that has, or last had, the keyboard focus
+#[derive(Clone, Copy, PartialEq)]
+pub enum Focus {
+    /// Node at a position
+    Node(i32),
+    /// Line by its index in the system's lines
+    Edge(usize),
 }
 
 pub enum ApiGraphMsg {
     NodeClicked(usize),
-    #[allow(dead_code)]
     EdgeClicked(usize, usize),
+    /// A node or line took the focus
+    Focused(Focus),
+    /// Key pressed on a focused node or line
+    Key(Focus, String),
+    /// A pointer (mouse, pen or finger) pressed at client coordinates
+    PointerDown(i32, f64, f64),
+    PointerMove(i32, f64, f64),
+    PointerUp(i32),
+    /// Wheel turned at client coordinates, by its vertical delta
+    Wheel(f64, f64, f64),
+    ResetView,
+    ExportSvg,
+    /// Frame of the morph between layouts
+    MorphFrame,
+}
+
+/// Animation from the layout of the previous system: positions it shares
+/// with the new one move from where they were, the others fade in
+struct Morph {
+    /// Previous coordinates by position
+    from: HashMap<i32, (f64, f64)>,
+    /// Start time (see `now`)
+    start: f64,
+    /// Eased progress from 0 to 1
+    progress: f64,
+    /// Timer sending frames, stopped when dropped
+    _frames: Interval,
 }
 
+/// Visible square of the drawing, in SVG units
+#[derive(Clone, Copy, PartialEq)]
+struct ViewBox {
+    x: f64,
+    y: f64,
+    size: f64,
+}
+
+impl Default for ViewBox {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            size: VIEW_SIZE,
+        }
+    }
+}
+
+impl ViewBox {
+    /// Magnification of the whole drawing (1 shows all of it)
+    fn zoom(&self) -> f64 {
+        VIEW_SIZE / self.size
+    }
+
+    /// Zoom by `factor` keeping the point `(px, py)` in place
+    fn zoom_about(&mut self, factor: f64, px: f64, py: f64) {
+        let zoom = (self.zoom() * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let size = VIEW_SIZE / zoom;
+        let ratio = size / self.size;
+        self.x = px - (px - self.x) * ratio;
+        self.y = py - (py - self.y) * ratio;
+        self.size = size;
+    }
+}
+
+impl std::fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} {}", self.x, self.y, self.size, self.size)
+    }
+}
+
+/// SVG graph of a system, zoomed with the wheel or a pinch and panned by
+/// dragging. Labels, nodes and strokes keep their on-screen size at any zoom,
+/// so zooming in spreads the graph out rather than magnifying its text.
 pub struct ApiGraphView {
     selected_node: Option<usize>,
     selected_edge: Option<(usize, usize)>,
+    svg: NodeRef,
+    view_box: ViewBox,
+    /// Pointers pressed on the drawing, at their last client coordinates
+    pointers: HashMap<i32, (f64, f64)>,
+    /// Whether the pressed pointers have moved far enough to pan
+    panning: bool,
+    /// Running morph from the previous system's layout
+    morph: Option<Morph>,
+    /// Node or line reached with Tab; arrow keys move it
+    focus: Option<Focus>,
+    /// Whether `focus` moved by key and the element is still to be focused
+    focus_pending: bool,
 }
 
 impl Component for ApiGraphView {
     type Message = ApiGraphMsg;
     type Properties = ApiGraphViewProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         Self {
-            selected_node: None,
-            selected_edge: None,
+            selected_node: node_index(ctx.props().selected_position),
+            selected_edge: edge_indices(ctx.props().selected_edge),
+            svg: NodeRef::default(),
+            view_box: ViewBox::default(),
+            pointers: HashMap::new(),
+            panning: false,
+            morph: None,
+            focus: None,
+            focus_pending: false,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             ApiGraphMsg::NodeClicked(idx) => {
                 // Toggle selection
@@ -48,6 +242,9 @@ impl Component for ApiGraphView {
                     self.selected_node = Some(idx);
                     self.selected_edge = None;
                 }
+                if let Some(ref on_select_position) = ctx.props().on_select_position {
+                    on_select_position.emit(self.selected_node.map(|idx| idx as i32 + 1));
+                }
                 true
             }
             ApiGraphMsg::EdgeClicked(from, to) => {
@@ -58,8 +255,107 @@ impl Component for ApiGraphView {
                     self.selected_edge = Some(edge);
                     self.selected_node = None;
                 }
+                if let Some(ref on_select_edge) = ctx.props().on_select_edge {
+                    on_select_edge.emit(
+                        self.selected_edge
+                            .map(|(a, b)| (a as i32 + 1, b as i32 + 1)),
+                    );
+                }
                 true
             }
+            ApiGraphMsg::Focused(focus) => {
+                let changed = self.focus != Some(focus);
+                self.focus = Some(focus);
+                changed
+            }
+            ApiGraphMsg::Key(focus, key) => self.key_pressed(ctx, focus, &key),
+            ApiGraphMsg::PointerDown(id, x, y) => {
+                self.pointers.insert(id, (x, y));
+                self.panning = self.pointers.len() > 1;
+                false
+            }
+            ApiGraphMsg::PointerMove(id, x, y) => self.pointer_moved(id, x, y),
+            ApiGraphMsg::PointerUp(id) => {
+                self.pointers.remove(&id);
+                if self.pointers.is_empty() {
+                    self.panning = false;
+                }
+                false
+            }
+            ApiGraphMsg::Wheel(x, y, delta) => {
+                let Some((px, py, _)) = self.to_view(x, y) else {
+                    return false;
+                };
+                let factor = if delta < 0.0 {
+                    WHEEL_ZOOM
+                } else {
+                    1.0 / WHEEL_ZOOM
+                };
+                self.view_box.zoom_about(factor, px, py);
+                true
+            }
+            ApiGraphMsg::ResetView => {
+                self.view_box = ViewBox::default();
+                true
+            }
+            ApiGraphMsg::MorphFrame => {
+                let Some(morph) = &mut self.morph else {
+                    return false;
+                };
+                let t = ((now() - morph.start) / MORPH_MS).clamp(0.0, 1.0);
+                if t >= 1.0 {
+                    self.morph = None;
+                } else {
+                    morph.progress = ease_in_out(t);
+                }
+                true
+            }
+            ApiGraphMsg::ExportSvg => {
+                if let Err(e) = self.export_svg(&ctx.props().system) {
+                    web_sys::console::warn_1(&format!("SVG export failed: {:?}", e).into());
+                }
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // A selection belongs to the system it was made in
+        if ctx.props().system.order != old_props.system.order {
+            self.selected_node = None;
+            self.selected_edge = None;
+            self.view_box = ViewBox::default();
+            self.focus = None;
+            self.start_morph(ctx, &old_props.system);
+        }
+        if ctx.props().selected_position != old_props.selected_position
+            || ctx.props().system.order != old_props.system.order
+        {
+            self.selected_node = node_index(ctx.props().selected_position);
+        }
+        if ctx.props().selected_edge != old_props.selected_edge
+            || ctx.props().system.order != old_props.system.order
+        {
+            self.selected_edge = edge_indices(ctx.props().selected_edge);
+        }
+        true
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if !std::mem::take(&mut self.focus_pending) {
+            return;
+        }
+        let selector = match self.focus {
+            Some(Focus::Node(position)) => format!("[data-position=\"{}\"]", position),
+            Some(Focus::Edge(index)) => format!("[data-line=\"{}\"]", index),
+            None => return,
+        };
+        let element = self
+            .svg
+            .cast::<web_sys::Element>()
+            .and_then(|svg| svg.query_selector(&selector).ok().flatten());
+        if let Some(element) = element {
+            let _ = element.unchecked_into::<web_sys::SvgElement>().focus();
         }
     }
 
@@ -67,35 +363,312 @@ impl Component for ApiGraphView {
         let system = &ctx.props().system;
         let show_edge_labels = ctx.props().show_edge_labels;
 
+        // e.g. "Triad γʹ · 3 Impulses · 3 Acts"
+        let caption = [
+            Some(match system.greek_numeral() {
+                Some(numeral) => format!("{} {}", system.display_name(), numeral),
+                None => system.display_name(),
+            }),
+            Some(system.term_count_label()),
+            (!system.connectives.is_empty()).then(|| system.connective_count_label()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        let link = ctx.link();
+        let onpointerdown = link.callback(|e: PointerEvent| {
+            ApiGraphMsg::PointerDown(e.pointer_id(), e.client_x() as f64, e.client_y() as f64)
+        });
+        let onpointermove = link.callback(|e: PointerEvent| {
+            ApiGraphMsg::PointerMove(e.pointer_id(), e.client_x() as f64, e.client_y() as f64)
+        });
+        let onpointerup = link.callback(|e: PointerEvent| ApiGraphMsg::PointerUp(e.pointer_id()));
+        let onpointercancel =
+            link.callback(|e: PointerEvent| ApiGraphMsg::PointerUp(e.pointer_id()));
+        let onwheel = link.callback(|e: WheelEvent| {
+            e.prevent_default();
+            ApiGraphMsg::Wheel(e.client_x() as f64, e.client_y() as f64, e.delta_y())
+        });
+        let on_reset = link.callback(|_: MouseEvent| ApiGraphMsg::ResetView);
+        let on_export = link.callback(|_: MouseEvent| ApiGraphMsg::ExportSvg);
+        let print = ctx.props().print;
+
         html! {
-            <div class="graph-view">
-                <svg
-                    class="graph-svg"
-                    viewBox="0 0 800 800"
-                    preserveAspectRatio="xMidYMid meet"
-                >
-                    { self.render_edges(system) }
-                    if show_edge_labels {
-                        { self.render_edge_labels(system) }
-                    }
-                    { self.render_nodes(ctx, system) }
-                </svg>
+            <div class={ classes!("graph-view", print.then_some("print")) }>
+                <p class="graph-caption">{ caption.clone() }</p>
+                if !print {
+                    <div class="graph-toolbar">
+                        <button class="graph-tool" onclick={ on_export } title="Download the diagram as an SVG file">
+                            { "Export SVG" }
+                        </button>
+                        if let Some(on_print) = ctx.props().on_print.clone() {
+                            <button
+                                class="graph-tool"
+                                onclick={ Callback::from(move |_: MouseEvent| on_print.emit(())) }
+                                title="Lay the diagram out for printing"
+                            >
+                                { "Print" }
+                            </button>
+                        }
+                        if self.view_box != ViewBox::default() {
+                            <button class="graph-tool" onclick={ on_reset } title="Show the whole graph">
+                                { format!("Reset view ({:.0}%)", self.view_box.zoom() * 100.0) }
+                            </button>
+                        }
+                    </div>
+                }
+                <div class="graph-stage">
+                    // The outer drawing holds the legend in place; the inner
+                    // one is zoomed and panned
+                    <svg
+                        ref={ self.svg.clone() }
+                        class="graph-svg"
+                        viewBox={ format!("0 0 {} {}", VIEW_SIZE, VIEW_SIZE) }
+                        preserveAspectRatio="xMidYMid meet"
+                        role="group"
+                        aria-label={ format!("Graph of the {}", caption) }
+                        aria-describedby={ (!print).then_some("graph-keys") }
+                        { onpointerdown }
+                        { onpointermove }
+                        { onpointerup }
+                        { onpointercancel }
+                        { onwheel }
+                    >
+                        <rect class="graph-backdrop" width={ VIEW_SIZE.to_string() } height={ VIEW_SIZE.to_string() } aria-hidden="true" />
+                        <svg
+                            width={ VIEW_SIZE.to_string() }
+                            height={ VIEW_SIZE.to_string() }
+                            viewBox={ self.view_box.to_string() }
+                        >
+                            { self.render_edges(ctx, system) }
+                            if show_edge_labels {
+                                <g aria-hidden="true">{ self.render_edge_labels(system) }</g>
+                            }
+                            { self.render_nodes(ctx, system) }
+                            { self.render_note_markers(ctx, system) }
+                        </svg>
+                        { render_legend(system, &caption) }
+                    </svg>
+                </div>
+                if !print {
+                    <p id="graph-keys" class="sr-only">
+                        { "Tab moves to the lines and then the positions; arrow keys move between them, Home and End go to the first and last, Enter selects and opens the details, and Escape clears the selection." }
+                    </p>
+                    <p class="sr-only" aria-live="polite">{ self.selection_description(system) }</p>
+                    { render_table(system) }
+                }
             </div>
         }
     }
 }
 
 impl ApiGraphView {
+    /// SVG coordinates of a client point, with the SVG units per client pixel
+    fn to_view(&self, client_x: f64, client_y: f64) -> Option<(f64, f64, f64)> {
+        let rect = self
+            .svg
+            .cast::<web_sys::Element>()?
+            .get_bounding_client_rect();
+        // With `xMidYMid meet` the square view box fills the shorter side
+        let side = rect.width().min(rect.height());
+        if side <= 0.0 {
+            return None;
+        }
+        let unit = self.view_box.size / side;
+        let left = rect.left() + (rect.width() - side) / 2.0;
+        let top = rect.top() + (rect.height() - side) / 2.0;
+        Some((
+            self.view_box.x + (client_x - left) * unit,
+            self.view_box.y + (client_y - top) * unit,
+            unit,
+        ))
+    }
+
+    /// Download the drawing as it is shown, with the stylesheet rules it
+    /// needs, as `<system>.svg`
+    fn export_svg(&self, system: &SystemView) -> Result<(), JsValue> {
+        let svg = self
+            .svg
+            .cast::<web_sys::Element>()
+            .ok_or("the graph is not rendered")?;
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .ok_or("no document")?;
+
+        let copy: web_sys::Element = svg.clone_node_with_deep(true)?.dyn_into()?;
+        copy.remove_attribute("class")?;
+        // Notes are personal, so their markers stay out of the download
+        if let Some(markers) = copy.query_selector(".note-markers")? {
+            markers.remove();
+        }
+        copy.set_attribute("width", &VIEW_SIZE.to_string())?;
+        copy.set_attribute("height", &VIEW_SIZE.to_string())?;
+        let style = document.create_element_ns(Some(SVG_NAMESPACE), "style")?;
+        style.set_text_content(Some(EXPORT_STYLE));
+        copy.prepend_with_node_1(&style)?;
+        let markup = web_sys::XmlSerializer::new()?.serialize_to_string(&copy)?;
+
+        let name = system
+            .name
+            .clone()
+            .unwrap_or_else(|| system.display_name().to_lowercase());
+        let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+        anchor.set_href(&format!(
+            "data:image/svg+xml;charset=utf-8,{}",
+            percent_encode(&markup)
+        ));
+        anchor.set_download(&format!("{}.svg", name.replace(char::is_whitespace, "-")));
+        anchor.click();
+        Ok(())
+    }
+
+    /// Morph from the layout of `previous` to the current system's, unless
+    /// the user prefers reduced motion
+    fn start_morph(&mut self, ctx: &Context<Self>, previous: &SystemView) {
+        self.morph = None;
+        if previous.coordinates.is_empty() || prefers_reduced_motion() {
+            return;
+        }
+        let link = ctx.link().clone();
+        self.morph = Some(Morph {
+            from: previous
+                .coordinates
+                .iter()
+                .map(|c| (c.position, (c.x, c.y)))
+                .collect(),
+            start: now(),
+            progress: 0.0,
+            _frames: Interval::new(MORPH_FRAME_MS, move || {
+                link.send_message(ApiGraphMsg::MorphFrame)
+            }),
+        });
+    }
+
+    /// Where the node at a position is drawn, moving during a morph
+    fn point_at(&self, system: &SystemView, position: i32) -> Option<(f64, f64)> {
+        let coord = system.coordinate_at(position)?;
+        let to = (coord.x, coord.y);
+        let Some(morph) = &self.morph else {
+            return Some(to);
+        };
+        Some(match morph.from.get(&position) {
+            Some(&(x, y)) => (
+                x + (to.0 - x) * morph.progress,
+                y + (to.1 - y) * morph.progress,
+            ),
+            None => to,
+        })
+    }
+
+    /// Centre of the nodes as drawn, and the radius of the circle they lie
+    /// on when they do
+    fn figure(&self, system: &SystemView) -> Option<Figure> {
+        let points: Vec<(f64, f64)> = system
+            .coordinates
+            .iter()
+            .filter_map(|c| self.point_at(system, c.position))
+            .collect();
+        if points.is_empty() {
+            return None;
+        }
+        let n = points.len() as f64;
+        let centre = (
+            points.iter().map(|p| p.0).sum::<f64>() / n,
+            points.iter().map(|p| p.1).sum::<f64>() / n,
+        );
+        let distances: Vec<f64> = points
+            .iter()
+            .map(|p| (p.0 - centre.0).hypot(p.1 - centre.1))
+            .collect();
+        let mean = distances.iter().sum::<f64>() / n;
+        let on_circle = points.len() >= 3
+            && mean > 0.0
+            && distances
+                .iter()
+                .all(|d| (d - mean).abs() <= mean * CIRCLE_TOLERANCE);
+        Some(Figure {
+            centre,
+            radius: on_circle.then_some(mean),
+        })
+    }
+
+    /// Opacity of the node at a position: positions new to the system fade in
+    fn opacity_at(&self, position: i32) -> f64 {
+        match &self.morph {
+            Some(morph) if !morph.from.contains_key(&position) => morph.progress,
+            _ => 1.0,
+        }
+    }
+
+    /// SVG units drawing what one unit draws unzoomed, so sizes multiplied by
+    /// it stay the same on screen
+    fn unit_scale(&self) -> f64 {
+        1.0 / self.view_box.zoom()
+    }
+
+    /// Pan with one pointer, or pan and zoom with a pinch of two
+    fn pointer_moved(&mut self, id: i32, x: f64, y: f64) -> bool {
+        let Some(&(last_x, last_y)) = self.pointers.get(&id) else {
+            return false;
+        };
+        if !self.panning && (x - last_x).hypot(y - last_y) < PAN_THRESHOLD {
+            // Still a click, which the node under the pointer receives
+            return false;
+        }
+        if !self.panning {
+            // Keep the drag when the pointer leaves the drawing; the click
+            // ending it then goes to the drawing rather than a node
+            if let Some(svg) = self.svg.cast::<web_sys::Element>() {
+                let _ = svg.set_pointer_capture(id);
+            }
+            self.panning = true;
+        }
+        let before = self.pinch();
+        self.pointers.insert(id, (x, y));
+        let Some(unit) = self.to_view(x, y).map(|(_, _, unit)| unit) else {
+            return false;
+        };
+        match (before, self.pinch()) {
+            (Some((mid_before, spread_before)), Some((mid, spread))) => {
+                self.view_box.x -= (mid.0 - mid_before.0) * unit;
+                self.view_box.y -= (mid.1 - mid_before.1) * unit;
+                if let Some((px, py, _)) = self.to_view(mid.0, mid.1) {
+                    if spread_before > 0.0 {
+                        self.view_box.zoom_about(spread / spread_before, px, py);
+                    }
+                }
+            }
+            _ => {
+                self.view_box.x -= (x - last_x) * unit;
+                self.view_box.y -= (y - last_y) * unit;
+            }
+        }
+        true
+    }
+
+    /// Midpoint and distance of two pressed pointers
+    fn pinch(&self) -> Option<((f64, f64), f64)> {
+        let mut pointers = self.pointers.values();
+        let (&(ax, ay), &(bx, by)) = (pointers.next()?, pointers.next()?);
+        Some((((ax + bx) / 2.0, (ay + by) / 2.0), (bx - ax).hypot(by - ay)))
+    }
+
     /// Render edges (lines) from the system
-    fn render_edges(&self, system: &SystemView) -> Html {
+    fn render_edges(&self, ctx: &Context<Self>, system: &SystemView) -> Html {
+        let k = self.unit_scale();
+        let figure = self.figure(system);
+        let tab_stop = self.edge_tab_stop(system);
         web_sys::console::log_1(
             &format!("render_edges: {} lines to render", system.lines.len()).into(),
         );
 
-        system
+        let edges = system
             .lines
             .iter()
-            .map(|line| {
+            .enumerate()
+            .map(|(index, line)| {
                 // Get positions (1-based from API)
                 let base_pos = line.base_position.unwrap_or(0);
                 let target_pos = line.target_position.unwrap_or(0);
@@ -115,8 +688,8 @@ impl ApiGraphView {
 
                 // Look up coordinates from the system's transformed coordinates array
                 // (Don't use embedded link coordinates - they aren't transformed correctly)
-                let (from_x, from_y) = if let Some(coord) = system.coordinate_at(base_pos) {
-                    (coord.x, coord.y)
+                let (from_x, from_y) = if let Some(point) = self.point_at(system, base_pos) {
+                    point
                 } else {
                     web_sys::console::log_1(
                         &format!("Could not find from coordinate for pos {}", base_pos).into(),
@@ -124,8 +697,8 @@ impl ApiGraphView {
                     return html! {};
                 };
 
-                let (to_x, to_y) = if let Some(coord) = system.coordinate_at(target_pos) {
-                    (coord.x, coord.y)
+                let (to_x, to_y) = if let Some(point) = self.point_at(system, target_pos) {
+                    point
                 } else {
                     web_sys::console::log_1(
                         &format!("Could not find to coordinate for pos {}", target_pos).into(),
@@ -144,37 +717,67 @@ impl ApiGraphView {
                 };
 
                 let is_selected = self.selected_edge == Some(edge_tuple);
+                let style = EdgeStyle::of(line.role, system.enneagram_figure(base_pos, target_pos));
+                let geometry = style.shape.between((from_x, from_y), (to_x, to_y), figure);
                 let stroke = if is_selected {
                     SELECTED_EDGE_COLOR
                 } else {
-                    DEFAULT_EDGE_COLOR
+                    style.color
                 };
-                let stroke_width = if is_selected { 3.0 } else { 1.5 };
+                let stroke_width = if is_selected { 3.0 } else { style.width } * k;
+                let dasharray = style
+                    .dasharray
+                    .map(|(dash, gap)| format!("{} {}", dash * k, gap * k))
+                    .unwrap_or_else(|| "none".to_string());
+                let onclick = ctx
+                    .link()
+                    .callback(move |_| ApiGraphMsg::EdgeClicked(from_idx, to_idx));
 
                 html! {
-                    <line
-                        x1={ from_x.to_string() }
-                        y1={ from_y.to_string() }
-                        x2={ to_x.to_string() }
-                        y2={ to_y.to_string() }
+                    <path
+                        d={ geometry.path }
+                        role="button"
+                        tabindex={ if tab_stop == index { "0" } else { "-1" } }
+                        aria-label={ system.line_description(base_pos, target_pos) }
+                        aria-pressed={ is_selected.to_string() }
+                        data-line={ index.to_string() }
+                        { onclick }
+                        onfocus={ ctx.link().callback(move |_| ApiGraphMsg::Focused(Focus::Edge(index))) }
+                        onkeydown={ self.on_key(ctx, Focus::Edge(index)) }
+                        fill="none"
                         stroke={ stroke }
                         stroke-width={ stroke_width.to_string() }
-                        class="edge"
-                    />
+                        stroke-dasharray={ dasharray }
+                        class={ format!("edge edge-{}", style.tag) }
+                        opacity={ self.opacity_at(base_pos).min(self.opacity_at(target_pos)).to_string() }
+                    >
+                        <title>{
+                            system
+                                .connective_view_between(base_pos, target_pos)
+                                .map(|c| c.title())
+                                .unwrap_or_else(|| system.connective_label(base_pos, target_pos))
+                        }</title>
+                    </path>
                 }
             })
-            .collect::<Html>()
+            .collect::<Html>();
+        html! {
+            <g class="graph-edges" role="group" aria-label="Lines">{ edges }</g>
+        }
     }
 
     /// Render edge labels for connectives
     /// Instead of iterating connectives independently, we iterate through lines
-    /// and find matching connectives to ensure labels align with the correct edges
+    /// and find the resolved connective between the same positions, so labels
+    /// align with the correct edges
     fn render_edge_labels(&self, system: &SystemView) -> Html {
+        let k = self.unit_scale();
+        let figure = self.figure(system);
         web_sys::console::log_1(
             &format!(
                 "render_edge_labels: {} lines, {} connectives",
                 system.lines.len(),
-                system.connectives.len()
+                system.connective_views.len()
             )
             .into(),
         );
@@ -183,52 +786,45 @@ impl ApiGraphView {
             let line_base_pos = line.base_position.unwrap_or(0);
             let line_target_pos = line.target_position.unwrap_or(0);
 
-            // Find the connective that matches this line's positions (bidirectional match)
             // Lines are stored with smaller position first, but connectives preserve semantic direction
-            let matching_connective = system.connectives.iter().enumerate().find(|(_, conn)| {
-                let conn_base = conn.base_position.unwrap_or(0);
-                let conn_target = conn.target_position.unwrap_or(0);
-                (conn_base == line_base_pos && conn_target == line_target_pos) ||
-                (conn_base == line_target_pos && conn_target == line_base_pos)
-            });
-
-            let Some((conn_idx, connective)) = matching_connective else {
+            let Some(connective) = system.connective_view_between(line_base_pos, line_target_pos) else {
                 web_sys::console::log_1(&format!("No connective found for line {}: {}→{}",
                     line_idx, line_base_pos, line_target_pos).into());
                 return html! {};
             };
 
-            // Get the label from the connective's character
-            let label = connective.character
-                .as_ref()
-                .map(|c| c.value.as_str())
-                .unwrap_or("");
-
-            if label.is_empty() {
+            let Some(label) = connective.label() else {
                 return html! {};
-            }
+            };
+
+            let label_fill = if connective.needs_research() {
+                NEEDS_RESEARCH_COLOR
+            } else {
+                EDGE_LABEL_COLOR
+            };
 
             web_sys::console::log_1(&format!("Line {} ({}→{}) matched with connective {} (label='{}')",
-                line_idx, line_base_pos, line_target_pos, conn_idx, label).into());
+                line_idx, line_base_pos, line_target_pos, connective.id, label).into());
 
             // Use the SAME coordinate lookup as render_edges to ensure alignment
-            let (from_x, from_y) = if let Some(coord) = system.coordinate_at(line_base_pos) {
-                (coord.x, coord.y)
+            let (from_x, from_y) = if let Some(point) = self.point_at(system, line_base_pos) {
+                point
             } else {
                 web_sys::console::log_1(&format!("No coordinate for base_pos {}", line_base_pos).into());
                 return html! {};
             };
 
-            let (to_x, to_y) = if let Some(coord) = system.coordinate_at(line_target_pos) {
-                (coord.x, coord.y)
+            let (to_x, to_y) = if let Some(point) = self.point_at(system, line_target_pos) {
+                point
             } else {
                 web_sys::console::log_1(&format!("No coordinate for target_pos {}", line_target_pos).into());
                 return html! {};
             };
 
-            // Calculate midpoint for label placement
-            let mid_x = (from_x + to_x) / 2.0;
-            let mid_y = (from_y + to_y) / 2.0;
+            // Place the label halfway along the edge as drawn, which is
+            // parallel there to the straight line between its ends
+            let style = EdgeStyle::of(line.role, system.enneagram_figure(line_base_pos, line_target_pos));
+            let (mid_x, mid_y) = style.shape.between((from_x, from_y), (to_x, to_y), figure).mid;
 
             // Calculate angle for label rotation
             let dx = to_x - from_x;
@@ -242,8 +838,8 @@ impl ApiGraphView {
                 angle
             };
 
-            let rect_width = label.len() as f64 * 7.0;
-            let rect_height = 16.0;
+            let rect_width = label.len() as f64 * 7.0 * k;
+            let rect_height = 16.0 * k;
 
             html! {
                 <>
@@ -251,7 +847,7 @@ impl ApiGraphView {
                     <circle
                         cx={ mid_x.to_string() }
                         cy={ mid_y.to_string() }
-                        r="3"
+                        r={ (3.0 * k).to_string() }
                         fill="red"
                         style="pointer-events: none;"
                     />
@@ -263,8 +859,8 @@ impl ApiGraphView {
                             height={ rect_height.to_string() }
                             fill="rgba(255, 255, 255, 0.9)"
                             stroke="rgba(37, 99, 235, 0.3)"
-                            stroke-width="0.5"
-                            rx="4"
+                            stroke-width={ (0.5 * k).to_string() }
+                            rx={ (4.0 * k).to_string() }
                             style="pointer-events: none;"
                         />
                         <text
@@ -273,8 +869,8 @@ impl ApiGraphView {
                             text-anchor="middle"
                             dominant-baseline="middle"
                             class="edge-label"
-                            fill="#2563eb"
-                            style="font-size: 10px; font-weight: 500; pointer-events: none; user-select: none;"
+                            fill={ label_fill }
+                            style={ format!("font-size: {}px; font-weight: 500; pointer-events: none; user-select: none;", 10.0 * k) }
                         >
                             { label }
                         </text>
@@ -286,8 +882,16 @@ impl ApiGraphView {
 
     /// Render nodes from coordinates and terms
     fn render_nodes(&self, ctx: &Context<Self>, system: &SystemView) -> Html {
-        system.coordinates.iter().map(|coord| {
+        let k = self.unit_scale();
+        let text_k = if ctx.props().print {
+            PRINT_LABEL_SCALE * k
+        } else {
+            k
+        };
+        let tab_stop = self.node_tab_stop(system);
+        let nodes = system.coordinates.iter().map(|coord| {
             let position = coord.position;
+            let (x, y) = self.point_at(system, position).unwrap_or((coord.x, coord.y));
             let idx = (position - 1) as usize;  // Convert 1-based position to 0-based index
 
             let is_selected = self.selected_node == Some(idx);
@@ -301,51 +905,609 @@ impl ApiGraphView {
                     .unwrap_or_else(|| DEFAULT_NODE_COLOR.to_string())
             };
 
-            let radius = if is_selected { 18.0 } else { 12.0 };
+            let radius = if is_selected { 18.0 } else { 12.0 } * k;
             let onclick = ctx.link().callback(move |_| ApiGraphMsg::NodeClicked(idx));
 
-            // Get term label for this position
+            // Get term label for this position (greyed out if unresearched)
             let term = system.term_at(position).unwrap_or("");
+            let term_fill = if system.term_needs_research_at(position) {
+                NEEDS_RESEARCH_COLOR
+            } else {
+                LABEL_COLOR
+            };
+
+            // Tooltip: "2nd Impulse: Function"
+            let title = if term.is_empty() {
+                system.ordinal_position_label(position)
+            } else {
+                format!("{}: {}", system.ordinal_position_label(position), term)
+            };
 
             html! {
-                <g class="node" onclick={ onclick }>
+                <g
+                    class="node"
+                    role="button"
+                    tabindex={ if tab_stop == Some(position) { "0" } else { "-1" } }
+                    aria-label={
+                        if ctx.props().noted_positions.contains(&position) {
+                            format!("{}; has notes", system.position_description(position))
+                        } else {
+                            system.position_description(position)
+                        }
+                    }
+                    aria-pressed={ is_selected.to_string() }
+                    data-position={ position.to_string() }
+                    onclick={ onclick }
+                    onfocus={ ctx.link().callback(move |_| ApiGraphMsg::Focused(Focus::Node(position))) }
+                    onkeydown={ self.on_key(ctx, Focus::Node(position)) }
+                    opacity={ self.opacity_at(position).to_string() }
+                >
+                    <title>{ title }</title>
                     <circle
-                        cx={ coord.x.to_string() }
-                        cy={ coord.y.to_string() }
+                        cx={ x.to_string() }
+                        cy={ y.to_string() }
                         r={ radius.to_string() }
                         fill={ fill }
                         stroke="white"
-                        stroke-width="2"
+                        stroke-width={ (2.0 * k).to_string() }
                         style="cursor: pointer;"
                     />
                     <text
-                        x={ coord.x.to_string() }
-                        y={ coord.y.to_string() }
+                        x={ x.to_string() }
+                        y={ y.to_string() }
                         text-anchor="middle"
                         dominant-baseline="middle"
                         fill="white"
                         stroke="black"
-                        stroke-width="1"
+                        stroke-width={ k.to_string() }
                         paint-order="stroke"
-                        style="font-size: 12px; font-weight: bold; pointer-events: none; user-select: none;"
+                        style={ format!("font-size: {}px; font-weight: bold; pointer-events: none; user-select: none;", 12.0 * text_k) }
                     >
                         { position }
                     </text>
                     // Render vocabulary label if available
                     if !term.is_empty() {
                         <text
-                            x={ coord.x.to_string() }
-                            y={ (coord.y + radius + 16.0).to_string() }
+                            x={ x.to_string() }
+                            y={ (y + radius + 16.0 * text_k).to_string() }
                             text-anchor="middle"
                             dominant-baseline="middle"
-                            fill="#333"
-                            style="font-size: 14px; font-weight: 500; pointer-events: none; user-select: none;"
+                            fill={ term_fill }
+                            style={ format!("font-size: {}px; font-weight: 500; pointer-events: none; user-select: none;", 14.0 * text_k) }
                         >
                             { term }
                         </text>
                     }
                 </g>
             }
-        }).collect::<Html>()
+        }).collect::<Html>();
+        html! {
+            <g class="graph-nodes" role="group" aria-label="Positions">{ nodes }</g>
+        }
+    }
+
+    /// Markers on the nodes and lines that have notes, drawn over them
+    fn render_note_markers(&self, ctx: &Context<Self>, system: &SystemView) -> Html {
+        let props = ctx.props();
+        if props.noted_positions.is_empty() && props.noted_edges.is_empty() {
+            return html! {};
+        }
+        let k = self.unit_scale();
+        let figure = self.figure(system);
+        let marker = |(x, y): (f64, f64)| {
+            html! {
+                <circle
+                    class="note-marker"
+                    cx={ x.to_string() }
+                    cy={ y.to_string() }
+                    r={ (4.5 * k).to_string() }
+                    fill={ NOTE_MARKER_COLOR }
+                    stroke="white"
+                    stroke-width={ (1.5 * k).to_string() }
+                />
+            }
+        };
+
+        let node_markers = props.noted_positions.iter().filter_map(|&position| {
+            let (x, y) = self.point_at(system, position)?;
+            // At the node's upper right, clear of the number and term
+            let offset = 12.0 * k * std::f64::consts::FRAC_1_SQRT_2;
+            Some(marker((x + offset, y - offset)))
+        });
+        let edge_markers = system.lines.iter().filter_map(|line| {
+            let (a, b) = (line.base_position?, line.target_position?);
+            if !props
+                .noted_edges
+                .iter()
+                .any(|&(x, y)| (x, y) == (a, b) || (y, x) == (a, b))
+            {
+                return None;
+            }
+            let style = EdgeStyle::of(line.role, system.enneagram_figure(a, b));
+            let geometry =
+                style
+                    .shape
+                    .between(self.point_at(system, a)?, self.point_at(system, b)?, figure);
+            Some(marker(geometry.mid))
+        });
+        html! {
+            <g class="note-markers" aria-hidden="true" style="pointer-events: none;">
+                { for edge_markers }
+                { for node_markers }
+            </g>
+        }
+    }
+
+    /// Keys pressed on a node or line, sent on with it
+    fn on_key(&self, ctx: &Context<Self>, focus: Focus) -> Callback<KeyboardEvent> {
+        ctx.link().batch_callback(move |e: KeyboardEvent| {
+            let key = e.key();
+            FOCUS_KEYS.contains(&key.as_str()).then(|| {
+                e.prevent_default();
+                ApiGraphMsg::Key(focus, key)
+            })
+        })
+    }
+
+    /// Move the focus between nodes, or between lines, with the arrow keys,
+    /// Home and End; select with Enter or Space, and clear with Escape
+    fn key_pressed(&mut self, ctx: &Context<Self>, focus: Focus, key: &str) -> bool {
+        let system = &ctx.props().system;
+        match (key, focus) {
+            ("Enter" | " ", Focus::Node(position)) => {
+                return Component::update(
+                    self,
+                    ctx,
+                    ApiGraphMsg::NodeClicked((position - 1) as usize),
+                );
+            }
+            ("Enter" | " ", Focus::Edge(index)) => {
+                let Some(line) = system.lines.get(index) else {
+                    return false;
+                };
+                let (Some(a), Some(b)) = (
+                    node_index(line.base_position),
+                    node_index(line.target_position),
+                ) else {
+                    return false;
+                };
+                return Component::update(self, ctx, ApiGraphMsg::EdgeClicked(a, b));
+            }
+            ("Escape", _) => {
+                if self.selected_node.is_none() && self.selected_edge.is_none() {
+                    return false;
+                }
+                self.selected_node = None;
+                self.selected_edge = None;
+                if let Some(ref on_select_position) = ctx.props().on_select_position {
+                    on_select_position.emit(None);
+                }
+                return true;
+            }
+            _ => {}
+        }
+
+        let targets: Vec<Focus> = match focus {
+            Focus::Node(_) => system
+                .coordinates
+                .iter()
+                .map(|c| Focus::Node(c.position))
+                .collect(),
+            Focus::Edge(_) => (0..system.lines.len()).map(Focus::Edge).collect(),
+        };
+        let Some(current) = targets.iter().position(|&t| t == focus) else {
+            return false;
+        };
+        let last = targets.len() - 1;
+        let next = match key {
+            "ArrowRight" | "ArrowDown" => {
+                if current == last {
+                    0
+                } else {
+                    current + 1
+                }
+            }
+            "ArrowLeft" | "ArrowUp" => {
+                if current == 0 {
+                    last
+                } else {
+                    current - 1
+                }
+            }
+            "Home" => 0,
+            "End" => last,
+            _ => return false,
+        };
+        self.focus = Some(targets[next]);
+        self.focus_pending = true;
+        true
+    }
+
+    /// Node reached with Tab: the one last focused, else the selected one,
+    /// else the first
+    fn node_tab_stop(&self, system: &SystemView) -> Option<i32> {
+        let focused = match self.focus {
+            Some(Focus::Node(position)) => Some(position),
+            _ => None,
+        };
+        focused
+            .or(self.selected_node.map(|idx| idx as i32 + 1))
+            .filter(|&position| system.coordinate_at(position).is_some())
+            .or_else(|| system.coordinates.first().map(|c| c.position))
+    }
+
+    /// Line reached with Tab: the one last focused, else the selected one,
+    /// else the first
+    fn edge_tab_stop(&self, system: &SystemView) -> usize {
+        let focused = match self.focus {
+            Some(Focus::Edge(index)) if index < system.lines.len() => Some(index),
+            _ => None,
+        };
+        focused
+            .or_else(|| {
+                let (a, b) = self.selected_edge?;
+                system.lines.iter().position(|line| {
+                    edge_indices(line.base_position.zip(line.target_position)) == Some((a, b))
+                })
+            })
+            .unwrap_or(0)
+    }
+
+    /// What is selected, read out by screen readers as it changes
+    fn selection_description(&self, system: &SystemView) -> String {
+        if let Some(idx) = self.selected_node {
+            format!("Selected {}", system.position_description(idx as i32 + 1))
+        } else if let Some((a, b)) = self.selected_edge {
+            format!(
+                "Selected {}",
+                system.line_description(a as i32 + 1, b as i32 + 1)
+            )
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// Positions and lines as tables, for screen readers and anyone who prefers
+/// them to the drawing
+fn render_table(system: &SystemView) -> Html {
+    let lines: Vec<(i32, i32)> = system
+        .lines
+        .iter()
+        .filter_map(|line| line.base_position.zip(line.target_position))
+        .collect();
+    html! {
+        <details class="graph-table">
+            <summary>{ "Positions and lines as a table" }</summary>
+            <table>
+                <caption>{ format!("Positions of the {}", system.display_name()) }</caption>
+                <thead>
+                    <tr>
+                        <th scope="col">{ "Position" }</th>
+                        <th scope="col">{ "Term" }</th>
+                        <th scope="col">{ "Connections" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for system.coordinates.iter().map(|coord| {
+                        let position = coord.position;
+                        html! {
+                            <tr>
+                                <th scope="row">{ system.ordinal_position_label(position) }</th>
+                                <td>{ system.term_at(position).unwrap_or("—") }</td>
+                                <td>{ system.position_description(position) }</td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+            if !lines.is_empty() {
+                <table>
+                    <caption>{ format!("Lines of the {}", system.display_name()) }</caption>
+                    <thead>
+                        <tr>
+                            <th scope="col">{ "Line" }</th>
+                            <th scope="col">{ "Role" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for system.lines.iter().zip(&lines).map(|(line, &(a, b))| html! {
+                            <tr>
+                                <th scope="row">{ system.line_description(a, b) }</th>
+                                <td>{ EdgeStyle::of(line.role, system.enneagram_figure(a, b)).label }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+        </details>
+    }
+}
+
+/// Milliseconds from the page's time origin
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map_or(0.0, |p| p.now())
+}
+
+/// Whether the user asked the system for less motion
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|w| {
+            w.match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|query| query.matches())
+}
+
+/// Cubic easing, slow at both ends
+fn ease_in_out(t: f64) -> f64 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// Index of the node at a position (1-based)
+fn node_index(position: Option<i32>) -> Option<usize> {
+    position.filter(|&p| p > 0).map(|p| (p - 1) as usize)
+}
+
+/// Indices of the nodes a line joins, smaller first as selections keep them
+fn edge_indices(edge: Option<(i32, i32)>) -> Option<(usize, usize)> {
+    let (a, b) = edge?;
+    let (a, b) = (node_index(Some(a))?, node_index(Some(b))?);
+    Some((a.min(b), a.max(b)))
+}
+
+/// Centre of a figure's nodes, and the radius of the circle through them when
+/// they lie on one
+#[derive(Clone, Copy)]
+struct Figure {
+    centre: (f64, f64),
+    radius: Option<f64>,
+}
+
+/// How an edge runs between its ends
+#[derive(Clone, Copy, PartialEq)]
+enum EdgeShape {
+    Straight,
+    /// Along the circle through the figure's nodes, when there is one
+    Arc,
+    /// Bowed away from the figure's centre, by a fraction of its length
+    Bow(f64),
+}
+
+/// An edge as drawn: its SVG path, and the point halfway along it
+struct EdgeGeometry {
+    path: String,
+    mid: (f64, f64),
+}
+
+impl EdgeShape {
+    /// Geometry of an edge of this shape between two points; arcs and bows
+    /// need the figure, and fall back to straight lines without it
+    fn between(self, from: (f64, f64), to: (f64, f64), figure: Option<Figure>) -> EdgeGeometry {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = dx.hypot(dy);
+        let chord_mid = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+        match (self, figure) {
+            (
+                EdgeShape::Arc,
+                Some(Figure {
+                    centre: (cx, cy),
+                    radius: Some(r),
+                }),
+            ) => {
+                // The minor arc, swept the way the end lies from the start
+                // around the centre; its midpoint is along the bisector
+                let (ux, uy) = (from.0 + to.0 - 2.0 * cx, from.1 + to.1 - 2.0 * cy);
+                let bisector = ux.hypot(uy);
+                if bisector > f64::EPSILON {
+                    let cross = (from.0 - cx) * (to.1 - cy) - (from.1 - cy) * (to.0 - cx);
+                    return EdgeGeometry {
+                        path: format!(
+                            "M {} {} A {r} {r} 0 0 {} {} {}",
+                            from.0,
+                            from.1,
+                            u8::from(cross > 0.0),
+                            to.0,
+                            to.1
+                        ),
+                        mid: (cx + ux / bisector * r, cy + uy / bisector * r),
+                    };
+                }
+            }
+            (EdgeShape::Bow(bow), Some(Figure { centre, .. })) if length > f64::EPSILON => {
+                // Normal to the line, turned away from the centre; the curve
+                // reaches half way to its control point
+                let (mut nx, mut ny) = (-dy / length, dx / length);
+                if nx * (chord_mid.0 - centre.0) + ny * (chord_mid.1 - centre.1) < 0.0 {
+                    (nx, ny) = (-nx, -ny);
+                }
+                let offset = bow * length;
+                return EdgeGeometry {
+                    path: format!(
+                        "M {} {} Q {} {} {} {}",
+                        from.0,
+                        from.1,
+                        chord_mid.0 + nx * 2.0 * offset,
+                        chord_mid.1 + ny * 2.0 * offset,
+                        to.0,
+                        to.1
+                    ),
+                    mid: (chord_mid.0 + nx * offset, chord_mid.1 + ny * offset),
+                };
+            }
+            _ => {}
+        }
+        EdgeGeometry {
+            path: format!("M {} {} L {} {}", from.0, from.1, to.0, to.1),
+            mid: chord_mid,
+        }
+    }
+}
+
+/// Stroke of a class of edges, in units drawn unzoomed
+#[derive(Clone, Copy, PartialEq)]
+struct EdgeStyle {
+    /// Tag naming the class, also as `edge-<tag>` on the drawn edge
+    tag: &'static str,
+    /// Name in the legend
+    label: &'static str,
+    color: &'static str,
+    width: f64,
+    /// Dash and gap lengths, for lines drawn dashed
+    dasharray: Option<(f64, f64)>,
+    shape: EdgeShape,
+}
+
+impl EdgeStyle {
+    /// Every style, in the order the legend lists them
+    const ALL: [EdgeStyle; 7] = [
+        EdgeStyle::SIDE,
+        EdgeStyle::CIRCUMFERENCE,
+        EdgeStyle::DIAGONAL,
+        EdgeStyle::CHORD,
+        EdgeStyle::TRIANGLE,
+        EdgeStyle::PROCESS,
+        EdgeStyle::LINE,
+    ];
+
+    const SIDE: EdgeStyle = EdgeStyle {
+        tag: "side",
+        label: "Side",
+        color: DEFAULT_EDGE_COLOR,
+        width: 2.0,
+        dasharray: None,
+        shape: EdgeShape::Straight,
+    };
+    const CIRCUMFERENCE: EdgeStyle = EdgeStyle {
+        tag: "circumference",
+        label: "Circumference",
+        color: DEFAULT_EDGE_COLOR,
+        width: 1.5,
+        dasharray: None,
+        shape: EdgeShape::Arc,
+    };
+    const DIAGONAL: EdgeStyle = EdgeStyle {
+        tag: "diagonal",
+        label: "Diagonal",
+        color: DEFAULT_EDGE_COLOR,
+        width: 1.5,
+        dasharray: Some((6.0, 4.0)),
+        shape: EdgeShape::Straight,
+    };
+    const CHORD: EdgeStyle = EdgeStyle {
+        tag: "chord",
+        label: "Chord",
+        color: DEFAULT_EDGE_COLOR,
+        width: 1.0,
+        dasharray: Some((2.0, 3.0)),
+        shape: EdgeShape::Straight,
+    };
+    const TRIANGLE: EdgeStyle = EdgeStyle {
+        tag: "triangle",
+        label: "Triangle",
+        color: TRIANGLE_EDGE_COLOR,
+        width: 2.5,
+        dasharray: None,
+        shape: EdgeShape::Straight,
+    };
+    const PROCESS: EdgeStyle = EdgeStyle {
+        tag: "process",
+        label: "Process line",
+        color: PROCESS_EDGE_COLOR,
+        width: 2.0,
+        dasharray: None,
+        shape: EdgeShape::Bow(PROCESS_BOW),
+    };
+    /// Lines without a known role
+    const LINE: EdgeStyle = EdgeStyle {
+        tag: "line",
+        label: "Line",
+        color: DEFAULT_EDGE_COLOR,
+        width: 1.5,
+        dasharray: None,
+        shape: EdgeShape::Straight,
+    };
+
+    /// Style of a line by its role, with the enneagram's inner figures set
+    /// apart from the other chords of the Ennead
+    fn of(role: Option<LineRole>, figure: Option<EnneagramFigure>) -> EdgeStyle {
+        match (figure, role) {
+            (Some(EnneagramFigure::Triangle), _) => EdgeStyle::TRIANGLE,
+            (Some(EnneagramFigure::Process), _) => EdgeStyle::PROCESS,
+            (None, Some(LineRole::Side)) => EdgeStyle::SIDE,
+            (None, Some(LineRole::Circumference)) => EdgeStyle::CIRCUMFERENCE,
+            (None, Some(LineRole::Diagonal)) => EdgeStyle::DIAGONAL,
+            (None, Some(LineRole::Chord)) => EdgeStyle::CHORD,
+            (None, Some(LineRole::Unknown) | None) => EdgeStyle::LINE,
+        }
+    }
+
+    /// Sample of this style for the legend, at height `y`
+    fn sample(&self, y: f64) -> Html {
+        let path = match self.shape {
+            EdgeShape::Straight => format!("M 20 {y} L 50 {y}"),
+            EdgeShape::Arc | EdgeShape::Bow(_) => format!("M 20 {y} Q 35 {} 50 {y}", y - 8.0),
+        };
+        let dasharray = self
+            .dasharray
+            .map(|(dash, gap)| format!("{} {}", dash, gap))
+            .unwrap_or_else(|| "none".to_string());
+        html! {
+            <>
+                <path
+                    d={ path }
+                    fill="none"
+                    stroke={ self.color }
+                    stroke-width={ self.width.to_string() }
+                    stroke-dasharray={ dasharray }
+                />
+                <text x="58" y={ y.to_string() } dominant-baseline="middle">{ self.label }</text>
+            </>
+        }
+    }
+}
+
+/// Title and line key at the bottom left, inside the margin the coordinates
+/// leave free
+fn render_legend(system: &SystemView, caption: &str) -> Html {
+    let styles: Vec<EdgeStyle> = EdgeStyle::ALL
+        .into_iter()
+        .filter(|style| {
+            system.lines.iter().any(|line| {
+                let (a, b) = (
+                    line.base_position.unwrap_or(0),
+                    line.target_position.unwrap_or(0),
+                );
+                EdgeStyle::of(line.role, system.enneagram_figure(a, b)) == *style
+            })
+        })
+        .collect();
+    // Keys stack up from the bottom, with the title above them
+    let key_y = |i: usize| 782.0 - 18.0 * (styles.len() - 1 - i) as f64;
+    let title_y = 782.0 - 18.0 * styles.len() as f64 - 4.0;
+    html! {
+        <g class="graph-legend" aria-hidden="true" style="pointer-events: none; user-select: none;">
+            <text class="legend-title" x="20" y={ title_y.to_string() }>{ caption.to_string() }</text>
+            { for styles.iter().enumerate().map(|(i, style)| style.sample(key_y(i))) }
+        </g>
     }
 }
+
+/// Percent-encoding of everything but unreserved characters, for data URLs
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
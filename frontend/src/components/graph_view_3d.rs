@@ -0,0 +1,479 @@
+use systematics_middleware::SystemView;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    HtmlCanvasElement, WebGlBuffer, WebGlProgram, WebGlRenderingContext as Gl, WebGlShader,
+};
+use yew::prelude::*;
+
+/// Size of the canvas, in pixels
+const CANVAS_SIZE: f64 = 800.0;
+
+/// Default colours for rendering, as in the 2D view
+const DEFAULT_NODE_COLOR: &str = "#4A90E2";
+const BOUNDARY_EDGE_COLOR: [f32; 3] = [0.53, 0.53, 0.53];
+const INTERIOR_EDGE_COLOR: [f32; 3] = [0.75, 0.75, 0.75];
+const SELECTED_NODE_COLOR: &str = "#FF6B6B";
+
+/// Diameter of a node, in pixels
+const NODE_SIZE: f32 = 24.0;
+
+/// Radians turned per pixel dragged
+const ORBIT_SPEED: f64 = 0.01;
+
+/// Camera distance from the centre, in radii of the system, and its limits
+const DEFAULT_DISTANCE: f64 = 3.5;
+const MIN_DISTANCE: f64 = 1.5;
+const MAX_DISTANCE: f64 = 10.0;
+
+/// Vertical field of view, in radians
+const FIELD_OF_VIEW: f64 = std::f64::consts::FRAC_PI_4;
+
+const VERTEX_SHADER: &str = r#"
+attribute vec3 a_position;
+attribute vec3 a_colour;
+uniform mat4 u_matrix;
+uniform float u_point_size;
+varying vec3 v_colour;
+void main() {
+    gl_Position = u_matrix * vec4(a_position, 1.0);
+    gl_PointSize = u_point_size;
+    v_colour = a_colour;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"
+precision mediump float;
+uniform bool u_round;
+varying vec3 v_colour;
+void main() {
+    if (u_round && length(gl_PointCoord - vec2(0.5)) > 0.5) {
+        discard;
+    }
+    gl_FragColor = vec4(v_colour, 1.0);
+}
+"#;
+
+#[derive(Properties, PartialEq)]
+pub struct ApiGraphView3dProps {
+    pub system: SystemView,
+    /// Position of the node to highlight, as selected in the 2D view
+    #[prop_or_default]
+    pub selected_position: Option<i32>,
+}
+
+pub enum ApiGraph3dMsg {
+    DragStart(i32, i32),
+    Drag(i32, i32),
+    DragEnd,
+    Zoom(f64),
+    ResetCamera,
+}
+
+/// Camera orbiting the system's centre
+#[derive(Clone, Copy, PartialEq)]
+struct Orbit {
+    /// Turn about the vertical axis, in radians
+    yaw: f64,
+    /// Tilt towards or away from the viewer, in radians
+    pitch: f64,
+    /// Distance from the centre, in radii of the system
+    distance: f64,
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            distance: DEFAULT_DISTANCE,
+        }
+    }
+}
+
+/// WebGL renderer of a 3D view of a system, with orbit controls: drag to
+/// turn the system, scroll to zoom, double-click to reset the camera.
+/// Systems with flat coordinates (z = 0) show as a plane that can be turned.
+pub struct ApiGraphView3d {
+    canvas: NodeRef,
+    orbit: Orbit,
+    /// Last pointer position while dragging
+    dragging: Option<(i32, i32)>,
+    /// Compiled shaders, created on the first render; `None` without WebGL
+    renderer: Option<Renderer>,
+}
+
+impl Component for ApiGraphView3d {
+    type Message = ApiGraph3dMsg;
+    type Properties = ApiGraphView3dProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            canvas: NodeRef::default(),
+            orbit: Orbit::default(),
+            dragging: None,
+            renderer: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ApiGraph3dMsg::DragStart(x, y) => {
+                self.dragging = Some((x, y));
+                false
+            }
+            ApiGraph3dMsg::Drag(x, y) => {
+                let Some((last_x, last_y)) = self.dragging else {
+                    return false;
+                };
+                self.dragging = Some((x, y));
+                self.orbit.yaw += (x - last_x) as f64 * ORBIT_SPEED;
+                // Stop short of looking straight down or up
+                let limit = std::f64::consts::FRAC_PI_2 - 0.01;
+                self.orbit.pitch =
+                    (self.orbit.pitch + (y - last_y) as f64 * ORBIT_SPEED).clamp(-limit, limit);
+                true
+            }
+            ApiGraph3dMsg::DragEnd => {
+                self.dragging = None;
+                false
+            }
+            ApiGraph3dMsg::Zoom(delta) => {
+                let factor = if delta > 0.0 { 1.1 } else { 1.0 / 1.1 };
+                self.orbit.distance =
+                    (self.orbit.distance * factor).clamp(MIN_DISTANCE, MAX_DISTANCE);
+                true
+            }
+            ApiGraph3dMsg::ResetCamera => {
+                self.orbit = Orbit::default();
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // A new system is seen from the front
+        if ctx.props().system.order != old_props.system.order {
+            self.orbit = Orbit::default();
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let system = &ctx.props().system;
+        let link = ctx.link();
+        let onpointerdown = link.callback(|e: PointerEvent| {
+            if let Some(target) = e.target_dyn_into::<web_sys::Element>() {
+                // Keep receiving moves while the pointer is outside the canvas
+                let _ = target.set_pointer_capture(e.pointer_id());
+            }
+            ApiGraph3dMsg::DragStart(e.client_x(), e.client_y())
+        });
+        let onpointermove =
+            link.callback(|e: PointerEvent| ApiGraph3dMsg::Drag(e.client_x(), e.client_y()));
+        let onpointerup = link.callback(|_: PointerEvent| ApiGraph3dMsg::DragEnd);
+        let onwheel = link.callback(|e: WheelEvent| {
+            e.prevent_default();
+            ApiGraph3dMsg::Zoom(e.delta_y())
+        });
+        let ondblclick = link.callback(|_: MouseEvent| ApiGraph3dMsg::ResetCamera);
+
+        let matrix = view_projection(&self.orbit);
+        let labels = normalized_points(system)
+            .into_iter()
+            .filter_map(|(position, point)| {
+                let (x, y) = project(&matrix, point)?;
+                let term = system.term_at(position).unwrap_or("");
+                let style = format!(
+                    "left: {:.1}%; top: {:.1}%;",
+                    x / CANVAS_SIZE * 100.0,
+                    y / CANVAS_SIZE * 100.0
+                );
+                Some(html! {
+                    <div class="label-3d" style={ style }>
+                        <span class="label-3d-position">{ position }</span>
+                        if !term.is_empty() {
+                            <span class="label-3d-term">{ term }</span>
+                        }
+                    </div>
+                })
+            })
+            .collect::<Html>();
+
+        html! {
+            <div class="graph-view graph-view-3d">
+                <p class="graph-caption">
+                    { format!("{} · drag to turn, scroll to zoom, double-click to reset", system.display_name()) }
+                </p>
+                <div class="graph-3d-stage">
+                    <canvas
+                        ref={ self.canvas.clone() }
+                        class="graph-canvas"
+                        role="img"
+                        aria-label={ format!("3D graph of the {}; switch the 3D view off for a graph that can be explored with the keyboard", system.display_name()) }
+                        width={ CANVAS_SIZE.to_string() }
+                        height={ CANVAS_SIZE.to_string() }
+                        { onpointerdown }
+                        { onpointermove }
+                        { onpointerup }
+                        { onwheel }
+                        { ondblclick }
+                    />
+                    <div class="graph-3d-labels">{ labels }</div>
+                    if self.renderer.is_none() && self.canvas.get().is_some() {
+                        <p class="graph-3d-unsupported">{ "3D view needs WebGL, which this browser does not provide" }</p>
+                    }
+                </div>
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        let Some(canvas) = self.canvas.cast::<HtmlCanvasElement>() else {
+            return;
+        };
+        if first_render {
+            self.renderer = Renderer::new(&canvas);
+            if self.renderer.is_none() {
+                web_sys::console::warn_1(&"WebGL is unavailable; the 3D view stays empty".into());
+                return;
+            }
+        }
+        if let Some(renderer) = &self.renderer {
+            renderer.draw(ctx.props(), &view_projection(&self.orbit));
+        }
+    }
+}
+
+/// WebGL context with the program drawing points and lines
+struct Renderer {
+    gl: Gl,
+    program: WebGlProgram,
+    positions: WebGlBuffer,
+    colours: WebGlBuffer,
+}
+
+impl Renderer {
+    fn new(canvas: &HtmlCanvasElement) -> Option<Self> {
+        let gl = canvas.get_context("webgl").ok()??.dyn_into::<Gl>().ok()?;
+        let vertex = compile(&gl, Gl::VERTEX_SHADER, VERTEX_SHADER)?;
+        let fragment = compile(&gl, Gl::FRAGMENT_SHADER, FRAGMENT_SHADER)?;
+        let program = gl.create_program()?;
+        gl.attach_shader(&program, &vertex);
+        gl.attach_shader(&program, &fragment);
+        gl.link_program(&program);
+        if !gl
+            .get_program_parameter(&program, Gl::LINK_STATUS)
+            .as_bool()
+            .unwrap_or(false)
+        {
+            let log = gl.get_program_info_log(&program).unwrap_or_default();
+            web_sys::console::warn_1(&format!("WebGL program failed to link: {}", log).into());
+            return None;
+        }
+        Some(Self {
+            positions: gl.create_buffer()?,
+            colours: gl.create_buffer()?,
+            gl,
+            program,
+        })
+    }
+
+    /// Draw the lines, then the nodes over them
+    fn draw(&self, props: &ApiGraphView3dProps, matrix: &[f32; 16]) {
+        let gl = &self.gl;
+        let system = &props.system;
+        gl.viewport(0, 0, CANVAS_SIZE as i32, CANVAS_SIZE as i32);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(Gl::COLOR_BUFFER_BIT | Gl::DEPTH_BUFFER_BIT);
+        gl.enable(Gl::DEPTH_TEST);
+        gl.use_program(Some(&self.program));
+        gl.uniform_matrix4fv_with_f32_array(
+            gl.get_uniform_location(&self.program, "u_matrix").as_ref(),
+            false,
+            matrix,
+        );
+
+        let points = normalized_points(system);
+        let point_at = |position: Option<i32>| {
+            let position = position?;
+            points
+                .iter()
+                .find(|(p, _)| *p == position)
+                .map(|(_, point)| *point)
+        };
+
+        let mut line_vertices = Vec::new();
+        let mut line_colours = Vec::new();
+        for line in &system.lines {
+            let (Some(base), Some(target)) =
+                (point_at(line.base_position), point_at(line.target_position))
+            else {
+                continue;
+            };
+            let colour = match line.role {
+                Some(role) if !role.is_boundary() => INTERIOR_EDGE_COLOR,
+                _ => BOUNDARY_EDGE_COLOR,
+            };
+            line_vertices.extend(base.iter().chain(target.iter()).map(|&v| v as f32));
+            line_colours.extend(colour.iter().chain(colour.iter()));
+        }
+        self.draw_arrays(Gl::LINES, &line_vertices, &line_colours, false);
+
+        let mut node_vertices = Vec::new();
+        let mut node_colours = Vec::new();
+        for (position, point) in &points {
+            let colour = if props.selected_position == Some(*position) {
+                SELECTED_NODE_COLOR
+            } else {
+                system.colour_at(*position).unwrap_or(DEFAULT_NODE_COLOR)
+            };
+            node_vertices.extend(point.iter().map(|&v| v as f32));
+            node_colours.extend(rgb(colour));
+        }
+        self.draw_arrays(Gl::POINTS, &node_vertices, &node_colours, true);
+    }
+
+    /// Draw vertices (x, y, z) with their colours (r, g, b)
+    fn draw_arrays(&self, mode: u32, vertices: &[f32], colours: &[f32], round: bool) {
+        let gl = &self.gl;
+        for (buffer, data, name) in [
+            (&self.positions, vertices, "a_position"),
+            (&self.colours, colours, "a_colour"),
+        ] {
+            let location = gl.get_attrib_location(&self.program, name);
+            if location < 0 {
+                return;
+            }
+            let bytes: Vec<u8> = data.iter().flat_map(|v| v.to_le_bytes()).collect();
+            gl.bind_buffer(Gl::ARRAY_BUFFER, Some(buffer));
+            gl.buffer_data_with_u8_array(Gl::ARRAY_BUFFER, &bytes, Gl::STREAM_DRAW);
+            gl.enable_vertex_attrib_array(location as u32);
+            gl.vertex_attrib_pointer_with_i32(location as u32, 3, Gl::FLOAT, false, 0, 0);
+        }
+        gl.uniform1f(
+            gl.get_uniform_location(&self.program, "u_point_size")
+                .as_ref(),
+            NODE_SIZE,
+        );
+        gl.uniform1i(
+            gl.get_uniform_location(&self.program, "u_round").as_ref(),
+            round as i32,
+        );
+        gl.draw_arrays(mode, 0, (vertices.len() / 3) as i32);
+    }
+}
+
+fn compile(gl: &Gl, kind: u32, source: &str) -> Option<WebGlShader> {
+    let shader = gl.create_shader(kind)?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+    if gl
+        .get_shader_parameter(&shader, Gl::COMPILE_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Some(shader)
+    } else {
+        let log = gl.get_shader_info_log(&shader).unwrap_or_default();
+        web_sys::console::warn_1(&format!("WebGL shader failed to compile: {}", log).into());
+        None
+    }
+}
+
+/// Points of the system by position, centred on the origin and scaled into
+/// the unit sphere, with y and z up and towards the viewer. The fetched
+/// coordinates are in viewport space (y down, see `prepare_system`).
+fn normalized_points(system: &SystemView) -> Vec<(i32, [f64; 3])> {
+    let points: Vec<(i32, [f64; 3])> = system
+        .coordinates
+        .iter()
+        .map(|c| (c.position, [c.x, -c.y, c.z]))
+        .collect();
+    if points.is_empty() {
+        return points;
+    }
+    let count = points.len() as f64;
+    let mut centre = [0.0; 3];
+    for (_, point) in &points {
+        for axis in 0..3 {
+            centre[axis] += point[axis] / count;
+        }
+    }
+    let radius = points
+        .iter()
+        .map(|(_, p)| {
+            ((p[0] - centre[0]).powi(2) + (p[1] - centre[1]).powi(2) + (p[2] - centre[2]).powi(2))
+                .sqrt()
+        })
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    points
+        .into_iter()
+        .map(|(position, p)| {
+            (
+                position,
+                [
+                    (p[0] - centre[0]) / radius,
+                    (p[1] - centre[1]) / radius,
+                    (p[2] - centre[2]) / radius,
+                ],
+            )
+        })
+        .collect()
+}
+
+/// Matrix (column-major) turning the system by the orbit, moving it away from
+/// the camera and projecting it in perspective
+fn view_projection(orbit: &Orbit) -> [f32; 16] {
+    let (sy, cy) = orbit.yaw.sin_cos();
+    let (sp, cp) = orbit.pitch.sin_cos();
+    // Yaw about y, then pitch about x
+    let rotation = [
+        [cy, sp * sy, -cp * sy],
+        [0.0, cp, sp],
+        [sy, -sp * cy, cp * cy],
+    ];
+    let f = 1.0 / (FIELD_OF_VIEW / 2.0).tan();
+    let (near, far) = (0.1, 100.0);
+    let depth = (far + near) / (near - far);
+    let offset = 2.0 * far * near / (near - far);
+
+    // Projection of (R p - (0, 0, distance))
+    let mut m = [0.0f32; 16];
+    for column in 0..3 {
+        let r = rotation[column];
+        m[column * 4] = (f * r[0]) as f32;
+        m[column * 4 + 1] = (f * r[1]) as f32;
+        m[column * 4 + 2] = (depth * r[2]) as f32;
+        m[column * 4 + 3] = -r[2] as f32;
+    }
+    m[14] = (depth * -orbit.distance + offset) as f32;
+    m[15] = orbit.distance as f32;
+    m
+}
+
+/// Canvas pixel of a point, or `None` behind the camera
+fn project(matrix: &[f32; 16], point: [f64; 3]) -> Option<(f64, f64)> {
+    let clip = |row: usize| {
+        (0..3)
+            .map(|column| matrix[column * 4 + row] as f64 * point[column])
+            .sum::<f64>()
+            + matrix[12 + row] as f64
+    };
+    let w = clip(3);
+    if w <= 0.0 {
+        return None;
+    }
+    let (x, y) = (clip(0) / w, clip(1) / w);
+    Some(((x + 1.0) / 2.0 * CANVAS_SIZE, (1.0 - y) / 2.0 * CANVAS_SIZE))
+}
+
+/// Components of a `#RRGGBB` colour, from 0 to 1 (grey if malformed)
+fn rgb(hex: &str) -> [f32; 3] {
+    let channel = |i: usize| {
+        hex.get(1 + 2 * i..3 + 2 * i)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .map_or(0.5, |c| c as f32 / 255.0)
+    };
+    [channel(0), channel(1), channel(2)]
+}
@@ -1,2 +1,8 @@
+pub mod edit_panel;
 pub mod graph_view;
+pub mod graph_view_3d;
+pub mod note_list;
+pub mod position_detail;
+pub mod print_view;
+pub mod search_box;
 pub mod system_selector;
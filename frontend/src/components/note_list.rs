@@ -0,0 +1,97 @@
+use systematics_middleware::AnnotationView;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::notes::LocalNote;
+
+#[derive(Properties, PartialEq)]
+pub struct NoteListProps {
+    /// What the notes are on (e.g., "Will"), for screen readers
+    pub subject: String,
+    /// Notes kept in this browser
+    pub notes: Vec<LocalNote>,
+    /// Notes on the server other than those sent from here
+    #[prop_or_default]
+    pub shared: Vec<AnnotationView>,
+    /// Called with the text of a new note
+    pub on_add: Callback<String>,
+    /// Called with the ID of a note to delete
+    pub on_remove: Callback<u32>,
+}
+
+/// Notes on a term or connective, with a field for writing another
+#[function_component(NoteList)]
+pub fn note_list(props: &NoteListProps) -> Html {
+    let draft = use_state(String::new);
+    let oninput = {
+        let draft = draft.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            draft.set(input.value());
+        })
+    };
+    let onsubmit = {
+        let draft = draft.clone();
+        let on_add = props.on_add.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if !draft.trim().is_empty() {
+                on_add.emit(draft.trim().to_string());
+                draft.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <section class="notes" aria-label={ format!("Notes on {}", props.subject) }>
+            <h4>{ "Notes" }</h4>
+            if !props.notes.is_empty() {
+                <ul class="note-list">
+                    { for props.notes.iter().map(|note| {
+                        let on_remove = props.on_remove.clone();
+                        let id = note.id;
+                        html! {
+                            <li class="note">
+                                <span class="note-text">{ &note.text }</span>
+                                if note.remote_id.is_some() {
+                                    <span class="note-synced" title="Saved to the server">{ "✓" }</span>
+                                }
+                                <button
+                                    class="note-remove"
+                                    title="Delete this note from this browser"
+                                    aria-label="Delete note"
+                                    onclick={ Callback::from(move |_| on_remove.emit(id)) }
+                                >
+                                    { "×" }
+                                </button>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            }
+            if !props.shared.is_empty() {
+                <ul class="note-list shared">
+                    { for props.shared.iter().map(|note| html! {
+                        <li class="note">
+                            <span class="note-text">{ &note.text }</span>
+                            <span class="note-author">{ format!("— {}", note.author) }</span>
+                        </li>
+                    }) }
+                </ul>
+            }
+            <form class="note-form" { onsubmit }>
+                <textarea
+                    class="note-input"
+                    rows="2"
+                    placeholder="Add a note…"
+                    aria-label={ format!("New note on {}", props.subject) }
+                    value={ (*draft).clone() }
+                    { oninput }
+                />
+                <button type="submit" class="note-add" disabled={ draft.trim().is_empty() }>
+                    { "Add" }
+                </button>
+            </form>
+        </section>
+    }
+}
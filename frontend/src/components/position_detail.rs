@@ -0,0 +1,111 @@
+use systematics_middleware::{AnnotationView, Link, Slice};
+use yew::prelude::*;
+
+use crate::components::note_list::NoteList;
+use crate::notes::LocalNote;
+
+#[derive(Properties, PartialEq)]
+pub struct PositionDetailProps {
+    pub slice: Slice,
+    pub on_close: Callback<()>,
+    /// Notes on the term, kept in this browser
+    #[prop_or_default]
+    pub notes: Vec<LocalNote>,
+    /// Others' notes on the term, from the server
+    #[prop_or_default]
+    pub shared_notes: Vec<AnnotationView>,
+    /// IDs of the connectives that have notes
+    #[prop_or_default]
+    pub noted_links: Vec<String>,
+    pub on_add_note: Callback<String>,
+    pub on_remove_note: Callback<u32>,
+}
+
+/// Label of a connective: its character, or its tag
+fn connective_label(link: &Link) -> &str {
+    link.character
+        .as_ref()
+        .map(|c| c.value.as_str())
+        .or(link.tag.as_deref())
+        .unwrap_or("")
+}
+
+/// Panel detailing one position of a system: its term, coordinate, colour,
+/// the connectives and lines meeting there, and the notes on the term
+#[function_component(PositionDetail)]
+pub fn position_detail(props: &PositionDetailProps) -> Html {
+    let slice = &props.slice;
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+
+    let system = slice
+        .system_name
+        .clone()
+        .unwrap_or_else(|| format!("K{}", slice.order));
+    let term = slice.term.as_ref().and_then(|t| t.character.as_ref());
+    let term_class = classes!(
+        "position-term",
+        term.is_some_and(|c| c.status.needs_research())
+            .then_some("needs-research"),
+    );
+
+    html! {
+        <aside class="position-detail">
+            <header>
+                <h3>{ format!("{} · position {}", system, slice.position) }</h3>
+                <button class="position-detail-close" onclick={ on_close }>{ "×" }</button>
+            </header>
+            <p class={ term_class }>
+                { term.map(|c| c.value.clone()).unwrap_or_else(|| "No term".to_string()) }
+            </p>
+            if let Some(ref colour) = slice.colour {
+                <p class="position-colour">
+                    <span class="swatch" style={ format!("background: {};", colour.value) }></span>
+                    { &colour.value }
+                </p>
+            }
+            if let Some(ref coordinate) = slice.coordinate {
+                <p class="position-coordinate">
+                    { format!("({:.3}, {:.3}, {:.3})", coordinate.x, coordinate.y, coordinate.z) }
+                </p>
+            }
+            if !slice.connectives.is_empty() {
+                <h4>{ "Connectives" }</h4>
+                <ul>
+                    { for slice.connectives.iter().map(|link| html! {
+                        <li>
+                            { format!(
+                                "{} → {}",
+                                link.base_position.unwrap_or(0),
+                                link.target_position.unwrap_or(0),
+                            ) }
+                            <span class="connective-label">{ connective_label(link) }</span>
+                            if props.noted_links.contains(&link.id) {
+                                <span class="note-marker-inline" title="Has notes">{ "✎" }</span>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            if !slice.lines.is_empty() {
+                <p class="position-lines">
+                    { format!(
+                        "{} line{}, {} on the boundary",
+                        slice.lines.len(),
+                        if slice.lines.len() == 1 { "" } else { "s" },
+                        slice.lines.iter().filter(|l| l.role.is_some_and(|r| r.is_boundary())).count(),
+                    ) }
+                </p>
+            }
+            <NoteList
+                subject={ term.map(|c| c.value.clone()).unwrap_or_else(|| format!("position {}", slice.position)) }
+                notes={ props.notes.clone() }
+                shared={ props.shared_notes.clone() }
+                on_add={ props.on_add_note.clone() }
+                on_remove={ props.on_remove_note.clone() }
+            />
+        </aside>
+    }
+}
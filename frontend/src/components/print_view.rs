@@ -0,0 +1,108 @@
+use systematics_middleware::{Language, SystemView};
+use yew::prelude::*;
+
+use crate::components::graph_view::ApiGraphView;
+use crate::routes::system_slug;
+
+/// Source cited in the footer of every printed page
+const CITATION: &str =
+    "Systematics after J. G. Bennett, The Dramatic Universe (1956–1966); diagram from systematics-v0.5";
+
+#[derive(Properties, PartialEq)]
+pub struct PrintViewProps {
+    /// Systems to print, a page each
+    pub systems: Vec<SystemView>,
+    /// Whether every system is printed, rather than the selected one
+    pub all: bool,
+    pub language: Language,
+    #[prop_or_default]
+    pub show_edge_labels: bool,
+    /// Called with `true` to print every system, `false` the selected one
+    pub on_scope: Callback<bool>,
+    pub on_close: Callback<()>,
+}
+
+/// Systems laid out for printing: on white, with larger labels, the legend,
+/// a list of the terms and a citation footer on each page
+#[function_component(PrintView)]
+pub fn print_view(props: &PrintViewProps) -> Html {
+    let on_print = Callback::from(|_: MouseEvent| {
+        if let Some(window) = web_sys::window() {
+            if let Err(e) = window.print() {
+                web_sys::console::warn_1(&e);
+            }
+        }
+    });
+    let on_scope = {
+        let on_scope = props.on_scope.clone();
+        let all = props.all;
+        Callback::from(move |_: MouseEvent| on_scope.emit(!all))
+    };
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_: MouseEvent| on_close.emit(()))
+    };
+    let printed = printed_on();
+
+    html! {
+        <div class="print-view">
+            <div class="print-toolbar">
+                <button class="graph-tool" onclick={ on_print }>{ "Print" }</button>
+                <button class="graph-tool" onclick={ on_scope }>
+                    { if props.all { "This system only" } else { "All systems" } }
+                </button>
+                <button class="graph-tool" onclick={ on_close }>{ "← Back" }</button>
+            </div>
+            if props.systems.is_empty() {
+                <div class="loading">{ "Loading systems..." }</div>
+            }
+            { for props.systems.iter().map(|system| html! {
+                <section class="print-page" key={ system.order }>
+                    <ApiGraphView
+                        system={ system.clone() }
+                        show_edge_labels={ props.show_edge_labels }
+                        print=true
+                    />
+                    <ol class="print-terms">
+                        { for system.coordinates.iter().map(|coord| html! {
+                            <li value={ coord.position.to_string() }>
+                                { system.term_at(coord.position).unwrap_or("—") }
+                            </li>
+                        }) }
+                    </ol>
+                    <footer class="print-citation">
+                        { format!("{}. {}, {} terms: {}", CITATION, system.display_name(), vocabulary_name(props.language), view_url(system, props.language)) }
+                        if let Some(ref date) = printed {
+                            { format!(" (printed {})", date) }
+                        }
+                    </footer>
+                </section>
+            }) }
+        </div>
+    }
+}
+
+/// e.g., "Energy"
+fn vocabulary_name(language: Language) -> String {
+    let slug = language.slug();
+    format!("{}{}", slug[..1].to_uppercase(), &slug[1..])
+}
+
+/// Address of a system's interactive view, for readers of the printout
+fn view_url(system: &SystemView, language: Language) -> String {
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    let query = if language == Language::Canonical {
+        String::new()
+    } else {
+        format!("?lang={}", language.slug())
+    };
+    format!("{}/system/{}{}", origin, system_slug(system), query)
+}
+
+/// Today's date, as `2025-01-31`
+fn printed_on() -> Option<String> {
+    let iso = String::from(web_sys::js_sys::Date::new_0().to_iso_string());
+    iso.get(..10).map(str::to_string)
+}
@@ -0,0 +1,196 @@
+use gloo_timers::callback::Timeout;
+use systematics_middleware::{SearchHit, SearchHitKind};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Pause in typing before searching, in milliseconds
+const DEBOUNCE_MS: u32 = 250;
+
+/// Characters typed before searching
+const MIN_SEARCH_LENGTH: usize = 2;
+
+#[derive(Properties, PartialEq)]
+pub struct SearchBoxProps {
+    /// Hits so far for the text last searched
+    pub hits: Vec<SearchHit>,
+    /// Hits in all pages
+    pub total: i32,
+    /// Whether there are more hits to fetch
+    pub has_more: bool,
+    pub loading: bool,
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Called with the text to search, once typing pauses (empty to clear)
+    pub on_search: Callback<String>,
+    pub on_more: Callback<()>,
+    /// Called with the hit picked, to jump to its system
+    pub on_pick: Callback<SearchHit>,
+}
+
+pub enum SearchBoxMsg {
+    Input(String),
+    Search,
+    Pick(SearchHit),
+    Clear,
+}
+
+/// Search input over every system, listing hits by the system they belong to
+pub struct SearchBox {
+    text: String,
+    /// Pending search, dropped (and so cancelled) as typing goes on
+    debounce: Option<Timeout>,
+    /// Whether the hits are listed
+    open: bool,
+}
+
+impl Component for SearchBox {
+    type Message = SearchBoxMsg;
+    type Properties = SearchBoxProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            text: String::new(),
+            debounce: None,
+            open: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SearchBoxMsg::Input(text) => {
+                self.text = text;
+                let link = ctx.link().clone();
+                self.debounce = Some(Timeout::new(DEBOUNCE_MS, move || {
+                    link.send_message(SearchBoxMsg::Search)
+                }));
+                true
+            }
+            SearchBoxMsg::Search => {
+                self.debounce = None;
+                let text = self.text.trim();
+                self.open = text.chars().count() >= MIN_SEARCH_LENGTH;
+                ctx.props().on_search.emit(if self.open {
+                    text.to_string()
+                } else {
+                    String::new()
+                });
+                true
+            }
+            SearchBoxMsg::Pick(hit) => {
+                self.open = false;
+                ctx.props().on_pick.emit(hit);
+                true
+            }
+            SearchBoxMsg::Clear => {
+                self.text.clear();
+                self.debounce = None;
+                self.open = false;
+                ctx.props().on_search.emit(String::new());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let oninput = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            SearchBoxMsg::Input(input.value())
+        });
+        let onkeydown = ctx
+            .link()
+            .batch_callback(|e: KeyboardEvent| match e.key().as_str() {
+                "Escape" => Some(SearchBoxMsg::Clear),
+                "Enter" => Some(SearchBoxMsg::Search),
+                _ => None,
+            });
+        let on_clear = ctx.link().callback(|_| SearchBoxMsg::Clear);
+        let on_more = {
+            let on_more = props.on_more.clone();
+            Callback::from(move |_| on_more.emit(()))
+        };
+
+        html! {
+            <div class="search-box">
+                <div class="search-field">
+                    <input
+                        type="search"
+                        class="search-input"
+                        placeholder="Search terms, connectives, coherences…"
+                        aria-label="Search all systems"
+                        value={ self.text.clone() }
+                        { oninput }
+                        { onkeydown }
+                    />
+                    if !self.text.is_empty() {
+                        <button class="search-clear" onclick={ on_clear } title="Clear the search">
+                            { "×" }
+                        </button>
+                    }
+                </div>
+                if self.open {
+                    <div class="search-results">
+                        if let Some(ref error) = props.error {
+                            <p class="search-status search-error">{ error }</p>
+                        } else if props.hits.is_empty() {
+                            <p class="search-status">
+                                { if props.loading { "Searching…" } else { "No matches" } }
+                            </p>
+                        } else {
+                            <p class="search-status">
+                                { format!("{} of {} matches", props.hits.len(), props.total) }
+                            </p>
+                            <ul class="search-hits">
+                                { for props.hits.iter().map(|hit| self.render_hit(ctx, hit)) }
+                            </ul>
+                            if props.has_more {
+                                <button class="search-more" onclick={ on_more } disabled={ props.loading }>
+                                    { if props.loading { "Loading…" } else { "More results" } }
+                                </button>
+                            }
+                        }
+                    </div>
+                }
+            </div>
+        }
+    }
+}
+
+impl SearchBox {
+    /// A hit with what it matched and where; hits outside any system (e.g.,
+    /// unplaced characters) cannot be jumped to
+    fn render_hit(&self, ctx: &Context<Self>, hit: &SearchHit) -> Html {
+        let kind = kind_label(hit.kind);
+        let place = match (hit.order, hit.position) {
+            (Some(order), Some(position)) => format!("Order {} · position {}", order, position),
+            (Some(order), None) => format!("Order {}", order),
+            (None, _) => "No system".to_string(),
+        };
+        let onclick = {
+            let hit = hit.clone();
+            ctx.link()
+                .callback(move |_| SearchBoxMsg::Pick(hit.clone()))
+        };
+        html! {
+            <li>
+                <button class="search-hit" onclick={ onclick } disabled={ hit.order.is_none() }>
+                    <span class="search-hit-value">{ &hit.value }</span>
+                    <span class="search-hit-meta">{ format!("{} · {}", kind, place) }</span>
+                </button>
+            </li>
+        }
+    }
+}
+
+fn kind_label(kind: SearchHitKind) -> &'static str {
+    match kind {
+        SearchHitKind::Term => "Term",
+        SearchHitKind::Connective => "Connective",
+        SearchHitKind::Character => "Character",
+        SearchHitKind::SystemName => "System",
+        SearchHitKind::CoherenceAttribute => "Coherence",
+        SearchHitKind::TermDesignation => "Term designation",
+        SearchHitKind::ConnectiveDesignation => "Connective designation",
+        SearchHitKind::Unknown => "Match",
+    }
+}
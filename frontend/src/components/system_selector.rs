@@ -1,3 +1,5 @@
+use systematics_middleware::Language;
+use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
 /// Simple display config for system selector (UI only)
@@ -6,6 +8,8 @@ pub struct SystemDisplay {
     pub name: String,
     pub display_name: String,
     pub k_notation: String,
+    /// False for user-defined custom systems
+    pub is_canonical: bool,
 }
 
 #[derive(Properties, PartialEq)]
@@ -17,6 +21,20 @@ pub struct SystemSelectorProps {
     pub show_edge_labels: bool,
     #[prop_or_default]
     pub on_toggle_edge_labels: Option<Callback<()>>,
+    #[prop_or_default]
+    pub show_3d: bool,
+    #[prop_or_default]
+    pub on_toggle_3d: Option<Callback<()>>,
+    /// Vocabulary of the terms shown
+    #[prop_or(Language::Canonical)]
+    pub language: Language,
+    #[prop_or_default]
+    pub on_select_language: Option<Callback<Language>>,
+    #[prop_or_default]
+    pub editing: bool,
+    /// Edit mode toggle, offered only with a server to save edits to
+    #[prop_or_default]
+    pub on_toggle_edit: Option<Callback<()>>,
 }
 
 #[function_component(SystemSelector)]
@@ -27,6 +45,11 @@ pub fn system_selector(props: &SystemSelectorProps) -> Html {
                 {
                     props.systems.iter().map(|system| {
                         let is_selected = system.name == props.selected;
+                        let class = classes!(
+                            "nav-button",
+                            is_selected.then_some("selected"),
+                            (!system.is_canonical).then_some("custom"),
+                        );
                         let system_name = system.name.clone();
                         let onclick = {
                             let on_select = props.on_select.clone();
@@ -37,7 +60,7 @@ pub fn system_selector(props: &SystemSelectorProps) -> Html {
 
                         html! {
                             <button
-                                class={ if is_selected { "nav-button selected" } else { "nav-button" } }
+                                class={ class }
                                 onclick={ onclick }
                                 title={ system.k_notation.clone() }
                             >
@@ -48,25 +71,74 @@ pub fn system_selector(props: &SystemSelectorProps) -> Html {
                 }
             </div>
 
-            // Edge labels toggle switch
-            if let Some(ref on_toggle) = props.on_toggle_edge_labels {
+            if props.on_toggle_edge_labels.is_some()
+                || props.on_toggle_3d.is_some()
+                || props.on_select_language.is_some()
+                || props.on_toggle_edit.is_some()
+            {
                 <div class="nav-controls">
-                    <label class="edge-label-toggle">
-                        <span class="toggle-label">{"Edge Labels"}</span>
-                        <div class="toggle-switch">
-                            <input
-                                type="checkbox"
-                                checked={props.show_edge_labels}
-                                onclick={{
-                                    let on_toggle = on_toggle.clone();
-                                    Callback::from(move |_| on_toggle.emit(()))
-                                }}
-                            />
-                            <span class="slider"></span>
-                        </div>
-                    </label>
+                    // Vocabulary picker
+                    if let Some(ref on_select) = props.on_select_language {
+                        { vocabulary_picker(props.language, on_select) }
+                    }
+                    // Edge labels toggle switch
+                    if let Some(ref on_toggle) = props.on_toggle_edge_labels {
+                        { toggle("Edge Labels", props.show_edge_labels, on_toggle) }
+                    }
+                    // 2D/3D view toggle switch
+                    if let Some(ref on_toggle) = props.on_toggle_3d {
+                        { toggle("3D View", props.show_3d, on_toggle) }
+                    }
+                    // Edit mode toggle switch
+                    if let Some(ref on_toggle) = props.on_toggle_edit {
+                        { toggle("Edit", props.editing, on_toggle) }
+                    }
                 </div>
             }
         </nav>
     }
 }
+
+/// Labelled toggle switch emitting on each click
+fn toggle(label: &str, checked: bool, on_toggle: &Callback<()>) -> Html {
+    let on_toggle = on_toggle.clone();
+    html! {
+        <label class="edge-label-toggle">
+            <span class="toggle-label">{ label.to_string() }</span>
+            <div class="toggle-switch">
+                <input
+                    type="checkbox"
+                    checked={ checked }
+                    onclick={ Callback::from(move |_| on_toggle.emit(())) }
+                />
+                <span class="slider"></span>
+            </div>
+        </label>
+    }
+}
+
+/// Picker of the vocabulary the terms are shown in
+fn vocabulary_picker(language: Language, on_select: &Callback<Language>) -> Html {
+    let on_select = on_select.clone();
+    let onchange = Callback::from(move |e: Event| {
+        if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+            if let Some(language) = Language::from_slug(&select.value()) {
+                on_select.emit(language);
+            }
+        }
+    });
+    html! {
+        <label class="edge-label-toggle">
+            <span class="toggle-label">{ "Vocabulary" }</span>
+            <select class="vocabulary-select" { onchange }>
+                { for Language::all_vocabularies().into_iter().map(|option| {
+                    let slug = option.slug();
+                    let name = format!("{}{}", slug[..1].to_uppercase(), &slug[1..]);
+                    html! {
+                        <option value={ slug } selected={ option == language }>{ name }</option>
+                    }
+                })}
+            </select>
+        </label>
+    }
+}
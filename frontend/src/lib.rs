@@ -5,11 +5,13 @@
 mod api;
 mod app;
 mod components;
+mod notes;
+mod routes;
 
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(start)]
 pub fn run_app() {
     // Use API-driven app with GraphQL integration
-    yew::Renderer::<app::ApiApp>::new().render();
+    yew::Renderer::<app::App>::new().render();
 }
@@ -0,0 +1,109 @@
+//! Personal notes on terms and connectives, kept in the browser's local
+//! storage. A note on a term is attached to its location (`loc_3_1`, see
+//! `SystemView::location_id`), so it stays with the term in every vocabulary;
+//! a note on a connective is attached to the connective link. Signed-in users'
+//! notes are also sent to the backend as annotations, and remember the
+//! annotation they became.
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::local_storage;
+
+/// Local storage key of the notes
+const NOTES_KEY: &str = "systematics-notes";
+
+/// A note on an entry or link
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LocalNote {
+    pub id: u32,
+    /// ID of the location or connective link the note is on
+    pub entry_id: String,
+    pub text: String,
+    /// ID of the backend annotation the note was sent as, once sent
+    #[serde(default)]
+    pub remote_id: Option<String>,
+}
+
+/// Every note kept in this browser, in the order they were written
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoteStore {
+    notes: Vec<LocalNote>,
+}
+
+impl NoteStore {
+    /// The notes kept from earlier visits; none where storage is unavailable
+    /// or holds something unreadable
+    pub fn load() -> Self {
+        local_storage()
+            .and_then(|storage| storage.get_item(NOTES_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(self)) else {
+            return;
+        };
+        if storage.set_item(NOTES_KEY, &json).is_err() {
+            web_sys::console::warn_1(&"Notes could not be saved (storage full?)".into());
+        }
+    }
+
+    /// Notes on an entry or link
+    pub fn on(&self, entry_id: &str) -> Vec<LocalNote> {
+        self.notes
+            .iter()
+            .filter(|n| n.entry_id == entry_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Check if an entry or link has notes
+    pub fn has(&self, entry_id: &str) -> bool {
+        self.notes.iter().any(|n| n.entry_id == entry_id)
+    }
+
+    /// IDs of the backend annotations the notes were sent as
+    pub fn remote_ids(&self) -> Vec<&str> {
+        self.notes
+            .iter()
+            .filter_map(|n| n.remote_id.as_deref())
+            .collect()
+    }
+
+    /// Write a note on an entry or link, returning it
+    pub fn add(&mut self, entry_id: &str, text: &str) -> LocalNote {
+        let note = LocalNote {
+            id: self.notes.iter().map(|n| n.id + 1).max().unwrap_or(1),
+            entry_id: entry_id.to_string(),
+            text: text.trim().to_string(),
+            remote_id: None,
+        };
+        self.notes.push(note.clone());
+        self.save();
+        note
+    }
+
+    /// Delete a note from this browser (an annotation it was sent as stays)
+    pub fn remove(&mut self, id: u32) {
+        self.notes.retain(|n| n.id != id);
+        self.save();
+    }
+
+    /// Notes not yet sent to the backend
+    pub fn unsynced(&self) -> Vec<LocalNote> {
+        self.notes
+            .iter()
+            .filter(|n| n.remote_id.is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Record the annotation a note was sent as
+    pub fn mark_synced(&mut self, id: u32, remote_id: String) {
+        if let Some(note) = self.notes.iter_mut().find(|n| n.id == id) {
+            note.remote_id = Some(remote_id);
+            self.save();
+        }
+    }
+}
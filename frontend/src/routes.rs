@@ -0,0 +1,99 @@
+//! Routes, and the view of a system kept in the query string, so that a link
+//! such as `/system/heptad?labels=on&lang=energy&position=3` restores it.
+//! `edge=1-3` highlights the line between two positions instead of a node.
+//! `/print/heptad` and `/print` lay out one system or all of them for print.
+
+use serde::{Deserialize, Serialize};
+use systematics_middleware::{Language, SystemView};
+use yew_router::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Routable)]
+pub enum Route {
+    /// The first system
+    #[at("/")]
+    Home,
+    /// A system by its slug (e.g., `heptad`)
+    #[at("/system/:name")]
+    System { name: String },
+    /// A system laid out for printing
+    #[at("/print/:name")]
+    Print { name: String },
+    /// Every system laid out for printing
+    #[at("/print")]
+    PrintAll,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+/// Query string of a system's route
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewQuery {
+    /// `on` to show edge labels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    /// Vocabulary of the terms by slug (e.g., `energy`); canonical if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Selected node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<i32>,
+    /// Highlighted line, as its two positions (e.g., `1-3`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge: Option<String>,
+    /// Present in demo mode, so links keep running without a backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demo: Option<String>,
+}
+
+impl ViewQuery {
+    pub fn new(
+        show_edge_labels: bool,
+        language: Language,
+        position: Option<i32>,
+        demo: bool,
+    ) -> Self {
+        Self {
+            labels: show_edge_labels.then(|| "on".to_string()),
+            lang: (language != Language::Canonical).then(|| language.slug().to_string()),
+            position,
+            edge: None,
+            demo: demo.then(String::new),
+        }
+    }
+
+    /// The same view with a line highlighted
+    pub fn with_edge(mut self, edge: Option<(i32, i32)>) -> Self {
+        self.edge = edge.map(|(a, b)| format!("{}-{}", a, b));
+        self
+    }
+
+    /// Positions of the highlighted line, if well formed
+    pub fn edge(&self) -> Option<(i32, i32)> {
+        let (a, b) = self.edge.as_deref()?.split_once('-')?;
+        Some((a.parse().ok()?, b.parse().ok()?))
+    }
+
+    pub fn show_edge_labels(&self) -> bool {
+        self.labels.as_deref() == Some("on")
+    }
+
+    /// The vocabulary asked for, canonical if none or an unknown one is
+    pub fn language(&self) -> Language {
+        self.lang
+            .as_deref()
+            .and_then(Language::from_slug)
+            .filter(|language| Language::all_vocabularies().contains(language))
+            .unwrap_or(Language::Canonical)
+    }
+}
+
+/// Slug of a system name in routes: lowercase, with dashes for spaces
+pub fn slug(name: &str) -> String {
+    name.trim().to_lowercase().replace(char::is_whitespace, "-")
+}
+
+/// Slug of a system, from its name or else its display name
+pub fn system_slug(system: &SystemView) -> String {
+    slug(&system.name.clone().unwrap_or_else(|| system.display_name()))
+}
@@ -0,0 +1,25 @@
+//! Write the TypeScript declarations of the wire format to a file, or to
+//! standard output without one.
+//!
+//! ```bash
+//! cargo run -p systematics-middleware --features typescript --bin systematics-ts -- systematics.ts
+//! ```
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let declarations = systematics_middleware::typescript::declarations();
+    match std::env::args_os().nth(1) {
+        Some(path) => match std::fs::write(&path, declarations) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Could not write {}: {}", path.to_string_lossy(), e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            print!("{}", declarations);
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! CBOR and MessagePack encodings of the wire types (`binary` feature).
+//!
+//! Large systems and the whole graph are mostly repeated field names and ids,
+//! which JSON spells out as text. The binary encodings carry the same serde
+//! representation, field names included, so every wire type encodes as it
+//! serializes to JSON and decodes with the same defaults and aliases; they
+//! are smaller and faster to parse, notably in WASM. The backend's REST
+//! endpoints serve them to clients sending `Accept: application/cbor` or
+//! `application/msgpack`.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A binary encoding of the wire types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryFormat {
+    Cbor,
+    MessagePack,
+}
+
+impl BinaryFormat {
+    pub const ALL: [BinaryFormat; 2] = [BinaryFormat::Cbor, BinaryFormat::MessagePack];
+
+    /// The format's media type
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            BinaryFormat::Cbor => "application/cbor",
+            BinaryFormat::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// The format of a media type (e.g., a `Content-Type` header), ignoring
+    /// parameters and case; `application/x-msgpack` and
+    /// `application/vnd.msgpack` are MessagePack too
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type.split(';').next()?.trim().to_ascii_lowercase();
+        match media_type.as_str() {
+            "application/cbor" => Some(BinaryFormat::Cbor),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(BinaryFormat::MessagePack)
+            }
+            _ => None,
+        }
+    }
+
+    /// Encode a value
+    pub fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<Vec<u8>, BinaryError> {
+        match self {
+            BinaryFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| BinaryError::new(*self, e))?;
+                Ok(bytes)
+            }
+            // Structs as maps, as JSON has them, rather than positional arrays
+            BinaryFormat::MessagePack => {
+                rmp_serde::to_vec_named(value).map_err(|e| BinaryError::new(*self, e))
+            }
+        }
+    }
+
+    /// Decode a value
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, BinaryError> {
+        match self {
+            BinaryFormat::Cbor => {
+                ciborium::from_reader(bytes).map_err(|e| BinaryError::new(*self, e))
+            }
+            BinaryFormat::MessagePack => {
+                rmp_serde::from_slice(bytes).map_err(|e| BinaryError::new(*self, e))
+            }
+        }
+    }
+}
+
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryFormat::Cbor => write!(f, "CBOR"),
+            BinaryFormat::MessagePack => write!(f, "MessagePack"),
+        }
+    }
+}
+
+/// Error raised when a value cannot be encoded or decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryError {
+    pub format: BinaryFormat,
+    pub message: String,
+}
+
+impl BinaryError {
+    fn new(format: BinaryFormat, error: impl fmt::Display) -> Self {
+        Self {
+            format,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.format, self.message)
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sample_triad;
+    use crate::SystemView;
+
+    #[test]
+    fn test_round_trip() {
+        let triad = sample_triad();
+        let json = serde_json::to_vec(&triad).unwrap();
+        for format in BinaryFormat::ALL {
+            let bytes = format.encode(&triad).unwrap();
+            assert!(bytes.len() < json.len(), "{}", format);
+            assert_eq!(format.decode::<SystemView>(&bytes).unwrap(), triad);
+        }
+        assert!(BinaryFormat::Cbor.decode::<SystemView>(b"\xff").is_err());
+    }
+
+    #[test]
+    fn test_media_types() {
+        for format in BinaryFormat::ALL {
+            assert_eq!(
+                BinaryFormat::from_media_type(format.media_type()),
+                Some(format)
+            );
+        }
+        assert_eq!(
+            BinaryFormat::from_media_type("Application/X-MsgPack; charset=binary"),
+            Some(BinaryFormat::MessagePack)
+        );
+        assert_eq!(BinaryFormat::from_media_type("application/json"), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Labels for systems, positions and connectives.
+//!
+//! Term and connective designations are stored in the plural ("Impulses",
+//! "Acts"), or as a collective for the Monad and Dyad ("Totality", "Force");
+//! unresearched orders have the placeholder `NEEDS_RESEARCH`, shown as no
+//! designation. The frontend and exporters label with these functions so
+//! their labels read the same.
+
+/// Designation of orders not yet researched
+pub const NEEDS_RESEARCH: &str = "Needs Research";
+
+/// Greek numeral glyphs for units, tens and hundreds
+const GREEK_UNITS: [char; 9] = ['α', 'β', 'γ', 'δ', 'ε', 'ϛ', 'ζ', 'η', 'θ'];
+const GREEK_TENS: [char; 9] = ['ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ϟ'];
+const GREEK_HUNDREDS: [char; 9] = ['ρ', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', 'ϡ'];
+
+/// Greek (alphabetic) numeral, marked with a keraia (e.g., "γʹ" for 3, "ιβʹ"
+/// for 12); `None` outside 1..=999
+pub fn greek_numeral(n: i32) -> Option<String> {
+    if !(1..=999).contains(&n) {
+        return None;
+    }
+    let digit = |glyphs: &[char; 9], d: i32| (d > 0).then(|| glyphs[d as usize - 1]);
+    let numeral: String = [
+        digit(&GREEK_HUNDREDS, n / 100),
+        digit(&GREEK_TENS, n / 10 % 10),
+        digit(&GREEK_UNITS, n % 10),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    Some(numeral + "ʹ")
+}
+
+/// Circled number glyph (e.g., '③' for 3); `None` outside 1..=20
+pub fn circled_numeral(n: i32) -> Option<char> {
+    (1..=20)
+        .contains(&n)
+        .then(|| char::from_u32('①' as u32 + n as u32 - 1))
+        .flatten()
+}
+
+/// English ordinal (e.g., "1st", "12th", "22nd")
+pub fn ordinal(n: i32) -> String {
+    let suffix = match (n.abs() % 10, n.abs() % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// A designation, unless missing or `NEEDS_RESEARCH`
+pub fn researched(designation: Option<&str>) -> Option<&str> {
+    designation.filter(|d| !d.is_empty() && *d != NEEDS_RESEARCH)
+}
+
+/// Singular of a designation ("Impulses" → "Impulse", "Mutualities" →
+/// "Mutuality"); collectives ("Totality", "Force") are left as they are
+pub fn singular(designation: &str) -> String {
+    if let Some(stem) = designation.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if designation.ends_with("ss") {
+        designation.to_string()
+    } else {
+        designation
+            .strip_suffix('s')
+            .unwrap_or(designation)
+            .to_string()
+    }
+}
+
+/// Plural of a designation ("Totality" → "Totalities"); plurals are left as
+/// they are
+pub fn plural(designation: &str) -> String {
+    if designation.ends_with('s') {
+        return designation.to_string();
+    }
+    match designation.strip_suffix('y') {
+        Some(stem) if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) => format!("{}ies", stem),
+        _ => format!("{}s", designation),
+    }
+}
+
+/// Number of things of a designation ("1 Impulse", "3 Impulses"), or of
+/// `fallback` ("9 Terms") without one
+pub fn count_label(count: usize, designation: Option<&str>, fallback: &str) -> String {
+    let designation = researched(designation).unwrap_or(fallback);
+    if count == 1 {
+        format!("{} {}", count, singular(designation))
+    } else {
+        format!("{} {}", count, plural(designation))
+    }
+}
+
+/// Label of a position by its term designation ("2nd Impulse"), or as a
+/// "Term" without one
+pub fn position_label(position: i32, term_designation: Option<&str>) -> String {
+    let designation = researched(term_designation).map_or("Term".to_string(), singular);
+    format!("{} {}", ordinal(position), designation)
+}
+
+/// Label of the connective between two positions by its connective
+/// designation ("Act 1–2"), or as a "Connective" without one
+pub fn connective_label(base: i32, target: i32, connective_designation: Option<&str>) -> String {
+    let designation = researched(connective_designation).map_or("Connective".to_string(), singular);
+    format!("{} {}–{}", designation, base, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numerals() {
+        assert_eq!(greek_numeral(3).as_deref(), Some("γʹ"));
+        assert_eq!(greek_numeral(6).as_deref(), Some("ϛʹ"));
+        assert_eq!(greek_numeral(12).as_deref(), Some("ιβʹ"));
+        assert_eq!(greek_numeral(20).as_deref(), Some("κʹ"));
+        assert_eq!(greek_numeral(241).as_deref(), Some("σμαʹ"));
+        assert_eq!(greek_numeral(0), None);
+
+        assert_eq!(circled_numeral(1), Some('①'));
+        assert_eq!(circled_numeral(12), Some('⑫'));
+        assert_eq!(circled_numeral(21), None);
+
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 111].map(ordinal).into();
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "111th"]
+        );
+    }
+
+    #[test]
+    fn test_designations() {
+        assert_eq!(singular("Impulses"), "Impulse");
+        assert_eq!(singular("Mutualities"), "Mutuality");
+        assert_eq!(singular("Interplays"), "Interplay");
+        assert_eq!(singular("Totality"), "Totality");
+        assert_eq!(plural("Totality"), "Totalities");
+        assert_eq!(plural("Force"), "Forces");
+        assert_eq!(plural("Interplay"), "Interplays");
+        assert_eq!(plural("Acts"), "Acts");
+
+        assert_eq!(count_label(3, Some("Impulses"), "Term"), "3 Impulses");
+        assert_eq!(count_label(1, Some("Totality"), "Term"), "1 Totality");
+        assert_eq!(count_label(9, Some(NEEDS_RESEARCH), "Term"), "9 Terms");
+        assert_eq!(position_label(2, Some("Impulses")), "2nd Impulse");
+        assert_eq!(position_label(10, None), "10th Term");
+        assert_eq!(connective_label(1, 2, Some("Acts")), "Act 1–2");
+        assert_eq!(
+            connective_label(3, 9, Some(NEEDS_RESEARCH)),
+            "Connective 3–9"
+        );
+    }
+}
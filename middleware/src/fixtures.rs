@@ -0,0 +1,96 @@
+//! Sample systems for tests (`fixtures` feature).
+//!
+//! Built with `SystemView::builder`, they match what the backend serves from
+//! the canonical data, as far as they go: characters have no sources or
+//! translations, and there are no groupings or instances.
+
+use crate::types::*;
+
+/// The Triad: Will, Function and Being, joined by three Acts and three sides
+pub fn sample_triad() -> SystemView {
+    let act = |n: i32| {
+        Character::with_auto_id(Language::Canonical, format!("Act{}", n))
+            .with_status(ResearchStatus::Provisional)
+    };
+    SystemView::builder(3)
+        .name("Triad")
+        .coherence("Dynamism")
+        .term_designation("Impulses")
+        .connective_designation("Acts")
+        .terms(["Will", "Function", "Being"])
+        .coordinates([(0.0, 1.0, 0.0), (0.0, -1.0, 0.0), (1.0, 0.0, 0.0)])
+        .colours(["#FF0000", "#0000FF", "#FFFF00"])
+        .connective(Link::connective(3, 1, 2).with_character(act(1)))
+        .connective(Link::connective(3, 2, 3).with_character(act(2)))
+        .connective(Link::connective(3, 3, 1).with_character(act(3)))
+        .line(Link::line(3, 1, 2).with_role(LineRole::Side))
+        .line(Link::line(3, 1, 3).with_role(LineRole::Side))
+        .line(Link::line(3, 2, 3).with_role(LineRole::Side))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_triad() {
+        let triad = sample_triad();
+        #[cfg(feature = "client")]
+        assert_eq!(triad.validate(), Ok(()));
+        assert_eq!(triad.term_at(2), Some("Function"));
+        assert_eq!(triad.terms[0].character_id, "char_canonical_will");
+        assert_eq!(triad.colours[0].id, "colour_3_1_hex");
+        assert_eq!(triad.lines[0].id, "line_coord_3_1_coord_3_2");
+        assert_eq!(
+            triad.lines[0].target_coordinate,
+            triad.coordinate_at(2).cloned()
+        );
+        assert_eq!(
+            triad.connectives[2].character_id.as_deref(),
+            Some("char_canonical_act3")
+        );
+        assert_eq!(triad.links.len(), 6);
+
+        // Edges are labelled from the resolved connectives, in either direction
+        let act3 = triad.connective_view_between(1, 3).unwrap();
+        assert_eq!(act3.label(), Some("Act3"));
+        assert!(!act3.needs_research());
+        assert_eq!(act3.title(), "Act 3–1: Being → Will");
+
+        // Descriptions for screen readers
+        assert_eq!(
+            triad.position_description(3),
+            "Position 3: Being, connected to Will by Act3 and to Function by Act2"
+        );
+        assert_eq!(
+            triad.line_description(1, 3),
+            "Act3 (Act 3–1), from Being to Will"
+        );
+
+        let json = serde_json::to_string(&triad).unwrap();
+        assert_eq!(serde_json::from_str::<SystemView>(&json).unwrap(), triad);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_optimistic_edits() {
+        let mut triad = sample_triad();
+        assert!(triad.rename_term(1, Language::Canonical, " Intent "));
+        assert_eq!(triad.term_at(1), Some("Intent"));
+        assert_eq!(triad.terms[0].character_id, "char_canonical_intent");
+        assert_eq!(triad.terms[0].status, ResearchStatus::Provisional);
+        let act3 = triad.connective_view_between(1, 3).unwrap();
+        assert_eq!(act3.title(), "Act 3–1: Being → Intent");
+
+        assert!(triad.label_connective(3, 1, Language::Values, "Trust"));
+        assert_eq!(
+            triad.connective_view_between(1, 3).and_then(|c| c.label()),
+            Some("Trust")
+        );
+        assert_eq!(triad.validate(), Ok(()));
+
+        assert!(!triad.rename_term(4, Language::Canonical, "Extra"));
+        assert!(!triad.label_connective(1, 1, Language::Values, "Self"));
+    }
+}
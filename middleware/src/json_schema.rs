@@ -0,0 +1,87 @@
+//! JSON Schema of the wire format (`json-schema` feature).
+//!
+//! Every wire type derives `schemars::JsonSchema`, following its serde
+//! attributes, and `json_schema()` gathers them into one draft 2020-12
+//! document, each type under `$defs` (e.g., `#/$defs/SystemView`). The schema
+//! describes what clients of this crate accept: fields they default may be
+//! left out, so it suits validating third-party producers and contract tests.
+
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, Schema};
+
+use crate::types::*;
+
+/// JSON Schema of every wire type, under `$defs`
+pub fn json_schema() -> Schema {
+    let mut generator = SchemaSettings::draft2020_12()
+        .for_deserialize()
+        .into_generator();
+    // The views clients decode; the types they use are defined along with them
+    generator.subschema_for::<SystemView>();
+    generator.subschema_for::<Slice>();
+    generator.subschema_for::<GraphView>();
+    generator.subschema_for::<OrderView>();
+    generator.subschema_for::<PositionView>();
+    generator.subschema_for::<LocationView>();
+    generator.subschema_for::<SearchHit>();
+    generator.subschema_for::<Page<SearchHit>>();
+    generator.subschema_for::<GraphQLError>();
+    // and the inputs they send
+    generator.subschema_for::<CustomSystemInput>();
+    let definitions = generator.take_definitions(true);
+    json_schema!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Systematics wire format",
+        "description": format!("Wire format version {}", API_VERSION),
+        "$defs": definitions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// Check that `value` has the properties the definition of `name`
+    /// requires, and no others than it declares
+    fn assert_conforms(schema: &Schema, name: &str, value: &Value) {
+        let definition = &schema.as_value()["$defs"][name];
+        let properties = definition["properties"].as_object().unwrap();
+        let value = value.as_object().unwrap();
+        for key in value.keys() {
+            assert!(properties.contains_key(key), "{name} has no property {key}");
+        }
+        for required in definition["required"].as_array().unwrap() {
+            let required = required.as_str().unwrap();
+            assert!(value.contains_key(required), "{name} requires {required}");
+        }
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema();
+        assert_eq!(
+            schema.as_value()["$defs"]["SystemView"]["required"],
+            json!(["order"])
+        );
+        assert_eq!(
+            schema.as_value()["$defs"]["Link"]["properties"]["linkType"]["$ref"],
+            "#/$defs/LinkType"
+        );
+
+        let system: SystemView = serde_json::from_value(json!({
+            "order": 3,
+            "lines": [{
+                "id": "line_3_1_2",
+                "baseId": "coord_3_1",
+                "targetId": "coord_3_2",
+                "linkType": "LINE",
+                "role": "SIDE"
+            }]
+        }))
+        .unwrap();
+        let value = serde_json::to_value(&system).unwrap();
+        assert_conforms(&schema, "SystemView", &value);
+        assert_conforms(&schema, "Link", &value["lines"][0]);
+    }
+}
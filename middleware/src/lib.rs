@@ -3,7 +3,34 @@
 //! Shared types and GraphQL schema for the Systematics project.
 //! This crate defines the wire format types used for communication
 //! between backend and frontend.
+//!
+//! The `server` feature derives the GraphQL types the backend's schema is
+//! built from, and the default `client` feature adds the helpers clients use
+//! on what they receive (`ApiError`, `SystemView::validate`,
+//! `GraphDelta::apply_to`, `is_supported_version`). The frontend builds with
+//! `wasm`, which never pulls in async-graphql. With `binary`, wire types also
+//! encode to CBOR and MessagePack.
+
+#[cfg(all(feature = "wasm", feature = "server", target_arch = "wasm32"))]
+compile_error!("the `server` feature pulls async-graphql into WASM builds; build the frontend with `wasm` only");
 
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod display;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+#[cfg(feature = "json-schema")]
+mod json_schema;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod operations;
 pub mod types;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+pub mod viewport;
 
 pub use types::*;
+pub use viewport::{transform_coordinates_to_viewport, Viewport};
+
+#[cfg(feature = "json-schema")]
+pub use json_schema::json_schema;
@@ -0,0 +1,144 @@
+//! Mock systems for offline and demo use (`mock` feature).
+//!
+//! `system_view(order)` generates a system of any order the way the backend
+//! generates custom systems: positions on a regular polygon, the twelve
+//! palette colours in turn, and a line and a connective for every pair. Labels
+//! are synthetic ("Term 1", "Connective 1–2"), and the same order always gives
+//! the same system, so the frontend can run without a backend and components
+//! can be tested against it.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use crate::types::*;
+
+/// Hex colours by position, cycled for orders above twelve
+pub const PALETTE: [&str; 12] = [
+    "#FF0000", "#0000FF", "#FFFF00", "#099902", "#9900FF", "#FFA500", "#00FFFF", "#8B4513",
+    "#FF00FF", "#FFFFFF", "#C0C0C0", "#FFD700",
+];
+
+/// A structurally valid system of an order of 1 and above
+pub fn system_view(order: i32) -> SystemView {
+    let mut builder = SystemView::builder(order)
+        .name(format!("System {}", order))
+        .custom()
+        .coherence("Mock")
+        .term_designation("Terms")
+        .connective_designation("Connectives")
+        .terms((1..=order).map(|position| format!("Term {}", position)))
+        .coordinates(polygon(order))
+        .colours((0..order).map(|k| PALETTE[k as usize % PALETTE.len()]));
+    for base in 1..=order {
+        for target in base + 1..=order {
+            let character = Character::with_auto_id(
+                Language::Canonical,
+                format!("Connective {}–{}", base, target),
+            )
+            .with_status(ResearchStatus::Provisional);
+            builder = builder
+                .connective(Link::connective(order, base, target).with_character(character))
+                .line(Link::line(order, base, target).with_role(role(order, base, target)));
+        }
+    }
+    builder.build()
+}
+
+/// Systems of several orders (e.g., `system_views(1..=12)`)
+pub fn system_views(orders: impl IntoIterator<Item = i32>) -> Vec<SystemView> {
+    orders.into_iter().map(system_view).collect()
+}
+
+/// The slice at one position of a system, as the `slice` query returns it
+pub fn slice(system: &SystemView, position: i32) -> Slice {
+    let meets = |link: &&Link| {
+        link.base_position == Some(position) || link.target_position == Some(position)
+    };
+    Slice {
+        order: system.order,
+        position,
+        system_name: system.name.clone(),
+        coherence: system.coherence.clone(),
+        term: system
+            .terms
+            .iter()
+            .find(|t| t.position == position)
+            .cloned(),
+        coordinate: system.coordinate_at(position).cloned(),
+        colour: system
+            .colours
+            .iter()
+            .find(|c| c.position == position)
+            .cloned(),
+        connectives: system.connectives.iter().filter(meets).cloned().collect(),
+        lines: system.lines.iter().filter(meets).cloned().collect(),
+    }
+}
+
+/// Points on the unit circle, position 1 at the top and the rest clockwise
+fn polygon(order: i32) -> Vec<(f64, f64, f64)> {
+    if order == 1 {
+        return vec![(0.0, 0.0, 0.0)];
+    }
+    (0..order)
+        .map(|k| {
+            let angle = FRAC_PI_2 - TAU * k as f64 / order as f64;
+            (angle.cos(), angle.sin(), 0.0)
+        })
+        .collect()
+}
+
+/// Role of the line between two positions of a regular polygon
+fn role(order: i32, base: i32, target: i32) -> LineRole {
+    let neighbours = target - base == 1 || (base == 1 && target == order);
+    match (order, neighbours) {
+        (..=4, true) => LineRole::Side,
+        (..=4, false) => LineRole::Diagonal,
+        (_, true) => LineRole::Circumference,
+        (_, false) => LineRole::Chord,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_view() {
+        for order in 1..=20 {
+            let system = system_view(order);
+            assert_eq!(system, system_view(order));
+            #[cfg(feature = "client")]
+            assert_eq!(system.validate(), Ok(()));
+            let pairs = (order * (order - 1) / 2) as usize;
+            assert_eq!(system.connective_views.len(), pairs);
+            assert_eq!(system.lines.len(), pairs);
+        }
+
+        let tetrad = system_view(4);
+        assert_eq!(tetrad.term_at(2), Some("Term 2"));
+        assert_eq!(tetrad.colour_at(4), Some("#099902"));
+        let roles: Vec<_> = tetrad.lines.iter().filter_map(|l| l.role).collect();
+        assert_eq!(
+            roles.iter().filter(|r| **r == LineRole::Diagonal).count(),
+            2
+        );
+        assert_eq!(
+            tetrad.connective_view_between(1, 3).unwrap().title(),
+            "Connective 1–3: Term 1 → Term 3"
+        );
+        assert_eq!(system_view(13).colour_at(13), Some("#FF0000"));
+    }
+
+    #[test]
+    fn test_slice() {
+        let pentad = system_view(5);
+        let slice = slice(&pentad, 2);
+        assert_eq!(slice.term.unwrap().character.unwrap().value, "Term 2");
+        assert_eq!(slice.connectives.len(), 4);
+        assert_eq!(slice.lines.len(), 4);
+        assert!(slice
+            .lines
+            .iter()
+            .any(|l| l.role == Some(LineRole::Circumference)));
+    }
+}
@@ -0,0 +1,513 @@
+//! GraphQL operations sent by the frontend.
+//!
+//! The documents are defined here rather than in the frontend client so the
+//! backend can recognise them: in allow-list mode the server executes these
+//! operations only, matched by the SHA-256 hash of their exact text.
+//!
+//! They are built from shared selections (`term()`, `system_link()`,
+//! `system()`, ...), which other clients and tests can extend into documents
+//! of their own with `Selection` and `query`. The backend's
+//! `tests/operations.rs` validates every document here against its schema.
+
+use std::fmt;
+
+/// Indentation of each level of a rendered document
+const INDENT: &str = "  ";
+
+/// Selection set of a GraphQL document: fields in order, objects with their
+/// own selection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    fields: Vec<(String, Option<Selection>)>,
+}
+
+impl Selection {
+    /// A selection of scalar fields
+    pub fn of(fields: &[&str]) -> Self {
+        Self::default().fields(fields)
+    }
+
+    /// Add scalar fields
+    pub fn fields(mut self, fields: &[&str]) -> Self {
+        self.fields
+            .extend(fields.iter().map(|field| (field.to_string(), None)));
+        self
+    }
+
+    /// Add a field selecting from an object (or a list of objects)
+    pub fn object(mut self, field: &str, selection: Selection) -> Self {
+        self.fields.push((field.to_string(), Some(selection)));
+        self
+    }
+
+    fn render(&self, depth: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = INDENT.repeat(depth);
+        for (field, selection) in &self.fields {
+            match selection {
+                None => writeln!(f, "{}{}", indent, field)?,
+                Some(selection) => {
+                    writeln!(f, "{}{} {{", indent, field)?;
+                    selection.render(depth + 1, f)?;
+                    writeln!(f, "{}}}", indent)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One field per line, objects indented
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(0, f)
+    }
+}
+
+/// The root of an operation, rendered one level in
+struct Root<'a>(&'a str, &'a Selection);
+
+impl fmt::Display for Root<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{} {{", INDENT, self.0)?;
+        self.1.render(2, f)?;
+        writeln!(f, "{}}}", INDENT)
+    }
+}
+
+/// A query selecting from one root field, e.g.
+/// `query("GetSystem", "$order: Int!", "system(order: $order)", &system())`.
+/// `variables` is empty for a query without any.
+pub fn query(name: &str, variables: &str, field: &str, selection: &Selection) -> String {
+    operation("query", name, variables, field, selection)
+}
+
+/// A subscription to one root field (see `query`)
+pub fn subscription(name: &str, variables: &str, field: &str, selection: &Selection) -> String {
+    operation("subscription", name, variables, field, selection)
+}
+
+/// A mutation of one root field (see `query`)
+pub fn mutation(name: &str, variables: &str, field: &str, selection: &Selection) -> String {
+    operation("mutation", name, variables, field, selection)
+}
+
+fn operation(
+    kind: &str,
+    name: &str,
+    variables: &str,
+    field: &str,
+    selection: &Selection,
+) -> String {
+    let variables = if variables.is_empty() {
+        String::new()
+    } else {
+        format!("({})", variables)
+    };
+    format!(
+        "{} {}{} {{\n{}}}\n",
+        kind,
+        name,
+        variables,
+        Root(field, selection)
+    )
+}
+
+// ============================================================================
+// Selections
+// ============================================================================
+
+pub fn character() -> Selection {
+    Selection::of(&["id", "language", "value", "valueI18n", "status"])
+}
+
+/// A term with its character
+pub fn term() -> Selection {
+    Selection::of(&[
+        "id",
+        "order",
+        "position",
+        "characterId",
+        "valueI18n",
+        "status",
+    ])
+    .object("character", character())
+}
+
+pub fn coordinate() -> Selection {
+    Selection::of(&["id", "order", "position", "x", "y", "z"])
+}
+
+pub fn colour() -> Selection {
+    Selection::of(&["id", "order", "position", "language", "value"])
+}
+
+/// The fields every link has, as the `graph` query lists them
+pub fn link() -> Selection {
+    Selection::of(&[
+        "id",
+        "baseId",
+        "targetId",
+        "linkType",
+        "characterId",
+        "tag",
+        "role",
+        "order",
+        "basePosition",
+        "targetPosition",
+    ])
+}
+
+/// A system's line or connective, with its character and end coordinates
+pub fn system_link() -> Selection {
+    link()
+        .object("character", character())
+        .object("baseCoordinate", coordinate())
+        .object("targetCoordinate", coordinate())
+}
+
+/// A connective resolved for labelling (`ConnectiveView`)
+pub fn connective_view() -> Selection {
+    Selection::of(&["id", "order", "basePosition", "targetPosition"])
+        .object("character", character())
+        .fields(&["designation", "baseTerm", "targetTerm"])
+}
+
+/// A grouping with its character and member positions
+pub fn grouping() -> Selection {
+    Selection::of(&["id", "order", "characterId"])
+        .object("character", Selection::of(&["id", "language", "value"]))
+        .fields(&["memberPositions", "parentId"])
+}
+
+/// Every field `SystemView` decodes
+pub fn system() -> Selection {
+    Selection::of(&[
+        "apiVersion",
+        "order",
+        "name",
+        "isCanonical",
+        "coherence",
+        "coherences",
+        "termDesignation",
+        "connectiveDesignation",
+    ])
+    .object("terms", term())
+    .object("coordinates", coordinate())
+    .object("colours", colour())
+    .object("lines", system_link())
+    .object("groupings", grouping())
+    .object("connectives", system_link())
+    .object("connectiveViews", connective_view())
+}
+
+/// Everything at one position (`Slice`)
+pub fn slice() -> Selection {
+    let link = link().object("character", character());
+    Selection::of(&["order", "position", "systemName", "coherence"])
+        .object("term", term())
+        .object("coordinate", coordinate())
+        .object("colour", colour())
+        .object("connectives", link.clone())
+        .object("lines", link)
+}
+
+/// The entries and links added or updated by a graph delta (`DeltaEntries`)
+pub fn delta_entries() -> Selection {
+    Selection::default()
+        .object("terms", term())
+        .object("coordinates", coordinate())
+        .object("colours", colour())
+        .object("characters", character())
+        .object("links", system_link())
+}
+
+/// A Location anchor with what is placed at it
+pub fn location() -> Selection {
+    Selection::of(&["id", "orderId", "positionId", "orderValue", "positionValue"])
+        .object("terms", term())
+        .object("coordinate", coordinate())
+        .object("colours", colour())
+        .object(
+            "instanceLabels",
+            Selection::of(&["id", "position", "value"]),
+        )
+}
+
+/// An instance with its labels (`InstanceView`)
+pub fn instance() -> Selection {
+    Selection::of(&["id", "name", "order"])
+        .object("labels", Selection::of(&["id", "position", "value"]))
+}
+
+/// A user note (`AnnotationView`)
+pub fn annotation() -> Selection {
+    Selection::of(&["id", "entryId", "text", "author", "authorId"])
+}
+
+/// A search match (`SearchHit`)
+pub fn search_hit() -> Selection {
+    Selection::of(&["kind", "id", "value", "order", "position"])
+}
+
+/// A page of a list (`Page<T>`), its items selected with `items`
+pub fn page(items: Selection) -> Selection {
+    Selection::default()
+        .object("items", items)
+        .fields(&["total", "hasNext", "cursor"])
+}
+
+/// An order attribute (system name, coherence or designation)
+pub fn order_attribute() -> Selection {
+    Selection::of(&["id", "value"])
+}
+
+// ============================================================================
+// Operations
+// ============================================================================
+
+/// `GetSystem($order: Int!, $language: GqlLanguage)`: one system by order,
+/// its terms in a vocabulary (canonical when `language` is omitted)
+pub fn get_system() -> String {
+    query(
+        "GetSystem",
+        "$order: Int!, $language: GqlLanguage",
+        "system(order: $order, language: $language)",
+        &system(),
+    )
+}
+
+/// `GetSystems($orders: [Int!]!)`: the systems for some orders
+pub fn get_systems() -> String {
+    query(
+        "GetSystems",
+        "$orders: [Int!]!",
+        "systems(orders: $orders)",
+        &system(),
+    )
+}
+
+/// `GetSystemByName($name: String!)`: one system by name
+pub fn get_system_by_name() -> String {
+    query(
+        "GetSystemByName",
+        "$name: String!",
+        "systemByName(name: $name)",
+        &system(),
+    )
+}
+
+/// `GetAllSystems`: every system
+pub fn get_all_systems() -> String {
+    query("GetAllSystems", "", "allSystems", &system())
+}
+
+/// `GetSlice($order: Int!, $position: Int!)`: everything at one position of a
+/// system
+pub fn get_slice() -> String {
+    query(
+        "GetSlice",
+        "$order: Int!, $position: Int!",
+        "slice(order: $order, position: $position)",
+        &slice(),
+    )
+}
+
+/// `GraphDelta`: subscription to the changes of each revision of the graph
+pub fn graph_delta() -> String {
+    let delta = Selection::of(&["apiVersion", "fromRevision", "toRevision"])
+        .object("added", delta_entries())
+        .object("updated", delta_entries())
+        .fields(&["removed", "other"]);
+    subscription("GraphDelta", "", "graphDelta", &delta)
+}
+
+/// `GetGraph`: the whole graph, with entry IDs by type and every link. Not a
+/// frontend operation, so servers in allow-list mode do not run it.
+pub fn get_graph() -> String {
+    let entries_by_type =
+        Selection::of(&["entryType", "count"]).object("entries", Selection::of(&["id"]));
+    let graph = Selection::of(&["apiVersion", "entryCount", "linkCount"])
+        .object("entriesByType", entries_by_type)
+        .object("links", link());
+    query("GetGraph", "", "graph", &graph)
+}
+
+/// `GetOrder($value: Int!)`: an Order anchor with its attributes and locations
+pub fn get_order() -> String {
+    let source = Selection::of(&["author", "book", "page"]);
+    let order = Selection::of(&["id", "value", "standardName", "isCanonical", "createdBy"])
+        .object("systemName", order_attribute())
+        .object("coherence", order_attribute().object("source", source))
+        .object("coherences", order_attribute())
+        .object("termDesignation", order_attribute())
+        .object("connectiveDesignation", order_attribute())
+        .object("locations", location());
+    query("GetOrder", "$value: Int!", "order(value: $value)", &order)
+}
+
+/// `GetPosition($value: Int!)`: a Position anchor with its locations across
+/// orders
+pub fn get_position() -> String {
+    let position = Selection::of(&["id", "value"]).object("locations", location());
+    query(
+        "GetPosition",
+        "$value: Int!",
+        "position(value: $value)",
+        &position,
+    )
+}
+
+/// `GetLocation($order: Int!, $position: Int!)`: one Location anchor
+pub fn get_location() -> String {
+    query(
+        "GetLocation",
+        "$order: Int!, $position: Int!",
+        "location(order: $order, position: $position)",
+        &location(),
+    )
+}
+
+/// `GetInstances($order: Int)`: the instances of one order, or of all
+pub fn get_instances() -> String {
+    query(
+        "GetInstances",
+        "$order: Int",
+        "instances(order: $order)",
+        &instance(),
+    )
+}
+
+/// `GetAnnotations($entryId: String!)`: the notes attached to an entry or
+/// link, in the order they were added
+pub fn get_annotations() -> String {
+    query(
+        "GetAnnotations",
+        "$entryId: String!",
+        "annotations(entryId: $entryId)",
+        &annotation(),
+    )
+}
+
+/// `AnnotateEntry($entryId: String!, $text: String!)`: attach a note to an
+/// entry or link, attributed to the signed-in user, returning it as
+/// `AnnotationView`
+pub fn annotate_entry() -> String {
+    mutation(
+        "AnnotateEntry",
+        "$entryId: String!, $text: String!",
+        "annotateEntry(entryId: $entryId, text: $text)",
+        &annotation(),
+    )
+}
+
+/// `SearchPage($text: String!, $first: Int, $after: String)`: a page of search
+/// hits; pass each page's cursor as `after` for the next (see `collect_pages`)
+pub fn search_page() -> String {
+    let search = Selection::default().object(
+        "search(text: $text, first: $first, after: $after)",
+        page(search_hit()),
+    );
+    query(
+        "SearchPage",
+        "$text: String!, $first: Int, $after: String",
+        "v2",
+        &search,
+    )
+}
+
+/// `CreateCustomSystem($input: CustomSystemInput!)`: create a custom system,
+/// returning it as `SystemView`
+pub fn create_custom_system() -> String {
+    mutation(
+        "CreateCustomSystem",
+        "$input: CustomSystemInput!",
+        "createCustomSystem(input: $input)",
+        &system(),
+    )
+}
+
+/// `RenameTerm($order: Int!, $position: Int!, $term: String!, $language: GqlLanguage)`:
+/// rename the term at a position in a vocabulary (canonical by default),
+/// returning the system as `SystemView` in that vocabulary; signed-in users only
+pub fn rename_term() -> String {
+    mutation(
+        "RenameTerm",
+        "$order: Int!, $position: Int!, $term: String!, $language: GqlLanguage",
+        "renameTerm(order: $order, position: $position, value: $term, language: $language)",
+        &system(),
+    )
+}
+
+/// `LabelConnective($order: Int!, $base: Int!, $target: Int!, $label: String!, $language: GqlLanguage!)`:
+/// name the connective between two positions in a non-canonical vocabulary,
+/// returning the system as `SystemView` in that vocabulary; signed-in users only
+pub fn label_connective() -> String {
+    mutation(
+        "LabelConnective",
+        "$order: Int!, $base: Int!, $target: Int!, $label: String!, $language: GqlLanguage!",
+        "labelConnective(order: $order, base: $base, target: $target, value: $label, language: $language)",
+        &system(),
+    )
+}
+
+/// Every frontend operation, as (operation name, document)
+pub fn frontend_operations() -> Vec<(&'static str, String)> {
+    vec![
+        ("GetSystem", get_system()),
+        ("GetSystems", get_systems()),
+        ("GetSystemByName", get_system_by_name()),
+        ("GetAllSystems", get_all_systems()),
+        ("GetSlice", get_slice()),
+        ("SearchPage", search_page()),
+        ("GraphDelta", graph_delta()),
+        ("RenameTerm", rename_term()),
+        ("LabelConnective", label_connective()),
+        ("CreateCustomSystem", create_custom_system()),
+        ("GetAnnotations", get_annotations()),
+        ("AnnotateEntry", annotate_entry()),
+    ]
+}
+
+/// Every operation defined here: the frontend's, then the anchor, graph and
+/// instance queries
+pub fn all_operations() -> Vec<(&'static str, String)> {
+    let mut operations = frontend_operations();
+    operations.extend([
+        ("GetGraph", get_graph()),
+        ("GetOrder", get_order()),
+        ("GetPosition", get_position()),
+        ("GetLocation", get_location()),
+        ("GetInstances", get_instances()),
+    ]);
+    operations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let selection = Selection::of(&["id"]).object("character", Selection::of(&["value"]));
+        assert_eq!(selection.to_string(), "id\ncharacter {\n  value\n}\n");
+        assert_eq!(
+            query("GetTerm", "$id: String!", "term(id: $id)", &selection),
+            "query GetTerm($id: String!) {\n  term(id: $id) {\n    id\n    character {\n      value\n    }\n  }\n}\n"
+        );
+        assert!(get_all_systems().starts_with("query GetAllSystems {\n  allSystems {\n"));
+    }
+
+    #[test]
+    fn test_operation_names() {
+        for (name, document) in all_operations() {
+            let kind = match name {
+                "GraphDelta" => "subscription",
+                "CreateCustomSystem" | "RenameTerm" | "LabelConnective" | "AnnotateEntry" => {
+                    "mutation"
+                }
+                _ => "query",
+            };
+            assert!(document.starts_with(&format!("{} {}", kind, name)));
+        }
+    }
+}
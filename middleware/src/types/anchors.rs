@@ -0,0 +1,150 @@
+//! Anchor types for Systematics wire format: orders, positions and the
+//! locations pairing them
+
+use super::{Citation, Colour, Coordinate, InstanceLabel, Language, Term};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use async_graphql::SimpleObject;
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+/// OrderAttribute - a value attached to an order: its system name, a
+/// coherence attribute, or a term or connective designation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct OrderAttribute {
+    pub id: String,
+    pub value: String,
+    /// Where this value was published
+    #[serde(default)]
+    pub source: Option<Citation>,
+}
+
+/// OrderView - an Order anchor with its attributes, locations, terms and
+/// coordinates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct OrderView {
+    pub id: String,
+    pub value: i32,
+    /// Standard name for the order (e.g., "Triad" for order 3)
+    #[serde(rename = "standardName", alias = "standard_name", default)]
+    pub standard_name: Option<String>,
+    /// False for user-defined custom systems
+    #[serde(
+        rename = "isCanonical",
+        alias = "is_canonical",
+        default = "default_is_canonical"
+    )]
+    pub is_canonical: bool,
+    /// User ID of the creator of a custom system
+    #[serde(rename = "createdBy", alias = "created_by", default)]
+    pub created_by: Option<String>,
+    #[serde(rename = "systemName", alias = "system_name", default)]
+    pub system_name: Option<OrderAttribute>,
+    /// Primary coherence attribute
+    #[serde(default)]
+    pub coherence: Option<OrderAttribute>,
+    /// All coherence attributes, primary first
+    #[serde(default)]
+    pub coherences: Vec<OrderAttribute>,
+    #[serde(rename = "termDesignation", alias = "term_designation", default)]
+    pub term_designation: Option<OrderAttribute>,
+    #[serde(
+        rename = "connectiveDesignation",
+        alias = "connective_designation",
+        default
+    )]
+    pub connective_designation: Option<OrderAttribute>,
+    #[serde(default)]
+    pub locations: Vec<LocationView>,
+    #[serde(default)]
+    pub terms: Vec<Term>,
+    #[serde(default)]
+    pub coordinates: Vec<Coordinate>,
+}
+
+fn default_is_canonical() -> bool {
+    true
+}
+
+impl OrderView {
+    /// Get the location at a position (1-based)
+    pub fn location_at(&self, position: i32) -> Option<&LocationView> {
+        self.locations
+            .iter()
+            .find(|l| l.position_value == Some(position))
+    }
+}
+
+/// PositionView - a Position anchor with its locations across orders
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct PositionView {
+    pub id: String,
+    pub value: i32,
+    #[serde(default)]
+    pub locations: Vec<LocationView>,
+}
+
+impl PositionView {
+    /// Get the location of this position in an order
+    pub fn location_in(&self, order: i32) -> Option<&LocationView> {
+        self.locations.iter().find(|l| l.order_value == Some(order))
+    }
+}
+
+/// LocationView - a Location anchor (order × position) with what is placed
+/// there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct LocationView {
+    pub id: String,
+    #[serde(rename = "orderId", alias = "order_id")]
+    pub order_id: String,
+    #[serde(rename = "positionId", alias = "position_id")]
+    pub position_id: String,
+    #[serde(rename = "orderValue", alias = "order_value")]
+    pub order_value: Option<i32>,
+    #[serde(rename = "positionValue", alias = "position_value")]
+    pub position_value: Option<i32>,
+    /// All terms at this location
+    #[serde(default)]
+    pub terms: Vec<Term>,
+    #[serde(default)]
+    pub coordinate: Option<Coordinate>,
+    /// Hex and named colours
+    #[serde(default)]
+    pub colours: Vec<Colour>,
+    /// Labels that instances bind to this location
+    #[serde(rename = "instanceLabels", alias = "instance_labels", default)]
+    pub instance_labels: Vec<InstanceLabel>,
+}
+
+impl LocationView {
+    /// Get the value of the first term's character
+    pub fn term_value(&self) -> Option<&str> {
+        self.terms
+            .iter()
+            .find_map(|t| t.character.as_ref())
+            .map(|c| c.value.as_str())
+    }
+
+    /// Get the colour in a representation language (`Hex` or `Name`)
+    pub fn colour(&self, language: Language) -> Option<&Colour> {
+        self.colours.iter().find(|c| c.language == language)
+    }
+}
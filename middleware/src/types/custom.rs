@@ -0,0 +1,134 @@
+//! Custom system, instance and annotation types for Systematics wire format
+
+use super::InstanceLabel;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use async_graphql::{InputObject, SimpleObject};
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+/// CustomSystemInput - definition of a user-defined system, as the
+/// `createCustomSystem` mutation takes it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(InputObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct CustomSystemInput {
+    /// Order value; must not already exist (e.g., 13 and above)
+    pub order: i32,
+    pub name: String,
+    #[serde(default)]
+    pub coherence: Option<String>,
+    /// Further coherence attributes after the primary one
+    #[serde(
+        rename = "alternateCoherences",
+        alias = "alternate_coherences",
+        default
+    )]
+    #[cfg_attr(feature = "server", graphql(default))]
+    pub alternate_coherences: Vec<String>,
+    #[serde(rename = "termDesignation", alias = "term_designation", default)]
+    pub term_designation: Option<String>,
+    #[serde(
+        rename = "connectiveDesignation",
+        alias = "connective_designation",
+        default
+    )]
+    pub connective_designation: Option<String>,
+    /// Term names by position; exactly `order` of them
+    pub terms: Vec<String>,
+    /// Connective names for each pair (1,2), (1,3), ... (2,3), ...; empty or complete
+    #[serde(default)]
+    #[cfg_attr(feature = "server", graphql(default))]
+    pub connectives: Vec<String>,
+}
+
+impl CustomSystemInput {
+    /// Create a definition naming a system and its terms, without connectives
+    pub fn new<S: Into<String>>(
+        order: i32,
+        name: impl Into<String>,
+        terms: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            order,
+            name: name.into(),
+            terms: terms.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the connective names, in pair order (see `connective_pairs`)
+    pub fn with_connectives<S: Into<String>>(
+        mut self,
+        connectives: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.connectives = connectives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Position pairs the connective names apply to, in order: (1,2), (1,3),
+    /// ... (2,3), ...
+    #[cfg(feature = "client")]
+    pub fn connective_pairs(&self) -> Vec<(i32, i32)> {
+        (1..=self.order)
+            .flat_map(|base| (base + 1..=self.order).map(move |target| (base, target)))
+            .collect()
+    }
+
+    /// Check whether the server would accept the counts: one term per
+    /// position, and no connectives or one per pair
+    #[cfg(feature = "client")]
+    pub fn is_complete(&self) -> bool {
+        let pairs = self.connective_pairs().len();
+        self.order >= 1
+            && self.terms.len() == self.order as usize
+            && (self.connectives.is_empty() || self.connectives.len() == pairs)
+    }
+}
+
+/// InstanceView - a system applied to a domain, with a label at each location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct InstanceView {
+    pub id: String,
+    pub name: String,
+    /// Order of the system the instance applies
+    pub order: i32,
+    #[serde(default)]
+    pub labels: Vec<InstanceLabel>,
+}
+
+/// AnnotationView - a user note attached to an entry or link, as
+/// `annotations(entryId)` and the `annotateEntry` mutation return it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct AnnotationView {
+    pub id: String,
+    /// ID of the annotated entry or link (e.g., "loc_3_1", "conn_loc_3_1_loc_3_2")
+    #[serde(rename = "entryId", alias = "entry_id")]
+    pub entry_id: String,
+    pub text: String,
+    pub author: String,
+    /// User ID of the author, for notes added by a signed-in user
+    #[serde(rename = "authorId", alias = "author_id", default)]
+    pub author_id: Option<String>,
+}
+
+impl InstanceView {
+    /// Get the label bound to a position
+    pub fn label_at(&self, position: i32) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|l| l.position == position)
+            .map(|l| l.value.as_str())
+    }
+}
@@ -0,0 +1,224 @@
+//! Graph delta types for Systematics wire format
+
+use super::{Character, Colour, Coordinate, Link, Term};
+#[cfg(feature = "client")]
+use super::{LinkType, SystemView};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use async_graphql::SimpleObject;
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+/// GraphDelta - the changes from one revision of the graph to the next, as
+/// sent by the `graphDelta` subscription and in webhook events
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct GraphDelta {
+    /// Wire format version the server sent (see `API_VERSION`)
+    #[serde(rename = "apiVersion", alias = "api_version", default)]
+    pub api_version: i32,
+    /// Content hash of the graph before the changes
+    #[serde(rename = "fromRevision", alias = "from_revision")]
+    pub from_revision: String,
+    /// Content hash of the graph after the changes
+    #[serde(rename = "toRevision", alias = "to_revision")]
+    pub to_revision: String,
+    #[serde(default)]
+    pub added: DeltaEntries,
+    #[serde(default)]
+    pub updated: DeltaEntries,
+    /// IDs of the entries and links removed, of any kind
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// IDs of added or updated entries of other kinds (orders, names,
+    /// designations, ...), which are not carried
+    #[serde(default)]
+    pub other: Vec<String>,
+}
+
+/// DeltaEntries - entries and links added or updated by a `GraphDelta`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct DeltaEntries {
+    #[serde(default)]
+    pub terms: Vec<Term>,
+    #[serde(default)]
+    pub coordinates: Vec<Coordinate>,
+    #[serde(default)]
+    pub colours: Vec<Colour>,
+    #[serde(default)]
+    pub characters: Vec<Character>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+impl DeltaEntries {
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+            && self.coordinates.is_empty()
+            && self.colours.is_empty()
+            && self.characters.is_empty()
+            && self.links.is_empty()
+    }
+}
+
+impl GraphDelta {
+    /// Check whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.other.is_empty()
+    }
+
+    /// The added entries and links, then the updated ones
+    fn changed(&self) -> impl Iterator<Item = &DeltaEntries> {
+        [&self.added, &self.updated].into_iter()
+    }
+
+    /// Check whether the delta adds or updates entries or links of an order
+    pub fn touches(&self, order: i32) -> bool {
+        self.changed().any(|entries| {
+            entries.terms.iter().any(|t| t.order == order)
+                || entries.coordinates.iter().any(|c| c.order == order)
+                || entries.colours.iter().any(|c| c.order == order)
+                || entries.links.iter().any(|l| l.order == Some(order))
+        })
+    }
+
+    /// Apply the delta to a system: entries and links of its order are added,
+    /// replaced or removed, and updated characters replace those its terms
+    /// and connectives resolve to. Returns whether the system changed.
+    ///
+    /// Entries of other kinds (see `other`) are not carried, so a system whose
+    /// name or designations changed must be fetched again.
+    #[cfg(feature = "client")]
+    pub fn apply_to(&self, system: &mut SystemView) -> bool {
+        let order = system.order;
+        let mut changed = false;
+
+        changed |= remove(&mut system.terms, &self.removed, |t| &t.id);
+        changed |= remove(&mut system.coordinates, &self.removed, |c| &c.id);
+        changed |= remove(&mut system.colours, &self.removed, |c| &c.id);
+        changed |= remove(&mut system.connectives, &self.removed, |l| &l.id);
+        changed |= remove(&mut system.lines, &self.removed, |l| &l.id);
+        changed |= remove(&mut system.groupings, &self.removed, |g| &g.id);
+
+        let terms = self
+            .changed()
+            .flat_map(|e| &e.terms)
+            .filter(|t| t.order == order);
+        changed |= upsert(&mut system.terms, terms, |t| &t.id);
+        let coordinates = self
+            .changed()
+            .flat_map(|e| &e.coordinates)
+            .filter(|c| c.order == order);
+        changed |= upsert(&mut system.coordinates, coordinates, |c| &c.id);
+        let colours = self
+            .changed()
+            .flat_map(|e| &e.colours)
+            .filter(|c| c.order == order);
+        changed |= upsert(&mut system.colours, colours, |c| &c.id);
+
+        let links: Vec<&Link> = self
+            .changed()
+            .flat_map(|e| &e.links)
+            .filter(|l| l.order == Some(order))
+            .filter(|l| !system.connectives.contains(l) && !system.lines.contains(l))
+            .collect();
+        // Changed connectives are resolved afresh
+        system
+            .connective_views
+            .retain(|v| !links.iter().any(|l| l.id == v.id));
+        let of_type = |link_type: LinkType| {
+            links
+                .iter()
+                .copied()
+                .filter(move |l| l.link_type == link_type)
+        };
+        changed |= upsert(
+            &mut system.connectives,
+            of_type(LinkType::Connective),
+            |l| &l.id,
+        );
+        changed |= upsert(&mut system.lines, of_type(LinkType::Line), |l| &l.id);
+
+        for character in self.changed().flat_map(|e| &e.characters) {
+            let resolved = system
+                .terms
+                .iter_mut()
+                .filter(|t| t.character_id == character.id)
+                .map(|t| &mut t.character)
+                .chain(
+                    system
+                        .connectives
+                        .iter_mut()
+                        .filter(|l| l.character_id.as_ref() == Some(&character.id))
+                        .map(|l| &mut l.character),
+                )
+                .chain(
+                    system
+                        .connective_views
+                        .iter_mut()
+                        .map(|v| &mut v.character)
+                        .filter(|c| c.as_ref().is_some_and(|c| c.id == character.id)),
+                );
+            for slot in resolved {
+                if slot.as_ref() != Some(character) {
+                    *slot = Some(character.clone());
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            system.terms.sort_by_key(|t| t.position);
+            system.coordinates.sort_by_key(|c| c.position);
+            system.colours.sort_by_key(|c| c.position);
+            system.resolve_links();
+        }
+        changed
+    }
+}
+
+/// Remove the items with an ID in `ids`; returns whether any were
+#[cfg(feature = "client")]
+fn remove<T>(items: &mut Vec<T>, ids: &[String], id: impl Fn(&T) -> &String) -> bool {
+    let len = items.len();
+    items.retain(|item| !ids.contains(id(item)));
+    items.len() != len
+}
+
+/// Replace the items with the IDs of `new` ones, adding the others; returns
+/// whether any differed
+#[cfg(feature = "client")]
+fn upsert<'a, T: Clone + PartialEq + 'a>(
+    items: &mut Vec<T>,
+    new: impl Iterator<Item = &'a T>,
+    id: impl Fn(&T) -> &String,
+) -> bool {
+    let mut changed = false;
+    for item in new {
+        match items.iter_mut().find(|existing| id(existing) == id(item)) {
+            Some(existing) if existing == item => {}
+            Some(existing) => {
+                *existing = item.clone();
+                changed = true;
+            }
+            None => {
+                items.push(item.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
@@ -1,35 +1,153 @@
 //! Entry types for Systematics wire format
 
-use super::Language;
+use super::{Language, Link, ResearchStatus, SearchHitKind};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[cfg(feature = "server")]
 use async_graphql::SimpleObject;
 
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
 /// Character - a reusable vocabulary element
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Character {
     pub id: String,
     pub language: Language,
+    /// Vocabulary name; distinguishes custom vocabularies
+    #[serde(rename = "languageName", alias = "language_name", default)]
+    pub language_name: Option<String>,
     pub value: String,
+    /// Translations of `value` by locale (e.g., "de" → "Wille"); empty where
+    /// the server has none
+    #[serde(rename = "valueI18n", alias = "value_i18n", default)]
+    #[cfg_attr(feature = "server", graphql(name = "valueI18n"))]
+    pub value_i18n: HashMap<String, String>,
+    #[serde(default)]
+    pub status: ResearchStatus,
+    /// Locale, if this character is a translation
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Where this vocabulary element was published
+    #[serde(default)]
+    pub source: Option<Citation>,
+}
+
+impl Character {
+    /// Create a character with an auto-generated ID (e.g., `char_canonical_will`)
+    pub fn with_auto_id(language: Language, value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            id: format!(
+                "char_{}_{}",
+                language.slug(),
+                value.to_lowercase().replace(' ', "_")
+            ),
+            language,
+            language_name: None,
+            value,
+            value_i18n: HashMap::new(),
+            status: ResearchStatus::default(),
+            locale: None,
+            source: None,
+        }
+    }
+
+    /// Set the research status
+    pub fn with_status(mut self, status: ResearchStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Add the translation of the value into a locale
+    pub fn with_translation(mut self, locale: impl Into<String>, value: impl Into<String>) -> Self {
+        self.value_i18n.insert(locale.into(), value.into());
+        self
+    }
+
+    /// The value in a locale, or the untranslated value where there is no
+    /// translation
+    pub fn value_in(&self, locale: &str) -> &str {
+        self.value_i18n.get(locale).unwrap_or(&self.value)
+    }
+}
+
+/// Citation - where a piece of content was published
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Citation {
+    pub author: String,
+    pub book: String,
+    #[serde(default)]
+    pub page: Option<i32>,
 }
 
 /// Term - a positional entry with character reference
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Term {
     pub id: String,
     pub order: i32,
     pub position: i32,
-    #[serde(rename = "characterId")]
+    #[serde(rename = "characterId", alias = "character_id")]
     pub character_id: String,
     pub character: Option<Character>,
+    /// Translations of the character's value by locale, as on `character`
+    #[serde(rename = "valueI18n", alias = "value_i18n", default)]
+    #[cfg_attr(feature = "server", graphql(name = "valueI18n"))]
+    pub value_i18n: HashMap<String, String>,
+    #[serde(default)]
+    pub status: ResearchStatus,
+}
+
+impl Term {
+    /// Create a term with an auto-generated ID for a given order and position
+    pub fn with_auto_id(order: i32, position: i32, character: Character) -> Self {
+        Self {
+            id: format!("term_{}_{}", order, position),
+            order,
+            position,
+            character_id: character.id.clone(),
+            value_i18n: character.value_i18n.clone(),
+            character: Some(character),
+            status: ResearchStatus::default(),
+        }
+    }
+
+    /// Set the research status
+    pub fn with_status(mut self, status: ResearchStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// The character's value in a locale, or untranslated where there is no
+    /// translation; `None` without a character
+    pub fn value_in(&self, locale: &str) -> Option<&str> {
+        let character = self.character.as_ref()?;
+        Some(
+            self.value_i18n
+                .get(locale)
+                .map_or_else(|| character.value_in(locale), String::as_str),
+        )
+    }
 }
 
 /// Coordinate - a 3D point at a specific location
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Coordinate {
     pub id: String,
     pub order: i32,
@@ -39,9 +157,25 @@ pub struct Coordinate {
     pub z: f64,
 }
 
+impl Coordinate {
+    /// Create a coordinate with an auto-generated ID for a given order and position
+    pub fn with_auto_id(order: i32, position: i32, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            id: format!("coord_{}_{}", order, position),
+            order,
+            position,
+            x,
+            y,
+            z,
+        }
+    }
+}
+
 /// Colour - a color value at a specific location
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Colour {
     pub id: String,
     pub order: i32,
@@ -50,13 +184,68 @@ pub struct Colour {
     pub value: String,
 }
 
+impl Colour {
+    /// Create a colour with an auto-generated ID (e.g., `colour_3_1_hex`)
+    pub fn with_auto_id(
+        order: i32,
+        position: i32,
+        language: Language,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: format!("colour_{}_{}_{}", order, position, language.slug()),
+            order,
+            position,
+            language,
+            value: value.into(),
+        }
+    }
+}
+
 /// Slice - all entries at a specific order+position
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Slice {
     pub order: i32,
     pub position: i32,
+    #[serde(rename = "systemName", alias = "system_name", default)]
+    pub system_name: Option<String>,
+    #[serde(default)]
+    pub coherence: Option<String>,
     pub term: Option<Term>,
     pub coordinate: Option<Coordinate>,
     pub colour: Option<Colour>,
+    /// Connectives whose base or target is this location
+    #[serde(default)]
+    pub connectives: Vec<Link>,
+    /// Lines whose base or target is this location's coordinate
+    #[serde(default)]
+    pub lines: Vec<Link>,
+}
+
+/// InstanceLabel - a user-supplied label bound to one location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct InstanceLabel {
+    pub id: String,
+    pub position: i32,
+    pub value: String,
+}
+
+/// SearchHit - a search match with the order/position that owns it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    /// ID of the matched entry, or of the link for connectives
+    pub id: String,
+    pub value: String,
+    pub order: Option<i32>,
+    pub position: Option<i32>,
 }
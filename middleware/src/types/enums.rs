@@ -5,9 +5,17 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "server")]
 use async_graphql::Enum;
 
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
 /// Language enum for vocabularies and representations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Language {
     /// Standard Elementary Systematics vocabulary
@@ -18,10 +26,16 @@ pub enum Language {
     Values,
     /// Social vocabulary framework
     Society,
+    /// Runtime-registered vocabulary (see `Character::language_name`)
+    Custom,
     /// Hexadecimal color representation
     Hex,
     /// Named color representation
     Name,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
 }
 
 impl Language {
@@ -29,7 +43,11 @@ impl Language {
     pub fn is_vocabulary(&self) -> bool {
         matches!(
             self,
-            Language::Canonical | Language::Energy | Language::Values | Language::Society
+            Language::Canonical
+                | Language::Energy
+                | Language::Values
+                | Language::Society
+                | Language::Custom
         )
     }
 
@@ -52,15 +70,244 @@ impl Language {
     pub fn all_representations() -> [Language; 2] {
         [Language::Hex, Language::Name]
     }
+
+    /// Lowercase name used in generated IDs (e.g., `char_canonical_will`)
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Language::Canonical => "canonical",
+            Language::Energy => "energy",
+            Language::Values => "values",
+            Language::Society => "society",
+            Language::Custom => "custom",
+            Language::Hex => "hex",
+            Language::Name => "name",
+            Language::Unknown => "unknown",
+        }
+    }
+
+    /// Language with the given slug (e.g., `energy` in a URL), in any case
+    pub fn from_slug(slug: &str) -> Option<Language> {
+        Self::all_vocabularies()
+            .into_iter()
+            .chain([Language::Custom])
+            .chain(Self::all_representations())
+            .find(|language| language.slug().eq_ignore_ascii_case(slug))
+    }
+}
+
+/// What a search hit matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchHitKind {
+    /// A term, by its character's value
+    Term,
+    /// A connective, by its character's value
+    Connective,
+    /// A character not placed at any location or connective
+    Character,
+    SystemName,
+    CoherenceAttribute,
+    TermDesignation,
+    ConnectiveDesignation,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
+}
+
+/// Type of a graph entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EntryType {
+    Order,
+    Position,
+    /// An order and position pair
+    Location,
+    SystemName,
+    CoherenceAttribute,
+    TermDesignation,
+    ConnectiveDesignation,
+    Sequence,
+    Term,
+    Colour,
+    Coordinate,
+    Character,
+    Instance,
+    InstanceLabel,
+    Annotation,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
 }
 
 /// Link type enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum LinkType {
     /// Geometric edge between coordinates
     Line,
     /// Semantic connection between terms
     Connective,
+    /// Structural grouping of locations
+    Grouping,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
+}
+
+/// Structural role of a line within its figure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LineRole {
+    /// Tetrad edge bounding the activity field
+    Side,
+    /// Tetrad edge crossing the activity field
+    Diagonal,
+    /// Edge between neighbouring points on the outer boundary
+    Circumference,
+    /// Edge crossing the interior
+    Chord,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
+}
+
+impl LineRole {
+    /// Check if this line lies on the outer boundary of the figure
+    pub fn is_boundary(&self) -> bool {
+        matches!(self, LineRole::Side | LineRole::Circumference)
+    }
+
+    /// Tag a line with this role carries (e.g., "side")
+    pub fn as_tag(&self) -> &'static str {
+        match self {
+            LineRole::Side => "side",
+            LineRole::Diagonal => "diagonal",
+            LineRole::Circumference => "circumference",
+            LineRole::Chord => "chord",
+            LineRole::Unknown => "unknown",
+        }
+    }
+}
+
+/// Inner figure of the enneagram a chord of the Ennead belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnneagramFigure {
+    /// Triangle joining positions 3, 6 and 9
+    Triangle,
+    /// Process lines joining positions 1-4-2-8-5-7-1
+    Process,
+}
+
+impl EnneagramFigure {
+    /// Process sequence, closing back on its first position
+    const PROCESS: [i32; 7] = [1, 4, 2, 8, 5, 7, 1];
+
+    /// Figure the line between two positions (1-based) of the Ennead lies
+    /// on, in either direction
+    pub fn of_line(a: i32, b: i32) -> Option<EnneagramFigure> {
+        if a != b && [a, b].iter().all(|p| [3, 6, 9].contains(p)) {
+            Some(EnneagramFigure::Triangle)
+        } else if Self::PROCESS
+            .windows(2)
+            .any(|w| (w[0], w[1]) == (a, b) || (w[1], w[0]) == (a, b))
+        {
+            Some(EnneagramFigure::Process)
+        } else {
+            None
+        }
+    }
+
+    /// Tag a line of this figure carries (e.g., "triangle")
+    pub fn as_tag(&self) -> &'static str {
+        match self {
+            EnneagramFigure::Triangle => "triangle",
+            EnneagramFigure::Process => "process",
+        }
+    }
+}
+
+/// Research status of vocabulary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Enum))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ResearchStatus {
+    /// Published in Bennett's works or the curated sources
+    #[default]
+    Canonical,
+    /// In use but not yet confirmed against the sources
+    Provisional,
+    /// Placeholder awaiting research
+    NeedsResearch,
+    /// Value not known to this client version
+    #[serde(other)]
+    #[cfg_attr(feature = "server", graphql(visible = false))]
+    Unknown,
+}
+
+impl ResearchStatus {
+    /// Check if this is placeholder content
+    pub fn needs_research(&self) -> bool {
+        matches!(self, ResearchStatus::NeedsResearch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_slugs() {
+        for language in Language::all_vocabularies() {
+            assert_eq!(Language::from_slug(language.slug()), Some(language));
+        }
+        assert_eq!(Language::from_slug("Energy"), Some(Language::Energy));
+        assert_eq!(Language::from_slug("hex"), Some(Language::Hex));
+        assert_eq!(Language::from_slug("unknown"), None);
+        assert_eq!(Language::from_slug("klingon"), None);
+    }
+
+    #[test]
+    fn test_enneagram_figures() {
+        assert_eq!(
+            EnneagramFigure::of_line(3, 6),
+            Some(EnneagramFigure::Triangle)
+        );
+        assert_eq!(
+            EnneagramFigure::of_line(9, 3),
+            Some(EnneagramFigure::Triangle)
+        );
+        assert_eq!(
+            EnneagramFigure::of_line(1, 4),
+            Some(EnneagramFigure::Process)
+        );
+        assert_eq!(
+            EnneagramFigure::of_line(1, 7),
+            Some(EnneagramFigure::Process)
+        );
+        assert_eq!(
+            EnneagramFigure::of_line(8, 2),
+            Some(EnneagramFigure::Process)
+        );
+        assert_eq!(EnneagramFigure::of_line(1, 2), None);
+        assert_eq!(EnneagramFigure::of_line(3, 3), None);
+        assert_eq!(EnneagramFigure::of_line(4, 5), None);
+    }
 }
@@ -2,22 +2,314 @@
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+/// Machine-readable error code, sent as `extensions.code` of a GraphQL error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// Order argument outside the supported range
+    InvalidOrder,
+    /// Position argument outside the supported range
+    InvalidPosition,
+    /// Order in range, but no system exists for it
+    UnknownOrder,
+    /// Input value rejected (empty, wrong count, etc.)
+    InvalidInput,
+    /// Entry or system with this identity already exists
+    AlreadyExists,
+    /// No entry or link with the given ID
+    UnknownEntry,
+    /// Persisted query hash not registered yet; resend with the query text
+    PersistedQueryNotFound,
+    /// Server accepts persisted queries only
+    PersistedQueryRequired,
+    /// Mutation sent as a GET request; mutations must use POST
+    MutationNotAllowed,
+    /// Operation not on the server's allow-list
+    OperationNotAllowed,
+    /// Client sent too many requests; retry after the `Retry-After` delay
+    RateLimited,
+    /// Bearer token missing a valid signature, issuer, audience or expiry
+    Unauthenticated,
+    /// Workspace name invalid, or no room for another workspace
+    WorkspaceUnavailable,
+    /// Session unknown (committed, discarded or expired), or no room for another
+    SessionUnavailable,
+    /// Data files could not be reloaded (none configured, or failed to load)
+    ReloadFailed,
+    /// Code not known to this client version
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Wire value of the code (e.g., `INVALID_ORDER`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidOrder => "INVALID_ORDER",
+            ErrorCode::InvalidPosition => "INVALID_POSITION",
+            ErrorCode::UnknownOrder => "UNKNOWN_ORDER",
+            ErrorCode::InvalidInput => "INVALID_INPUT",
+            ErrorCode::AlreadyExists => "ALREADY_EXISTS",
+            ErrorCode::UnknownEntry => "UNKNOWN_ENTRY",
+            ErrorCode::PersistedQueryNotFound => "PERSISTED_QUERY_NOT_FOUND",
+            ErrorCode::PersistedQueryRequired => "PERSISTED_QUERY_REQUIRED",
+            ErrorCode::MutationNotAllowed => "MUTATION_NOT_ALLOWED",
+            ErrorCode::OperationNotAllowed => "OPERATION_NOT_ALLOWED",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::Unauthenticated => "UNAUTHENTICATED",
+            ErrorCode::WorkspaceUnavailable => "WORKSPACE_UNAVAILABLE",
+            ErrorCode::SessionUnavailable => "SESSION_UNAVAILABLE",
+            ErrorCode::ReloadFailed => "RELOAD_FAILED",
+            ErrorCode::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Extensions of a GraphQL error: what failed, which argument, and how to fix it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ErrorExtensions {
+    #[serde(default)]
+    pub code: Option<ErrorCode>,
+    /// Argument or input field the error refers to (e.g., "order", "labels")
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Suggestion for correcting the request
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+/// Segment of the path to the response field an error belongs to: a field
+/// name, or an index into a list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum PathSegment {
+    Index(i32),
+    Field(String),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Index(index) => write!(f, "{}", index),
+            PathSegment::Field(field) => f.write_str(field),
+        }
+    }
+}
+
+/// Error entry of a GraphQL response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct GraphQLError {
+    pub message: String,
+    /// Response field the error belongs to (e.g., `system.terms.0`); empty
+    /// for errors about the request as a whole
+    #[serde(default)]
+    pub path: Vec<PathSegment>,
+    #[serde(default)]
+    pub extensions: ErrorExtensions,
+}
+
+impl GraphQLError {
+    /// Path as dot-separated segments (e.g., "system.terms.0")
+    pub fn path_string(&self) -> String {
+        let segments: Vec<String> = self.path.iter().map(|s| s.to_string()).collect();
+        segments.join(".")
+    }
+}
+
 /// API error type for client-side error handling
+#[cfg(feature = "client")]
 #[derive(Debug)]
 pub enum ApiError {
+    /// No response: the request could not be sent or the server is unreachable
     NetworkError(String),
+    /// No response within the client's time limit
+    Timeout,
+    /// Response with an unsuccessful HTTP status, and the errors its body
+    /// reported, if any
+    Http {
+        status: u16,
+        /// Seconds the server asked to wait before retrying (`Retry-After`)
+        retry_after: Option<u32>,
+        errors: Vec<GraphQLError>,
+    },
     ParseError(String),
     NotFound(String),
+    /// Errors reported by the GraphQL server
+    GraphQL(Vec<GraphQLError>),
+    /// Response decoded, but inconsistent (see `SystemView::validate`)
+    Invalid(ValidationError),
+}
+
+#[cfg(feature = "client")]
+impl ApiError {
+    /// Errors reported by the server, if any
+    pub fn errors(&self) -> &[GraphQLError] {
+        match self {
+            ApiError::GraphQL(errors) | ApiError::Http { errors, .. } => errors,
+            _ => &[],
+        }
+    }
+
+    /// Code of the first server error that carries one
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.errors().iter().find_map(|e| e.extensions.code)
+    }
+
+    /// HTTP status of the response, for errors answered with one
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ApiError::Http { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Seconds the server asked to wait before retrying
+    pub fn retry_after(&self) -> Option<u32> {
+        match self {
+            ApiError::Http { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed: the server was
+    /// unreachable, slow, overloaded or failing, or limited the client's
+    /// rate. Errors in the request or the data it returned are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::NetworkError(_) | ApiError::Timeout => true,
+            ApiError::Http { status, .. } => *status >= 500 || matches!(status, 408 | 429),
+            ApiError::GraphQL(_) => self.code() == Some(ErrorCode::RateLimited),
+            ApiError::ParseError(_) | ApiError::NotFound(_) | ApiError::Invalid(_) => false,
+        }
+    }
+
+    /// Whether no GraphQL server answered at the endpoint: it could not be
+    /// reached or timed out, a proxy in front of it found no server (e.g.,
+    /// 502), or what answered was not GraphQL (e.g., a static host's 404, or
+    /// a page that does not parse)
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            ApiError::NetworkError(_) | ApiError::Timeout | ApiError::ParseError(_) => true,
+            ApiError::Http { status, errors, .. } => {
+                errors.is_empty() && matches!(status, 404 | 405 | 502 | 503 | 504)
+            }
+            ApiError::NotFound(_) | ApiError::GraphQL(_) | ApiError::Invalid(_) => false,
+        }
+    }
+}
+
+/// Messages of server errors, with the paths of those that have one
+#[cfg(feature = "client")]
+fn messages(errors: &[GraphQLError]) -> String {
+    let messages: Vec<String> = errors
+        .iter()
+        .map(|e| {
+            if e.path.is_empty() {
+                e.message.clone()
+            } else {
+                format!("{} (at {})", e.message, e.path_string())
+            }
+        })
+        .collect();
+    messages.join(", ")
 }
 
+#[cfg(feature = "client")]
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ApiError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            ApiError::Timeout => write!(f, "Request timed out"),
+            ApiError::Http { status, errors, .. } if errors.is_empty() => {
+                write!(f, "Request failed with status {}", status)
+            }
+            ApiError::Http { status, errors, .. } => {
+                write!(
+                    f,
+                    "Request failed with status {}: {}",
+                    status,
+                    messages(errors)
+                )
+            }
             ApiError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             ApiError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            ApiError::GraphQL(errors) => write!(f, "Server error: {}", messages(errors)),
+            ApiError::Invalid(error) => write!(f, "Invalid data: {}", error),
         }
     }
 }
 
+#[cfg(feature = "client")]
 impl std::error::Error for ApiError {}
+
+#[cfg(feature = "client")]
+impl From<ValidationError> for ApiError {
+    fn from(error: ValidationError) -> Self {
+        ApiError::Invalid(error)
+    }
+}
+
+/// Positional inconsistency in a decoded system, found by `SystemView::validate`
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// Order below 1
+    InvalidOrder(i32),
+    /// No entry of a kind ("term", "coordinate", "colour") at a position
+    Missing { kind: &'static str, position: i32 },
+    /// More than one entry of a kind at a position
+    Duplicate { kind: &'static str, position: i32 },
+    /// Entry, link or grouping referring to a position outside `1..=order`
+    OutOfRange {
+        kind: &'static str,
+        id: String,
+        position: i32,
+    },
+}
+
+#[cfg(feature = "client")]
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidOrder(order) => write!(f, "order {} is below 1", order),
+            ValidationError::Missing { kind, position } => {
+                write!(f, "no {} at position {}", kind, position)
+            }
+            ValidationError::Duplicate { kind, position } => {
+                write!(f, "more than one {} at position {}", kind, position)
+            }
+            ValidationError::OutOfRange { kind, id, position } => {
+                write!(
+                    f,
+                    "{} {} is at position {}, out of range",
+                    kind, id, position
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+impl std::error::Error for ValidationError {}
@@ -0,0 +1,104 @@
+//! Full-graph view types for Systematics wire format
+
+use super::{EntryType, LineRole, LinkType};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use async_graphql::SimpleObject;
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+/// GraphView - the whole graph: counts, entries grouped by type, and links
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct GraphView {
+    /// Wire format version the server sent (see `API_VERSION`); 0 from servers
+    /// that predate the field
+    #[serde(rename = "apiVersion", alias = "api_version", default)]
+    pub api_version: i32,
+    #[serde(rename = "entryCount", alias = "entry_count")]
+    pub entry_count: i32,
+    #[serde(rename = "linkCount", alias = "link_count")]
+    pub link_count: i32,
+    /// Entries by type, in the order each type first appears
+    #[serde(rename = "entriesByType", alias = "entries_by_type", default)]
+    pub entries_by_type: Vec<EntryGroup>,
+    #[serde(default)]
+    pub links: Vec<GraphLink>,
+}
+
+impl GraphView {
+    /// Get the group of entries of a type, if the graph has any
+    pub fn group(&self, entry_type: EntryType) -> Option<&EntryGroup> {
+        self.entries_by_type
+            .iter()
+            .find(|g| g.entry_type == entry_type)
+    }
+
+    /// Get the number of entries of a type
+    pub fn count(&self, entry_type: EntryType) -> i32 {
+        self.group(entry_type).map_or(0, |g| g.count)
+    }
+
+    /// Get the links of a type
+    pub fn links_of(&self, link_type: LinkType) -> impl Iterator<Item = &GraphLink> {
+        self.links.iter().filter(move |l| l.link_type == link_type)
+    }
+}
+
+/// EntryGroup - the entries of one type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct EntryGroup {
+    #[serde(rename = "entryType", alias = "entry_type")]
+    pub entry_type: EntryType,
+    pub count: i32,
+    /// Entries of this type, in graph order (possibly only the first few)
+    #[serde(default)]
+    pub entries: Vec<EntryRef>,
+}
+
+/// EntryRef - an entry, by ID
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct EntryRef {
+    pub id: String,
+}
+
+/// GraphLink - a link of the whole graph. Unlike a system's lines and
+/// connectives, a grouping joins several locations, so it has no single base.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct GraphLink {
+    pub id: String,
+    /// Base entry, when the link has exactly one
+    #[serde(rename = "baseId", alias = "base_id")]
+    pub base_id: Option<String>,
+    /// Target entry, when the link has exactly one
+    #[serde(rename = "targetId", alias = "target_id")]
+    pub target_id: Option<String>,
+    #[serde(rename = "linkType", alias = "link_type")]
+    pub link_type: LinkType,
+    #[serde(rename = "characterId", alias = "character_id")]
+    pub character_id: Option<String>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub role: Option<LineRole>,
+    pub order: Option<i32>,
+    #[serde(rename = "basePosition", alias = "base_position")]
+    pub base_position: Option<i32>,
+    #[serde(rename = "targetPosition", alias = "target_position")]
+    pub target_position: Option<i32>,
+}
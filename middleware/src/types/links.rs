@@ -1,36 +1,194 @@
 //! Link types for Systematics wire format
 
-use super::{Character, Coordinate, LinkType};
+use super::{Character, Coordinate, LineRole, LinkType};
+use crate::display;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "server")]
 use async_graphql::SimpleObject;
 
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
 /// Link - a connection between entries (either Line or Connective)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Link {
     pub id: String,
-    #[serde(rename = "baseId")]
+    #[serde(rename = "baseId", alias = "base_id")]
     pub base_id: String,
-    #[serde(rename = "targetId")]
+    #[serde(rename = "targetId", alias = "target_id")]
     pub target_id: String,
-    #[serde(rename = "linkType")]
+    #[serde(rename = "linkType", alias = "link_type")]
     pub link_type: LinkType,
-    #[serde(rename = "characterId")]
+    #[serde(rename = "characterId", alias = "character_id")]
     pub character_id: Option<String>,
     pub tag: Option<String>,
+    /// Structural role (for lines): side/diagonal or circumference/chord
+    #[serde(default)]
+    pub role: Option<LineRole>,
     pub order: Option<i32>,
-    #[serde(rename = "basePosition")]
+    #[serde(rename = "basePosition", alias = "base_position")]
     pub base_position: Option<i32>,
-    #[serde(rename = "targetPosition")]
+    #[serde(rename = "targetPosition", alias = "target_position")]
     pub target_position: Option<i32>,
     /// Resolved character for this link (for connectives)
     pub character: Option<Character>,
     /// Resolved base coordinate
-    #[serde(rename = "baseCoordinate")]
+    #[serde(rename = "baseCoordinate", alias = "base_coordinate")]
     pub base_coordinate: Option<Coordinate>,
     /// Resolved target coordinate
-    #[serde(rename = "targetCoordinate")]
+    #[serde(rename = "targetCoordinate", alias = "target_coordinate")]
     pub target_coordinate: Option<Coordinate>,
 }
+
+impl Link {
+    /// Create a line between the coordinates at two positions of an order
+    pub fn line(order: i32, base: i32, target: i32) -> Self {
+        Self::between(
+            LinkType::Line,
+            order,
+            (base, format!("coord_{}_{}", order, base)),
+            (target, format!("coord_{}_{}", order, target)),
+        )
+    }
+
+    /// Create a connective between the locations at two positions of an order
+    pub fn connective(order: i32, base: i32, target: i32) -> Self {
+        Self::between(
+            LinkType::Connective,
+            order,
+            (base, format!("loc_{}_{}", order, base)),
+            (target, format!("loc_{}_{}", order, target)),
+        )
+    }
+
+    fn between(
+        link_type: LinkType,
+        order: i32,
+        (base_position, base_id): (i32, String),
+        (target_position, target_id): (i32, String),
+    ) -> Self {
+        let prefix = match link_type {
+            LinkType::Line => "line",
+            _ => "conn",
+        };
+        Self {
+            id: format!("{}_{}_{}", prefix, base_id, target_id),
+            base_id,
+            target_id,
+            link_type,
+            character_id: None,
+            tag: None,
+            role: None,
+            order: Some(order),
+            base_position: Some(base_position),
+            target_position: Some(target_position),
+            character: None,
+            base_coordinate: None,
+            target_coordinate: None,
+        }
+    }
+
+    /// Set the structural role of a line, and the tag carrying it
+    pub fn with_role(mut self, role: LineRole) -> Self {
+        self.role = Some(role);
+        self.tag = Some(role.as_tag().to_string());
+        self
+    }
+
+    /// Set the character labelling a connective, and the tag referencing it
+    pub fn with_character(mut self, character: Character) -> Self {
+        self.character_id = Some(character.id.clone());
+        self.tag = Some(character.id.clone());
+        self.character = Some(character);
+        self
+    }
+}
+
+/// ConnectiveView - a connective resolved for labelling: its character, the
+/// order's connective designation, and the terms it joins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ConnectiveView {
+    /// ID of the connective link
+    pub id: String,
+    pub order: i32,
+    #[serde(rename = "basePosition", alias = "base_position")]
+    pub base_position: i32,
+    #[serde(rename = "targetPosition", alias = "target_position")]
+    pub target_position: i32,
+    /// Character naming the connective, in the system's vocabulary
+    pub character: Option<Character>,
+    /// Connective designation of the order (e.g., "Acts")
+    pub designation: Option<String>,
+    /// Value of the term at the base position
+    #[serde(rename = "baseTerm", alias = "base_term")]
+    pub base_term: Option<String>,
+    /// Value of the term at the target position
+    #[serde(rename = "targetTerm", alias = "target_term")]
+    pub target_term: Option<String>,
+}
+
+impl ConnectiveView {
+    /// Get the character value labelling the edge, if named
+    pub fn label(&self) -> Option<&str> {
+        self.character
+            .as_ref()
+            .map(|c| c.value.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Check whether the naming character is placeholder content
+    pub fn needs_research(&self) -> bool {
+        self.character
+            .as_ref()
+            .is_some_and(|c| c.status.needs_research())
+    }
+
+    /// Check whether the connective joins two positions, in either direction
+    pub fn joins(&self, a: i32, b: i32) -> bool {
+        (self.base_position, self.target_position) == (a, b)
+            || (self.base_position, self.target_position) == (b, a)
+    }
+
+    /// Get the title of the edge: the connective by its designation and the
+    /// terms it joins (e.g., "Act 1–2: Will → Function")
+    pub fn title(&self) -> String {
+        let label = display::connective_label(
+            self.base_position,
+            self.target_position,
+            self.designation.as_deref(),
+        );
+        match (&self.base_term, &self.target_term) {
+            (Some(base), Some(target)) => format!("{}: {} → {}", label, base, target),
+            _ => label,
+        }
+    }
+}
+
+/// Grouping - a named structural unit of positions (e.g., a Pentad limits dyad)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Grouping {
+    pub id: String,
+    pub order: Option<i32>,
+    #[serde(rename = "characterId", alias = "character_id")]
+    pub character_id: Option<String>,
+    /// Resolved label character
+    pub character: Option<Character>,
+    #[serde(rename = "memberPositions", alias = "member_positions", default)]
+    pub member_positions: Vec<i32>,
+    /// Enclosing grouping (if nested)
+    #[serde(rename = "parentId", alias = "parent_id")]
+    pub parent_id: Option<String>,
+}
@@ -3,14 +3,26 @@
 //! These types define the JSON structure exchanged between backend and frontend.
 //! They support both serialization (backend) and deserialization (frontend).
 
+mod anchors;
+mod custom;
+mod delta;
 mod entries;
 mod enums;
 mod error;
+mod graph;
 mod links;
+mod page;
 mod system;
+mod version;
 
+pub use anchors::*;
+pub use custom::*;
+pub use delta::*;
 pub use entries::*;
 pub use enums::*;
 pub use error::*;
+pub use graph::*;
 pub use links::*;
+pub use page::*;
 pub use system::*;
+pub use version::*;
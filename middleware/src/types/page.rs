@@ -0,0 +1,71 @@
+//! Pagination types for Systematics wire format
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+#[cfg(feature = "client")]
+use std::future::Future;
+
+/// Page - one page of a list queried with `first` and `after`, and where the
+/// next one starts. The backend outputs each GraphQL page type (e.g.,
+/// `GqlSearchHitPage` for `v2 { search }`) with these fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Items in the whole list
+    pub total: i32,
+    #[serde(rename = "hasNext", alias = "has_next")]
+    pub has_next: bool,
+    /// Opaque cursor to pass as `after` for the next page; null on the last
+    pub cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// The page of up to `limit` items from `offset` into a whole list. Its
+    /// cursor is the offset of the next page.
+    pub fn of(all: Vec<T>, offset: usize, limit: usize) -> Self {
+        let total = all.len();
+        let end = offset.saturating_add(limit).min(total);
+        let has_next = end < total;
+        Self {
+            items: all.into_iter().skip(offset).take(limit).collect(),
+            total: total as i32,
+            has_next,
+            cursor: has_next.then(|| end.to_string()),
+        }
+    }
+
+    /// The cursor to continue from, if there is a next page
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.cursor.as_deref().filter(|_| self.has_next)
+    }
+}
+
+/// Fetch every page of a list and collect their items. `fetch` is called with
+/// no cursor, then with each page's cursor until a page has no next; a server
+/// repeating a cursor ends the loop rather than looping forever.
+#[cfg(feature = "client")]
+pub async fn collect_pages<T, E, F, Fut>(mut fetch: F) -> Result<Vec<T>, E>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Page<T>, E>>,
+{
+    let mut items = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let page = fetch(after.clone()).await?;
+        let next = page.next_cursor().map(str::to_string);
+        items.extend(page.items);
+        match next {
+            Some(cursor) if after.as_ref() != Some(&cursor) => after = Some(cursor),
+            _ => return Ok(items),
+        }
+    }
+}
@@ -1,33 +1,105 @@
 //! System view types for Systematics wire format
 
-use super::{Colour, Coordinate, Link, Term};
+use super::{
+    Character, Colour, ConnectiveView, Coordinate, EnneagramFigure, Grouping, InstanceView,
+    Language, Link, Term, API_VERSION,
+};
+#[cfg(feature = "client")]
+use super::{ResearchStatus, ValidationError};
+use crate::display;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "server")]
 use async_graphql::SimpleObject;
 
+#[cfg(feature = "typescript")]
+use ts_rs::TS;
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
 /// SystemView - a complete view of a system at a given order
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(SimpleObject))]
+#[cfg_attr(feature = "typescript", derive(TS))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct SystemView {
+    /// Wire format version the server sent (see `API_VERSION`); 0 from servers
+    /// that predate the field
+    #[serde(rename = "apiVersion", alias = "api_version", default)]
+    pub api_version: i32,
     pub order: i32,
     pub name: Option<String>,
+    /// False for user-defined custom systems
+    #[serde(
+        rename = "isCanonical",
+        alias = "is_canonical",
+        default = "default_is_canonical"
+    )]
+    pub is_canonical: bool,
+    /// Primary coherence attribute
     pub coherence: Option<String>,
-    #[serde(rename = "termDesignation")]
+    /// All coherence attributes, primary first
+    #[serde(default)]
+    pub coherences: Vec<String>,
+    #[serde(rename = "termDesignation", alias = "term_designation")]
     pub term_designation: Option<String>,
-    #[serde(rename = "connectiveDesignation")]
+    #[serde(rename = "connectiveDesignation", alias = "connective_designation")]
     pub connective_designation: Option<String>,
+    #[serde(default)]
     pub terms: Vec<Term>,
+    #[serde(default)]
     pub coordinates: Vec<Coordinate>,
+    #[serde(default)]
     pub colours: Vec<Colour>,
+    #[serde(default)]
     pub connectives: Vec<Link>,
+    #[serde(default)]
     pub lines: Vec<Link>,
+    /// Connectives resolved for labelling edges
+    #[serde(rename = "connectiveViews", alias = "connective_views", default)]
+    pub connective_views: Vec<ConnectiveView>,
     /// All links (both lines and connectives)
     #[serde(default)]
     pub links: Vec<Link>,
+    /// Structural groupings (e.g., the Pentad's nested dyads)
+    #[serde(default)]
+    pub groupings: Vec<Grouping>,
+    /// Instances applying this system to a domain
+    #[serde(default)]
+    pub instances: Vec<InstanceView>,
+}
+
+fn default_is_canonical() -> bool {
+    true
 }
 
 impl SystemView {
+    /// Start building a system of an order (see `SystemViewBuilder`)
+    pub fn builder(order: i32) -> SystemViewBuilder {
+        SystemViewBuilder {
+            system: SystemView {
+                api_version: API_VERSION,
+                order,
+                name: None,
+                is_canonical: true,
+                coherence: None,
+                coherences: Vec::new(),
+                term_designation: None,
+                connective_designation: None,
+                terms: Vec::new(),
+                coordinates: Vec::new(),
+                colours: Vec::new(),
+                connectives: Vec::new(),
+                lines: Vec::new(),
+                connective_views: Vec::new(),
+                links: Vec::new(),
+                groupings: Vec::new(),
+                instances: Vec::new(),
+            },
+        }
+    }
+
     /// Get the system name, falling back to order-based name
     pub fn display_name(&self) -> String {
         self.name.clone().unwrap_or_else(|| {
@@ -76,6 +148,20 @@ impl SystemView {
             .map(|c| c.value.as_str())
     }
 
+    /// Check whether the term at a position (1-based) is placeholder content
+    pub fn term_needs_research_at(&self, position: i32) -> bool {
+        self.terms
+            .iter()
+            .find(|t| t.position == position)
+            .map(|t| {
+                t.status.needs_research()
+                    || t.character
+                        .as_ref()
+                        .is_some_and(|c| c.status.needs_research())
+            })
+            .unwrap_or(false)
+    }
+
     /// Get the colour value at a position (1-based)
     pub fn colour_at(&self, position: i32) -> Option<&str> {
         self.colours
@@ -84,8 +170,463 @@ impl SystemView {
             .map(|c| c.value.as_str())
     }
 
+    /// Get the groupings containing a position (1-based)
+    pub fn groupings_at(&self, position: i32) -> Vec<&Grouping> {
+        self.groupings
+            .iter()
+            .filter(|g| g.member_positions.contains(&position))
+            .collect()
+    }
+
     /// Get the coordinate at a position (1-based)
     pub fn coordinate_at(&self, position: i32) -> Option<&Coordinate> {
         self.coordinates.iter().find(|c| c.position == position)
     }
+
+    /// Get the order as a Greek numeral (e.g., "γʹ" for Triad)
+    pub fn greek_numeral(&self) -> Option<String> {
+        display::greek_numeral(self.order)
+    }
+
+    /// Get the glyph of a position (1-based, e.g., '③')
+    pub fn position_glyph(&self, position: i32) -> Option<char> {
+        display::circled_numeral(position)
+    }
+
+    /// Get the singular term designation (e.g., "Impulse" for Triad), if
+    /// researched
+    pub fn term_designation_singular(&self) -> Option<String> {
+        display::researched(self.term_designation.as_deref()).map(display::singular)
+    }
+
+    /// Get the number of terms with their designation (e.g., "3 Impulses")
+    pub fn term_count_label(&self) -> String {
+        display::count_label(
+            self.order.max(0) as usize,
+            self.term_designation.as_deref(),
+            "Term",
+        )
+    }
+
+    /// Get the ordinal label of a position (1-based, e.g., "2nd Impulse")
+    pub fn ordinal_position_label(&self, position: i32) -> String {
+        display::position_label(position, self.term_designation.as_deref())
+    }
+
+    /// Get the singular connective designation (e.g., "Act" for Triad), if
+    /// researched
+    pub fn connective_designation_singular(&self) -> Option<String> {
+        display::researched(self.connective_designation.as_deref()).map(display::singular)
+    }
+
+    /// Get the number of connectives with their designation (e.g., "3 Acts")
+    pub fn connective_count_label(&self) -> String {
+        display::count_label(
+            self.connectives.len(),
+            self.connective_designation.as_deref(),
+            "Connective",
+        )
+    }
+
+    /// Get the label of the connective between two positions (e.g., "Act 1–2")
+    pub fn connective_label(&self, base: i32, target: i32) -> String {
+        display::connective_label(base, target, self.connective_designation.as_deref())
+    }
+
+    /// Get the term at a position, or the position itself without one (e.g.,
+    /// "Will", or "position 4")
+    fn term_or_position(&self, position: i32) -> String {
+        self.term_at(position)
+            .filter(|t| !t.is_empty())
+            .map_or_else(|| format!("position {}", position), str::to_string)
+    }
+
+    /// Get the name of the connective between two positions: its character
+    /// if named, or its label (e.g., "Act3", or "Act 1–2")
+    fn connection_name(&self, a: i32, b: i32) -> String {
+        match self.connective_view_between(a, b) {
+            Some(view) => view.label().map_or_else(
+                || self.connective_label(view.base_position, view.target_position),
+                str::to_string,
+            ),
+            None => self.connective_label(a.min(b), a.max(b)),
+        }
+    }
+
+    /// Get a description of a position and the positions its lines join it
+    /// to, for screen readers (e.g., "Position 3: Being, connected to Will by
+    /// Act3 and to Function by Act2")
+    pub fn position_description(&self, position: i32) -> String {
+        let mut description = match self.term_at(position).filter(|t| !t.is_empty()) {
+            Some(term) => format!("Position {}: {}", position, term),
+            None => format!("Position {}", position),
+        };
+        let mut others: Vec<i32> = self
+            .lines
+            .iter()
+            .filter_map(|l| match (l.base_position?, l.target_position?) {
+                (a, b) if a == position => Some(b),
+                (a, b) if b == position => Some(a),
+                _ => None,
+            })
+            .collect();
+        others.sort_unstable();
+        others.dedup();
+        let connections: Vec<String> = others
+            .iter()
+            .map(|&other| {
+                format!(
+                    "to {} by {}",
+                    self.term_or_position(other),
+                    self.connection_name(position, other)
+                )
+            })
+            .collect();
+        if let Some((last, rest)) = connections.split_last() {
+            description.push_str(", connected ");
+            if !rest.is_empty() {
+                description.push_str(&rest.join(", "));
+                description.push_str(" and ");
+            }
+            description.push_str(last);
+        }
+        description
+    }
+
+    /// Get a description of the line between two positions, for screen
+    /// readers: its connective, in the connective's direction when there is
+    /// one (e.g., "Act3 (Act 3–1), from Being to Will")
+    pub fn line_description(&self, a: i32, b: i32) -> String {
+        let Some(view) = self.connective_view_between(a, b) else {
+            return format!(
+                "{}, between {} and {}",
+                self.connective_label(a.min(b), a.max(b)),
+                self.term_or_position(a.min(b)),
+                self.term_or_position(a.max(b))
+            );
+        };
+        let label = self.connective_label(view.base_position, view.target_position);
+        let name = match view.label() {
+            Some(character) => format!("{} ({})", character, label),
+            None => label,
+        };
+        format!(
+            "{}, from {} to {}",
+            name,
+            self.term_or_position(view.base_position),
+            self.term_or_position(view.target_position)
+        )
+    }
+
+    /// Get the enneagram figure the line between two positions lies on, for
+    /// the Ennead
+    pub fn enneagram_figure(&self, a: i32, b: i32) -> Option<EnneagramFigure> {
+        (self.order == 9)
+            .then(|| EnneagramFigure::of_line(a, b))
+            .flatten()
+    }
+
+    /// Get the ID of the location at a position (e.g., "loc_3_1"), which stays
+    /// the same in every vocabulary
+    pub fn location_id(&self, position: i32) -> String {
+        format!("loc_{}_{}", self.order, position)
+    }
+
+    /// Get the resolved connective between two positions, in either direction
+    pub fn connective_view_between(&self, a: i32, b: i32) -> Option<&ConnectiveView> {
+        self.connective_views.iter().find(|c| c.joins(a, b))
+    }
+
+    /// Name the term at a position with a provisional character in
+    /// `language`, as the `renameTerm` mutation does, to show an edit before
+    /// the server confirms it. Returns false if there is no term there.
+    #[cfg(feature = "client")]
+    pub fn rename_term(&mut self, position: i32, language: Language, value: &str) -> bool {
+        let value = value.trim();
+        let Some(term) = self.terms.iter_mut().find(|t| t.position == position) else {
+            return false;
+        };
+        let character =
+            Character::with_auto_id(language, value).with_status(ResearchStatus::Provisional);
+        term.character_id = character.id.clone();
+        term.character = Some(character);
+        term.value_i18n.clear();
+        term.status = ResearchStatus::Provisional;
+        for view in &mut self.connective_views {
+            if view.base_position == position {
+                view.base_term = Some(value.to_string());
+            }
+            if view.target_position == position {
+                view.target_term = Some(value.to_string());
+            }
+        }
+        true
+    }
+
+    /// Name the connective between two positions (either way round) with a
+    /// provisional character in `language`, as the `labelConnective` mutation
+    /// does. Returns false if there is no connective between them.
+    #[cfg(feature = "client")]
+    pub fn label_connective(&mut self, a: i32, b: i32, language: Language, value: &str) -> bool {
+        let Some(view) = self.connective_views.iter_mut().find(|c| c.joins(a, b)) else {
+            return false;
+        };
+        let character = Character::with_auto_id(language, value.trim())
+            .with_status(ResearchStatus::Provisional);
+        view.character = Some(Character {
+            id: format!("char_{}_{}", language.slug(), view.id),
+            ..character
+        });
+        true
+    }
+
+    /// Derive what links carry from the entries: line end coordinates, a
+    /// resolved view of each connective (keeping the character of an existing
+    /// view, which may be in another vocabulary), and `links`
+    pub(crate) fn resolve_links(&mut self) {
+        let coordinates = &self.coordinates;
+        let at = |position: Option<i32>| {
+            coordinates
+                .iter()
+                .find(|c| Some(c.position) == position)
+                .cloned()
+        };
+        for line in &mut self.lines {
+            line.base_coordinate = at(line.base_position).or(line.base_coordinate.take());
+            line.target_coordinate = at(line.target_position).or(line.target_coordinate.take());
+        }
+
+        let previous = std::mem::take(&mut self.connective_views);
+        let term =
+            |position: Option<i32>| position.and_then(|p| self.term_at(p)).map(str::to_string);
+        self.connective_views = self
+            .connectives
+            .iter()
+            .map(|connective| {
+                let character = previous
+                    .iter()
+                    .find(|v| v.id == connective.id)
+                    .map_or_else(|| connective.character.clone(), |v| v.character.clone());
+                ConnectiveView {
+                    id: connective.id.clone(),
+                    order: self.order,
+                    base_position: connective.base_position.unwrap_or_default(),
+                    target_position: connective.target_position.unwrap_or_default(),
+                    character,
+                    designation: self.connective_designation.clone(),
+                    base_term: term(connective.base_position),
+                    target_term: term(connective.target_position),
+                }
+            })
+            .collect();
+        self.links = self
+            .connectives
+            .iter()
+            .chain(&self.lines)
+            .cloned()
+            .collect();
+    }
+
+    /// Check that the system is positionally consistent: its terms,
+    /// coordinates and colours (in each language) cover positions
+    /// `1..=order` exactly once, and its links, resolved connectives and
+    /// groupings refer only to those positions
+    #[cfg(feature = "client")]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.order < 1 {
+            return Err(ValidationError::InvalidOrder(self.order));
+        }
+        check_coverage(
+            "term",
+            self.order,
+            self.terms.iter().map(|t| (&t.id, t.position)),
+        )?;
+        check_coverage(
+            "coordinate",
+            self.order,
+            self.coordinates.iter().map(|c| (&c.id, c.position)),
+        )?;
+        let mut languages = Vec::new();
+        for colour in &self.colours {
+            if !languages.contains(&colour.language) {
+                languages.push(colour.language);
+            }
+        }
+        for language in languages {
+            check_coverage(
+                "colour",
+                self.order,
+                self.colours
+                    .iter()
+                    .filter(|c| c.language == language)
+                    .map(|c| (&c.id, c.position)),
+            )?;
+        }
+
+        let links = self
+            .connectives
+            .iter()
+            .chain(&self.lines)
+            .chain(&self.links);
+        let link_positions = links.flat_map(|l| {
+            [l.base_position, l.target_position]
+                .into_iter()
+                .flatten()
+                .map(move |p| ("link", &l.id, p))
+        });
+        let connective_view_positions = self.connective_views.iter().flat_map(|c| {
+            [c.base_position, c.target_position]
+                .into_iter()
+                .map(move |p| ("connective", &c.id, p))
+        });
+        let grouping_positions = self.groupings.iter().flat_map(|g| {
+            g.member_positions
+                .iter()
+                .map(move |&p| ("grouping", &g.id, p))
+        });
+        match link_positions
+            .chain(connective_view_positions)
+            .chain(grouping_positions)
+            .find(|&(_, _, position)| !(1..=self.order).contains(&position))
+        {
+            Some((kind, id, position)) => Err(ValidationError::OutOfRange {
+                kind,
+                id: id.clone(),
+                position,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builder of a `SystemView`, for tests and fixtures. Entries listed in
+/// position order get the IDs the backend generates (`term_3_1`,
+/// `coord_3_1`, ...); lines and connectives are built with `Link::line` and
+/// `Link::connective`.
+#[derive(Debug, Clone)]
+pub struct SystemViewBuilder {
+    system: SystemView,
+}
+
+impl SystemViewBuilder {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.system.name = Some(name.into());
+        self
+    }
+
+    /// Mark the system as user-defined
+    pub fn custom(mut self) -> Self {
+        self.system.is_canonical = false;
+        self
+    }
+
+    /// Add a coherence attribute; the first added is the primary one
+    pub fn coherence(mut self, coherence: impl Into<String>) -> Self {
+        let coherence = coherence.into();
+        self.system
+            .coherence
+            .get_or_insert_with(|| coherence.clone());
+        self.system.coherences.push(coherence);
+        self
+    }
+
+    pub fn term_designation(mut self, designation: impl Into<String>) -> Self {
+        self.system.term_designation = Some(designation.into());
+        self
+    }
+
+    pub fn connective_designation(mut self, designation: impl Into<String>) -> Self {
+        self.system.connective_designation = Some(designation.into());
+        self
+    }
+
+    /// Add terms of canonical characters, from position 1
+    pub fn terms<S: Into<String>>(mut self, values: impl IntoIterator<Item = S>) -> Self {
+        let order = self.system.order;
+        self.system
+            .terms
+            .extend(values.into_iter().zip(1..).map(|(value, position)| {
+                Term::with_auto_id(
+                    order,
+                    position,
+                    Character::with_auto_id(Language::Canonical, value),
+                )
+            }));
+        self
+    }
+
+    /// Add coordinates (x, y, z), from position 1
+    pub fn coordinates(mut self, points: impl IntoIterator<Item = (f64, f64, f64)>) -> Self {
+        let order = self.system.order;
+        self.system.coordinates.extend(
+            points
+                .into_iter()
+                .zip(1..)
+                .map(|((x, y, z), position)| Coordinate::with_auto_id(order, position, x, y, z)),
+        );
+        self
+    }
+
+    /// Add hex colours, from position 1
+    pub fn colours<S: Into<String>>(mut self, values: impl IntoIterator<Item = S>) -> Self {
+        let order = self.system.order;
+        self.system.colours.extend(
+            values.into_iter().zip(1..).map(|(value, position)| {
+                Colour::with_auto_id(order, position, Language::Hex, value)
+            }),
+        );
+        self
+    }
+
+    pub fn connective(mut self, connective: Link) -> Self {
+        self.system.connectives.push(connective);
+        self
+    }
+
+    pub fn line(mut self, line: Link) -> Self {
+        self.system.lines.push(line);
+        self
+    }
+
+    pub fn grouping(mut self, grouping: Grouping) -> Self {
+        self.system.groupings.push(grouping);
+        self
+    }
+
+    /// Finish the system: lines get their end coordinates, connectives their
+    /// resolved views, and `links` lists the connectives, then the lines
+    pub fn build(mut self) -> SystemView {
+        self.system.resolve_links();
+        self.system
+    }
+}
+
+/// Check that `entries` (IDs and positions) cover positions `1..=order`
+/// exactly once
+#[cfg(feature = "client")]
+fn check_coverage<'a>(
+    kind: &'static str,
+    order: i32,
+    entries: impl Iterator<Item = (&'a String, i32)>,
+) -> Result<(), ValidationError> {
+    let mut seen = vec![false; order as usize];
+    for (id, position) in entries {
+        if !(1..=order).contains(&position) {
+            return Err(ValidationError::OutOfRange {
+                kind,
+                id: id.clone(),
+                position,
+            });
+        }
+        if std::mem::replace(&mut seen[position as usize - 1], true) {
+            return Err(ValidationError::Duplicate { kind, position });
+        }
+    }
+    match seen.iter().position(|&seen| !seen) {
+        Some(index) => Err(ValidationError::Missing {
+            kind,
+            position: index as i32 + 1,
+        }),
+        None => Ok(()),
+    }
 }
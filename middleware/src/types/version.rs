@@ -0,0 +1,16 @@
+//! Wire format version
+
+/// Version of the wire format, sent as the `apiVersion` of systems and graphs.
+///
+/// Adding a field or enum value keeps the version: clients ignore fields they
+/// do not know, default those an older server leaves out, and read enum values
+/// they do not know as `Unknown`. Removing, renaming or retyping a field bumps
+/// it.
+pub const API_VERSION: i32 = 1;
+
+/// Check whether a client of this crate can read data sent at `api_version`
+/// (0 for servers that predate the field)
+#[cfg(feature = "client")]
+pub fn is_supported_version(api_version: i32) -> bool {
+    api_version <= API_VERSION
+}
@@ -0,0 +1,89 @@
+//! TypeScript declarations of the wire format (`typescript` feature).
+//!
+//! Every wire type derives `ts_rs::TS`, following its serde attributes, so the
+//! declarations name fields and enum values exactly as they are sent. Generate
+//! them with the `systematics-ts` binary:
+//!
+//! ```bash
+//! cargo run -p systematics-middleware --features typescript --bin systematics-ts -- systematics.ts
+//! ```
+
+use ts_rs::TS;
+
+use crate::types::*;
+
+/// Declaration of one type, exported
+fn declaration<T: TS>() -> String {
+    let docs = T::docs().unwrap_or_default();
+    format!("{}export {}\n", docs, T::decl())
+}
+
+/// Declarations of every wire type, as one TypeScript module. Types come
+/// before those that use them, so the module reads top down.
+pub fn declarations() -> String {
+    let declarations = [
+        // Enums
+        declaration::<Language>(),
+        declaration::<ResearchStatus>(),
+        declaration::<LinkType>(),
+        declaration::<LineRole>(),
+        declaration::<SearchHitKind>(),
+        declaration::<EntryType>(),
+        declaration::<ErrorCode>(),
+        // Entries and links
+        declaration::<Citation>(),
+        declaration::<Character>(),
+        declaration::<Term>(),
+        declaration::<Coordinate>(),
+        declaration::<Colour>(),
+        declaration::<Link>(),
+        declaration::<ConnectiveView>(),
+        declaration::<Grouping>(),
+        declaration::<InstanceLabel>(),
+        declaration::<InstanceView>(),
+        declaration::<AnnotationView>(),
+        declaration::<CustomSystemInput>(),
+        declaration::<SearchHit>(),
+        declaration::<Page<SearchHit>>(),
+        // Views
+        declaration::<Slice>(),
+        declaration::<SystemView>(),
+        declaration::<OrderAttribute>(),
+        declaration::<LocationView>(),
+        declaration::<OrderView>(),
+        declaration::<PositionView>(),
+        declaration::<EntryRef>(),
+        declaration::<EntryGroup>(),
+        declaration::<GraphLink>(),
+        declaration::<GraphView>(),
+        declaration::<DeltaEntries>(),
+        declaration::<GraphDelta>(),
+        // Errors
+        declaration::<ErrorExtensions>(),
+        declaration::<PathSegment>(),
+        declaration::<GraphQLError>(),
+    ];
+    format!(
+        "// Systematics wire format, version {}.\n\
+         // Generated from systematics-middleware by systematics-ts; do not edit.\n\n\
+         export const API_VERSION = {};\n\n{}",
+        API_VERSION,
+        API_VERSION,
+        declarations.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations() {
+        let declarations = declarations();
+        assert!(declarations.contains("export const API_VERSION = 1;"));
+        assert!(declarations.contains("export type SystemView = {"));
+        assert!(declarations.contains("apiVersion: number"));
+        assert!(declarations.contains("termDesignation: string | null"));
+        assert!(declarations.contains(r#""SIDE" | "DIAGONAL""#));
+    }
+}
@@ -0,0 +1,207 @@
+//! Fitting coordinates into a drawing area.
+//!
+//! Coordinates come in any scale (e.g., -1 to 1, 0 to 10, or 0,0,0 for a
+//! single point) with y up. A `Viewport` scales and centres them to fit within
+//! its margins, keeping their aspect ratio, with y down as in SVG. Depth (z)
+//! is scaled alike about the points' mid-depth, so 3D views keep the shape of
+//! a solid.
+
+use crate::types::Coordinate;
+
+/// Smallest extent fitted, so that a single point or a line along one axis
+/// does not divide by zero
+const MIN_EXTENT: f64 = 0.0001;
+
+/// Drawing area, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub width: f64,
+    pub height: f64,
+    /// Space kept clear on each side (e.g., for labels)
+    pub margin: f64,
+}
+
+impl Viewport {
+    /// Centre of the drawing area
+    pub fn centre(&self) -> (f64, f64) {
+        (self.width / 2.0, self.height / 2.0)
+    }
+
+    /// Transform fitting points (x, y) within the margins
+    pub fn fit(&self, points: impl IntoIterator<Item = (f64, f64)>) -> ViewportTransform {
+        let mut min_x = f64::INFINITY;
+        let mut max_x = f64::NEG_INFINITY;
+        let mut min_y = f64::INFINITY;
+        let mut max_y = f64::NEG_INFINITY;
+        let mut count = 0;
+        for (x, y) in points {
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+            count += 1;
+        }
+
+        let (viewport_x, viewport_y) = self.centre();
+        // No points, or a single one: centre it
+        if count < 2 {
+            return ViewportTransform {
+                centre: (0.0, 0.0),
+                scale: 0.0,
+                viewport_centre: (viewport_x, viewport_y),
+            };
+        }
+
+        // The larger extent scales both axes, preserving the aspect ratio,
+        // into the smaller side of the area within the margins
+        let extent = (max_x - min_x).max(max_y - min_y).max(MIN_EXTENT);
+        let available = (self.width - 2.0 * self.margin).min(self.height - 2.0 * self.margin);
+        ViewportTransform {
+            centre: ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0),
+            scale: available / extent,
+            viewport_centre: (viewport_x, viewport_y),
+        }
+    }
+}
+
+/// Mapping from coordinate space to a viewport, from `Viewport::fit`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportTransform {
+    /// Centre of the fitted points' bounding box
+    centre: (f64, f64),
+    /// Pixels per coordinate unit
+    scale: f64,
+    viewport_centre: (f64, f64),
+}
+
+impl ViewportTransform {
+    /// Pixels per coordinate unit
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Position of a point in the viewport: centred at the origin, scaled,
+    /// flipped (y+ is up in coordinates, down in SVG), then moved to the
+    /// viewport's centre
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - self.centre.0) * self.scale + self.viewport_centre.0,
+            -(y - self.centre.1) * self.scale + self.viewport_centre.1,
+        )
+    }
+}
+
+/// Transform coordinates from API space to viewport space
+pub fn transform_coordinates_to_viewport(
+    coords: Vec<Coordinate>,
+    viewport: &Viewport,
+) -> Vec<Coordinate> {
+    let transform = viewport.fit(coords.iter().map(|c| (c.x, c.y)));
+    let (min_z, max_z) = coords
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), c| {
+            (lo.min(c.z), hi.max(c.z))
+        });
+    let mid_z = (min_z + max_z) / 2.0;
+    coords
+        .into_iter()
+        .map(|mut coord| {
+            (coord.x, coord.y) = transform.apply(coord.x, coord.y);
+            coord.z = (coord.z - mid_z) * transform.scale();
+            coord
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Viewport = Viewport {
+        width: 800.0,
+        height: 600.0,
+        margin: 100.0,
+    };
+
+    fn coordinates(points: &[(f64, f64)]) -> Vec<Coordinate> {
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| Coordinate {
+                id: format!("coord_{}_{}", points.len(), i + 1),
+                order: points.len() as i32,
+                position: i as i32 + 1,
+                x,
+                y,
+                z: 0.0,
+            })
+            .collect()
+    }
+
+    fn points(coords: &[Coordinate]) -> Vec<(f64, f64)> {
+        coords.iter().map(|c| (c.x, c.y)).collect()
+    }
+
+    #[test]
+    fn test_fits_within_margins() {
+        // A square fits the smaller side (600 - 2 * 100), flipped and centred
+        let square = coordinates(&[(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]);
+        let fitted = transform_coordinates_to_viewport(square, &VIEWPORT);
+        assert_eq!(
+            points(&fitted),
+            vec![
+                (200.0, 500.0),
+                (600.0, 500.0),
+                (600.0, 100.0),
+                (200.0, 100.0)
+            ]
+        );
+
+        // Depth is scaled alike, about the mid-depth
+        let mut solid = coordinates(&[(-1.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+        (solid[0].z, solid[1].z, solid[2].z) = (3.0, 3.0, 5.0);
+        let depths: Vec<f64> = transform_coordinates_to_viewport(solid, &VIEWPORT)
+            .iter()
+            .map(|c| c.z)
+            .collect();
+        assert_eq!(depths, vec![-200.0, -200.0, 200.0]);
+
+        // Offset and scaled coordinates land the same
+        let offset = coordinates(&[(10.0, 10.0), (30.0, 10.0), (30.0, 30.0), (10.0, 30.0)]);
+        assert_eq!(
+            points(&transform_coordinates_to_viewport(offset, &VIEWPORT)),
+            points(&fitted)
+        );
+    }
+
+    #[test]
+    fn test_degenerate_coordinates() {
+        assert!(transform_coordinates_to_viewport(Vec::new(), &VIEWPORT).is_empty());
+
+        // A single point is centred, wherever it is
+        for point in [(0.0, 0.0), (5.0, -3.0)] {
+            let fitted = transform_coordinates_to_viewport(coordinates(&[point]), &VIEWPORT);
+            assert_eq!(points(&fitted), vec![(400.0, 300.0)]);
+        }
+
+        // Collinear points span the available size along their line, and sit
+        // on the centre line across it
+        let horizontal = coordinates(&[(-1.0, 2.0), (0.0, 2.0), (1.0, 2.0)]);
+        assert_eq!(
+            points(&transform_coordinates_to_viewport(horizontal, &VIEWPORT)),
+            vec![(200.0, 300.0), (400.0, 300.0), (600.0, 300.0)]
+        );
+        let vertical = coordinates(&[(4.0, 0.0), (4.0, 1.0)]);
+        assert_eq!(
+            points(&transform_coordinates_to_viewport(vertical, &VIEWPORT)),
+            vec![(400.0, 500.0), (400.0, 100.0)]
+        );
+
+        // Coincident points are centred together
+        let coincident = coordinates(&[(1.0, 1.0), (1.0, 1.0)]);
+        assert_eq!(
+            points(&transform_coordinates_to_viewport(coincident, &VIEWPORT)),
+            vec![(400.0, 300.0), (400.0, 300.0)]
+        );
+    }
+}
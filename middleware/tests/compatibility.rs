@@ -0,0 +1,189 @@
+//! Wire format compatibility: clients built from this crate must keep reading
+//! what older and newer servers send (see `API_VERSION`).
+
+use serde_json::json;
+use systematics_middleware::{
+    is_supported_version, GraphView, Language, LineRole, LinkType, ResearchStatus, SystemView,
+    API_VERSION,
+};
+
+/// A system as a server of the current version sends it
+fn current_system() -> serde_json::Value {
+    json!({
+        "apiVersion": API_VERSION,
+        "order": 3,
+        "name": "Triad",
+        "isCanonical": true,
+        "coherence": "Dynamism",
+        "coherences": ["Dynamism"],
+        "termDesignation": "Impulse",
+        "connectiveDesignation": "Act",
+        "terms": [{
+            "id": "term_3_1",
+            "order": 3,
+            "position": 1,
+            "characterId": "char_canonical_will",
+            "status": "CANONICAL",
+            "character": {
+                "id": "char_canonical_will",
+                "language": "CANONICAL",
+                "value": "Will",
+                "status": "CANONICAL"
+            }
+        }],
+        "coordinates": [{ "id": "coord_3_1", "order": 3, "position": 1, "x": 0.0, "y": 1.0, "z": 0.0 }],
+        "colours": [{ "id": "colour_3_1_hex", "order": 3, "position": 1, "language": "HEX", "value": "#FF0000" }],
+        "connectives": [],
+        "lines": [{
+            "id": "line_3_1_2",
+            "baseId": "coord_3_1",
+            "targetId": "coord_3_2",
+            "linkType": "LINE",
+            "characterId": null,
+            "tag": null,
+            "role": "SIDE",
+            "order": 3,
+            "basePosition": 1,
+            "targetPosition": 2,
+            "baseCoordinate": null,
+            "targetCoordinate": null
+        }],
+        "groupings": []
+    })
+}
+
+#[test]
+fn test_current_system_round_trips() {
+    let system: SystemView = serde_json::from_value(current_system()).unwrap();
+    assert_eq!(system.api_version, API_VERSION);
+    assert_eq!(system.term_at(1), Some("Will"));
+    assert_eq!(system.lines[0].role, Some(LineRole::Side));
+
+    let again: SystemView = serde_json::from_value(serde_json::to_value(&system).unwrap()).unwrap();
+    assert_eq!(again, system);
+}
+
+#[test]
+fn test_older_server_fields_default() {
+    // Before apiVersion, isCanonical, coherences, groupings and instances
+    let system: SystemView = serde_json::from_value(json!({
+        "order": 1,
+        "name": "Monad",
+        "coherence": null,
+        "termDesignation": null,
+        "connectiveDesignation": null,
+        "terms": [{ "id": "term_1_1", "order": 1, "position": 1, "characterId": "c", "character": null }],
+        "coordinates": [],
+        "colours": [],
+        "connectives": [],
+        "lines": []
+    }))
+    .unwrap();
+    assert_eq!(system.api_version, 0);
+    assert!(is_supported_version(system.api_version));
+    assert!(system.is_canonical);
+    assert!(system.coherences.is_empty() && system.groupings.is_empty());
+    assert_eq!(system.terms[0].status, ResearchStatus::Canonical);
+    assert!(system.terms[0].value_i18n.is_empty());
+
+    // Selections that leave out whole lists
+    let system: SystemView =
+        serde_json::from_value(json!({ "order": 4, "name": "Tetrad" })).unwrap();
+    assert!(system.terms.is_empty() && system.lines.is_empty());
+}
+
+#[test]
+fn test_newer_server_additions_ignored() {
+    let mut value = current_system();
+    value["palette"] = json!({ "name": "Spectrum" });
+    value["terms"][0]["weight"] = json!(0.5);
+    value["terms"][0]["character"]["language"] = json!("KLINGON");
+    value["terms"][0]["character"]["status"] = json!("DISPUTED");
+    value["lines"][0]["linkType"] = json!("WEB");
+    value["lines"][0]["role"] = json!("SPIRAL");
+    value["colours"][0]["language"] = json!("RGB");
+
+    let system: SystemView = serde_json::from_value(value).unwrap();
+    let character = system.terms[0].character.as_ref().unwrap();
+    assert_eq!(character.language, Language::Unknown);
+    assert_eq!(character.status, ResearchStatus::Unknown);
+    assert_eq!(system.lines[0].link_type, LinkType::Unknown);
+    assert_eq!(system.lines[0].role, Some(LineRole::Unknown));
+    assert_eq!(system.colours[0].language, Language::Unknown);
+    assert_eq!(system.term_at(1), Some("Will"));
+}
+
+#[test]
+fn test_localized_values() {
+    let mut value = current_system();
+    let translations = json!({ "de": "Wille", "fr": "Volonté" });
+    value["terms"][0]["valueI18n"] = translations.clone();
+    value["terms"][0]["character"]["valueI18n"] = translations;
+
+    let system: SystemView = serde_json::from_value(value).unwrap();
+    let term = &system.terms[0];
+    assert_eq!(term.value_in("de"), Some("Wille"));
+    assert_eq!(term.value_in("es"), Some("Will"));
+    assert_eq!(term.character.as_ref().unwrap().value_in("fr"), "Volonté");
+}
+
+#[test]
+fn test_snake_case_aliases() {
+    let camel: SystemView = serde_json::from_value(current_system()).unwrap();
+    let snake: SystemView = serde_json::from_value(json!({
+        "api_version": API_VERSION,
+        "order": 3,
+        "name": "Triad",
+        "is_canonical": true,
+        "coherence": "Dynamism",
+        "coherences": ["Dynamism"],
+        "term_designation": "Impulse",
+        "connective_designation": "Act",
+        "terms": [{
+            "id": "term_3_1",
+            "order": 3,
+            "position": 1,
+            "character_id": "char_canonical_will",
+            "status": "CANONICAL",
+            "character": {
+                "id": "char_canonical_will",
+                "language": "CANONICAL",
+                "value": "Will",
+                "status": "CANONICAL"
+            }
+        }],
+        "coordinates": [{ "id": "coord_3_1", "order": 3, "position": 1, "x": 0.0, "y": 1.0, "z": 0.0 }],
+        "colours": [{ "id": "colour_3_1_hex", "order": 3, "position": 1, "language": "HEX", "value": "#FF0000" }],
+        "lines": [{
+            "id": "line_3_1_2",
+            "base_id": "coord_3_1",
+            "target_id": "coord_3_2",
+            "link_type": "LINE",
+            "role": "SIDE",
+            "order": 3,
+            "base_position": 1,
+            "target_position": 2
+        }]
+    }))
+    .unwrap();
+    assert_eq!(snake, camel);
+}
+
+#[test]
+fn test_graph_versions() {
+    let older: GraphView =
+        serde_json::from_value(json!({ "entryCount": 10, "linkCount": 2 })).unwrap();
+    assert_eq!(older.api_version, 0);
+    assert!(older.entries_by_type.is_empty() && older.links.is_empty());
+
+    let newer: GraphView = serde_json::from_value(json!({
+        "apiVersion": API_VERSION + 1,
+        "entryCount": 10,
+        "linkCount": 0,
+        "entriesByType": [{ "entryType": "HYPERLINK", "count": 10, "entries": [] }],
+        "links": []
+    }))
+    .unwrap();
+    assert!(!is_supported_version(newer.api_version));
+    assert_eq!(newer.entries_by_type[0].count, 10);
+}
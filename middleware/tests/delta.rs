@@ -0,0 +1,102 @@
+//! Applying graph deltas to systems (`GraphDelta::apply_to`).
+
+use systematics_middleware::{
+    Character, DeltaEntries, GraphDelta, Language, LineRole, Link, ResearchStatus, SystemView, Term,
+};
+
+/// A Dyad with its connective named, and the line joining its positions
+fn dyad() -> SystemView {
+    SystemView::builder(2)
+        .connective_designation("Interplays")
+        .terms(["Essence", "Existence"])
+        .coordinates([(-1.0, 0.0, 0.0), (1.0, 0.0, 0.0)])
+        .colours(["#FF0000", "#0000FF"])
+        .connective(
+            Link::connective(2, 1, 2)
+                .with_character(Character::with_auto_id(Language::Canonical, "Tension")),
+        )
+        .line(Link::line(2, 1, 2).with_role(LineRole::Side))
+        .build()
+}
+
+fn delta(added: DeltaEntries, updated: DeltaEntries, removed: &[&str]) -> GraphDelta {
+    GraphDelta {
+        from_revision: "a".to_string(),
+        to_revision: "b".to_string(),
+        added,
+        updated,
+        removed: removed.iter().map(|id| id.to_string()).collect(),
+        ..GraphDelta::default()
+    }
+}
+
+#[test]
+fn test_updates_replace_entries_and_characters() {
+    let mut system = dyad();
+    let renamed = Term::with_auto_id(2, 1, Character::with_auto_id(Language::Canonical, "Being"));
+    let updated = DeltaEntries {
+        terms: vec![renamed],
+        characters: vec![Character::with_auto_id(Language::Canonical, "Tension")
+            .with_status(ResearchStatus::NeedsResearch)],
+        ..DeltaEntries::default()
+    };
+    let change = delta(DeltaEntries::default(), updated, &[]);
+    assert!(change.touches(2));
+    assert!(!change.touches(3));
+
+    assert!(change.apply_to(&mut system));
+    assert_eq!(system.term_at(1), Some("Being"));
+    assert_eq!(system.terms.len(), 2);
+    // Labels follow the new term and the connective's updated character
+    let view = system.connective_view_between(2, 1).unwrap();
+    assert_eq!(view.title(), "Interplay 1–2: Being → Existence");
+    assert!(view.needs_research());
+    assert_eq!(
+        system.links,
+        [system.connectives.clone(), system.lines.clone()].concat()
+    );
+
+    // Applying it again changes nothing
+    assert!(!change.apply_to(&mut system));
+}
+
+#[test]
+fn test_entries_of_other_orders_are_ignored() {
+    let mut system = dyad();
+    let added = DeltaEntries {
+        terms: vec![Term::with_auto_id(
+            3,
+            1,
+            Character::with_auto_id(Language::Canonical, "Will"),
+        )],
+        links: vec![Link::line(3, 1, 2)],
+        ..DeltaEntries::default()
+    };
+    let change = delta(added, DeltaEntries::default(), &[]);
+    assert!(!change.touches(2));
+    assert!(!change.apply_to(&mut system));
+    assert_eq!(system, dyad());
+}
+
+#[test]
+fn test_removals() {
+    let mut system = dyad();
+    let change = delta(
+        DeltaEntries::default(),
+        DeltaEntries::default(),
+        &["conn_loc_2_1_loc_2_2", "note_1"],
+    );
+    assert!(change.apply_to(&mut system));
+    assert!(system.connectives.is_empty());
+    assert!(system.connective_views.is_empty());
+    assert_eq!(system.links, system.lines);
+    assert_eq!(system.validate(), Ok(()));
+
+    // Adding the connective back resolves its view again
+    let added = DeltaEntries {
+        links: dyad().connectives,
+        ..DeltaEntries::default()
+    };
+    assert!(delta(added, DeltaEntries::default(), &[]).apply_to(&mut system));
+    assert_eq!(system, dyad());
+}
@@ -0,0 +1,105 @@
+//! Client errors: what servers report, and which errors are worth retrying.
+
+use serde_json::json;
+use systematics_middleware::{ApiError, ErrorCode, GraphQLError, PathSegment};
+
+fn error(value: serde_json::Value) -> GraphQLError {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_error_paths() {
+    let field = error(json!({
+        "message": "Term not found",
+        "path": ["system", "terms", 0],
+        "extensions": { "code": "UNKNOWN_ENTRY" }
+    }));
+    assert_eq!(
+        field.path,
+        vec![
+            PathSegment::Field("system".to_string()),
+            PathSegment::Field("terms".to_string()),
+            PathSegment::Index(0),
+        ]
+    );
+    assert_eq!(field.path_string(), "system.terms.0");
+
+    // Request errors have no path
+    let request = error(json!({ "message": "Unknown operation" }));
+    assert!(request.path.is_empty());
+
+    let error = ApiError::GraphQL(vec![field, request]);
+    assert_eq!(error.code(), Some(ErrorCode::UnknownEntry));
+    assert_eq!(
+        error.to_string(),
+        "Server error: Term not found (at system.terms.0), Unknown operation"
+    );
+}
+
+#[test]
+fn test_retryable_errors() {
+    let rate_limited = ApiError::Http {
+        status: 429,
+        retry_after: Some(2),
+        errors: vec![error(json!({
+            "message": "Too many requests",
+            "extensions": { "code": "RATE_LIMITED" }
+        }))],
+    };
+    assert!(rate_limited.is_retryable());
+    assert_eq!(rate_limited.retry_after(), Some(2));
+    assert_eq!(rate_limited.code(), Some(ErrorCode::RateLimited));
+
+    // Server down or failing
+    assert!(ApiError::NetworkError("Connection refused".to_string()).is_retryable());
+    assert!(ApiError::Timeout.is_retryable());
+    let unavailable = ApiError::Http {
+        status: 503,
+        retry_after: None,
+        errors: Vec::new(),
+    };
+    assert!(unavailable.is_retryable());
+    assert_eq!(unavailable.to_string(), "Request failed with status 503");
+
+    // Bad query
+    let bad_request = ApiError::Http {
+        status: 400,
+        retry_after: None,
+        errors: Vec::new(),
+    };
+    assert!(!bad_request.is_retryable());
+    assert_eq!(bad_request.status(), Some(400));
+    let invalid = ApiError::GraphQL(vec![error(json!({
+        "message": "Order must be between 1 and 12",
+        "extensions": { "code": "INVALID_ORDER", "field": "order" }
+    }))]);
+    assert!(!invalid.is_retryable());
+    assert!(!ApiError::ParseError("expected value".to_string()).is_retryable());
+}
+
+#[test]
+fn test_unreachable_errors() {
+    let status = |status| ApiError::Http {
+        status,
+        retry_after: None,
+        errors: Vec::new(),
+    };
+    // No server, or something other than a GraphQL server, at the endpoint
+    assert!(ApiError::NetworkError("Connection refused".to_string()).is_unreachable());
+    assert!(ApiError::Timeout.is_unreachable());
+    assert!(ApiError::ParseError("expected value".to_string()).is_unreachable());
+    assert!(status(404).is_unreachable());
+    assert!(status(502).is_unreachable());
+
+    // A GraphQL server that answered
+    assert!(!status(500).is_unreachable());
+    let rate_limited = ApiError::Http {
+        status: 503,
+        retry_after: Some(2),
+        errors: vec![error(json!({ "message": "Too many requests" }))],
+    };
+    assert!(!rate_limited.is_unreachable());
+    assert!(
+        !ApiError::GraphQL(vec![error(json!({ "message": "Unknown operation" }))]).is_unreachable()
+    );
+}
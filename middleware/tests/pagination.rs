@@ -0,0 +1,72 @@
+//! Paging lists (`Page::of`) and collecting every page (`collect_pages`).
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use systematics_middleware::{collect_pages, Page};
+
+/// Run a future that never waits, as fetches from an in-memory list do
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Fetch a page of 1..=n as a server would, from its cursor
+async fn fetch(n: i32, limit: usize, after: Option<String>) -> Result<Page<i32>, String> {
+    let offset = after.map_or(Ok(0), |cursor| cursor.parse().map_err(|_| cursor))?;
+    Ok(Page::of((1..=n).collect(), offset, limit))
+}
+
+#[test]
+fn test_page_of() {
+    let first = Page::of((1..=7).collect(), 0, 3);
+    assert_eq!(first.items, vec![1, 2, 3]);
+    assert_eq!(first.total, 7);
+    assert!(first.has_next);
+    assert_eq!(first.next_cursor(), Some("3"));
+
+    let last = Page::of((1..=7).collect(), 6, 3);
+    assert_eq!(last.items, vec![7]);
+    assert!(!last.has_next);
+    assert_eq!(last.next_cursor(), None);
+
+    let json = serde_json::to_value(&first).unwrap();
+    assert_eq!(json["hasNext"], true);
+    assert_eq!(serde_json::from_value::<Page<i32>>(json).unwrap(), first);
+}
+
+#[test]
+fn test_collect_pages() {
+    let all = block_on(collect_pages(|after| fetch(7, 3, after)));
+    assert_eq!(all, Ok((1..=7).collect::<Vec<_>>()));
+    let empty = block_on(collect_pages(|after| fetch(0, 3, after)));
+    assert_eq!(empty, Ok(vec![]));
+
+    // A failed fetch fails the whole collection
+    let failed = block_on(collect_pages(|_| async {
+        Err::<Page<i32>, _>("unreachable".to_string())
+    }));
+    assert_eq!(failed, Err("unreachable".to_string()));
+
+    // A server repeating its cursor does not loop forever
+    let mut calls = 0;
+    let repeated = block_on(collect_pages(|_| {
+        calls += 1;
+        async {
+            Ok::<_, String>(Page {
+                items: vec![1],
+                total: 10,
+                has_next: true,
+                cursor: Some("1".to_string()),
+            })
+        }
+    }));
+    assert_eq!(repeated, Ok(vec![1, 1]));
+    assert_eq!(calls, 2);
+}
@@ -0,0 +1,104 @@
+//! Positional consistency of decoded systems (`SystemView::validate`), and of
+//! custom system definitions before they are sent.
+
+use systematics_middleware::{
+    ApiError, Colour, CustomSystemInput, Language, LineRole, Link, SystemView, ValidationError,
+};
+
+/// A consistent Dyad: a term, coordinate and hex colour at each position, and
+/// the line joining them
+fn dyad() -> SystemView {
+    SystemView::builder(2)
+        .terms(["Essence", "Existence"])
+        .coordinates([(-1.0, 0.0, 0.0), (1.0, 0.0, 0.0)])
+        .colours(["#FF0000", "#0000FF"])
+        .line(Link::line(2, 1, 2).with_role(LineRole::Side))
+        .build()
+}
+
+#[test]
+fn test_consistent_system_validates() {
+    assert_eq!(dyad().validate(), Ok(()));
+}
+
+#[test]
+fn test_coverage_is_exactly_once() {
+    let mut system = dyad();
+    system.terms.pop();
+    assert_eq!(
+        system.validate(),
+        Err(ValidationError::Missing {
+            kind: "term",
+            position: 2
+        })
+    );
+
+    let mut system = dyad();
+    system.coordinates[1].position = 1;
+    assert_eq!(
+        system.validate(),
+        Err(ValidationError::Duplicate {
+            kind: "coordinate",
+            position: 1
+        })
+    );
+
+    // Each colour language covers the positions on its own
+    let mut system = dyad();
+    system
+        .colours
+        .push(Colour::with_auto_id(2, 1, Language::Name, "Red"));
+    assert_eq!(
+        system.validate(),
+        Err(ValidationError::Missing {
+            kind: "colour",
+            position: 2
+        })
+    );
+    system
+        .colours
+        .push(Colour::with_auto_id(2, 2, Language::Name, "Blue"));
+    assert_eq!(system.validate(), Ok(()));
+}
+
+#[test]
+fn test_positions_stay_in_range() {
+    let mut system = dyad();
+    system.lines[0].target_position = Some(3);
+    let error = system.validate().unwrap_err();
+    assert_eq!(
+        error,
+        ValidationError::OutOfRange {
+            kind: "link",
+            id: "line_coord_2_1_coord_2_2".to_string(),
+            position: 3
+        }
+    );
+    assert_eq!(
+        ApiError::from(error).to_string(),
+        "Invalid data: link line_coord_2_1_coord_2_2 is at position 3, out of range"
+    );
+
+    let mut system = dyad();
+    system.order = 0;
+    assert_eq!(system.validate(), Err(ValidationError::InvalidOrder(0)));
+}
+
+#[test]
+fn test_custom_system_input_counts() {
+    let triad = CustomSystemInput::new(3, "Triad", ["Will", "Being", "Function"]);
+    assert_eq!(triad.connective_pairs(), [(1, 2), (1, 3), (2, 3)]);
+    assert!(triad.is_complete());
+    assert!(triad
+        .clone()
+        .with_connectives(["Act", "Act", "Act"])
+        .is_complete());
+    assert!(!triad.clone().with_connectives(["Act"]).is_complete());
+
+    let short = CustomSystemInput::new(3, "Triad", ["Will", "Being"]);
+    assert!(!short.is_complete());
+    assert_eq!(
+        serde_json::to_value(&triad).unwrap()["alternateCoherences"],
+        serde_json::json!([])
+    );
+}